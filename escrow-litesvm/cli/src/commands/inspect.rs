@@ -0,0 +1,71 @@
+use {
+    crate::{
+        context::Context,
+        display,
+        output::{self, EscrowState, Output},
+    },
+    anyhow::Context as _,
+    clap::Args,
+    escrow_client::pda,
+    solana_pubkey::Pubkey,
+};
+
+#[derive(Args)]
+pub struct InspectArgs {
+    /// Escrow address
+    pub escrow: Pubkey,
+}
+
+pub async fn run(context: &Context, args: InspectArgs) -> anyhow::Result<()> {
+    let client = context.rpc.client();
+    let escrow = context.rpc.fetch_escrow(&args.escrow).await?;
+    let state = &escrow.account;
+
+    // The vault lives under whichever token program owns mint A
+    let token_program = client.get_account(&state.mint_a).await.context("Failed to fetch mint A")?.owner;
+    let vault = pda::vault_address(&escrow.address, &state.mint_a, &token_program);
+    let vault_balance = client.get_token_account_balance(&vault).await.ok().map(|balance| balance.amount);
+    let lamports = client.get_balance(&escrow.address).await?;
+
+    let slot = client.get_slot().await?;
+    let now = client.get_block_time(slot).await.context("Failed to read cluster time")?;
+
+    if context.output == Output::Json {
+        return output::json(&output::Inspection {
+            escrow: EscrowState::from(&escrow),
+            token_program: token_program.to_string(),
+            vault: vault.to_string(),
+            vault_balance: vault_balance.map(|amount| amount.parse()).transpose()?,
+            rent: lamports,
+            expired: state.expires_at != 0 && now >= state.expires_at,
+            cluster_time: now,
+            slot,
+        });
+    }
+
+    println!("Escrow          {}", escrow.address);
+    println!("Maker           {}", state.maker);
+    println!("Seed            {}", state.seed);
+    println!("Status          {}", display::status(&state.status));
+    println!();
+    println!("Mint A          {}", state.mint_a);
+    println!("Mint B          {}", state.mint_b);
+    println!("Token program   {}", token_program);
+    println!("Vault           {}", vault);
+    println!("Vault balance   {}", vault_balance.as_deref().unwrap_or("missing"));
+    println!();
+    println!("Deposited       {}", state.deposit_total);
+    println!("Filled          {}", state.filled);
+    println!("Remaining       {}", state.remaining);
+    println!("Receive         {}", state.receive);
+    println!("Price           {:.6} mint B per mint A", display::price(state));
+    println!();
+    println!("Created at      {} ({})", state.created_at, display::relative(state.created_at, now));
+    println!("Expires         {}", display::expiry_status(state.expires_at, now));
+    println!("Cluster time    {} (slot {})", now, slot);
+    println!();
+    println!("Rent            {} lamports", lamports);
+    println!("Metadata        {}", display::metadata(&state.metadata));
+
+    Ok(())
+}
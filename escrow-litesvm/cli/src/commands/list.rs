@@ -0,0 +1,65 @@
+use {
+    crate::{
+        context::Context,
+        display,
+        output::{self, EscrowState, Output},
+    },
+    clap::Args,
+    escrow_client::{
+        accounts::{sort_by_price, EscrowFilter},
+        interface::types::EscrowStatus,
+    },
+    solana_pubkey::Pubkey,
+};
+
+#[derive(Args)]
+pub struct ListArgs {
+    #[arg(long)]
+    pub mint_a: Option<Pubkey>,
+    #[arg(long)]
+    pub mint_b: Option<Pubkey>,
+    #[arg(long)]
+    pub maker: Option<Pubkey>,
+    /// Leave out escrows reserved for a taker
+    #[arg(long)]
+    pub open_only: bool,
+}
+
+pub async fn run(context: &Context, args: ListArgs) -> anyhow::Result<()> {
+    let filter = EscrowFilter {
+        maker: args.maker,
+        mint_a: args.mint_a,
+        mint_b: args.mint_b,
+        status: args.open_only.then_some(EscrowStatus::Open),
+    };
+
+    let mut escrows = context.rpc.fetch_escrows(&filter).await?;
+    sort_by_price(&mut escrows);
+
+    if context.output == Output::Json {
+        return output::json(&escrows.iter().map(EscrowState::from).collect::<Vec<_>>());
+    }
+
+    println!(
+        "{:<44}  {:<44}  {:<44}  {:<44}  {:>20}  {:>20}  {:>14}  {:<9}  {:>10}",
+        "ESCROW", "MAKER", "MINT A", "MINT B", "REMAINING", "RECEIVE", "PRICE", "STATUS", "EXPIRES",
+    );
+    for escrow in &escrows {
+        let state = &escrow.account;
+        println!(
+            "{:<44}  {:<44}  {:<44}  {:<44}  {:>20}  {:>20}  {:>14.6}  {:<9}  {:>10}",
+            escrow.address.to_string(),
+            state.maker.to_string(),
+            state.mint_a.to_string(),
+            state.mint_b.to_string(),
+            state.remaining,
+            state.receive,
+            display::price(state),
+            display::status(&state.status),
+            display::expiry(state.expires_at),
+        );
+    }
+    println!("{} escrow(s)", escrows.len());
+
+    Ok(())
+}
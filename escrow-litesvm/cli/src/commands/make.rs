@@ -0,0 +1,74 @@
+use {
+    super::{SendArgs, TokenProgram},
+    crate::{
+        context::Context,
+        output::{self, Output},
+    },
+    clap::Args,
+    escrow_client::{pda, MakeBuilder},
+    solana_pubkey::Pubkey,
+    std::time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Args)]
+pub struct MakeArgs {
+    /// Mint deposited into the escrow
+    #[arg(long)]
+    pub mint_a: Pubkey,
+    /// Mint asked for in return
+    #[arg(long)]
+    pub mint_b: Pubkey,
+    /// Amount of mint A to deposit, in base units
+    #[arg(long)]
+    pub deposit: u64,
+    /// Amount of mint B to receive for the whole deposit, in base units
+    #[arg(long)]
+    pub receive: u64,
+    /// Seed distinguishing this escrow from the maker's others, the current time in
+    /// milliseconds by default
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Unix timestamp after which the escrow can no longer be taken
+    #[arg(long, default_value_t = 0)]
+    pub expires_at: i64,
+    /// Unix timestamp before which the escrow cannot be taken yet
+    #[arg(long, default_value_t = 0)]
+    pub starts_at: i64,
+    #[arg(long, value_enum, default_value_t)]
+    pub token_program: TokenProgram,
+    #[command(flatten)]
+    pub send: SendArgs,
+}
+
+pub async fn run(context: &Context, args: MakeArgs) -> anyhow::Result<()> {
+    let maker = context.signer()?;
+    let seed = args.seed.unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64);
+
+    let builder = MakeBuilder::new(maker.pubkey(), seed, args.mint_a, args.deposit, args.mint_b, args.receive)
+        .expires_at(args.expires_at)
+        .starts_at(args.starts_at)
+        .token_program(args.token_program.id());
+    let escrow = builder.escrow();
+
+    if args.send.dry_run {
+        return super::dry_run(context, &[builder.instruction()], &maker.pubkey()).await;
+    }
+
+    let signature = super::send(context, &args.send, &[builder.instruction()], maker.as_ref()).await?;
+    let vault = pda::vault_address(&escrow, &args.mint_a, &args.token_program.id());
+
+    if context.output == Output::Json {
+        return output::json(&output::Sent {
+            escrow: escrow.to_string(),
+            vault: Some(vault.to_string()),
+            seed: Some(seed),
+            signature: signature.to_string(),
+        });
+    }
+
+    println!("Escrow:    {}", escrow);
+    println!("Vault:     {}", vault);
+    println!("Seed:      {}", seed);
+    println!("Signature: {}", signature);
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use {
+    super::{SendArgs, TokenProgram},
+    crate::{
+        context::Context,
+        output::{self, Output},
+    },
+    anyhow::{bail, Context as _},
+    clap::Args,
+    escrow_client::{pda, MakeBuilder},
+    serde::{Deserialize, Serialize},
+    solana_pubkey::Pubkey,
+    std::{
+        io::Read,
+        path::PathBuf,
+        str::FromStr,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+#[derive(Args)]
+pub struct MakeBatchArgs {
+    /// CSV with a header row and the columns mint_a, mint_b, deposit, receive and
+    /// expiry, where an empty or zero expiry never expires
+    #[arg(long)]
+    pub file: PathBuf,
+    /// First seed, incremented per row. The current time in milliseconds by default.
+    #[arg(long)]
+    pub first_seed: Option<u64>,
+    #[arg(long, value_enum, default_value_t)]
+    pub token_program: TokenProgram,
+    #[command(flatten)]
+    pub send: SendArgs,
+}
+
+#[derive(Deserialize)]
+struct Record {
+    mint_a: String,
+    mint_b: String,
+    deposit: u64,
+    receive: u64,
+    #[serde(default)]
+    expiry: Option<i64>,
+}
+
+struct Offer {
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    deposit: u64,
+    receive: u64,
+    expires_at: i64,
+}
+
+/// Outcome of one CSV row
+#[derive(Serialize)]
+struct RowResult {
+    /// Line in the file, counting the header as line 1
+    line: usize,
+    escrow: String,
+    vault: String,
+    seed: u64,
+    /// Transaction the row was sent in, `null` when it failed
+    signature: Option<String>,
+    error: Option<String>,
+}
+
+// Every row is checked before anything is sent, so a typo on the last line can't
+// leave half a book behind
+fn read_offers(reader: impl Read) -> anyhow::Result<Vec<(usize, Offer)>> {
+    let mut offers = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, record) in csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(reader).deserialize::<Record>().enumerate() {
+        let line = index + 2;
+        let parsed = record.map_err(anyhow::Error::from).and_then(|record| {
+            let mint = |mint: &str| Pubkey::from_str(mint).with_context(|| format!("invalid mint {}", mint));
+            Ok(Offer {
+                mint_a: mint(&record.mint_a)?,
+                mint_b: mint(&record.mint_b)?,
+                deposit: record.deposit,
+                receive: record.receive,
+                expires_at: record.expiry.unwrap_or(0),
+            })
+        });
+
+        match parsed {
+            Ok(offer) => offers.push((line, offer)),
+            Err(err) => errors.push(format!("line {}: {:#}", line, err)),
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!("Invalid offers, nothing was sent:\n{}", errors.join("\n"));
+    }
+    if offers.is_empty() {
+        bail!("No offers in the file");
+    }
+    Ok(offers)
+}
+
+pub async fn run(context: &Context, args: MakeBatchArgs) -> anyhow::Result<()> {
+    let file = std::fs::File::open(&args.file).with_context(|| format!("Failed to open {}", args.file.display()))?;
+    let offers = read_offers(file)?;
+
+    let maker = context.signer()?;
+    let first_seed = args.first_seed.unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64);
+    let token_program = args.token_program.id();
+
+    let mut results = Vec::with_capacity(offers.len());
+    let mut instructions = Vec::with_capacity(offers.len());
+    for (index, (line, offer)) in offers.iter().enumerate() {
+        let seed = first_seed + index as u64;
+        let builder = MakeBuilder::new(maker.pubkey(), seed, offer.mint_a, offer.deposit, offer.mint_b, offer.receive)
+            .expires_at(offer.expires_at)
+            .token_program(token_program);
+        let escrow = builder.escrow();
+
+        instructions.push(builder.instruction());
+        results.push(RowResult {
+            line: *line,
+            escrow: escrow.to_string(),
+            vault: pda::vault_address(&escrow, &offer.mint_a, &token_program).to_string(),
+            seed,
+            signature: None,
+            error: None,
+        });
+    }
+
+    let batches = context.rpc.pack(&instructions, &maker.pubkey(), args.send.nonce.as_ref())?;
+
+    if args.send.dry_run {
+        for batch in &batches {
+            super::dry_run(context, batch, &maker.pubkey()).await?;
+        }
+        return Ok(());
+    }
+
+    // A failed transaction fails only its own rows, the rest are still sent
+    let mut rows = results.iter_mut();
+    for batch in &batches {
+        let outcome = super::send(context, &args.send, batch, maker.as_ref()).await;
+        for row in rows.by_ref().take(batch.len()) {
+            match &outcome {
+                Ok(signature) => row.signature = Some(signature.to_string()),
+                Err(err) => row.error = Some(format!("{:#}", err)),
+            }
+        }
+    }
+
+    if context.output == Output::Json {
+        return output::json(&results);
+    }
+
+    println!("{:>5}  {:<44}  {:>20}  {}", "LINE", "ESCROW", "SEED", "RESULT");
+    for row in &results {
+        let result = match (&row.signature, &row.error) {
+            (Some(signature), _) => signature.clone(),
+            (None, Some(error)) => format!("failed: {}", error),
+            (None, None) => "not sent".to_string(),
+        };
+        println!("{:>5}  {:<44}  {:>20}  {}", row.line, row.escrow, row.seed, result);
+    }
+
+    let failed = results.iter().filter(|row| row.error.is_some()).count();
+    println!("{} offer(s) in {} transaction(s), {} failed", results.len(), batches.len(), failed);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_offers_with_optional_expiry() {
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let csv = format!("mint_a,mint_b,deposit,receive,expiry\n{a},{b},100,200,\n{a}, {b},5,7,1700000000\n", a = mint_a, b = mint_b);
+
+        let offers = read_offers(csv.as_bytes()).unwrap();
+        assert_eq!(offers.len(), 2);
+        assert_eq!((offers[0].0, offers[0].1.deposit, offers[0].1.expires_at), (2, 100, 0));
+        assert_eq!((offers[1].0, offers[1].1.mint_b, offers[1].1.expires_at), (3, mint_b, 1_700_000_000));
+    }
+
+    #[test]
+    fn reports_every_invalid_row() {
+        let mint = Pubkey::new_unique();
+        let csv = format!("mint_a,mint_b,deposit,receive,expiry\nnope,{m},1,1,0\n{m},{m},1,1,0\n{m},{m},-1,1,0\n", m = mint);
+
+        let err = read_offers(csv.as_bytes()).unwrap_err().to_string();
+        assert!(err.contains("line 2: invalid mint nope"), "{}", err);
+        assert!(err.contains("line 4:"), "{}", err);
+        assert!(!err.contains("line 3:"), "{}", err);
+    }
+}
@@ -0,0 +1,98 @@
+pub mod inspect;
+pub mod list;
+pub mod make;
+pub mod make_batch;
+pub mod refund;
+pub mod take;
+pub mod watch;
+
+use {
+    crate::{
+        context::Context,
+        display,
+        output::{self, Output},
+    },
+    anyhow::bail,
+    clap::Args,
+    escrow_client::pda,
+    solana_instruction::Instruction,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_pubkey::{pubkey, Pubkey},
+};
+
+const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Names an escrow either by address or by its maker and seed
+#[derive(Args)]
+pub struct EscrowRef {
+    /// Escrow address
+    #[arg(long, conflicts_with_all = ["maker", "seed"])]
+    pub escrow: Option<Pubkey>,
+    /// Maker of the escrow, used with --seed
+    #[arg(long, requires = "seed")]
+    pub maker: Option<Pubkey>,
+    /// Seed the escrow was made with
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+impl EscrowRef {
+    /// The escrow address, with `default_maker` standing in for a missing --maker
+    pub fn address(&self, default_maker: &Pubkey) -> anyhow::Result<Pubkey> {
+        match (self.escrow, self.maker, self.seed) {
+            (Some(escrow), _, _) => Ok(escrow),
+            (None, maker, Some(seed)) => Ok(pda::escrow_address(&maker.unwrap_or(*default_maker), seed)),
+            (None, _, None) => bail!("Pass --escrow, or --seed with an optional --maker"),
+        }
+    }
+}
+
+/// Token program the mints belong to
+#[derive(Clone, Copy, Default, clap::ValueEnum)]
+pub enum TokenProgram {
+    #[default]
+    Token,
+    Token2022,
+}
+
+impl TokenProgram {
+    pub fn id(self) -> Pubkey {
+        match self {
+            TokenProgram::Token => TOKEN_PROGRAM_ID,
+            TokenProgram::Token2022 => TOKEN_2022_PROGRAM_ID,
+        }
+    }
+}
+
+/// How a mutating command sends its transaction
+#[derive(Args)]
+pub struct SendArgs {
+    /// Simulate and print the outcome without sending anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Durable nonce account to use instead of a recent blockhash, advanced by the signer
+    #[arg(long)]
+    pub nonce: Option<Pubkey>,
+}
+
+/// Sends `instructions` paid for and signed by `signer`
+pub async fn send(context: &Context, args: &SendArgs, instructions: &[Instruction], signer: &dyn Signer) -> anyhow::Result<Signature> {
+    Ok(match args.nonce {
+        Some(nonce) => context.rpc.send_with_nonce(instructions, signer, &nonce).await?,
+        None => context.rpc.send(instructions, signer, &[]).await?,
+    })
+}
+
+/// Simulates what a mutating command would send and prints the outcome, for --dry-run
+pub async fn dry_run(context: &Context, instructions: &[Instruction], payer: &Pubkey) -> anyhow::Result<()> {
+    let simulation = context.rpc.simulate(instructions, payer).await?;
+    match context.output {
+        Output::Json => output::json(&output::DryRun::from(&simulation)),
+        Output::Text => {
+            display::simulation(&simulation);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use {
+    super::{EscrowRef, SendArgs, TokenProgram},
+    crate::{
+        context::Context,
+        output::{self, Output},
+    },
+    clap::Args,
+    escrow_client::RefundBuilder,
+};
+
+#[derive(Args)]
+pub struct RefundArgs {
+    #[command(flatten)]
+    pub escrow: EscrowRef,
+    #[arg(long, value_enum, default_value_t)]
+    pub token_program: TokenProgram,
+    #[command(flatten)]
+    pub send: SendArgs,
+}
+
+pub async fn run(context: &Context, args: RefundArgs) -> anyhow::Result<()> {
+    let maker = context.signer()?;
+    let escrow = context.rpc.fetch_escrow(&args.escrow.address(&maker.pubkey())?).await?;
+
+    let builder = RefundBuilder::for_escrow(&escrow).token_program(args.token_program.id());
+
+    if args.send.dry_run {
+        return super::dry_run(context, &[builder.instruction()], &maker.pubkey()).await;
+    }
+
+    let signature = super::send(context, &args.send, &[builder.instruction()], maker.as_ref()).await?;
+
+    if context.output == Output::Json {
+        return output::json(&output::Sent {
+            escrow: escrow.address.to_string(),
+            vault: None,
+            seed: None,
+            signature: signature.to_string(),
+        });
+    }
+
+    println!("Escrow:    {}", escrow.address);
+    println!("Signature: {}", signature);
+    Ok(())
+}
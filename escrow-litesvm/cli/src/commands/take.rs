@@ -0,0 +1,57 @@
+use {
+    super::{EscrowRef, SendArgs, TokenProgram},
+    crate::{
+        context::Context,
+        output::{self, Output},
+    },
+    clap::Args,
+    escrow_client::TakeBuilder,
+};
+
+#[derive(Args)]
+pub struct TakeArgs {
+    #[command(flatten)]
+    pub escrow: EscrowRef,
+    /// Fill only this much of mint A, in base units, instead of everything left
+    #[arg(long)]
+    pub amount: Option<u64>,
+    /// Pay the maker in native SOL, when mint B is wrapped SOL
+    #[arg(long)]
+    pub unwrap_sol: bool,
+    #[arg(long, value_enum, default_value_t)]
+    pub token_program: TokenProgram,
+    #[command(flatten)]
+    pub send: SendArgs,
+}
+
+pub async fn run(context: &Context, args: TakeArgs) -> anyhow::Result<()> {
+    let taker = context.signer()?;
+    let escrow = context.rpc.fetch_escrow(&args.escrow.address(&taker.pubkey())?).await?;
+
+    let mut builder = TakeBuilder::for_escrow(taker.pubkey(), &escrow).token_program(args.token_program.id());
+    if let Some(amount) = args.amount {
+        builder = builder.amount(amount);
+    }
+    if args.unwrap_sol {
+        builder = builder.unwrap_sol();
+    }
+
+    if args.send.dry_run {
+        return super::dry_run(context, &[builder.instruction()], &taker.pubkey()).await;
+    }
+
+    let signature = super::send(context, &args.send, &[builder.instruction()], taker.as_ref()).await?;
+
+    if context.output == Output::Json {
+        return output::json(&output::Sent {
+            escrow: escrow.address.to_string(),
+            vault: None,
+            seed: None,
+            signature: signature.to_string(),
+        });
+    }
+
+    println!("Escrow:    {}", escrow.address);
+    println!("Signature: {}", signature);
+    Ok(())
+}
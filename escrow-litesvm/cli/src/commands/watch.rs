@@ -0,0 +1,138 @@
+use {
+    crate::{
+        context::Context,
+        output::{self, Output},
+    },
+    anyhow::{anyhow, bail},
+    clap::Args,
+    escrow_client::{accounts::EscrowFilter, subscribe::websocket_url, subscribe_events, ProgramEvent},
+    futures::StreamExt,
+    serde::Serialize,
+    solana_pubkey::Pubkey,
+    std::{collections::HashSet, str::FromStr},
+};
+
+#[derive(Args)]
+pub struct WatchArgs {
+    /// Pair to watch, as <MINT_A>/<MINT_B>
+    #[arg(long)]
+    pub pair: Pair,
+    /// Websocket URL, derived from the RPC URL by default
+    #[arg(long)]
+    pub ws_url: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pair {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+}
+
+impl FromStr for Pair {
+    type Err = anyhow::Error;
+
+    fn from_str(pair: &str) -> anyhow::Result<Self> {
+        let Some((mint_a, mint_b)) = pair.split_once('/') else {
+            bail!("Expected <MINT_A>/<MINT_B>, got {}", pair);
+        };
+        let parse = |mint: &str| Pubkey::from_str(mint).map_err(|_| anyhow!("Invalid mint {}", mint));
+        Ok(Self { mint_a: parse(mint_a)?, mint_b: parse(mint_b)? })
+    }
+}
+
+/// One line of `--output json`
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Update {
+    Created { escrow: String, maker: String, seed: u64, deposit: u64, receive: u64, expires_at: i64, starts_at: i64, signature: String },
+    Filled { escrow: String, taker: String, amount: u64, price: u64, remaining: u64, signature: String },
+    Refunded { escrow: String, amount: u64, signature: String },
+    Expired { escrow: String, amount: u64, signature: String },
+}
+
+pub async fn run(context: &Context, args: WatchArgs) -> anyhow::Result<()> {
+    let ws_url = args.ws_url.unwrap_or_else(|| websocket_url(&context.rpc.client().url()));
+    let mut events = subscribe_events(ws_url);
+
+    // Taken and refunded events don't name the mints, so remember which escrows belong
+    // to the pair. Subscribing first means an escrow made in between still shows up.
+    let filter = EscrowFilter { mint_a: Some(args.pair.mint_a), mint_b: Some(args.pair.mint_b), ..EscrowFilter::default() };
+    let mut escrows: HashSet<Pubkey> = context.rpc.fetch_escrows(&filter).await?.into_iter().map(|escrow| escrow.address).collect();
+
+    if context.output == Output::Text {
+        println!("Watching {} escrow(s) of {}/{}", escrows.len(), args.pair.mint_a, args.pair.mint_b);
+    }
+
+    while let Some(logged) = events.next().await {
+        let signature = logged.signature.to_string();
+        let update = match logged.event {
+            ProgramEvent::Made(made) if made.mint_a == args.pair.mint_a && made.mint_b == args.pair.mint_b => {
+                escrows.insert(made.escrow);
+                Update::Created {
+                    escrow: made.escrow.to_string(),
+                    maker: made.maker.to_string(),
+                    seed: made.seed,
+                    deposit: made.deposit,
+                    receive: made.receive,
+                    expires_at: made.expires_at,
+                    starts_at: made.starts_at,
+                    signature,
+                }
+            }
+            ProgramEvent::Taken(taken) if escrows.contains(&taken.escrow) => {
+                if taken.remaining == 0 {
+                    escrows.remove(&taken.escrow);
+                }
+                Update::Filled {
+                    escrow: taken.escrow.to_string(),
+                    taker: taken.taker.to_string(),
+                    amount: taken.amount,
+                    price: taken.price,
+                    remaining: taken.remaining,
+                    signature,
+                }
+            }
+            ProgramEvent::Refunded(refunded) if escrows.remove(&refunded.escrow) => {
+                Update::Refunded { escrow: refunded.escrow.to_string(), amount: refunded.amount, signature }
+            }
+            ProgramEvent::ExpiredClosed(closed) if escrows.remove(&closed.escrow) => {
+                Update::Expired { escrow: closed.escrow.to_string(), amount: closed.amount, signature }
+            }
+            _ => continue,
+        };
+
+        match context.output {
+            // One document per line, so the stream can be read as it arrives
+            Output::Json => println!("{}", serde_json::to_string(&update)?),
+            Output::Text => print(&update),
+        }
+    }
+
+    Ok(())
+}
+
+fn print(update: &Update) {
+    match update {
+        Update::Created { escrow, deposit, receive, signature, .. } => {
+            println!("created   {}  deposit {} for {}  {}", escrow, deposit, receive, signature)
+        }
+        Update::Filled { escrow, amount, price, remaining, signature, .. } => {
+            println!("filled    {}  {} for {}, {} left  {}", escrow, amount, price, remaining, signature)
+        }
+        Update::Refunded { escrow, amount, signature } => println!("refunded  {}  {} returned  {}", escrow, amount, signature),
+        Update::Expired { escrow, amount, signature } => println!("expired   {}  {} returned  {}", escrow, amount, signature),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pair() {
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(format!("{}/{}", mint_a, mint_b).parse::<Pair>().unwrap(), Pair { mint_a, mint_b });
+        assert!(mint_a.to_string().parse::<Pair>().is_err());
+        assert!(format!("{}/nope", mint_a).parse::<Pair>().is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! Named profiles in `~/.config/escrow/config.toml`, so switching clusters is one flag:
+//!
+//! ```toml
+//! default_profile = "devnet"
+//!
+//! [profiles.localnet]
+//! url = "http://127.0.0.1:8899"
+//!
+//! [profiles.mainnet]
+//! url = "https://api.mainnet-beta.solana.com"
+//! keypair = "~/.config/solana/mainnet.json"
+//! commitment = "finalized"
+//!
+//! [profiles.mainnet.priority_fee]
+//! percentile = 75
+//! max_micro_lamports = 1000000
+//! ```
+//!
+//! Flags and environment variables override whatever the selected profile sets.
+
+use {
+    anyhow::{anyhow, bail, Context as _},
+    escrow_client::PriorityFee,
+    serde::Deserialize,
+    std::{collections::BTreeMap, fs, path::PathBuf},
+};
+
+pub const DEFAULT_URL: &str = "https://api.devnet.solana.com";
+pub const DEFAULT_COMMITMENT: &str = "confirmed";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Profile used when --profile is not passed
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub url: Option<String>,
+    pub keypair: Option<PathBuf>,
+    pub commitment: Option<String>,
+    pub priority_fee: Option<FeeSettings>,
+}
+
+/// Mirrors [`PriorityFee`]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeeSettings {
+    pub percentile: u8,
+    pub min_micro_lamports: Option<u64>,
+    pub max_micro_lamports: Option<u64>,
+    pub compute_unit_limit: Option<u32>,
+}
+
+impl Config {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".config/escrow/config.toml"))
+    }
+
+    /// Reads the config file, an empty config when it does not exist
+    pub fn load(path: &PathBuf) -> anyhow::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents).with_context(|| format!("Invalid config {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("Failed to read config {}", path.display())),
+        }
+    }
+
+    pub fn parse(contents: &str) -> anyhow::Result<Self> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    /// The profile `name`, or the default profile when `name` is `None`. Having no
+    /// default profile is fine, naming one that does not exist is not.
+    pub fn profile(&self, name: Option<&str>) -> anyhow::Result<Profile> {
+        let Some(name) = name.or(self.default_profile.as_deref()) else {
+            return Ok(Profile::default());
+        };
+        match self.profiles.get(name) {
+            Some(profile) => Ok(profile.clone()),
+            None if self.profiles.is_empty() => bail!("Profile {} not found, no profiles are configured", name),
+            None => bail!(
+                "Profile {} not found, configured profiles are {}",
+                name,
+                self.profiles.keys().map(String::as_str).collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+}
+
+impl FeeSettings {
+    pub fn priority_fee(&self) -> anyhow::Result<PriorityFee> {
+        if self.percentile > 100 {
+            return Err(anyhow!("Priority fee percentile must be between 0 and 100, not {}", self.percentile));
+        }
+
+        let mut fee = PriorityFee::percentile(self.percentile);
+        if let Some(min) = self.min_micro_lamports {
+            fee = fee.min_micro_lamports(min);
+        }
+        if let Some(max) = self.max_micro_lamports {
+            fee = fee.max_micro_lamports(max);
+        }
+        if let Some(limit) = self.compute_unit_limit {
+            fee = fee.compute_unit_limit(limit);
+        }
+        Ok(fee)
+    }
+}
+
+/// Expands a leading `~/`, which keypair paths in the config commonly use
+pub fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        default_profile = "devnet"
+
+        [profiles.devnet]
+        url = "https://api.devnet.solana.com"
+
+        [profiles.mainnet]
+        url = "https://api.mainnet-beta.solana.com"
+        keypair = "~/.config/solana/mainnet.json"
+        commitment = "finalized"
+
+        [profiles.mainnet.priority_fee]
+        percentile = 75
+        max_micro_lamports = 1000000
+    "#;
+
+    #[test]
+    fn selects_named_or_default_profile() {
+        let config = Config::parse(CONFIG).unwrap();
+
+        let devnet = config.profile(None).unwrap();
+        assert_eq!(devnet.url.as_deref(), Some("https://api.devnet.solana.com"));
+        assert!(devnet.priority_fee.is_none());
+
+        let mainnet = config.profile(Some("mainnet")).unwrap();
+        assert_eq!(mainnet.commitment.as_deref(), Some("finalized"));
+        let fee = mainnet.priority_fee.unwrap().priority_fee().unwrap();
+        assert_eq!((fee.percentile, fee.min_micro_lamports, fee.max_micro_lamports), (75, 0, 1_000_000));
+
+        let err = config.profile(Some("testnet")).unwrap_err().to_string();
+        assert_eq!(err, "Profile testnet not found, configured profiles are devnet, mainnet");
+    }
+
+    #[test]
+    fn empty_config_has_an_empty_default_profile() {
+        let profile = Config::parse("").unwrap().profile(None).unwrap();
+        assert!(profile.url.is_none() && profile.keypair.is_none() && profile.commitment.is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(Config::parse("[profiles.devnet]\nrpc = \"http://localhost\"").is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use {
+    crate::{
+        config::{self, Config, Profile},
+        output::Output,
+        signer::SignerSource,
+        GlobalArgs,
+    },
+    anyhow::anyhow,
+    escrow_client::EscrowRpc,
+    solana_commitment_config::CommitmentConfig,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_signer::Signer,
+    std::{path::PathBuf, str::FromStr},
+};
+
+/// What every command needs: an RPC connection and the signer
+pub struct Context {
+    pub rpc: EscrowRpc,
+    pub output: Output,
+    signer: SignerSource,
+}
+
+/// Flags layered over the selected profile, over the built-in defaults
+pub struct Settings {
+    pub url: String,
+    pub keypair: Option<PathBuf>,
+    pub commitment: String,
+    pub priority_fee: Option<config::FeeSettings>,
+}
+
+impl Settings {
+    pub fn resolve(args: &GlobalArgs, profile: Profile) -> Self {
+        Self {
+            url: args.url.clone().or(profile.url).unwrap_or_else(|| config::DEFAULT_URL.to_string()),
+            keypair: args.keypair.clone().or(profile.keypair.map(config::expand_home)),
+            commitment: args.commitment.clone().or(profile.commitment).unwrap_or_else(|| config::DEFAULT_COMMITMENT.to_string()),
+            priority_fee: profile.priority_fee,
+        }
+    }
+}
+
+impl Context {
+    pub fn new(args: &GlobalArgs) -> anyhow::Result<Self> {
+        let config = match args.config.clone().or_else(Config::default_path) {
+            Some(path) => Config::load(&path)?,
+            None => Config::default(),
+        };
+        let settings = Settings::resolve(args, config.profile(args.profile.as_deref())?);
+
+        let commitment = CommitmentConfig::from_str(&settings.commitment)
+            .map_err(|_| anyhow!("Unknown commitment {}, use processed, confirmed or finalized", settings.commitment))?;
+
+        let client = RpcClient::new_with_commitment(settings.url, commitment);
+        let signer = SignerSource::parse(match settings.keypair {
+            Some(path) => path,
+            None => default_keypair_path()?,
+        })?;
+
+        let mut rpc = EscrowRpc::from_client(client);
+        if let Some(fee) = &settings.priority_fee {
+            rpc = rpc.with_priority_fee(fee.priority_fee()?);
+        }
+
+        Ok(Self { rpc, output: args.output, signer })
+    }
+
+    pub fn signer(&self) -> anyhow::Result<Box<dyn Signer>> {
+        self.signer.load()
+    }
+}
+
+// The same default as the Solana CLI
+fn default_keypair_path() -> anyhow::Result<PathBuf> {
+    let home = dirs::home_dir().ok_or_else(|| anyhow!("No home directory, pass --keypair"))?;
+    Ok(home.join(".config/solana/id.json"))
+}
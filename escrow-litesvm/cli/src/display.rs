@@ -0,0 +1,107 @@
+//! Human-readable rendering of escrow state.
+
+use escrow_client::{
+    interface::{accounts::Escrow, types::EscrowStatus},
+    Simulation,
+};
+
+pub fn status(status: &EscrowStatus) -> &'static str {
+    match status {
+        EscrowStatus::Open => "open",
+        EscrowStatus::Reserved => "reserved",
+    }
+}
+
+/// Mint B asked per unit of mint A, for display only
+pub fn price(escrow: &Escrow) -> f64 {
+    if escrow.deposit_total == 0 {
+        return 0.0;
+    }
+    escrow.receive as f64 / escrow.deposit_total as f64
+}
+
+pub fn expiry(expires_at: i64) -> String {
+    if expires_at == 0 { "never".to_string() } else { expires_at.to_string() }
+}
+
+/// `timestamp` relative to `now`, e.g. "in 2h 5m" or "3d 4h ago"
+pub fn relative(timestamp: i64, now: i64) -> String {
+    let delta = timestamp - now;
+    let duration = duration(delta.unsigned_abs());
+    if delta >= 0 { format!("in {}", duration) } else { format!("{} ago", duration) }
+}
+
+fn duration(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86_400, seconds % 86_400 / 3_600, seconds % 3_600 / 60);
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", seconds),
+        (0, 0, _) => format!("{}m {}s", minutes, seconds % 60),
+        (0, _, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
+/// Whether the escrow can still be taken at cluster time `now`
+pub fn expiry_status(expires_at: i64, now: i64) -> String {
+    if expires_at == 0 {
+        "never".to_string()
+    } else if now >= expires_at {
+        format!("{}, expired {}", expires_at, relative(expires_at, now))
+    } else {
+        format!("{}, {}", expires_at, relative(expires_at, now))
+    }
+}
+
+/// Metadata as text when it is UTF-8, as hex otherwise
+pub fn metadata(metadata: &[u8]) -> String {
+    match std::str::from_utf8(metadata) {
+        _ if metadata.is_empty() => "none".to_string(),
+        Ok(text) => format!("{:?}", text),
+        Err(_) => metadata.iter().map(|byte| format!("{:02x}", byte)).collect(),
+    }
+}
+
+/// Prints a --dry-run simulation: cost, balance changes and logs
+pub fn simulation(simulation: &Simulation) {
+    println!("Dry run, nothing was sent");
+    println!();
+    println!("Fee             {} lamports", simulation.fee);
+    match simulation.compute_units {
+        Some(units) => println!("Compute units   {}", units),
+        None => println!("Compute units   unknown"),
+    }
+
+    println!();
+    println!("Balance changes");
+    for change in &simulation.changes {
+        let tokens = change.tokens.map(|tokens| format!("{:+} tokens", tokens)).unwrap_or_default();
+        println!("  {:<44}  {:>+15} lamports  {}", change.address, change.lamports, tokens);
+    }
+
+    println!();
+    println!("Logs");
+    for log in &simulation.logs {
+        println!("  {}", log);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_times_relative_to_cluster_time() {
+        assert_eq!(relative(1_000 + 7_500, 1_000), "in 2h 5m");
+        assert_eq!(relative(1_000, 1_000 + 273_600), "3d 4h ago");
+        assert_eq!(expiry_status(0, 5), "never");
+        assert_eq!(expiry_status(100, 130), "100, expired 30s ago");
+        assert_eq!(expiry_status(190, 100), "190, in 1m 30s");
+    }
+
+    #[test]
+    fn renders_metadata_as_text_or_hex() {
+        assert_eq!(metadata(b""), "none");
+        assert_eq!(metadata(b"otc desk"), "\"otc desk\"");
+        assert_eq!(metadata(&[0xff, 0x01]), "ff01");
+    }
+}
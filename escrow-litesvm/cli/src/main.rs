@@ -0,0 +1,129 @@
+//! `escrow`, a command line interface to the escrow program.
+//!
+//! ```text
+//! escrow make --mint-a <MINT> --mint-b <MINT> --deposit 1000 --receive 2000
+//! escrow make-batch --file offers.csv
+//! escrow take --maker <PUBKEY> --seed 42
+//! escrow refund --seed 42 --dry-run
+//! escrow list --mint-a <MINT> --mint-b <MINT> --open-only
+//! escrow inspect <ESCROW>
+//! escrow watch --pair <MINT_A>/<MINT_B>
+//! ```
+//!
+//! Amounts are in base units of the mint, as stored on chain. Every command takes
+//! `--output json` for scripts. Cluster, keypair and
+//! fee defaults can be kept in named profiles, see [`config`].
+
+mod commands;
+mod config;
+mod context;
+mod display;
+mod output;
+mod signer;
+
+use {
+    clap::{Args, Parser, Subcommand},
+    context::Context,
+    std::path::PathBuf,
+};
+
+#[derive(Parser)]
+#[command(name = "escrow", version, about = "Make, take and refund token escrows")]
+struct Cli {
+    #[command(flatten)]
+    global: GlobalArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+pub struct GlobalArgs {
+    /// Profile from the config file to take defaults from
+    #[arg(long, short = 'p', global = true, env = "ESCROW_PROFILE")]
+    pub profile: Option<String>,
+    /// Config file holding the profiles, ~/.config/escrow/config.toml by default
+    #[arg(long, global = true, env = "ESCROW_CONFIG")]
+    pub config: Option<PathBuf>,
+    /// JSON RPC URL of the cluster, devnet unless the profile sets one
+    #[arg(long, short = 'u', global = true, env = "ESCROW_URL")]
+    pub url: Option<String>,
+    /// Keypair file or hardware wallet (usb://ledger) that signs and pays, the Solana
+    /// CLI default when neither this nor the profile sets one
+    #[arg(long, short = 'k', global = true, env = "ESCROW_KEYPAIR")]
+    pub keypair: Option<PathBuf>,
+    /// Commitment to read state and confirm transactions at, confirmed by default
+    #[arg(long, global = true)]
+    pub commitment: Option<String>,
+    /// Print results as text or as JSON for scripts
+    #[arg(long, short = 'o', global = true, value_enum, default_value_t)]
+    pub output: output::Output,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Deposit mint A into a new escrow asking for mint B
+    Make(commands::make::MakeArgs),
+    /// Make one escrow per row of a CSV file, packed into as few transactions as fit
+    MakeBatch(commands::make_batch::MakeBatchArgs),
+    /// Pay an escrow's price in mint B and receive its mint A
+    Take(commands::take::TakeArgs),
+    /// Close one of your escrows and recover the deposit
+    Refund(commands::refund::RefundArgs),
+    /// List escrows, cheapest first
+    List(commands::list::ListArgs),
+    /// Show everything about one escrow
+    Inspect(commands::inspect::InspectArgs),
+    /// Stream escrows of a pair as they are made, filled and refunded
+    Watch(commands::watch::WatchArgs),
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let context = Context::new(&cli.global)?;
+
+    match cli.command {
+        Command::Make(args) => commands::make::run(&context, args).await,
+        Command::MakeBatch(args) => commands::make_batch::run(&context, args).await,
+        Command::Take(args) => commands::take::run(&context, args).await,
+        Command::Refund(args) => commands::refund::run(&context, args).await,
+        Command::List(args) => commands::list::run(&context, args).await,
+        Command::Inspect(args) => commands::inspect::run(&context, args).await,
+        Command::Watch(args) => commands::watch::run(&context, args).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, clap::CommandFactory};
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn escrow_is_named_by_address_or_by_seed() {
+        let parse = |args: &[&str]| Cli::try_parse_from([&["escrow", "take"], args].concat());
+
+        assert!(parse(&["--seed", "42"]).is_ok());
+        assert!(parse(&["--escrow", "11111111111111111111111111111111"]).is_ok());
+        // --maker alone does not name an escrow, and --escrow excludes the rest
+        assert!(parse(&["--maker", "11111111111111111111111111111111"]).is_err());
+        assert!(parse(&["--escrow", "11111111111111111111111111111111", "--seed", "1"]).is_err());
+    }
+
+    #[test]
+    fn flags_override_the_profile() {
+        let cli = Cli::try_parse_from(["escrow", "list", "--profile", "mainnet", "--url", "http://127.0.0.1:8899"]).unwrap();
+        let profile = config::Profile {
+            url: Some("https://api.mainnet-beta.solana.com".to_string()),
+            commitment: Some("finalized".to_string()),
+            ..Default::default()
+        };
+
+        let settings = context::Settings::resolve(&cli.global, profile);
+        assert_eq!(settings.url, "http://127.0.0.1:8899");
+        assert_eq!(settings.commitment, "finalized");
+    }
+}
@@ -0,0 +1,155 @@
+//! `--output json`: what each command reports, in a shape scripts can parse.
+//!
+//! Addresses and signatures are base58 strings and amounts are base units, exactly as
+//! the text output shows them.
+
+use {
+    crate::display,
+    escrow_client::{interface::accounts::Escrow, Fetched, Simulation},
+    serde::Serialize,
+};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Output {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Prints `value` as pretty JSON on one document
+pub fn json<T: Serialize>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Decoded escrow account
+#[derive(Serialize)]
+pub struct EscrowState {
+    pub address: String,
+    pub seed: u64,
+    pub maker: String,
+    pub mint_a: String,
+    pub mint_b: String,
+    pub status: &'static str,
+    pub receive: u64,
+    pub deposit_total: u64,
+    pub filled: u64,
+    pub remaining: u64,
+    pub price: f64,
+    pub created_at: i64,
+    /// `null` when the escrow never expires
+    pub expires_at: Option<i64>,
+    pub bump: u8,
+    pub metadata: Vec<u8>,
+}
+
+impl From<&Fetched<Escrow>> for EscrowState {
+    fn from(escrow: &Fetched<Escrow>) -> Self {
+        let state = &escrow.account;
+        Self {
+            address: escrow.address.to_string(),
+            seed: state.seed,
+            maker: state.maker.to_string(),
+            mint_a: state.mint_a.to_string(),
+            mint_b: state.mint_b.to_string(),
+            status: display::status(&state.status),
+            receive: state.receive,
+            deposit_total: state.deposit_total,
+            filled: state.filled,
+            remaining: state.remaining,
+            price: display::price(state),
+            created_at: state.created_at,
+            expires_at: (state.expires_at != 0).then_some(state.expires_at),
+            bump: state.bump,
+            metadata: state.metadata.clone(),
+        }
+    }
+}
+
+/// `escrow inspect`: the escrow, its vault and where it stands at cluster time
+#[derive(Serialize)]
+pub struct Inspection {
+    #[serde(flatten)]
+    pub escrow: EscrowState,
+    pub token_program: String,
+    pub vault: String,
+    /// `null` when the vault account does not exist
+    pub vault_balance: Option<u64>,
+    /// Lamports held by the escrow account
+    pub rent: u64,
+    pub expired: bool,
+    pub cluster_time: i64,
+    pub slot: u64,
+}
+
+/// Result of a mutating command that was sent
+#[derive(Serialize)]
+pub struct Sent {
+    pub escrow: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vault: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    pub signature: String,
+}
+
+/// Result of a `--dry-run`
+#[derive(Serialize)]
+pub struct DryRun {
+    pub fee: u64,
+    pub compute_units: Option<u64>,
+    pub changes: Vec<Change>,
+    pub logs: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct Change {
+    pub address: String,
+    pub lamports: i64,
+    /// `null` for accounts that are not token accounts
+    pub tokens: Option<i128>,
+}
+
+impl From<&Simulation> for DryRun {
+    fn from(simulation: &Simulation) -> Self {
+        Self {
+            fee: simulation.fee,
+            compute_units: simulation.compute_units,
+            changes: simulation
+                .changes
+                .iter()
+                .map(|change| Change { address: change.address.to_string(), lamports: change.lamports, tokens: change.tokens })
+                .collect(),
+            logs: simulation.logs.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, escrow_client::BalanceChange, solana_pubkey::Pubkey};
+
+    #[test]
+    fn dry_run_serializes_addresses_as_base58() {
+        let address = Pubkey::new_unique();
+        let simulation = Simulation {
+            fee: 5_000,
+            compute_units: Some(41_000),
+            changes: vec![BalanceChange { address, lamports: -5_000, tokens: None }],
+            logs: vec!["Program log: Instruction: Take".to_string()],
+        };
+
+        let value = serde_json::to_value(DryRun::from(&simulation)).unwrap();
+        assert_eq!(value["fee"], 5_000);
+        assert_eq!(value["compute_units"], 41_000);
+        assert_eq!(value["changes"][0]["address"], address.to_string());
+        assert_eq!(value["changes"][0]["lamports"], -5_000);
+        assert!(value["changes"][0]["tokens"].is_null());
+    }
+
+    #[test]
+    fn sent_leaves_out_what_the_command_does_not_know() {
+        let sent = Sent { escrow: "escrow".to_string(), vault: None, seed: None, signature: "signature".to_string() };
+        assert_eq!(serde_json::to_string(&sent).unwrap(), r#"{"escrow":"escrow","signature":"signature"}"#);
+    }
+}
@@ -0,0 +1,82 @@
+//! Where the signing key lives: a keypair file, or a hardware wallet addressed the
+//! way the Solana CLI does it, `usb://ledger` or `usb://ledger/<WALLET_ID>?key=<ACCOUNT>/<CHANGE>`.
+//!
+//! Hardware wallets sign on the device, so every transaction has to be approved there.
+
+use {
+    anyhow::{anyhow, bail, Context as _},
+    solana_derivation_path::DerivationPath,
+    solana_keypair::read_keypair_file,
+    solana_remote_wallet::{locator::Locator, remote_keypair::generate_remote_keypair, remote_wallet::maybe_wallet_manager},
+    solana_signer::Signer,
+    std::path::PathBuf,
+};
+
+const USB_SCHEME: &str = "usb://";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignerSource {
+    File(PathBuf),
+    Usb { locator: String, derivation_path: Option<String> },
+}
+
+impl SignerSource {
+    pub fn parse(source: PathBuf) -> anyhow::Result<Self> {
+        let Some(uri) = source.to_str().filter(|source| source.starts_with(USB_SCHEME)) else {
+            return Ok(Self::File(source));
+        };
+
+        let (locator, query) = uri.split_once('?').unwrap_or((uri, ""));
+        let mut derivation_path = None;
+        for (name, value) in query.split('&').filter(|pair| !pair.is_empty()).map(|pair| pair.split_once('=').unwrap_or((pair, ""))) {
+            match name {
+                "key" => derivation_path = Some(value.to_string()),
+                _ => bail!("Unknown query parameter {} in {}, only key is supported", name, uri),
+            }
+        }
+
+        Ok(Self::Usb { locator: locator.to_string(), derivation_path })
+    }
+
+    pub fn load(&self) -> anyhow::Result<Box<dyn Signer>> {
+        match self {
+            Self::File(path) => {
+                let keypair = read_keypair_file(path)
+                    .map_err(|err| anyhow!("{}", err))
+                    .with_context(|| format!("Failed to read keypair {}", path.display()))?;
+                Ok(Box::new(keypair))
+            }
+            Self::Usb { locator, derivation_path } => {
+                let locator = Locator::new_from_path(locator.as_str()).with_context(|| format!("Invalid wallet {}", locator))?;
+                let derivation_path = match derivation_path {
+                    Some(key) => DerivationPath::from_key_str(key).with_context(|| format!("Invalid key {}", key))?,
+                    None => DerivationPath::default(),
+                };
+
+                let wallet_manager = maybe_wallet_manager()?.ok_or_else(|| anyhow!("No hardware wallet found, is it plugged in and unlocked?"))?;
+                let keypair = generate_remote_keypair(locator, derivation_path, &wallet_manager, false, "signer")
+                    .context("Failed to open the hardware wallet, is the Solana app open?")?;
+                Ok(Box::new(keypair))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_files_and_usb_wallets() {
+        assert_eq!(SignerSource::parse("id.json".into()).unwrap(), SignerSource::File("id.json".into()));
+        assert_eq!(
+            SignerSource::parse("usb://ledger".into()).unwrap(),
+            SignerSource::Usb { locator: "usb://ledger".to_string(), derivation_path: None },
+        );
+        assert_eq!(
+            SignerSource::parse("usb://ledger?key=1/0".into()).unwrap(),
+            SignerSource::Usb { locator: "usb://ledger".to_string(), derivation_path: Some("1/0".to_string()) },
+        );
+        assert!(SignerSource::parse("usb://ledger?account=1".into()).is_err());
+    }
+}
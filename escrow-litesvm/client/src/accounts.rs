@@ -0,0 +1,315 @@
+//! Fetching and decoding program accounts over RPC.
+
+use {
+    crate::error::{ClientError, Result},
+    anchor_lang::{
+        prelude::{borsh, Pubkey},
+        AccountDeserialize,
+        Discriminator,
+    },
+    escrow_interface::{
+        accounts::{Escrow, FillReceipt},
+        layout::{DISCRIMINATOR_OFFSET, MAKER_OFFSET, MINT_A_OFFSET, MINT_B_OFFSET, STATUS_OFFSET},
+        types::EscrowStatus,
+        ID as PROGRAM_ID,
+    },
+    solana_account::Account,
+    solana_account_decoder_client_types::UiAccountEncoding,
+    solana_commitment_config::CommitmentConfig,
+    solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client_api::{
+        config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        filter::{Memcmp, RpcFilterType},
+    },
+};
+
+/// A decoded account together with its address
+#[derive(Clone, Debug)]
+pub struct Fetched<T> {
+    pub address: Pubkey,
+    pub account: T,
+}
+
+/// Decodes account data, discriminator included
+pub fn decode<T: AccountDeserialize>(address: &Pubkey, data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..]).map_err(|source| ClientError::Decode { address: *address, source })
+}
+
+/// Checks a fetched account belongs to the program before decoding it
+pub(crate) fn decode_fetched<T: AccountDeserialize>(address: &Pubkey, account: Option<Account>) -> Result<Fetched<T>> {
+    let account = account.ok_or(ClientError::AccountNotFound(*address))?;
+
+    if account.owner != PROGRAM_ID {
+        return Err(ClientError::WrongOwner { address: *address, owner: account.owner });
+    }
+
+    Ok(Fetched { address: *address, account: decode(address, &account.data)? })
+}
+
+fn fetch<T: AccountDeserialize>(rpc_client: &RpcClient, address: &Pubkey) -> Result<Fetched<T>> {
+    let account = rpc_client.get_account_with_commitment(address, rpc_client.commitment())?.value;
+    decode_fetched(address, account)
+}
+
+pub fn fetch_escrow(rpc_client: &RpcClient, address: &Pubkey) -> Result<Fetched<Escrow>> {
+    fetch(rpc_client, address)
+}
+
+pub fn fetch_receipt(rpc_client: &RpcClient, address: &Pubkey) -> Result<Fetched<FillReceipt>> {
+    fetch(rpc_client, address)
+}
+
+/// Narrows [`fetch_escrows`] down with memcmp filters on the fixed-offset fields
+#[derive(Clone, Default)]
+pub struct EscrowFilter {
+    pub maker: Option<Pubkey>,
+    pub mint_a: Option<Pubkey>,
+    pub mint_b: Option<Pubkey>,
+    pub status: Option<EscrowStatus>,
+}
+
+impl EscrowFilter {
+    /// Open escrows offering `mint_a` for `mint_b`
+    pub fn open_pair(mint_a: Pubkey, mint_b: Pubkey) -> Self {
+        Self { mint_a: Some(mint_a), mint_b: Some(mint_b), status: Some(EscrowStatus::Open), ..Self::default() }
+    }
+
+    /// Every escrow made by `maker`, whatever its status
+    pub fn by_maker(maker: Pubkey) -> Self {
+        Self { maker: Some(maker), ..Self::default() }
+    }
+
+    fn rpc_filters(&self) -> Vec<RpcFilterType> {
+        let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(DISCRIMINATOR_OFFSET, Escrow::DISCRIMINATOR))];
+
+        for (offset, key) in [(MAKER_OFFSET, self.maker), (MINT_A_OFFSET, self.mint_a), (MINT_B_OFFSET, self.mint_b)] {
+            if let Some(key) = key {
+                filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(offset, key.as_ref())));
+            }
+        }
+
+        // A fieldless enum serializes to its variant index in a single byte
+        if let Some(status) = &self.status {
+            let status = borsh::to_vec(status).expect("Status always serializes");
+            filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(STATUS_OFFSET, &status)));
+        }
+
+        filters
+    }
+
+    pub(crate) fn program_accounts_config(&self, commitment: CommitmentConfig) -> RpcProgramAccountsConfig {
+        RpcProgramAccountsConfig {
+            filters: Some(self.rpc_filters()),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(commitment),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        }
+    }
+}
+
+/// Every open escrow matching `filter`, in one `getProgramAccounts` request
+pub fn fetch_escrows(rpc_client: &RpcClient, filter: &EscrowFilter) -> Result<Vec<Fetched<Escrow>>> {
+    let config = filter.program_accounts_config(rpc_client.commitment());
+
+    rpc_client
+        .get_program_accounts_with_config(&PROGRAM_ID, config)?
+        .into_iter()
+        .map(|(address, account)| Ok(Fetched { address, account: decode(&address, &account.data)? }))
+        .collect()
+}
+
+/// Sorts cheapest first by price per unit of mint A, `receive / deposit_total`,
+/// compared by cross-multiplication so no precision is lost
+pub fn sort_by_price(escrows: &mut [Fetched<Escrow>]) {
+    escrows.sort_by(|a, b| {
+        let a_price = a.account.receive as u128 * b.account.deposit_total as u128;
+        let b_price = b.account.receive as u128 * a.account.deposit_total as u128;
+        a_price.cmp(&b_price).then_with(|| a.address.cmp(&b.address))
+    });
+}
+
+/// Open escrows offering `mint_a` for `mint_b`, cheapest first. Expired escrows stay
+/// open until closed, so check `expires_at` before taking one.
+pub fn find_open_escrows(rpc_client: &RpcClient, mint_a: &Pubkey, mint_b: &Pubkey) -> Result<Vec<Fetched<Escrow>>> {
+    let mut escrows = fetch_escrows(rpc_client, &EscrowFilter::open_pair(*mint_a, *mint_b))?;
+    sort_by_price(&mut escrows);
+    Ok(escrows)
+}
+
+/// Every escrow made by `maker`, cheapest first
+pub fn find_by_maker(rpc_client: &RpcClient, maker: &Pubkey) -> Result<Vec<Fetched<Escrow>>> {
+    let mut escrows = fetch_escrows(rpc_client, &EscrowFilter::by_maker(*maker))?;
+    sort_by_price(&mut escrows);
+    Ok(escrows)
+}
+
+/// Whether `close_expired` would accept `escrow` at cluster time `now`: it has an
+/// expiry, that expiry has passed, and it is open or reserved
+pub fn is_expired(escrow: &Escrow, now: i64) -> bool {
+    escrow.expires_at != 0 && now >= escrow.expires_at && matches!(escrow.status, EscrowStatus::Open | EscrowStatus::Reserved)
+}
+
+/// Escrows `close_expired` can be cranked on at cluster time `now`, oldest expiry
+/// first. There is no RPC filter for a timestamp comparison, so every escrow is
+/// fetched and the expiry checked here.
+pub fn find_expired(rpc_client: &RpcClient, now: i64) -> Result<Vec<Fetched<Escrow>>> {
+    Ok(expired(fetch_escrows(rpc_client, &EscrowFilter::default())?, now))
+}
+
+pub(crate) fn expired(escrows: Vec<Fetched<Escrow>>, now: i64) -> Vec<Fetched<Escrow>> {
+    let mut expired: Vec<_> = escrows.into_iter().filter(|escrow| is_expired(&escrow.account, now)).collect();
+    expired.sort_by_key(|escrow| (escrow.account.expires_at, escrow.address));
+    expired
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        anchor_lang::AccountSerialize,
+        escrow_interface::types::TakerGate,
+    };
+
+    #[test]
+    fn decodes_escrow_written_by_the_program() {
+        let escrow = ::anchor_escrow::state::Escrow {
+            seed: 7,
+            maker: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            status: ::anchor_escrow::state::EscrowStatus::Open,
+            receive: 20,
+            deposit_total: 10,
+            filled: 4,
+            remaining: 6,
+            created_at: 1_700_000_000,
+            expires_at: 0,
+            starts_at: 0,
+            bump: 255,
+            gate: ::anchor_escrow::state::TakerGate::None,
+            operator: Pubkey::default(),
+            fill_or_kill: false,
+            reserved_for: Pubkey::default(),
+            nonce: 0,
+            metadata: b"tag".to_vec(),
+        };
+        let mut data = Vec::new();
+        escrow.try_serialize(&mut data).unwrap();
+
+        let address = Pubkey::new_unique();
+        let decoded: Escrow = decode(&address, &data).unwrap();
+        assert_eq!(decoded.maker, escrow.maker);
+        assert_eq!(decoded.remaining, 6);
+        assert_eq!(decoded.metadata, b"tag");
+
+        // A receipt's discriminator does not match
+        assert!(matches!(decode::<FillReceipt>(&address, &data), Err(ClientError::Decode { .. })));
+    }
+
+    #[test]
+    fn filters_on_discriminator_and_requested_keys_only() {
+        let filter = EscrowFilter { maker: Some(Pubkey::new_unique()), ..EscrowFilter::default() };
+        let filters = filter.rpc_filters();
+
+        assert_eq!(filters.len(), 2);
+        let RpcFilterType::Memcmp(maker) = &filters[1] else { panic!("Expected a memcmp filter") };
+        assert_eq!(maker.offset(), MAKER_OFFSET);
+    }
+
+    #[test]
+    fn open_pair_filters_on_both_mints_and_open_status() {
+        let filters = EscrowFilter::open_pair(Pubkey::new_unique(), Pubkey::new_unique()).rpc_filters();
+
+        let offsets: Vec<usize> = filters
+            .iter()
+            .map(|filter| match filter {
+                RpcFilterType::Memcmp(memcmp) => memcmp.offset(),
+                _ => panic!("Expected memcmp filters only"),
+            })
+            .collect();
+        assert_eq!(offsets, vec![DISCRIMINATOR_OFFSET, MINT_A_OFFSET, MINT_B_OFFSET, STATUS_OFFSET]);
+
+        // The status byte matches what the program writes for an open escrow
+        let RpcFilterType::Memcmp(status) = &filters[3] else { unreachable!() };
+        let open = borsh::to_vec(&::anchor_escrow::state::EscrowStatus::Open).unwrap();
+        assert_eq!(status.bytes().unwrap().as_ref(), open.as_slice());
+    }
+
+    #[test]
+    fn sorts_cheapest_price_per_unit_first() {
+        let escrow = |deposit_total: u64, receive: u64| Fetched {
+            address: Pubkey::new_unique(),
+            account: Escrow {
+                seed: 0,
+                maker: Pubkey::default(),
+                mint_a: Pubkey::default(),
+                mint_b: Pubkey::default(),
+                status: EscrowStatus::Open,
+                receive,
+                deposit_total,
+                filled: 0,
+                remaining: deposit_total,
+                created_at: 0,
+                expires_at: 0,
+                starts_at: 0,
+                bump: 0,
+                gate: TakerGate::None,
+                operator: Pubkey::default(),
+                fill_or_kill: false,
+                reserved_for: Pubkey::default(),
+                nonce: 0,
+                metadata: Vec::new(),
+            },
+        };
+
+        // 3.0, 0.5, 2.0 and an overflow-prone 1.0 per unit
+        let mut escrows = vec![escrow(10, 30), escrow(10, 5), escrow(3, 6), escrow(u64::MAX, u64::MAX)];
+        sort_by_price(&mut escrows);
+
+        let prices: Vec<(u64, u64)> = escrows.iter().map(|e| (e.account.deposit_total, e.account.receive)).collect();
+        assert_eq!(prices, vec![(10, 5), (u64::MAX, u64::MAX), (3, 6), (10, 30)]);
+    }
+
+    #[test]
+    fn finds_expired_escrows_oldest_first() {
+        let escrow = |expires_at: i64, status: EscrowStatus| Fetched {
+            address: Pubkey::new_unique(),
+            account: Escrow {
+                seed: 0,
+                maker: Pubkey::default(),
+                mint_a: Pubkey::default(),
+                mint_b: Pubkey::default(),
+                status,
+                receive: 1,
+                deposit_total: 1,
+                filled: 0,
+                remaining: 1,
+                created_at: 0,
+                expires_at,
+                starts_at: 0,
+                bump: 0,
+                gate: TakerGate::None,
+                operator: Pubkey::default(),
+                fill_or_kill: false,
+                reserved_for: Pubkey::default(),
+                nonce: 0,
+                metadata: Vec::new(),
+            },
+        };
+
+        let escrows = vec![
+            escrow(90, EscrowStatus::Open),
+            escrow(0, EscrowStatus::Open),
+            escrow(50, EscrowStatus::Reserved),
+            escrow(100, EscrowStatus::Open),
+            escrow(101, EscrowStatus::Open),
+        ];
+
+        // Expiring at exactly `now` counts, as it does on chain
+        let expiries: Vec<i64> = expired(escrows, 100).iter().map(|escrow| escrow.account.expires_at).collect();
+        assert_eq!(expiries, vec![50, 90, 100]);
+    }
+}
@@ -0,0 +1,690 @@
+//! Typed builders for the fungible escrow instructions. Each derives the escrow, vault,
+//! receipt and associated token accounts itself through [`pda::DerivedAccounts`], so
+//! callers only supply the parties, the mints and the terms.
+
+use {
+    crate::{accounts::Fetched, pda::{self, DerivedAccounts}},
+    anchor_lang::{
+        prelude::Pubkey,
+        solana_program::{instruction::{AccountMeta, Instruction}, sysvar},
+        system_program, AnchorSerialize, InstructionData, ToAccountMetas,
+    },
+    anchor_spl::{associated_token::{self, get_associated_token_address_with_program_id}, token},
+    escrow_interface::{
+        accounts::Escrow,
+        client::{accounts, args},
+        constants::QUOTE_DOMAIN,
+        types::Quote,
+        ID as PROGRAM_ID,
+    },
+};
+
+pub struct MakeBuilder {
+    maker: Pubkey,
+    seed: u64,
+    mint_a: Pubkey,
+    deposit: u64,
+    mint_b: Pubkey,
+    receive: u64,
+    expires_at: i64,
+    starts_at: i64,
+    token_program: Pubkey,
+}
+
+impl MakeBuilder {
+    /// Offers `deposit` of `mint_a` for `receive` of `mint_b`, takeable right away and
+    /// never expiring
+    pub fn new(maker: Pubkey, seed: u64, mint_a: Pubkey, deposit: u64, mint_b: Pubkey, receive: u64) -> Self {
+        Self { maker, seed, mint_a, deposit, mint_b, receive, expires_at: 0, starts_at: 0, token_program: token::ID }
+    }
+
+    /// Unix timestamp after which the escrow can no longer be taken
+    pub fn expires_at(mut self, expires_at: i64) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
+    /// Unix timestamp before which the escrow cannot be taken yet
+    pub fn starts_at(mut self, starts_at: i64) -> Self {
+        self.starts_at = starts_at;
+        self
+    }
+
+    /// Locks the escrow against takes for `seconds` after cluster time `now`, which
+    /// sets `starts_at`
+    pub fn with_timelock(self, now: i64, seconds: i64) -> Self {
+        self.starts_at(now.saturating_add(seconds))
+    }
+
+    pub fn token_program(mut self, token_program: Pubkey) -> Self {
+        self.token_program = token_program;
+        self
+    }
+
+    pub fn escrow(&self) -> Pubkey {
+        pda::escrow_address(&self.maker, self.seed)
+    }
+
+    pub fn derived(&self) -> DerivedAccounts {
+        DerivedAccounts::new(&self.maker, self.seed, &self.mint_a, &self.mint_b, &self.token_program)
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let derived = self.derived();
+
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts::Make {
+                maker: self.maker,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                maker_ata_a: derived.maker_ata_a,
+                escrow: derived.escrow,
+                vault: derived.vault,
+                associated_token_program: associated_token::ID,
+                token_program: self.token_program,
+                system_program: system_program::ID,
+                maker_counter: pda::maker_counter_address(&self.maker),
+            }.to_account_metas(None),
+            data: args::Make {
+                seed: self.seed,
+                deposit: self.deposit,
+                receive: self.receive,
+                expires_at: self.expires_at,
+                starts_at: self.starts_at,
+            }.data(),
+        }
+    }
+}
+
+/// The accounts a take of an escrow gated on a collection passes after its own: the
+/// taker's token account holding `nft_mint`, and that NFT's metadata
+pub fn collection_gate_accounts(taker: &Pubkey, nft_mint: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(get_associated_token_address_with_program_id(taker, nft_mint, &token::ID), false),
+        AccountMeta::new_readonly(pda::metadata_address(nft_mint), false),
+    ]
+}
+
+/// The account a take of an escrow gated on an attestation passes after its own: the
+/// attestation of `schema` about the taker under `credential`
+pub fn attestation_gate_accounts(taker: &Pubkey, credential: &Pubkey, schema: &Pubkey) -> Vec<AccountMeta> {
+    vec![AccountMeta::new_readonly(pda::attestation_address(credential, schema, taker), false)]
+}
+
+pub struct TakeBuilder {
+    taker: Pubkey,
+    escrow: Pubkey,
+    maker: Pubkey,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    amount: Option<u64>,
+    unwrap_sol: bool,
+    gate_accounts: Vec<AccountMeta>,
+    token_program: Pubkey,
+}
+
+impl TakeBuilder {
+    /// Fills the whole remaining amount of `escrow`
+    pub fn new(taker: Pubkey, escrow: Pubkey, maker: Pubkey, mint_a: Pubkey, mint_b: Pubkey) -> Self {
+        Self { taker, escrow, maker, mint_a, mint_b, amount: None, unwrap_sol: false, gate_accounts: Vec::new(), token_program: token::ID }
+    }
+
+    pub fn for_escrow(taker: Pubkey, escrow: &Fetched<Escrow>) -> Self {
+        Self::new(taker, escrow.address, escrow.account.maker, escrow.account.mint_a, escrow.account.mint_b)
+    }
+
+    /// Fills only `amount` of mint A, leaving the rest of the escrow open
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    /// Pays the maker in native SOL instead of wSOL, mint B must be the native mint
+    pub fn unwrap_sol(mut self) -> Self {
+        self.unwrap_sol = true;
+        self
+    }
+
+    /// Proves the taker holds `nft_mint`, for escrows gated on its collection
+    pub fn collection_nft(mut self, nft_mint: Pubkey) -> Self {
+        self.gate_accounts = collection_gate_accounts(&self.taker, &nft_mint);
+        self
+    }
+
+    /// Passes the taker's attestation, for escrows gated on `credential` and `schema`
+    pub fn attestation(mut self, credential: Pubkey, schema: Pubkey) -> Self {
+        self.gate_accounts = attestation_gate_accounts(&self.taker, &credential, &schema);
+        self
+    }
+
+    pub fn token_program(mut self, token_program: Pubkey) -> Self {
+        self.token_program = token_program;
+        self
+    }
+
+    pub fn taker(&self) -> Pubkey {
+        self.taker
+    }
+
+    /// Where the taker receives mint A
+    pub fn taker_ata_a(&self) -> Pubkey {
+        get_associated_token_address_with_program_id(&self.taker, &self.mint_a, &self.token_program)
+    }
+
+    /// Where the taker pays mint B from
+    pub fn taker_ata_b(&self) -> Pubkey {
+        get_associated_token_address_with_program_id(&self.taker, &self.mint_b, &self.token_program)
+    }
+
+    /// Every address the take passes, the taker's included
+    pub fn derived(&self) -> DerivedAccounts {
+        DerivedAccounts::for_escrow(&self.escrow, &self.maker, &self.mint_a, &self.mint_b, &self.token_program).with_taker(&self.taker)
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let derived = self.derived();
+        let taker = derived.taker.expect("derived with the taker");
+
+        // The maker is paid either into its ATA or through the temporary wSOL account
+        let (maker_ata_b, maker_wsol) = if self.unwrap_sol {
+            (None, Some(derived.wsol))
+        } else {
+            (Some(derived.maker_ata_b), None)
+        };
+
+        let accounts = accounts::Take {
+            taker: self.taker,
+            maker: self.maker,
+            mint_a: self.mint_a,
+            mint_b: self.mint_b,
+            taker_ata_a: taker.taker_ata_a,
+            taker_ata_b: taker.taker_ata_b,
+            maker_ata_b,
+            maker_wsol,
+            escrow: self.escrow,
+            vault: derived.vault,
+            receipt: taker.receipt,
+            associated_token_program: associated_token::ID,
+            token_program: self.token_program,
+            system_program: system_program::ID,
+        };
+
+        // `take_partial` shares the `Take` accounts
+        let data = match self.amount {
+            Some(amount) => args::TakePartial { amount }.data(),
+            None => args::Take {}.data(),
+        };
+
+        let mut accounts = accounts.to_account_metas(None);
+        accounts.extend_from_slice(&self.gate_accounts);
+
+        Instruction { program_id: PROGRAM_ID, accounts, data }
+    }
+}
+
+/// `take_sol`, for escrows asking native SOL. The taker pays the maker straight from
+/// its wallet, so no mint B accounts are passed. The taker's ATA for mint A must exist.
+pub struct TakeSolBuilder {
+    taker: Pubkey,
+    escrow: Pubkey,
+    maker: Pubkey,
+    mint_a: Pubkey,
+    amount: u64,
+    gate_accounts: Vec<AccountMeta>,
+    token_program: Pubkey,
+}
+
+impl TakeSolBuilder {
+    pub fn new(taker: Pubkey, escrow: Pubkey, maker: Pubkey, mint_a: Pubkey, amount: u64) -> Self {
+        Self { taker, escrow, maker, mint_a, amount, gate_accounts: Vec::new(), token_program: token::ID }
+    }
+
+    /// Fills the whole remaining amount of `escrow`
+    pub fn for_escrow(taker: Pubkey, escrow: &Fetched<Escrow>) -> Self {
+        Self::new(taker, escrow.address, escrow.account.maker, escrow.account.mint_a, escrow.account.remaining)
+    }
+
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    /// Proves the taker holds `nft_mint`, for escrows gated on its collection
+    pub fn collection_nft(mut self, nft_mint: Pubkey) -> Self {
+        self.gate_accounts = collection_gate_accounts(&self.taker, &nft_mint);
+        self
+    }
+
+    /// Passes the taker's attestation, for escrows gated on `credential` and `schema`
+    pub fn attestation(mut self, credential: Pubkey, schema: Pubkey) -> Self {
+        self.gate_accounts = attestation_gate_accounts(&self.taker, &credential, &schema);
+        self
+    }
+
+    pub fn token_program(mut self, token_program: Pubkey) -> Self {
+        self.token_program = token_program;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let mut accounts = accounts::TakeSol {
+            taker: self.taker,
+            maker: self.maker,
+            mint_a: self.mint_a,
+            taker_ata_a: get_associated_token_address_with_program_id(&self.taker, &self.mint_a, &self.token_program),
+            escrow: self.escrow,
+            vault: pda::vault_address(&self.escrow, &self.mint_a, &self.token_program),
+            receipt: pda::receipt_address(&self.escrow, &self.taker),
+            token_program: self.token_program,
+            system_program: system_program::ID,
+        }.to_account_metas(None);
+        accounts.extend_from_slice(&self.gate_accounts);
+
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts,
+            data: args::TakeSol { amount: self.amount }.data(),
+        }
+    }
+}
+
+/// The bytes a maker signs to hand `quote` to its taker, as the program rebuilds them
+pub fn quote_message(quote: &Quote) -> Vec<u8> {
+    let mut message = QUOTE_DOMAIN.to_vec();
+    // Serializing into a Vec cannot fail
+    quote.serialize(&mut message).unwrap();
+    message
+}
+
+/// `take_quote`, filling at a price the maker signed off-chain. The maker signs
+/// [`quote_message`] and passes the signature to the taker named in the quote, who
+/// submits it in front of the take so the program can check it.
+pub struct TakeQuoteBuilder {
+    maker: Pubkey,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    quote: Quote,
+    signature: [u8; 64],
+    token_program: Pubkey,
+}
+
+impl TakeQuoteBuilder {
+    pub fn new(maker: Pubkey, mint_a: Pubkey, mint_b: Pubkey, quote: Quote, signature: [u8; 64]) -> Self {
+        Self { maker, mint_a, mint_b, quote, signature, token_program: token::ID }
+    }
+
+    pub fn token_program(mut self, token_program: Pubkey) -> Self {
+        self.token_program = token_program;
+        self
+    }
+
+    /// The ed25519 verification of the maker's signature, which must come right before the take
+    pub fn signature_instruction(&self) -> Instruction {
+        solana_ed25519_program::new_ed25519_instruction_with_signature(
+            &quote_message(&self.quote),
+            &self.signature,
+            &self.maker.to_bytes(),
+        )
+    }
+
+    pub fn take_instruction(&self) -> Instruction {
+        let (escrow, taker) = (self.quote.escrow, self.quote.taker);
+        let derived = DerivedAccounts::for_escrow(&escrow, &self.maker, &self.mint_a, &self.mint_b, &self.token_program).with_taker(&taker);
+        let taker_accounts = derived.taker.expect("derived with the taker");
+
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts::TakeQuote {
+                taker,
+                maker: self.maker,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                taker_ata_a: taker_accounts.taker_ata_a,
+                taker_ata_b: taker_accounts.taker_ata_b,
+                maker_ata_b: derived.maker_ata_b,
+                escrow,
+                vault: derived.vault,
+                receipt: taker_accounts.receipt,
+                instructions: sysvar::instructions::ID,
+                associated_token_program: associated_token::ID,
+                token_program: self.token_program,
+                system_program: system_program::ID,
+            }.to_account_metas(None),
+            data: args::TakeQuote { quote: self.quote.clone() }.data(),
+        }
+    }
+
+    /// Both instructions, in the order the program requires
+    pub fn instructions(&self) -> [Instruction; 2] {
+        [self.signature_instruction(), self.take_instruction()]
+    }
+}
+
+pub struct RefundBuilder {
+    maker: Pubkey,
+    escrow: Pubkey,
+    mint_a: Pubkey,
+    operator: Option<Pubkey>,
+    token_program: Pubkey,
+}
+
+impl RefundBuilder {
+    pub fn new(maker: Pubkey, seed: u64, mint_a: Pubkey) -> Self {
+        Self { maker, escrow: pda::escrow_address(&maker, seed), mint_a, operator: None, token_program: token::ID }
+    }
+
+    pub fn for_escrow(escrow: &Fetched<Escrow>) -> Self {
+        Self { maker: escrow.account.maker, escrow: escrow.address, mint_a: escrow.account.mint_a, operator: None, token_program: token::ID }
+    }
+
+    /// Signed by the escrow's registered operator instead of the maker, through
+    /// `operator_refund`. The deposit and rent still go to the maker.
+    pub fn operator(mut self, operator: Pubkey) -> Self {
+        self.operator = Some(operator);
+        self
+    }
+
+    pub fn token_program(mut self, token_program: Pubkey) -> Self {
+        self.token_program = token_program;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let maker_ata_a = get_associated_token_address_with_program_id(&self.maker, &self.mint_a, &self.token_program);
+        let vault = pda::vault_address(&self.escrow, &self.mint_a, &self.token_program);
+
+        let (accounts, data) = match self.operator {
+            Some(operator) => (
+                accounts::OperatorRefund {
+                    operator,
+                    maker: self.maker,
+                    mint_a: self.mint_a,
+                    maker_ata_a,
+                    escrow: self.escrow,
+                    vault,
+                    token_program: self.token_program,
+                }.to_account_metas(None),
+                args::OperatorRefund {}.data(),
+            ),
+            None => (
+                accounts::Refund {
+                    maker: self.maker,
+                    mint_a: self.mint_a,
+                    maker_ata_a,
+                    escrow: self.escrow,
+                    vault,
+                    token_program: self.token_program,
+                }.to_account_metas(None),
+                args::Refund {}.data(),
+            ),
+        };
+
+        Instruction { program_id: PROGRAM_ID, accounts, data }
+    }
+}
+
+/// `close_expired`, the permissionless crank returning an expired escrow's deposit
+/// to its maker. The cranker earns `CLOSE_EXPIRED_BOUNTY` out of the escrow's rent,
+/// and pays the fee and the maker's ATA rent if it was closed.
+pub struct CloseExpiredBuilder {
+    cranker: Pubkey,
+    maker: Pubkey,
+    escrow: Pubkey,
+    mint_a: Pubkey,
+    token_program: Pubkey,
+}
+
+impl CloseExpiredBuilder {
+    pub fn new(cranker: Pubkey, maker: Pubkey, seed: u64, mint_a: Pubkey) -> Self {
+        Self { cranker, maker, escrow: pda::escrow_address(&maker, seed), mint_a, token_program: token::ID }
+    }
+
+    pub fn for_escrow(cranker: Pubkey, escrow: &Fetched<Escrow>) -> Self {
+        Self { cranker, maker: escrow.account.maker, escrow: escrow.address, mint_a: escrow.account.mint_a, token_program: token::ID }
+    }
+
+    pub fn token_program(mut self, token_program: Pubkey) -> Self {
+        self.token_program = token_program;
+        self
+    }
+
+    pub fn escrow(&self) -> Pubkey {
+        self.escrow
+    }
+
+    /// The ATA the deposit goes back to, recreated at the cranker's expense if missing
+    pub fn maker_ata_a(&self) -> Pubkey {
+        get_associated_token_address_with_program_id(&self.maker, &self.mint_a, &self.token_program)
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts::CloseExpired {
+                cranker: self.cranker,
+                maker: self.maker,
+                mint_a: self.mint_a,
+                maker_ata_a: self.maker_ata_a(),
+                escrow: self.escrow,
+                vault: pda::vault_address(&self.escrow, &self.mint_a, &self.token_program),
+                associated_token_program: associated_token::ID,
+                token_program: self.token_program,
+                system_program: system_program::ID,
+            }.to_account_metas(None),
+            data: args::CloseExpired {}.data(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The builders must produce exactly what the program's own account structs do
+    #[test]
+    fn take_accounts_match_program() {
+        let (taker, maker, mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = pda::escrow_address(&maker, 3);
+        let ata = |owner: &Pubkey, mint: &Pubkey| anchor_spl::associated_token::get_associated_token_address(owner, mint);
+
+        let expected = ::anchor_escrow::accounts::Take {
+            taker,
+            maker,
+            mint_a,
+            mint_b,
+            taker_ata_a: ata(&taker, &mint_a),
+            taker_ata_b: ata(&taker, &mint_b),
+            maker_ata_b: None,
+            maker_wsol: Some(pda::wsol_address(&escrow)),
+            escrow,
+            vault: ata(&escrow, &mint_a),
+            receipt: pda::receipt_address(&escrow, &taker),
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        }.to_account_metas(None);
+
+        let instruction = TakeBuilder::new(taker, escrow, maker, mint_a, mint_b).amount(5).unwrap_sol().instruction();
+        assert_eq!(instruction.accounts, expected);
+        assert_eq!(instruction.data, ::anchor_escrow::instruction::TakePartial { amount: 5 }.data());
+    }
+
+    #[test]
+    fn make_with_timelock_serializes_the_start_time() {
+        let (maker, mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        let instruction = MakeBuilder::new(maker, 2, mint_a, 10, mint_b, 20)
+            .expires_at(2_000)
+            .with_timelock(1_000, 300)
+            .instruction();
+
+        let expected = ::anchor_escrow::instruction::Make {
+            seed: 2,
+            deposit: 10,
+            receive: 20,
+            expires_at: 2_000,
+            starts_at: 1_300,
+        };
+        assert_eq!(instruction.data, expected.data());
+    }
+
+    #[test]
+    fn take_sol_accounts_match_program() {
+        let (taker, maker, mint_a) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = pda::escrow_address(&maker, 6);
+        let ata = |owner: &Pubkey| anchor_spl::associated_token::get_associated_token_address(owner, &mint_a);
+
+        let expected = ::anchor_escrow::accounts::TakeSol {
+            taker,
+            maker,
+            mint_a,
+            taker_ata_a: ata(&taker),
+            escrow,
+            vault: ata(&escrow),
+            receipt: pda::receipt_address(&escrow, &taker),
+            token_program: token::ID,
+            system_program: system_program::ID,
+        }.to_account_metas(None);
+
+        let instruction = TakeSolBuilder::new(taker, escrow, maker, mint_a, 7).instruction();
+        assert_eq!(instruction.accounts, expected);
+        assert_eq!(instruction.data, ::anchor_escrow::instruction::TakeSol { amount: 7 }.data());
+    }
+
+    #[test]
+    fn collection_nft_appends_the_gate_proof_after_the_take_accounts() {
+        let (taker, maker, mint_a, mint_b, nft_mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = pda::escrow_address(&maker, 4);
+
+        let plain = TakeBuilder::new(taker, escrow, maker, mint_a, mint_b).instruction();
+        let gated = TakeBuilder::new(taker, escrow, maker, mint_a, mint_b).collection_nft(nft_mint).instruction();
+
+        let (accounts, proof) = gated.accounts.split_at(plain.accounts.len());
+        assert_eq!(accounts, plain.accounts.as_slice());
+        assert_eq!(proof, [
+            AccountMeta::new_readonly(anchor_spl::associated_token::get_associated_token_address(&taker, &nft_mint), false),
+            AccountMeta::new_readonly(pda::metadata_address(&nft_mint), false),
+        ]);
+    }
+
+    #[test]
+    fn operator_refund_accounts_match_program() {
+        let (operator, maker, mint_a) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = pda::escrow_address(&maker, 9);
+        let ata = |owner: &Pubkey| anchor_spl::associated_token::get_associated_token_address(owner, &mint_a);
+
+        let expected = ::anchor_escrow::accounts::OperatorRefund {
+            operator,
+            maker,
+            mint_a,
+            maker_ata_a: ata(&maker),
+            escrow,
+            vault: ata(&escrow),
+            token_program: token::ID,
+        }.to_account_metas(None);
+
+        let instruction = RefundBuilder::new(maker, 9, mint_a).operator(operator).instruction();
+        assert_eq!(instruction.accounts, expected);
+        assert_eq!(instruction.data, ::anchor_escrow::instruction::OperatorRefund {}.data());
+    }
+
+    #[test]
+    fn attestation_appends_the_takers_attestation() {
+        let (taker, maker, mint_a, credential, schema) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = pda::escrow_address(&maker, 5);
+
+        let instruction = TakeSolBuilder::new(taker, escrow, maker, mint_a, 1).attestation(credential, schema).instruction();
+
+        assert_eq!(
+            instruction.accounts.last(),
+            Some(&AccountMeta::new_readonly(pda::attestation_address(&credential, &schema, &taker), false))
+        );
+    }
+
+    #[test]
+    fn take_quote_matches_program() {
+        let (taker, maker, mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = pda::escrow_address(&maker, 8);
+        let ata = |owner: &Pubkey, mint: &Pubkey| anchor_spl::associated_token::get_associated_token_address(owner, mint);
+
+        let quote = Quote { escrow, nonce: 3, taker, amount: 5, price: 4, filled: 2, expires_at: 1_000 };
+        let program_quote =
+            ::anchor_escrow::state::Quote { escrow, nonce: 3, taker, amount: 5, price: 4, filled: 2, expires_at: 1_000 };
+
+        let expected = ::anchor_escrow::accounts::TakeQuote {
+            taker,
+            maker,
+            mint_a,
+            mint_b,
+            taker_ata_a: ata(&taker, &mint_a),
+            taker_ata_b: ata(&taker, &mint_b),
+            maker_ata_b: ata(&maker, &mint_b),
+            escrow,
+            vault: ata(&escrow, &mint_a),
+            receipt: pda::receipt_address(&escrow, &taker),
+            instructions: sysvar::instructions::ID,
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        }.to_account_metas(None);
+
+        let [signature, take] = TakeQuoteBuilder::new(maker, mint_a, mint_b, quote.clone(), [0; 64]).instructions();
+        assert_eq!(take.accounts, expected);
+        assert_eq!(take.data, ::anchor_escrow::instruction::TakeQuote { quote: program_quote.clone() }.data());
+        assert_eq!(quote_message(&quote), program_quote.message());
+        assert_eq!(signature.program_id, anchor_lang::solana_program::ed25519_program::ID);
+    }
+
+    #[test]
+    fn make_and_refund_derive_the_same_escrow_and_vault() {
+        let (maker, mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        let make = MakeBuilder::new(maker, 9, mint_a, 10, mint_b, 20).instruction();
+        let refund = RefundBuilder::new(maker, 9, mint_a).instruction();
+
+        // escrow and vault are at indexes 4 and 5 in make, 3 and 4 in refund
+        assert_eq!(make.accounts[4].pubkey, refund.accounts[3].pubkey);
+        assert_eq!(make.accounts[5].pubkey, refund.accounts[4].pubkey);
+        assert_eq!(make.accounts[4].pubkey, pda::escrow_address(&maker, 9));
+    }
+
+    #[test]
+    fn refund_accounts_match_program() {
+        let (maker, mint_a) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = pda::escrow_address(&maker, 5);
+        let ata = |owner: &Pubkey| anchor_spl::associated_token::get_associated_token_address(owner, &mint_a);
+
+        let expected = ::anchor_escrow::accounts::Refund {
+            maker,
+            mint_a,
+            maker_ata_a: ata(&maker),
+            escrow,
+            vault: ata(&escrow),
+            token_program: token::ID,
+        }.to_account_metas(None);
+
+        assert_eq!(RefundBuilder::new(maker, 5, mint_a).instruction().accounts, expected);
+    }
+
+    #[test]
+    fn close_expired_accounts_match_program() {
+        let (cranker, maker, mint_a) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = pda::escrow_address(&maker, 4);
+        let ata = |owner: &Pubkey| anchor_spl::associated_token::get_associated_token_address(owner, &mint_a);
+
+        let expected = ::anchor_escrow::accounts::CloseExpired {
+            cranker,
+            maker,
+            mint_a,
+            maker_ata_a: ata(&maker),
+            escrow,
+            vault: ata(&escrow),
+            associated_token_program: associated_token::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        }.to_account_metas(None);
+
+        assert_eq!(CloseExpiredBuilder::new(cranker, maker, 4, mint_a).instruction().accounts, expected);
+    }
+}
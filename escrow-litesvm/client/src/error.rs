@@ -0,0 +1,53 @@
+use {
+    crate::program_error::EscrowClientError,
+    anchor_lang::{prelude::Pubkey, solana_program::instruction::InstructionError},
+    solana_message::CompileError,
+    solana_signature::Signature,
+    solana_signer::SignerError,
+    solana_transaction_error::TransactionError,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("RPC request failed: {0}")]
+    Rpc(#[from] solana_rpc_client_api::client_error::Error),
+    #[error("Websocket subscription failed: {0}")]
+    Pubsub(#[from] solana_pubsub_client::nonblocking::pubsub_client::PubsubClientError),
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("Account {address} is owned by {owner}, not the escrow program")]
+    WrongOwner { address: Pubkey, owner: Pubkey },
+    #[error("Account {address} could not be decoded: {source}")]
+    Decode { address: Pubkey, source: anchor_lang::error::Error },
+    #[error("Lookup table {address} could not be decoded: {err}")]
+    LookupTable { address: Pubkey, err: InstructionError },
+    #[error("Transaction of {size} bytes exceeds the packet size limit even with a single instruction")]
+    TransactionTooLarge { size: usize },
+    #[error("Message could not be compiled: {0}")]
+    Compile(#[from] CompileError),
+    #[error("Transaction could not be signed: {0}")]
+    Signing(#[from] SignerError),
+    /// The program rejected the transaction, see [`EscrowClientError`]
+    #[error("{error}")]
+    Program { error: EscrowClientError, signature: Option<Signature>, logs: Vec<String> },
+    #[error("Simulation failed: {err}")]
+    Preflight { err: TransactionError, logs: Vec<String> },
+    #[error("Transaction {signature} failed: {err}")]
+    TransactionFailed { signature: Signature, err: TransactionError },
+    #[error("Transaction {signature} expired before it landed, its blockhash or nonce has moved on and it must be signed again")]
+    BlockhashExpired { signature: Signature },
+    #[error("{0} is not a signer of this transaction")]
+    NotASigner(Pubkey),
+    #[error("Signature by {0} does not match the message")]
+    InvalidSignature(Pubkey),
+    #[error("Transaction is missing signatures from {0:?}")]
+    MissingSignatures(Vec<Pubkey>),
+    #[error("Nonce account {address} {reason}")]
+    InvalidNonce { address: Pubkey, reason: String },
+    #[error("Transaction could not be decoded: {0}")]
+    InvalidTransaction(String),
+    #[error("Transaction did not land after {attempts} attempts")]
+    RetriesExhausted { attempts: u32 },
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
@@ -0,0 +1,165 @@
+//! Typed program events out of a transaction's logs and inner instructions.
+//!
+//! `emit!` events are base64 "Program data:" log lines, and only lines logged while
+//! the escrow program itself is executing are decoded, so another program's data
+//! with a colliding discriminator is ignored. `emit_cpi!` events are self-invocations
+//! of the program carrying the event tag ahead of the event bytes.
+
+use {
+    crate::PROGRAM_ID,
+    anchor_lang::{prelude::Pubkey, AnchorDeserialize, Discriminator},
+    base64::{engine::general_purpose::STANDARD, Engine},
+    escrow_interface::events::{
+        EscrowMade, EscrowRefunded, EscrowStatusChanged, EscrowTaken, EscrowTermsUpdated, ExpiredEscrowClosed,
+    },
+    solana_transaction_status_client_types::{option_serializer::OptionSerializer, UiInstruction, UiTransactionStatusMeta},
+    std::str::FromStr,
+};
+
+// `emit_cpi!` self-invokes the program with this tag ahead of the event bytes
+const EVENT_IX_TAG_LE: [u8; 8] = 0x1d9acb512ea545e4_u64.to_le_bytes();
+
+pub enum ProgramEvent {
+    Made(EscrowMade),
+    Taken(EscrowTaken),
+    Refunded(EscrowRefunded),
+    TermsUpdated(EscrowTermsUpdated),
+    StatusChanged(EscrowStatusChanged),
+    ExpiredClosed(ExpiredEscrowClosed),
+}
+
+fn decode_as<E: Discriminator + AnchorDeserialize>(data: &[u8]) -> Option<E> {
+    let payload = data.strip_prefix(E::DISCRIMINATOR)?;
+    E::deserialize(&mut &payload[..]).ok()
+}
+
+/// Decodes event bytes, discriminator included
+pub fn decode_event(data: &[u8]) -> Option<ProgramEvent> {
+    decode_as(data).map(ProgramEvent::Made)
+        .or_else(|| decode_as(data).map(ProgramEvent::Taken))
+        .or_else(|| decode_as(data).map(ProgramEvent::Refunded))
+        .or_else(|| decode_as(data).map(ProgramEvent::TermsUpdated))
+        .or_else(|| decode_as(data).map(ProgramEvent::StatusChanged))
+        .or_else(|| decode_as(data).map(ProgramEvent::ExpiredClosed))
+}
+
+/// Events logged with `emit!` by the escrow program, in order
+pub fn decode_logs(logs: &[String]) -> Vec<ProgramEvent> {
+    let program_id = PROGRAM_ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        if let Some(invoked) = log.strip_prefix("Program ").and_then(|rest| rest.split_once(" invoke [")) {
+            stack.push(invoked.0);
+        } else if log.starts_with("Program ") && (log.ends_with(" success") || log.contains(" failed: ")) {
+            stack.pop();
+        } else if let Some(data) = log.strip_prefix("Program data: ") {
+            if stack.last() == Some(&program_id.as_str()) {
+                events.extend(STANDARD.decode(data).ok().and_then(|data| decode_event(&data)));
+            }
+        }
+    }
+
+    events
+}
+
+/// An event carried by an inner instruction, if it is an `emit_cpi!` self-invocation
+pub fn decode_inner_instruction(program_id: &Pubkey, data: &[u8]) -> Option<ProgramEvent> {
+    if *program_id != PROGRAM_ID {
+        return None;
+    }
+    decode_event(data.strip_prefix(&EVENT_IX_TAG_LE[..])?)
+}
+
+/// Every event a fetched transaction emitted, logged ones first. `account_keys` are
+/// the message's static keys followed by any addresses loaded from lookup tables,
+/// which is how inner instructions index their program.
+pub fn decode_transaction(meta: &UiTransactionStatusMeta, account_keys: &[Pubkey]) -> Vec<ProgramEvent> {
+    let mut events = match &meta.log_messages {
+        OptionSerializer::Some(logs) => decode_logs(logs),
+        _ => Vec::new(),
+    };
+
+    if let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions {
+        for instruction in inner_instructions.iter().flat_map(|inner| &inner.instructions) {
+            let UiInstruction::Compiled(compiled) = instruction else { continue };
+            let Some(program_id) = account_keys.get(compiled.program_id_index as usize) else { continue };
+            let Ok(data) = bs58::decode(&compiled.data).into_vec() else { continue };
+            events.extend(decode_inner_instruction(program_id, &data));
+        }
+    }
+
+    events
+}
+
+/// `decode_transaction` for keys as the RPC returns them, base58 encoded
+pub fn decode_transaction_with_keys(meta: &UiTransactionStatusMeta, account_keys: &[String]) -> Vec<ProgramEvent> {
+    let keys: Vec<Pubkey> = account_keys.iter().filter_map(|key| Pubkey::from_str(key).ok()).collect();
+    decode_transaction(meta, &keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde::Deserialize};
+
+    // getTransaction results reduced to the message keys and the status meta
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Fixture {
+        account_keys: Vec<String>,
+        meta: UiTransactionStatusMeta,
+    }
+
+    fn fixture(json: &str) -> Vec<ProgramEvent> {
+        let fixture: Fixture = serde_json::from_str(json).unwrap();
+        decode_transaction_with_keys(&fixture.meta, &fixture.account_keys)
+    }
+
+    #[test]
+    fn decodes_emit_logs_of_the_escrow_program_only() {
+        let events = fixture(include_str!("../fixtures/take_emit.json"));
+
+        // The token program logs the same bytes from inside the CPI, only the escrow's line counts
+        assert_eq!(events.len(), 1);
+        let ProgramEvent::Taken(taken) = &events[0] else { panic!("Expected EscrowTaken") };
+        assert_eq!(taken.escrow, Pubkey::new_from_array([8; 32]));
+        assert_eq!(taken.taker, Pubkey::new_from_array([1; 32]));
+        assert_eq!((taken.amount, taken.price, taken.remaining), (10, 20, 0));
+    }
+
+    #[test]
+    fn decodes_emit_cpi_inner_instructions() {
+        let events = fixture(include_str!("../fixtures/make_emit_cpi.json"));
+
+        // The token transfer is skipped, the self-invocation carries the event
+        assert_eq!(events.len(), 1);
+        let ProgramEvent::Made(made) = &events[0] else { panic!("Expected EscrowMade") };
+        assert_eq!(made.maker, Pubkey::new_from_array([2; 32]));
+        assert_eq!((made.seed, made.deposit, made.receive, made.expires_at, made.starts_at), (42, 10, 20, 0, 0));
+    }
+
+    #[test]
+    fn matches_program_encoding() {
+        let event = ::anchor_escrow::events::EscrowRefunded { escrow: Pubkey::new_unique(), maker: Pubkey::new_unique(), amount: 7 };
+        let data = anchor_lang::Event::data(&event);
+
+        let Some(ProgramEvent::Refunded(refunded)) = decode_event(&data) else { panic!("Expected EscrowRefunded") };
+        assert_eq!((refunded.escrow, refunded.maker, refunded.amount), (event.escrow, event.maker, 7));
+        assert!(decode_inner_instruction(&Pubkey::new_unique(), &[&EVENT_IX_TAG_LE[..], &data].concat()).is_none());
+    }
+
+    #[test]
+    fn decodes_status_changes() {
+        let event = ::anchor_escrow::events::EscrowStatusChanged {
+            escrow: Pubkey::new_unique(),
+            status: ::anchor_escrow::state::EscrowStatus::Reserved,
+            reserved_for: Pubkey::new_unique(),
+        };
+        let data = anchor_lang::Event::data(&event);
+
+        let Some(ProgramEvent::StatusChanged(changed)) = decode_event(&data) else { panic!("Expected EscrowStatusChanged") };
+        assert_eq!((changed.escrow, changed.reserved_for), (event.escrow, event.reserved_for));
+        assert!(matches!(changed.status, escrow_interface::types::EscrowStatus::Reserved));
+    }
+}
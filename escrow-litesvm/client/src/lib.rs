@@ -0,0 +1,41 @@
+//! Rust SDK for the `anchor_escrow` program.
+//!
+//! Builds on the IDL-generated [`escrow_interface`] with what bots and services need
+//! beyond raw account structs: builders that derive every PDA and ATA an instruction
+//! takes, helpers to fetch and decode escrows over RPC, and [`EscrowRpc`] to submit
+//! transactions with preflight, retries and confirmation.
+//!
+//! ```ignore
+//! let make = MakeBuilder::new(maker, seed, mint_a, 1_000, mint_b, 2_000).instruction();
+//! let escrow = accounts::fetch_escrow(&rpc_client, &pda::escrow_address(&maker, seed))?;
+//! let take = TakeBuilder::for_escrow(taker, &escrow).instruction();
+//! ```
+
+pub mod accounts;
+pub mod builders;
+pub mod error;
+pub mod events;
+pub mod lookup_table;
+pub mod nonce;
+pub mod offline;
+pub mod pda;
+pub mod preview;
+pub mod priority_fee;
+pub mod program_error;
+pub mod rpc;
+pub mod subscribe;
+
+pub use {
+    accounts::Fetched,
+    builders::{attestation_gate_accounts, collection_gate_accounts, quote_message, CloseExpiredBuilder, MakeBuilder, RefundBuilder, TakeBuilder, TakeQuoteBuilder, TakeSolBuilder},
+    error::{ClientError, Result},
+    escrow_interface::{self as interface, ID as PROGRAM_ID},
+    events::ProgramEvent,
+    offline::OfflineTransaction,
+    pda::{DerivedAccounts, TakerAccounts},
+    preview::{BalanceChange, Simulation, TakePreview},
+    priority_fee::PriorityFee,
+    program_error::EscrowClientError,
+    rpc::{EscrowRpc, RetryConfig},
+    subscribe::{subscribe_escrows, subscribe_events, EscrowEvent, LoggedEvent},
+};
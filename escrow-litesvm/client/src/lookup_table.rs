@@ -0,0 +1,261 @@
+//! Version 0 transactions and the address lookup table they compile against.
+//!
+//! Every escrow instruction carries the same handful of program accounts. Looking
+//! them up from a table replaces each 32-byte key with a one-byte index, which keeps
+//! takes with several optional accounts under the packet size limit, and lets
+//! [`pack`] fit more instructions into each transaction.
+//!
+//! Make stays one instruction rather than a create-escrow and a fund-vault pair. Split
+//! within one transaction, the pair repeats the maker, mint and escrow indexes and a
+//! second discriminator, so it is larger with or without a table and packs no more
+//! makes per transaction. Split across two, it pays a second signature, a second
+//! blockhash and a second set of compute budget instructions, while both halves
+//! write-lock the same maker and escrow, so no priority fee is saved either. The
+//! tests below measure both against the real make.
+
+use {
+    crate::{
+        error::{ClientError, Result},
+        PROGRAM_ID,
+    },
+    anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction, system_program},
+    anchor_spl::{associated_token, token, token_2022},
+    solana_address_lookup_table_interface::{instruction as alt_instruction, state::AddressLookupTable},
+    solana_hash::Hash,
+    solana_message::{v0, AddressLookupTableAccount, Message, VersionedMessage},
+    solana_signature::Signature,
+    solana_signer::signers::Signers,
+    solana_transaction::versioned::VersionedTransaction,
+};
+
+/// Largest serialized transaction the cluster accepts, `PACKET_DATA_SIZE`
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Addresses shared by every escrow transaction: the program itself, both token
+/// programs, the associated token and system programs, and the native mint
+pub fn default_addresses() -> Vec<Pubkey> {
+    vec![
+        PROGRAM_ID,
+        token::ID,
+        token_2022::ID,
+        associated_token::ID,
+        system_program::ID,
+        token::spl_token::native_mint::ID,
+    ]
+}
+
+/// Instructions creating a table owned by `authority` and filling it with `addresses`.
+/// `recent_slot` must be a recent finalized slot, it seeds the table's address.
+pub fn create_instructions(authority: &Pubkey, payer: &Pubkey, recent_slot: u64, addresses: Vec<Pubkey>) -> (Pubkey, Vec<Instruction>) {
+    let (create, table) = alt_instruction::create_lookup_table(*authority, *payer, recent_slot);
+    let extend = alt_instruction::extend_lookup_table(table, *authority, Some(*payer), addresses);
+    (table, vec![create, extend])
+}
+
+/// Decodes a fetched lookup table account
+pub fn decode(address: &Pubkey, data: &[u8]) -> Result<AddressLookupTableAccount> {
+    let table = AddressLookupTable::deserialize(data).map_err(|err| ClientError::LookupTable { address: *address, err })?;
+    Ok(AddressLookupTableAccount { key: *address, addresses: table.addresses.to_vec() })
+}
+
+/// Compiles a v0 message when `lookup_tables` are given, a legacy one otherwise
+pub fn build_message(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedMessage> {
+    Ok(if lookup_tables.is_empty() {
+        VersionedMessage::Legacy(Message::new_with_blockhash(instructions, Some(payer), &blockhash))
+    } else {
+        VersionedMessage::V0(v0::Message::try_compile(payer, instructions, lookup_tables, blockhash)?)
+    })
+}
+
+/// [`build_message`], signed
+pub fn build_transaction<T: Signers + ?Sized>(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+    signers: &T,
+) -> Result<VersionedTransaction> {
+    let message = build_message(instructions, payer, lookup_tables, blockhash)?;
+    Ok(VersionedTransaction::try_new(message, signers)?)
+}
+
+/// Serialized size of the signed transaction `instructions` would make
+pub fn transaction_size(instructions: &[Instruction], payer: &Pubkey, lookup_tables: &[AddressLookupTableAccount]) -> Result<usize> {
+    let message = build_message(instructions, payer, lookup_tables, Hash::default())?;
+    let signatures = vec![Signature::default(); message.header().num_required_signatures as usize];
+    let transaction = VersionedTransaction { signatures, message };
+    Ok(bincode::serialized_size(&transaction).expect("Transactions always serialize") as usize)
+}
+
+/// Splits `instructions` into as few transactions as fit the packet size limit, in
+/// order. `prefix` is what gets prepended to every transaction when it is sent, such
+/// as compute budget or nonce instructions, and is counted but not included.
+pub fn pack(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    lookup_tables: &[AddressLookupTableAccount],
+    prefix: &[Instruction],
+) -> Result<Vec<Vec<Instruction>>> {
+    let size = |batch: &[Instruction]| transaction_size(&[prefix, batch].concat(), payer, lookup_tables);
+
+    let mut batches: Vec<Vec<Instruction>> = Vec::new();
+    let mut current: Vec<Instruction> = Vec::new();
+    for instruction in instructions {
+        current.push(instruction.clone());
+        if size(&current)? <= MAX_TRANSACTION_SIZE {
+            continue;
+        }
+
+        // Start a new transaction with the instruction that did not fit, which must
+        // fit on its own
+        let overflow = vec![current.pop().unwrap()];
+        let alone = size(&overflow)?;
+        if alone > MAX_TRANSACTION_SIZE {
+            return Err(ClientError::TransactionTooLarge { size: alone });
+        }
+        batches.push(std::mem::replace(&mut current, overflow));
+    }
+    if !current.is_empty() {
+        batches.push(current);
+    }
+
+    Ok(batches)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::TakeBuilder, solana_keypair::Keypair, solana_signer::Signer};
+
+    #[test]
+    fn v0_take_is_smaller_than_legacy() {
+        let taker = Keypair::new();
+        let (maker, mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let escrow = crate::pda::escrow_address(&maker, 1);
+        let take = TakeBuilder::new(taker.pubkey(), escrow, maker, mint_a, mint_b).instruction();
+
+        let table = AddressLookupTableAccount { key: Pubkey::new_unique(), addresses: default_addresses() };
+        let legacy = build_transaction(&[take.clone()], &taker.pubkey(), &[], Hash::default(), &[&taker]).unwrap();
+        let v0 = build_transaction(&[take], &taker.pubkey(), &[table], Hash::default(), &[&taker]).unwrap();
+
+        // The invoked program stays a static key, the programs passed as accounts move into the table
+        let VersionedMessage::V0(message) = &v0.message else { panic!("Expected a v0 message") };
+        assert!(message.account_keys.contains(&PROGRAM_ID));
+        assert_eq!(message.address_table_lookups[0].readonly_indexes.len(), 3);
+
+        let size = |transaction: &VersionedTransaction| bincode::serialized_size(transaction).unwrap();
+        assert!(size(&v0) < size(&legacy), "v0 {} bytes, legacy {} bytes", size(&v0), size(&legacy));
+    }
+
+    // What a split make would look like: the escrow created with the terms, then the
+    // vault created and funded. Data is a discriminator followed by the arguments.
+    fn split_make(maker: &Pubkey, seed: u64, mint_a: &Pubkey, mint_b: &Pubkey) -> [Instruction; 2] {
+        use anchor_lang::prelude::AccountMeta;
+
+        let combined = crate::MakeBuilder::new(*maker, seed, *mint_a, 10, *mint_b, 10).instruction();
+        let [_, _, _, maker_ata_a, escrow, vault, ..] = combined.accounts.as_slice() else { unreachable!() };
+        let create = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*maker, true),
+                AccountMeta::new_readonly(*mint_a, false),
+                AccountMeta::new_readonly(*mint_b, false),
+                escrow.clone(),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            // seed, receive, expires_at
+            data: vec![0; 8 + 24],
+        };
+        let fund = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*maker, true),
+                AccountMeta::new_readonly(*mint_a, false),
+                maker_ata_a.clone(),
+                escrow.clone(),
+                vault.clone(),
+                AccountMeta::new_readonly(associated_token::ID, false),
+                AccountMeta::new_readonly(token::ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            // deposit
+            data: vec![0; 8 + 8],
+        };
+        [create, fund]
+    }
+
+    #[test]
+    fn combined_make_packs_at_least_as_well_as_a_split_one() {
+        let maker = Pubkey::new_unique();
+        let mints: Vec<(Pubkey, Pubkey)> = (0..8).map(|_| (Pubkey::new_unique(), Pubkey::new_unique())).collect();
+        let combined: Vec<Instruction> = mints
+            .iter()
+            .enumerate()
+            .map(|(seed, (mint_a, mint_b))| crate::MakeBuilder::new(maker, seed as u64, *mint_a, 10, *mint_b, 10).instruction())
+            .collect();
+        let split: Vec<Instruction> = mints
+            .iter()
+            .enumerate()
+            .flat_map(|(seed, (mint_a, mint_b))| split_make(&maker, seed as u64, mint_a, mint_b))
+            .collect();
+
+        let table = AddressLookupTableAccount { key: Pubkey::new_unique(), addresses: default_addresses() };
+        let budget = crate::priority_fee::with_compute_budget(&[], 1_000, Some(100_000));
+        for tables in [&[][..], &[table][..]] {
+            // One make alone, and a split one sharing its transaction
+            let one = transaction_size(&combined[..1], &maker, tables).unwrap();
+            let pair = transaction_size(&split[..2], &maker, tables).unwrap();
+            assert!(one < pair, "Combined {} bytes, split {} bytes", one, pair);
+
+            // Split over two transactions, each signed and budgeted
+            let apart: usize = split[..2]
+                .iter()
+                .map(|half| transaction_size(&[&budget[..], std::slice::from_ref(half)].concat(), &maker, tables).unwrap())
+                .sum();
+            let whole = transaction_size(&[&budget[..], &combined[..1]].concat(), &maker, tables).unwrap();
+            assert!(whole < apart, "Combined {} bytes, split apart {} bytes", whole, apart);
+
+            // Batches: never more transactions for the combined instruction
+            let combined_batches = pack(&combined, &maker, tables, &budget).unwrap().len();
+            let split_batches = pack(&split, &maker, tables, &budget).unwrap().len();
+            assert!(combined_batches <= split_batches, "Combined {} transactions, split {}", combined_batches, split_batches);
+        }
+
+        // Both halves write-lock the maker and the escrow, the accounts fees compete over
+        let locked = |instruction: &Instruction| crate::priority_fee::write_locked_accounts(std::slice::from_ref(instruction));
+        let [create, fund] = split_make(&maker, 0, &mints[0].0, &mints[0].1);
+        assert!(locked(&create).iter().all(|account| locked(&fund).contains(account)));
+        assert_eq!(
+            crate::priority_fee::write_locked_accounts(&[create, fund]),
+            locked(&combined[0]),
+        );
+    }
+
+    #[test]
+    fn packs_makes_into_as_few_transactions_as_fit() {
+        let maker = Pubkey::new_unique();
+        let makes: Vec<Instruction> = (0..6)
+            .map(|seed| crate::MakeBuilder::new(maker, seed, Pubkey::new_unique(), 10, Pubkey::new_unique(), 10).instruction())
+            .collect();
+
+        let batches = pack(&makes, &maker, &[], &[]).unwrap();
+        assert_eq!(batches.iter().map(Vec::len).sum::<usize>(), 6);
+        assert!(batches.len() > 1, "Six makes never fit one legacy transaction");
+        for batch in &batches {
+            assert!(transaction_size(batch, &maker, &[]).unwrap() <= MAX_TRANSACTION_SIZE);
+        }
+        // Greedy: adding the next batch's first instruction would overflow
+        for pair in batches.windows(2) {
+            let grown = [&pair[0][..], &pair[1][..1]].concat();
+            assert!(transaction_size(&grown, &maker, &[]).unwrap() > MAX_TRANSACTION_SIZE);
+        }
+
+        // A lookup table packs at least as tightly
+        let table = AddressLookupTableAccount { key: Pubkey::new_unique(), addresses: default_addresses() };
+        assert!(pack(&makes, &maker, &[table], &[]).unwrap().len() <= batches.len());
+    }
+}
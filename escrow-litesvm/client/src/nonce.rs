@@ -0,0 +1,109 @@
+//! Durable nonce transactions, for signing workflows slower than a blockhash lives.
+//!
+//! A transaction built against a nonce account uses the nonce's stored value as its
+//! blockhash and starts by advancing the nonce, so it stays valid until it lands or
+//! the nonce is advanced by something else, however long the signers take.
+
+use {
+    crate::error::{ClientError, Result},
+    anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction},
+    solana_hash::Hash,
+    solana_message::VersionedMessage,
+    solana_nonce::{state::State, versions::Versions},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk_ids::system_program,
+    solana_system_interface::instruction::advance_nonce_account,
+};
+
+// `SystemInstruction::AdvanceNonceAccount` as its bincode u32 tag
+const ADVANCE_NONCE_TAG: [u8; 4] = 4u32.to_le_bytes();
+
+/// State of an initialized nonce account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Nonce {
+    pub address: Pubkey,
+    pub authority: Pubkey,
+    /// Value the next transaction must use as its blockhash
+    pub blockhash: Hash,
+}
+
+/// Decodes a nonce account's data
+pub fn decode(address: &Pubkey, data: &[u8]) -> Result<Nonce> {
+    let invalid = |reason: &str| ClientError::InvalidNonce { address: *address, reason: reason.to_string() };
+
+    let versions: Versions = bincode::deserialize(data).map_err(|_| invalid("is not a nonce account"))?;
+    match versions.state() {
+        State::Initialized(data) => Ok(Nonce { address: *address, authority: data.authority, blockhash: data.blockhash() }),
+        State::Uninitialized => Err(invalid("is not initialized")),
+    }
+}
+
+pub async fn fetch(client: &RpcClient, address: &Pubkey) -> Result<Nonce> {
+    let account = client
+        .get_account_with_commitment(address, client.commitment())
+        .await?
+        .value
+        .ok_or(ClientError::AccountNotFound(*address))?;
+
+    if account.owner != system_program::ID {
+        return Err(ClientError::InvalidNonce { address: *address, reason: "is not owned by the system program".to_string() });
+    }
+    decode(address, &account.data)
+}
+
+/// `instructions` behind the advance of `nonce`, which the runtime requires first
+pub fn with_advance(instructions: &[Instruction], nonce: &Nonce) -> Vec<Instruction> {
+    std::iter::once(advance_nonce_account(&nonce.address, &nonce.authority)).chain(instructions.iter().cloned()).collect()
+}
+
+/// The nonce account a message advances, `None` when it uses a recent blockhash
+pub fn nonce_account(message: &VersionedMessage) -> Option<Pubkey> {
+    let keys = message.static_account_keys();
+    let first = message.instructions().first()?;
+
+    let is_advance = keys.get(first.program_id_index as usize) == Some(&system_program::ID)
+        && first.data.starts_with(&ADVANCE_NONCE_TAG);
+    if !is_advance {
+        return None;
+    }
+    keys.get(*first.accounts.first()? as usize).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_message::Message,
+        solana_nonce::state::{Data, DurableNonce},
+    };
+
+    fn nonce_data(authority: Pubkey, blockhash: Hash) -> Vec<u8> {
+        let state = State::Initialized(Data::new(authority, DurableNonce::from_blockhash(&blockhash), 5_000));
+        bincode::serialize(&Versions::new(state)).unwrap()
+    }
+
+    #[test]
+    fn decodes_initialized_nonce_accounts_only() {
+        let (address, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let nonce = decode(&address, &nonce_data(authority, Hash::new_unique())).unwrap();
+        assert_eq!(nonce.authority, authority);
+
+        let uninitialized = bincode::serialize(&Versions::new(State::Uninitialized)).unwrap();
+        assert!(matches!(decode(&address, &uninitialized), Err(ClientError::InvalidNonce { .. })));
+        assert!(matches!(decode(&address, &[1, 2, 3]), Err(ClientError::InvalidNonce { .. })));
+    }
+
+    #[test]
+    fn finds_the_advanced_nonce_account() {
+        let payer = Pubkey::new_unique();
+        let nonce = Nonce { address: Pubkey::new_unique(), authority: payer, blockhash: Hash::new_unique() };
+        let transfer = Instruction::new_with_bytes(Pubkey::new_unique(), &[], Vec::new());
+
+        let durable = Message::new_with_blockhash(&with_advance(&[transfer.clone()], &nonce), Some(&payer), &nonce.blockhash);
+        assert_eq!(nonce_account(&VersionedMessage::Legacy(durable)), Some(nonce.address));
+
+        let recent = Message::new(&[transfer], Some(&payer));
+        assert_eq!(nonce_account(&VersionedMessage::Legacy(recent)), None);
+    }
+}
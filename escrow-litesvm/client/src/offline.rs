@@ -0,0 +1,142 @@
+//! Transactions built on one machine and signed on another.
+//!
+//! An [`OfflineTransaction`] travels as base64 of the wire-format transaction, with
+//! zeroed signatures for the signers that have not signed yet. Each signer decodes
+//! it, checks the message, adds its signature and passes it on, and whoever holds the
+//! complete transaction hands it to [`EscrowRpc::submit`](crate::EscrowRpc::submit).
+
+use {
+    crate::error::{ClientError, Result},
+    anchor_lang::prelude::Pubkey,
+    base64::{engine::general_purpose::STANDARD, Engine},
+    solana_message::VersionedMessage,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_transaction::versioned::VersionedTransaction,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfflineTransaction {
+    transaction: VersionedTransaction,
+}
+
+impl OfflineTransaction {
+    pub fn new(message: VersionedMessage) -> Self {
+        let signatures = vec![Signature::default(); message.header().num_required_signatures as usize];
+        Self { transaction: VersionedTransaction { signatures, message } }
+    }
+
+    pub fn from_base64(encoded: &str) -> Result<Self> {
+        let bytes = STANDARD.decode(encoded.trim()).map_err(|err| ClientError::InvalidTransaction(err.to_string()))?;
+        let transaction: VersionedTransaction = bincode::deserialize(&bytes).map_err(|err| ClientError::InvalidTransaction(err.to_string()))?;
+
+        if transaction.signatures.len() != transaction.message.header().num_required_signatures as usize {
+            return Err(ClientError::InvalidTransaction("signature count does not match the message".to_string()));
+        }
+        Ok(Self { transaction })
+    }
+
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(bincode::serialize(&self.transaction).expect("Transactions always serialize"))
+    }
+
+    pub fn message(&self) -> &VersionedMessage {
+        &self.transaction.message
+    }
+
+    /// The bytes every signer signs
+    pub fn message_data(&self) -> Vec<u8> {
+        self.transaction.message.serialize()
+    }
+
+    /// Every key that must sign, the fee payer first
+    pub fn signers(&self) -> &[Pubkey] {
+        let required = self.transaction.message.header().num_required_signatures as usize;
+        &self.transaction.message.static_account_keys()[..required]
+    }
+
+    pub fn missing_signers(&self) -> Vec<Pubkey> {
+        self.signers()
+            .iter()
+            .zip(&self.transaction.signatures)
+            .filter(|(_, signature)| **signature == Signature::default())
+            .map(|(signer, _)| *signer)
+            .collect()
+    }
+
+    /// Signs with a local key, which must be one of [`signers`](Self::signers)
+    pub fn sign(&mut self, signer: &dyn Signer) -> Result<()> {
+        let signature = signer.try_sign_message(&self.message_data())?;
+        self.add_signature(&signer.pubkey(), signature)
+    }
+
+    /// Adds a signature produced elsewhere, e.g. by a hardware wallet or a remote signer
+    pub fn add_signature(&mut self, signer: &Pubkey, signature: Signature) -> Result<()> {
+        let index = self.signers().iter().position(|key| key == signer).ok_or(ClientError::NotASigner(*signer))?;
+
+        if !signature.verify(signer.as_ref(), &self.message_data()) {
+            return Err(ClientError::InvalidSignature(*signer));
+        }
+
+        self.transaction.signatures[index] = signature;
+        Ok(())
+    }
+
+    /// The transaction, once every signer has signed
+    pub fn into_signed(self) -> Result<VersionedTransaction> {
+        let missing = self.missing_signers();
+        if !missing.is_empty() {
+            return Err(ClientError::MissingSignatures(missing));
+        }
+        Ok(self.transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{lookup_table::build_message, RefundBuilder},
+        solana_hash::Hash,
+        solana_keypair::Keypair,
+    };
+
+    #[test]
+    fn round_trips_through_base64_between_signers() {
+        let (fee_payer, maker) = (Keypair::new(), Keypair::new());
+        let refund = RefundBuilder::new(maker.pubkey(), 1, Pubkey::new_unique()).instruction();
+
+        let message = build_message(&[refund], &fee_payer.pubkey(), &[], Hash::new_unique()).unwrap();
+        let exported = OfflineTransaction::new(message).to_base64();
+
+        // The maker signs on an air-gapped machine, the fee payer elsewhere
+        let mut on_maker = OfflineTransaction::from_base64(&exported).unwrap();
+        assert_eq!(on_maker.missing_signers(), vec![fee_payer.pubkey(), maker.pubkey()]);
+        on_maker.sign(&maker).unwrap();
+
+        let mut on_payer = OfflineTransaction::from_base64(&on_maker.to_base64()).unwrap();
+        assert!(matches!(
+            on_payer.clone().into_signed(),
+            Err(ClientError::MissingSignatures(missing)) if missing == vec![fee_payer.pubkey()]
+        ));
+        on_payer.sign(&fee_payer).unwrap();
+
+        let signed = on_payer.into_signed().unwrap();
+        assert!(signed.verify_with_results().into_iter().all(|valid| valid));
+    }
+
+    #[test]
+    fn rejects_strangers_and_forged_signatures() {
+        let maker = Keypair::new();
+        let refund = RefundBuilder::new(maker.pubkey(), 1, Pubkey::new_unique()).instruction();
+        let message = build_message(&[refund], &maker.pubkey(), &[], Hash::new_unique()).unwrap();
+        let mut transaction = OfflineTransaction::new(message);
+
+        let stranger = Keypair::new();
+        assert!(matches!(transaction.sign(&stranger), Err(ClientError::NotASigner(_))));
+
+        let forged = stranger.sign_message(&transaction.message_data());
+        assert!(matches!(transaction.add_signature(&maker.pubkey(), forged), Err(ClientError::InvalidSignature(_))));
+        assert_eq!(transaction.missing_signers(), vec![maker.pubkey()]);
+    }
+}
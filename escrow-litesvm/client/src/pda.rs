@@ -0,0 +1,128 @@
+//! Addresses derived from the program's seeds, matching the `seeds` constraints on-chain.
+
+use {
+    anchor_lang::prelude::Pubkey,
+    anchor_spl::associated_token::get_associated_token_address_with_program_id,
+    escrow_interface::{
+        constants::{ESCROW_SEED, MAKER_COUNTER_SEED, RECEIPT_SEED, WSOL_SEED},
+        ID as PROGRAM_ID,
+    },
+};
+
+pub fn escrow_address(maker: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(&[ESCROW_SEED, maker.as_ref(), &seed.to_le_bytes()], &PROGRAM_ID).0
+}
+
+/// The escrow's associated token account for mint A
+pub fn vault_address(escrow: &Pubkey, mint_a: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(escrow, mint_a, token_program)
+}
+
+/// Numbers the maker's escrows, created by their first make
+pub fn maker_counter_address(maker: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MAKER_COUNTER_SEED, maker.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn receipt_address(escrow: &Pubkey, taker: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[RECEIPT_SEED, escrow.as_ref(), taker.as_ref()], &PROGRAM_ID).0
+}
+
+/// Temporary account the maker's wSOL proceeds pass through when a take unwraps them
+pub fn wsol_address(escrow: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[WSOL_SEED, escrow.as_ref()], &PROGRAM_ID).0
+}
+
+/// Metaplex Token Metadata, which keeps an NFT's collection in its metadata account
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = anchor_lang::prelude::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Token Metadata's metadata account for `mint`
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()], &TOKEN_METADATA_PROGRAM_ID).0
+}
+
+/// Solana Attestation Service, which holds the attestations an escrow can be gated on
+pub const ATTESTATION_PROGRAM_ID: Pubkey = anchor_lang::prelude::pubkey!("22zoJMtdu4tQc2PzL74ZUT7FrwgB1Udec8DdW4yw4BdG");
+
+/// The attestation of `schema` about `subject` under `credential`
+pub fn attestation_address(credential: &Pubkey, schema: &Pubkey, subject: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"attestation", credential.as_ref(), schema.as_ref(), subject.as_ref()], &ATTESTATION_PROGRAM_ID).0
+}
+
+/// Every PDA and associated token account the program touches for one escrow, derived
+/// up front so an instruction can be built without deriving anything again
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DerivedAccounts {
+    pub escrow: Pubkey,
+    pub maker_ata_a: Pubkey,
+    pub maker_ata_b: Pubkey,
+    pub vault: Pubkey,
+    pub wsol: Pubkey,
+    /// The taker's side, once [`DerivedAccounts::with_taker`] named one
+    pub taker: Option<TakerAccounts>,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    token_program: Pubkey,
+}
+
+/// Accounts only a take needs, which depend on who takes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TakerAccounts {
+    pub taker: Pubkey,
+    pub taker_ata_a: Pubkey,
+    pub taker_ata_b: Pubkey,
+    pub receipt: Pubkey,
+}
+
+impl DerivedAccounts {
+    /// Derives the escrow from the maker and seed first
+    pub fn new(maker: &Pubkey, seed: u64, mint_a: &Pubkey, mint_b: &Pubkey, token_program: &Pubkey) -> Self {
+        Self::for_escrow(&escrow_address(maker, seed), maker, mint_a, mint_b, token_program)
+    }
+
+    /// For an escrow whose address is already known, e.g. one fetched over RPC
+    pub fn for_escrow(escrow: &Pubkey, maker: &Pubkey, mint_a: &Pubkey, mint_b: &Pubkey, token_program: &Pubkey) -> Self {
+        Self {
+            escrow: *escrow,
+            maker_ata_a: get_associated_token_address_with_program_id(maker, mint_a, token_program),
+            maker_ata_b: get_associated_token_address_with_program_id(maker, mint_b, token_program),
+            vault: vault_address(escrow, mint_a, token_program),
+            wsol: wsol_address(escrow),
+            taker: None,
+            mint_a: *mint_a,
+            mint_b: *mint_b,
+            token_program: *token_program,
+        }
+    }
+
+    pub fn with_taker(mut self, taker: &Pubkey) -> Self {
+        self.taker = Some(TakerAccounts {
+            taker: *taker,
+            taker_ata_a: get_associated_token_address_with_program_id(taker, &self.mint_a, &self.token_program),
+            taker_ata_b: get_associated_token_address_with_program_id(taker, &self.mint_b, &self.token_program),
+            receipt: receipt_address(&self.escrow, taker),
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived_accounts_match_the_single_address_helpers() {
+        let (maker, taker, mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let token_program = anchor_spl::token::ID;
+
+        let derived = DerivedAccounts::new(&maker, 7, &mint_a, &mint_b, &token_program).with_taker(&taker);
+
+        assert_eq!(derived.escrow, escrow_address(&maker, 7));
+        assert_eq!(derived.vault, vault_address(&derived.escrow, &mint_a, &token_program));
+        assert_eq!(derived.maker_ata_b, get_associated_token_address_with_program_id(&maker, &mint_b, &token_program));
+        assert_eq!(derived.wsol, wsol_address(&derived.escrow));
+
+        let taker_accounts = derived.taker.unwrap();
+        assert_eq!(taker_accounts.receipt, receipt_address(&derived.escrow, &taker));
+        assert_eq!(taker_accounts.taker_ata_a, get_associated_token_address_with_program_id(&taker, &mint_a, &token_program));
+    }
+}
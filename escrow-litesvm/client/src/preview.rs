@@ -0,0 +1,236 @@
+//! What a transaction will do, worked out by simulating it against current state.
+//!
+//! The balances are read before and after the simulation rather than computed from
+//! the escrow's terms, so transfer fees, rounding and any accounts the transaction
+//! creates are all reflected exactly as the cluster would apply them.
+
+use {
+    crate::{
+        builders::TakeBuilder,
+        error::{ClientError, Result},
+    },
+    anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction},
+    anchor_spl::{token, token_2022},
+    solana_account::Account,
+    solana_account_decoder_client_types::{UiAccount, UiAccountEncoding},
+    solana_message::Message,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+    solana_rpc_client_api::response::RpcSimulateTransactionResult,
+    solana_transaction::Transaction,
+};
+
+// The amount sits at the same offset in SPL Token and Token-2022 accounts
+const TOKEN_AMOUNT_OFFSET: usize = 64;
+
+// Token-2022 accounts with extensions are longer, never shorter
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TakePreview {
+    /// Mint B leaving the taker's account
+    pub pays: u64,
+    /// Mint A arriving in the taker's account
+    pub receives: u64,
+    /// Transaction fee in lamports
+    pub fee: u64,
+    /// Lamports the taker's wallet spends beyond the fee, mostly rent for the accounts
+    /// the take creates. Negative when the take returns more rent than it costs.
+    pub rent: i64,
+    pub compute_units: Option<u64>,
+    pub logs: Vec<String>,
+}
+
+/// Outcome of simulating an arbitrary transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Simulation {
+    /// Transaction fee in lamports
+    pub fee: u64,
+    pub compute_units: Option<u64>,
+    /// Every writable account whose lamports or token amount would change
+    pub changes: Vec<BalanceChange>,
+    pub logs: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub address: Pubkey,
+    pub lamports: i64,
+    /// Change in the token amount, `None` when the account is not a token account
+    pub tokens: Option<i128>,
+}
+
+fn is_token_account(account: Option<&Account>) -> bool {
+    account.is_some_and(|account| {
+        (account.owner == token::ID || account.owner == token_2022::ID) && account.data.len() >= TOKEN_ACCOUNT_LEN
+    })
+}
+
+fn token_amount(account: Option<&Account>) -> u64 {
+    account
+        .and_then(|account| account.data.get(TOKEN_AMOUNT_OFFSET..TOKEN_AMOUNT_OFFSET + 8))
+        .map(|amount| u64::from_le_bytes(amount.try_into().unwrap()))
+        .unwrap_or(0)
+}
+
+fn lamports(account: Option<&Account>) -> u64 {
+    account.map(|account| account.lamports).unwrap_or(0)
+}
+
+/// Simulates `instructions` paid for by `payer` and reports the balance changes of
+/// every account they write
+pub async fn simulate(client: &RpcClient, instructions: &[Instruction], payer: &Pubkey) -> Result<Simulation> {
+    let mut watched = vec![*payer];
+    for meta in instructions.iter().flat_map(|instruction| &instruction.accounts) {
+        if meta.is_writable && !watched.contains(&meta.pubkey) {
+            watched.push(meta.pubkey);
+        }
+    }
+
+    let (fee, before, simulation) = run_simulation(client, instructions, payer, &watched).await?;
+    let after = simulated_accounts(&simulation);
+    let changes = watched
+        .iter()
+        .enumerate()
+        .filter_map(|(index, address)| balance_change(*address, at(&before, index), at(&after, index)))
+        .collect();
+
+    Ok(Simulation { fee, compute_units: simulation.units_consumed, changes, logs: simulation.logs.unwrap_or_default() })
+}
+
+fn balance_change(address: Pubkey, before: Option<&Account>, after: Option<&Account>) -> Option<BalanceChange> {
+    let lamports = lamports(after) as i64 - lamports(before) as i64;
+    let tokens = (is_token_account(before) || is_token_account(after))
+        .then(|| token_amount(after) as i128 - token_amount(before) as i128);
+
+    (lamports != 0 || tokens.is_some_and(|tokens| tokens != 0)).then_some(BalanceChange { address, lamports, tokens })
+}
+
+// Fetches `watched` and simulates without a signature against the cluster's own
+// blockhash, returning the fee, the accounts before and the simulation with them after
+async fn run_simulation(
+    client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    watched: &[Pubkey],
+) -> Result<(u64, Vec<Option<Account>>, RpcSimulateTransactionResult)> {
+    let blockhash = client.get_latest_blockhash().await?;
+    let message = Message::new_with_blockhash(instructions, Some(payer), &blockhash);
+    let fee = client.get_fee_for_message(&message).await?;
+
+    let before = client.get_multiple_accounts_with_commitment(watched, client.commitment()).await?.value;
+
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(client.commitment()),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: watched.iter().map(Pubkey::to_string).collect(),
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let simulation = client
+        .simulate_transaction_with_config(&Transaction::new_unsigned(message), config)
+        .await?
+        .value;
+
+    if let Some(err) = simulation.err.clone() {
+        return Err(ClientError::preflight(err, simulation.logs.unwrap_or_default()));
+    }
+
+    Ok((fee, before, simulation))
+}
+
+fn simulated_accounts(simulation: &RpcSimulateTransactionResult) -> Vec<Option<Account>> {
+    simulation
+        .accounts
+        .iter()
+        .flatten()
+        .map(|account| account.as_ref().and_then(|account: &UiAccount| account.decode()))
+        .collect()
+}
+
+fn at(accounts: &[Option<Account>], index: usize) -> Option<&Account> {
+    accounts.get(index)?.as_ref()
+}
+
+/// Simulates `take` and reports the taker's balance changes
+pub async fn preview_take(client: &RpcClient, take: &TakeBuilder) -> Result<TakePreview> {
+    let taker = take.taker();
+    let watched = [taker, take.taker_ata_a(), take.taker_ata_b()];
+
+    let (fee, before, simulation) = run_simulation(client, &[take.instruction()], &taker, &watched).await?;
+    let after = simulated_accounts(&simulation);
+
+    Ok(summarize(&before, &after, fee, simulation.units_consumed, simulation.logs.unwrap_or_default()))
+}
+
+// `before` and `after` hold the taker's wallet, then its mint A and mint B accounts
+fn summarize(before: &[Option<Account>], after: &[Option<Account>], fee: u64, compute_units: Option<u64>, logs: Vec<String>) -> TakePreview {
+    let lamports_spent = lamports(at(before, 0)) as i64 - lamports(at(after, 0)) as i64;
+
+    TakePreview {
+        pays: token_amount(at(before, 2)).saturating_sub(token_amount(at(after, 2))),
+        receives: token_amount(at(after, 1)).saturating_sub(token_amount(at(before, 1))),
+        fee,
+        rent: lamports_spent - fee as i64,
+        compute_units,
+        logs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallet(lamports: u64) -> Option<Account> {
+        Some(Account { lamports, ..Account::default() })
+    }
+
+    fn token_account(amount: u64) -> Option<Account> {
+        let mut data = vec![0; 165];
+        data[TOKEN_AMOUNT_OFFSET..TOKEN_AMOUNT_OFFSET + 8].copy_from_slice(&amount.to_le_bytes());
+        Some(Account { lamports: 2_039_280, data, ..Account::default() })
+    }
+
+    #[test]
+    fn reports_amounts_and_rent_of_created_accounts() {
+        // The take creates the taker's mint A account, paying its rent
+        let before = [wallet(1_000_000_000), None, token_account(50)];
+        let after = [wallet(1_000_000_000 - 5_000 - 2_039_280), token_account(10), token_account(30)];
+
+        let preview = summarize(&before, &after, 5_000, Some(41_000), Vec::new());
+        assert_eq!(preview.pays, 20);
+        assert_eq!(preview.receives, 10);
+        assert_eq!(preview.fee, 5_000);
+        assert_eq!(preview.rent, 2_039_280);
+    }
+
+    #[test]
+    fn reports_only_accounts_that_change() {
+        let address = Pubkey::new_unique();
+        let owned_token_account = |amount| {
+            token_account(amount).map(|account| Account { owner: token::ID, ..account })
+        };
+
+        // A new token account shows its rent and its whole amount
+        let created = balance_change(address, None, owned_token_account(10).as_ref()).unwrap();
+        assert_eq!((created.lamports, created.tokens), (2_039_280, Some(10)));
+
+        // A closed one shows both going back to zero
+        let closed = balance_change(address, owned_token_account(10).as_ref(), None).unwrap();
+        assert_eq!((closed.lamports, closed.tokens), (-2_039_280, Some(-10)));
+
+        // A wallet has no token amount, and an untouched account is left out
+        let paid = balance_change(address, wallet(100).as_ref(), wallet(40).as_ref()).unwrap();
+        assert_eq!((paid.lamports, paid.tokens), (-60, None));
+        assert_eq!(balance_change(address, wallet(100).as_ref(), wallet(100).as_ref()), None);
+    }
+
+    #[test]
+    fn missing_simulated_accounts_count_as_empty() {
+        let preview = summarize(&[wallet(100), None, None], &[], 0, None, Vec::new());
+        assert_eq!((preview.pays, preview.receives, preview.rent), (0, 0, 100));
+    }
+}
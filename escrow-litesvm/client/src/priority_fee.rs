@@ -0,0 +1,135 @@
+//! Priority fees estimated from what recent transactions paid to write-lock the same
+//! accounts, prepended to a transaction as ComputeBudget instructions.
+
+use {
+    crate::error::Result,
+    anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction},
+    solana_compute_budget_interface::{ComputeBudgetInstruction, ID as COMPUTE_BUDGET_PROGRAM_ID},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+};
+
+// `getRecentPrioritizationFees` accepts at most this many accounts
+const MAX_FEE_ACCOUNTS: usize = 128;
+
+#[derive(Clone, Debug)]
+pub struct PriorityFee {
+    /// Percentile of the recent per-slot fees to pay, from 0 to 100
+    pub percentile: u8,
+    /// Bounds on the estimated price, in micro-lamports per compute unit
+    pub min_micro_lamports: u64,
+    pub max_micro_lamports: u64,
+    /// Compute unit limit to request, left at the runtime default when `None`
+    pub compute_unit_limit: Option<u32>,
+}
+
+impl PriorityFee {
+    pub fn percentile(percentile: u8) -> Self {
+        assert!(percentile <= 100, "Percentile must be between 0 and 100");
+        Self { percentile, min_micro_lamports: 0, max_micro_lamports: u64::MAX, compute_unit_limit: None }
+    }
+
+    pub fn min_micro_lamports(mut self, min_micro_lamports: u64) -> Self {
+        self.min_micro_lamports = min_micro_lamports;
+        self
+    }
+
+    pub fn max_micro_lamports(mut self, max_micro_lamports: u64) -> Self {
+        self.max_micro_lamports = max_micro_lamports;
+        self
+    }
+
+    pub fn compute_unit_limit(mut self, compute_unit_limit: u32) -> Self {
+        self.compute_unit_limit = Some(compute_unit_limit);
+        self
+    }
+
+    /// Price in micro-lamports per compute unit to bid, given the fees of recent slots
+    pub fn price(&self, mut recent_fees: Vec<u64>) -> u64 {
+        recent_fees.sort_unstable();
+        percentile(&recent_fees, self.percentile).clamp(self.min_micro_lamports, self.max_micro_lamports)
+    }
+}
+
+// Nearest-rank percentile of sorted samples, 0 when there are none
+fn percentile(sorted: &[u64], percentile: u8) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+
+    let rank = (sorted.len() * percentile as usize).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+/// Accounts the instructions write-lock, which are the ones fees compete over
+pub fn write_locked_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = instructions
+        .iter()
+        .flat_map(|instruction| &instruction.accounts)
+        .filter(|meta| meta.is_writable)
+        .map(|meta| meta.pubkey)
+        .collect();
+    accounts.sort();
+    accounts.dedup();
+    accounts.truncate(MAX_FEE_ACCOUNTS);
+    accounts
+}
+
+/// Queries recent fees for the accounts `instructions` write-lock and prices them per `fee`
+pub async fn estimate(client: &RpcClient, instructions: &[Instruction], fee: &PriorityFee) -> Result<u64> {
+    let recent = client.get_recent_prioritization_fees(&write_locked_accounts(instructions)).await?;
+    Ok(fee.price(recent.into_iter().map(|slot_fee| slot_fee.prioritization_fee).collect()))
+}
+
+/// `instructions` preceded by ComputeBudget instructions setting the unit price and
+/// limit. Instructions that already set their own budget are returned unchanged.
+pub fn with_compute_budget(instructions: &[Instruction], micro_lamports: u64, compute_unit_limit: Option<u32>) -> Vec<Instruction> {
+    if instructions.iter().any(|instruction| instruction.program_id == COMPUTE_BUDGET_PROGRAM_ID) {
+        return instructions.to_vec();
+    }
+
+    compute_unit_limit
+        .map(ComputeBudgetInstruction::set_compute_unit_limit)
+        .into_iter()
+        .chain((micro_lamports > 0).then(|| ComputeBudgetInstruction::set_compute_unit_price(micro_lamports)))
+        .chain(instructions.iter().cloned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, anchor_lang::prelude::AccountMeta};
+
+    #[test]
+    fn picks_nearest_rank_percentile_within_bounds() {
+        let fees = vec![50, 0, 10, 40, 30, 20, 0, 0, 100, 60];
+
+        assert_eq!(PriorityFee::percentile(50).price(fees.clone()), 20);
+        assert_eq!(PriorityFee::percentile(90).price(fees.clone()), 60);
+        assert_eq!(PriorityFee::percentile(100).price(fees.clone()), 100);
+        assert_eq!(PriorityFee::percentile(0).price(fees.clone()), 0);
+
+        assert_eq!(PriorityFee::percentile(100).max_micro_lamports(75).price(fees.clone()), 75);
+        assert_eq!(PriorityFee::percentile(50).min_micro_lamports(1_000).price(Vec::new()), 1_000);
+    }
+
+    #[test]
+    fn prepends_budget_once_and_only_for_write_locks() {
+        let (writable, readonly) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let instruction = Instruction {
+            program_id: crate::PROGRAM_ID,
+            accounts: vec![AccountMeta::new(writable, true), AccountMeta::new_readonly(readonly, false), AccountMeta::new(writable, false)],
+            data: vec![],
+        };
+
+        assert_eq!(write_locked_accounts(&[instruction.clone()]), vec![writable]);
+
+        let budgeted = with_compute_budget(&[instruction.clone()], 5_000, Some(200_000));
+        assert_eq!(budgeted.len(), 3);
+        assert_eq!(budgeted[0], ComputeBudgetInstruction::set_compute_unit_limit(200_000));
+        assert_eq!(budgeted[1], ComputeBudgetInstruction::set_compute_unit_price(5_000));
+
+        // A zero price adds nothing, and an existing budget is never doubled
+        assert_eq!(with_compute_budget(&[instruction], 0, None).len(), 1);
+        assert_eq!(with_compute_budget(&budgeted, 5_000, Some(200_000)), budgeted);
+    }
+}
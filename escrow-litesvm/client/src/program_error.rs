@@ -0,0 +1,192 @@
+//! On-chain failures of escrow transactions, mapped from their raw custom codes.
+
+use {
+    anchor_lang::solana_program::instruction::InstructionError,
+    solana_signature::Signature,
+    solana_transaction_error::TransactionError,
+};
+
+// Anchor constraint and account errors, from `anchor_lang::error::ErrorCode`
+const CONSTRAINT_HAS_ONE: u32 = 2001;
+const CONSTRAINT_SEEDS: u32 = 2006;
+const CONSTRAINT_TOKEN_OWNER: u32 = 2015;
+const ACCOUNT_NOT_INITIALIZED: u32 = 3012;
+const ACCOUNT_NOT_ASSOCIATED_TOKEN_ACCOUNT: u32 = 3014;
+
+// Shared by the token programs and the system program, both out of funds
+const INSUFFICIENT_FUNDS: u32 = 1;
+
+/// Why an escrow instruction failed, in terms a user can act on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum EscrowClientError {
+    #[error("Unwrapping the maker's proceeds needs mint B to be wrapped SOL")]
+    MintNotNative,
+    #[error("No account was given to pay the maker into")]
+    MissingMakerDestination,
+    #[error("The expiry must be in the future")]
+    InvalidExpiry,
+    #[error("Escrow expired")]
+    EscrowExpired,
+    #[error("The amounts overflow")]
+    MathOverflow,
+    #[error("The escrow's status does not allow this")]
+    InvalidStatusTransition,
+    #[error("The metadata is too long")]
+    MetadataTooLong,
+    #[error("The fill amount must be positive and at most what is left in the escrow")]
+    InvalidFillAmount,
+    #[error("The escrow has not expired yet")]
+    EscrowNotExpired,
+    #[error("The terms can only change while the escrow is open and unfilled")]
+    TermsLocked,
+    #[error("A token account involved is frozen")]
+    TokenAccountFrozen,
+    #[error("The quote must be signed by the maker in the instruction before the take")]
+    MissingQuoteSignature,
+    #[error("The maker's signature does not match the quote")]
+    InvalidQuoteSignature,
+    #[error("The quote is for another escrow or taker, or the escrow was filled since")]
+    QuoteMismatch,
+    #[error("Quote expired")]
+    QuoteExpired,
+    #[error("The escrow is gated and the accounts proving the taker qualifies are missing")]
+    MissingGateAccounts,
+    #[error("The taker does not hold a verified NFT of the escrow's collection")]
+    NotInCollection,
+    #[error("The taker has no valid attestation from the escrow's issuer")]
+    InvalidAttestation,
+    #[error("The signer is not the operator the maker registered for this escrow")]
+    NotOperator,
+    #[error("The start time is not before the expiry")]
+    InvalidStartTime,
+    #[error("The escrow cannot be taken before its start time")]
+    EscrowNotStarted,
+    #[error("The escrow is fill-or-kill, so a take must fill the whole remaining amount")]
+    PartialFillNotAllowed,
+    #[error("The escrow is reserved for another taker")]
+    ReservedForAnotherTaker,
+    #[error("Mint A charges a transfer fee, which the escrow cannot account for")]
+    TransferFeeMint,
+    #[error("An account does not belong to this escrow")]
+    AccountMismatch,
+    #[error("An address does not match the escrow's derived address")]
+    AddressMismatch,
+    #[error("A token account belongs to someone else")]
+    WrongTokenAccountOwner,
+    #[error("A token account is not the owner's associated token account")]
+    NotAssociatedTokenAccount,
+    #[error("The escrow does not exist, it may already be taken or refunded")]
+    AccountNotInitialized,
+    #[error("Not enough funds to pay for the transaction")]
+    InsufficientFunds,
+}
+
+impl EscrowClientError {
+    /// Program errors in declaration order, numbered from Anchor's 6000 offset
+    const PROGRAM_ERRORS: [Self; 24] = [
+        Self::MintNotNative,
+        Self::MissingMakerDestination,
+        Self::InvalidExpiry,
+        Self::EscrowExpired,
+        Self::MathOverflow,
+        Self::InvalidStatusTransition,
+        Self::MetadataTooLong,
+        Self::InvalidFillAmount,
+        Self::EscrowNotExpired,
+        Self::TermsLocked,
+        Self::TokenAccountFrozen,
+        Self::MissingQuoteSignature,
+        Self::InvalidQuoteSignature,
+        Self::QuoteMismatch,
+        Self::QuoteExpired,
+        Self::MissingGateAccounts,
+        Self::NotInCollection,
+        Self::InvalidAttestation,
+        Self::NotOperator,
+        Self::InvalidStartTime,
+        Self::EscrowNotStarted,
+        Self::PartialFillNotAllowed,
+        Self::ReservedForAnotherTaker,
+        Self::TransferFeeMint,
+    ];
+
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            6000.. => Self::PROGRAM_ERRORS.get((code - 6000) as usize).copied(),
+            CONSTRAINT_HAS_ONE => Some(Self::AccountMismatch),
+            CONSTRAINT_SEEDS => Some(Self::AddressMismatch),
+            CONSTRAINT_TOKEN_OWNER => Some(Self::WrongTokenAccountOwner),
+            ACCOUNT_NOT_INITIALIZED => Some(Self::AccountNotInitialized),
+            ACCOUNT_NOT_ASSOCIATED_TOKEN_ACCOUNT => Some(Self::NotAssociatedTokenAccount),
+            INSUFFICIENT_FUNDS => Some(Self::InsufficientFunds),
+            _ => None,
+        }
+    }
+
+    pub fn from_transaction_error(err: &TransactionError) -> Option<Self> {
+        match err {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => Self::from_code(*code),
+            TransactionError::InsufficientFundsForFee | TransactionError::InsufficientFundsForRent { .. } => Some(Self::InsufficientFunds),
+            _ => None,
+        }
+    }
+}
+
+impl crate::ClientError {
+    /// A failed simulation, mapped to the program error that caused it when known
+    pub(crate) fn preflight(err: TransactionError, logs: Vec<String>) -> Self {
+        match EscrowClientError::from_transaction_error(&err) {
+            Some(error) => Self::Program { error, signature: None, logs },
+            None => Self::Preflight { err, logs },
+        }
+    }
+
+    /// A failed transaction, mapped to the program error that caused it when known
+    pub(crate) fn failed(signature: Signature, err: TransactionError) -> Self {
+        match EscrowClientError::from_transaction_error(&err) {
+            Some(error) => Self::Program { error, signature: Some(signature), logs: Vec::new() },
+            None => Self::TransactionFailed { signature, err },
+        }
+    }
+
+    pub fn program_error(&self) -> Option<EscrowClientError> {
+        match self {
+            Self::Program { error, .. } => Some(*error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every code and name in the committed IDL maps to the variant of the same name
+    #[test]
+    fn program_errors_match_idl() {
+        let idl: serde_json::Value = serde_json::from_str(include_str!("../../interface/idls/anchor_escrow.json")).unwrap();
+        let errors = idl["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), EscrowClientError::PROGRAM_ERRORS.len());
+
+        for error in errors {
+            let code = error["code"].as_u64().unwrap() as u32;
+            let mapped = EscrowClientError::from_code(code).unwrap_or_else(|| panic!("Code {} is not mapped", code));
+            assert_eq!(format!("{:?}", mapped), error["name"].as_str().unwrap());
+        }
+    }
+
+    #[test]
+    fn maps_custom_codes_and_leaves_the_rest_raw() {
+        let custom = |code| TransactionError::InstructionError(0, InstructionError::Custom(code));
+
+        assert_eq!(EscrowClientError::from_transaction_error(&custom(6003)), Some(EscrowClientError::EscrowExpired));
+        assert_eq!(EscrowClientError::from_transaction_error(&custom(2001)), Some(EscrowClientError::AccountMismatch));
+        assert_eq!(EscrowClientError::from_transaction_error(&custom(6099)), None);
+        assert_eq!(EscrowClientError::from_transaction_error(&TransactionError::AccountInUse), None);
+
+        let error = crate::ClientError::preflight(custom(6003), vec!["Program log: AnchorError".to_string()]);
+        assert_eq!(error.to_string(), "Escrow expired");
+        assert_eq!(error.program_error(), Some(EscrowClientError::EscrowExpired));
+        assert!(matches!(crate::ClientError::preflight(custom(6099), Vec::new()), crate::ClientError::Preflight { .. }));
+    }
+}
@@ -0,0 +1,471 @@
+//! Async submission of escrow transactions over RPC.
+//!
+//! [`EscrowRpc`] simulates each transaction before sending it, then polls until it
+//! reaches the client's commitment. A transaction whose blockhash expires before it
+//! lands is re-signed against a fresh one, and transport errors are retried with
+//! exponential backoff. Program errors are returned straight away, retrying them
+//! would only fail again. [`EscrowRpc::prepare`] and [`EscrowRpc::submit`] split the
+//! same flow around signing, for keys that never touch this machine, and
+//! [`EscrowRpc::prepare_with_nonce`] builds against a durable nonce for signers that
+//! take longer than a blockhash lives.
+//!
+//! Building, sending and confirming each run in a `tracing` span carrying the payer
+//! and, once known, the signature, for whatever subscriber the application installs.
+
+use {
+    crate::{
+        accounts::{decode, decode_fetched, expired, sort_by_price, EscrowFilter, Fetched},
+        builders::{CloseExpiredBuilder, MakeBuilder, RefundBuilder, TakeBuilder},
+        error::{ClientError, Result},
+        lookup_table,
+        nonce,
+        offline::OfflineTransaction,
+        preview::{self, Simulation, TakePreview},
+        priority_fee::{self, PriorityFee},
+    },
+    anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction},
+    escrow_interface::{
+        accounts::{Escrow, FillReceipt},
+        ID as PROGRAM_ID,
+    },
+    solana_commitment_config::CommitmentConfig,
+    solana_hash::Hash,
+    solana_message::AddressLookupTableAccount,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::config::RpcSendTransactionConfig,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_transaction::versioned::VersionedTransaction,
+    std::time::Duration,
+    tracing::{debug, instrument, warn, Span},
+};
+
+#[derive(Clone, Debug)]
+pub struct RetryConfig {
+    /// Sends attempted before giving up, each with a freshly signed transaction
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Interval between `getSignatureStatuses` polls while confirming
+    pub poll_interval: Duration,
+    pub skip_preflight: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+            poll_interval: Duration::from_millis(500),
+            skip_preflight: false,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Delay before attempt `attempt + 1`, doubling from `initial_backoff` up to `max_backoff`
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+}
+
+// Outcome of polling a sent transaction
+enum Confirmation {
+    Confirmed,
+    // The blockhash expired without the transaction landing, so it never will
+    Expired,
+}
+
+// What a transaction's blockhash is, which decides when it can no longer land
+enum Lifetime {
+    Blockhash(Hash),
+    // Valid until the nonce account holds anything other than `value`
+    Nonce { account: Pubkey, value: Hash },
+}
+
+impl Lifetime {
+    fn of(transaction: &VersionedTransaction) -> Self {
+        let blockhash = *transaction.message.recent_blockhash();
+        match nonce::nonce_account(&transaction.message) {
+            Some(account) => Lifetime::Nonce { account, value: blockhash },
+            None => Lifetime::Blockhash(blockhash),
+        }
+    }
+}
+
+pub struct EscrowRpc {
+    client: RpcClient,
+    retry: RetryConfig,
+    priority_fee: Option<PriorityFee>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl EscrowRpc {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::from_client(RpcClient::new_with_commitment(url.into(), CommitmentConfig::confirmed()))
+    }
+
+    pub fn from_client(client: RpcClient) -> Self {
+        Self { client, retry: RetryConfig::default(), priority_fee: None, lookup_tables: Vec::new() }
+    }
+
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Priority fee applied to every send that does not pick its own
+    pub fn with_priority_fee(mut self, priority_fee: PriorityFee) -> Self {
+        self.priority_fee = Some(priority_fee);
+        self
+    }
+
+    /// Sends v0 transactions compiled against these tables instead of legacy ones
+    pub fn with_lookup_tables(mut self, lookup_tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.lookup_tables = lookup_tables;
+        self
+    }
+
+    pub fn client(&self) -> &RpcClient {
+        &self.client
+    }
+
+    pub async fn make(&self, builder: &MakeBuilder, maker: &dyn Signer) -> Result<Signature> {
+        self.send(&[builder.instruction()], maker, &[]).await
+    }
+
+    pub async fn take(&self, builder: &TakeBuilder, taker: &dyn Signer) -> Result<Signature> {
+        self.send(&[builder.instruction()], taker, &[]).await
+    }
+
+    /// Signed by the maker, or by the operator when the builder names one
+    pub async fn refund(&self, builder: &RefundBuilder, signer: &dyn Signer) -> Result<Signature> {
+        self.send(&[builder.instruction()], signer, &[]).await
+    }
+
+    pub async fn close_expired(&self, builder: &CloseExpiredBuilder, cranker: &dyn Signer) -> Result<Signature> {
+        self.send(&[builder.instruction()], cranker, &[]).await
+    }
+
+    /// Simulates `take` to show what the taker pays and receives before signing it
+    pub async fn preview_take(&self, take: &TakeBuilder) -> Result<TakePreview> {
+        preview::preview_take(&self.client, take).await
+    }
+
+    /// Simulates `instructions` paid for by `payer` without signing or sending them
+    pub async fn simulate(&self, instructions: &[Instruction], payer: &Pubkey) -> Result<Simulation> {
+        preview::simulate(&self.client, instructions, payer).await
+    }
+
+    /// Sends `instructions` paid for by `payer`, signed by `payer` and `signers`, and
+    /// waits until the transaction reaches the client's commitment
+    pub async fn send(&self, instructions: &[Instruction], payer: &dyn Signer, signers: &[&dyn Signer]) -> Result<Signature> {
+        self.send_with_priority_fee(instructions, payer, signers, self.priority_fee.as_ref()).await
+    }
+
+    /// [`send`](Self::send) with a priority fee for this call only, or none at all. The
+    /// fee is estimated again on every attempt, so retries follow a rising market.
+    #[instrument(name = "send", skip_all, fields(payer = %payer.pubkey(), signature))]
+    pub async fn send_with_priority_fee(
+        &self,
+        instructions: &[Instruction],
+        payer: &dyn Signer,
+        signers: &[&dyn Signer],
+        priority_fee: Option<&PriorityFee>,
+    ) -> Result<Signature> {
+        let all_signers: Vec<&dyn Signer> = std::iter::once(payer).chain(signers.iter().copied()).collect();
+
+        for attempt in 1..=self.retry.max_attempts {
+            if attempt > 1 {
+                tokio::time::sleep(self.retry.backoff(attempt - 1)).await;
+            }
+
+            // Every attempt signs against a fresh blockhash, and only once the previous
+            // attempt confirmed or expired, so a resend can never land twice
+            let blockhash = match self.client.get_latest_blockhash().await {
+                Ok(blockhash) => blockhash,
+                Err(err) if err.get_transaction_error().is_none() => {
+                    warn!(attempt, error = %err, "failed to fetch a blockhash");
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let instructions = self.with_priority_fee_instructions(instructions, priority_fee).await?;
+            let transaction = lookup_table::build_transaction(&instructions, &payer.pubkey(), &self.lookup_tables, blockhash, &all_signers)?;
+            debug!(attempt, %blockhash, "built transaction");
+
+            let signature = transaction.signatures[0];
+            Span::current().record("signature", tracing::field::display(&signature));
+            if self.send_once(&transaction).await?.is_none() {
+                // The send may still have reached the leader, so it is waited on like one
+                // that went through rather than signed again straight away
+                warn!(attempt, %signature, "send outcome unknown, waiting for it to land or expire");
+            }
+            match self.confirm(&signature, &Lifetime::Blockhash(blockhash)).await? {
+                Confirmation::Confirmed => return Ok(signature),
+                Confirmation::Expired => warn!(attempt, "blockhash expired before the transaction landed, signing again"),
+            }
+        }
+
+        Err(ClientError::RetriesExhausted { attempts: self.retry.max_attempts })
+    }
+
+    async fn with_priority_fee_instructions(&self, instructions: &[Instruction], priority_fee: Option<&PriorityFee>) -> Result<Vec<Instruction>> {
+        Ok(match priority_fee {
+            Some(fee) => {
+                let price = priority_fee::estimate(&self.client, instructions, fee).await?;
+                priority_fee::with_compute_budget(instructions, price, fee.compute_unit_limit)
+            }
+            None => instructions.to_vec(),
+        })
+    }
+
+    /// Builds the transaction [`send`](Self::send) would, without signing it, so it can be
+    /// signed elsewhere and handed back to [`submit`](Self::submit). It must be submitted
+    /// before its blockhash expires, roughly a minute after this call.
+    #[instrument(skip_all, fields(%payer))]
+    pub async fn prepare(&self, instructions: &[Instruction], payer: &Pubkey) -> Result<OfflineTransaction> {
+        let blockhash = self.client.get_latest_blockhash().await?;
+        let instructions = self.with_priority_fee_instructions(instructions, self.priority_fee.as_ref()).await?;
+        let message = lookup_table::build_message(&instructions, payer, &self.lookup_tables, blockhash)?;
+        Ok(OfflineTransaction::new(message))
+    }
+
+    /// [`prepare`](Self::prepare) against the durable nonce in `nonce_account` instead of a
+    /// recent blockhash, advanced by `nonce_authority`. The transaction stays valid until
+    /// it is submitted or the nonce is advanced by something else.
+    #[instrument(skip_all, fields(%payer, %nonce_account))]
+    pub async fn prepare_with_nonce(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        nonce_account: &Pubkey,
+        nonce_authority: &Pubkey,
+    ) -> Result<OfflineTransaction> {
+        let nonce = nonce::fetch(&self.client, nonce_account).await?;
+        if nonce.authority != *nonce_authority {
+            return Err(ClientError::InvalidNonce {
+                address: *nonce_account,
+                reason: format!("is advanced by {}, not {}", nonce.authority, nonce_authority),
+            });
+        }
+
+        let instructions = self.with_priority_fee_instructions(instructions, self.priority_fee.as_ref()).await?;
+        let message = lookup_table::build_message(&nonce::with_advance(&instructions, &nonce), payer, &self.lookup_tables, nonce.blockhash)?;
+        Ok(OfflineTransaction::new(message))
+    }
+
+    /// [`send`](Self::send) against the durable nonce in `nonce_account`, advanced by `payer`
+    pub async fn send_with_nonce(&self, instructions: &[Instruction], payer: &dyn Signer, nonce_account: &Pubkey) -> Result<Signature> {
+        let mut transaction = self.prepare_with_nonce(instructions, &payer.pubkey(), nonce_account, &payer.pubkey()).await?;
+        transaction.sign(payer)?;
+        self.submit(&transaction.into_signed()?).await
+    }
+
+    /// Splits `instructions` paid for by `payer` into as few transactions as fit, leaving
+    /// room for the priority fee instructions and, with `nonce_account`, the nonce advance
+    /// that [`send`](Self::send) or [`send_with_nonce`](Self::send_with_nonce) prepend
+    pub fn pack(&self, instructions: &[Instruction], payer: &Pubkey, nonce_account: Option<&Pubkey>) -> Result<Vec<Vec<Instruction>>> {
+        let mut prefix = Vec::new();
+        if let Some(nonce_account) = nonce_account {
+            let placeholder = nonce::Nonce { address: *nonce_account, authority: *payer, blockhash: Hash::default() };
+            prefix = nonce::with_advance(&prefix, &placeholder);
+        }
+        if let Some(fee) = &self.priority_fee {
+            // Any nonzero price takes the same space as the estimated one
+            prefix = priority_fee::with_compute_budget(&prefix, 1, fee.compute_unit_limit);
+        }
+        lookup_table::pack(instructions, payer, &self.lookup_tables, &prefix)
+    }
+
+    /// Sends a transaction signed elsewhere and waits for it to confirm. It cannot be
+    /// re-signed, so only transport errors are retried, until its blockhash expires or,
+    /// for a durable nonce transaction, until its nonce is advanced.
+    #[instrument(skip_all, fields(signature = %transaction.signatures[0]))]
+    pub async fn submit(&self, transaction: &VersionedTransaction) -> Result<Signature> {
+        let lifetime = Lifetime::of(transaction);
+
+        for attempt in 1..=self.retry.max_attempts {
+            if attempt > 1 {
+                tokio::time::sleep(self.retry.backoff(attempt - 1)).await;
+            }
+
+            let Some(signature) = self.send_once(transaction).await? else { continue };
+            match self.confirm(&signature, &lifetime).await? {
+                Confirmation::Confirmed => return Ok(signature),
+                Confirmation::Expired => return Err(ClientError::BlockhashExpired { signature }),
+            }
+        }
+
+        Err(ClientError::RetriesExhausted { attempts: self.retry.max_attempts })
+    }
+
+    /// Waits for a transaction sent through another channel, such as a bundle relay, to
+    /// reach the client's commitment. Fails with [`ClientError::BlockhashExpired`] once it
+    /// can no longer land.
+    #[instrument(skip_all, fields(signature = %transaction.signatures[0]))]
+    pub async fn await_confirmation(&self, transaction: &VersionedTransaction) -> Result<Signature> {
+        let signature = transaction.signatures[0];
+        match self.confirm(&signature, &Lifetime::of(transaction)).await? {
+            Confirmation::Confirmed => Ok(signature),
+            Confirmation::Expired => Err(ClientError::BlockhashExpired { signature }),
+        }
+    }
+
+    // Preflights and sends, `None` when a transport error is worth retrying
+    #[instrument(skip_all, fields(signature = %transaction.signatures[0]))]
+    async fn send_once(&self, transaction: &VersionedTransaction) -> Result<Option<Signature>> {
+        if !self.retry.skip_preflight {
+            let simulation = self.client.simulate_transaction(transaction).await?.value;
+            if let Some(err) = simulation.err {
+                return Err(ClientError::preflight(err, simulation.logs.unwrap_or_default()));
+            }
+        }
+
+        let config = RpcSendTransactionConfig {
+            skip_preflight: true,
+            preflight_commitment: Some(self.client.commitment().commitment),
+            max_retries: Some(0),
+            ..RpcSendTransactionConfig::default()
+        };
+        match self.client.send_transaction_with_config(transaction, config).await {
+            Ok(signature) => Ok(Some(signature)),
+            Err(err) if err.get_transaction_error().is_none() => {
+                warn!(error = %err, "failed to send, retrying");
+                Ok(None)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    #[instrument(skip_all, fields(%signature))]
+    async fn confirm(&self, signature: &Signature, lifetime: &Lifetime) -> Result<Confirmation> {
+        let commitment = self.client.commitment();
+
+        loop {
+            // Checked ahead of the status, so a nonce advanced by this very transaction
+            // is never mistaken for one advanced by another
+            let expired = self.expired(lifetime).await?;
+            let status = self.client.get_signature_statuses(&[*signature]).await?.value.remove(0);
+
+            if let Some(status) = status {
+                if let Some(err) = status.err {
+                    return Err(ClientError::failed(*signature, err));
+                }
+                if status.satisfies_commitment(commitment) {
+                    debug!(slot = status.slot, "confirmed");
+                    return Ok(Confirmation::Confirmed);
+                }
+            } else if expired {
+                return Ok(Confirmation::Expired);
+            }
+
+            tokio::time::sleep(self.retry.poll_interval).await;
+        }
+    }
+
+    async fn expired(&self, lifetime: &Lifetime) -> Result<bool> {
+        Ok(match lifetime {
+            Lifetime::Blockhash(blockhash) => !self.client.is_blockhash_valid(blockhash, self.client.commitment()).await?,
+            Lifetime::Nonce { account, value } => nonce::fetch(&self.client, account).await?.blockhash != *value,
+        })
+    }
+
+    /// Creates a lookup table holding [`lookup_table::default_addresses`] and `extra`,
+    /// owned by `authority`. Tables only resolve from the slot after they were last
+    /// extended, so wait a slot before passing it to [`with_lookup_tables`](Self::with_lookup_tables).
+    pub async fn create_lookup_table(&self, authority: &dyn Signer, extra: &[Pubkey]) -> Result<AddressLookupTableAccount> {
+        let recent_slot = self.client.get_slot_with_commitment(CommitmentConfig::finalized()).await?;
+
+        let mut addresses = lookup_table::default_addresses();
+        for address in extra {
+            if !addresses.contains(address) {
+                addresses.push(*address);
+            }
+        }
+
+        let (table, instructions) = lookup_table::create_instructions(&authority.pubkey(), &authority.pubkey(), recent_slot, addresses.clone());
+        self.send_with_priority_fee(&instructions, authority, &[], None).await?;
+
+        Ok(AddressLookupTableAccount { key: table, addresses })
+    }
+
+    pub async fn fetch_lookup_table(&self, address: &Pubkey) -> Result<AddressLookupTableAccount> {
+        let account = self.client
+            .get_account_with_commitment(address, self.client.commitment())
+            .await?
+            .value
+            .ok_or(ClientError::AccountNotFound(*address))?;
+        lookup_table::decode(address, &account.data)
+    }
+
+    pub async fn fetch_escrow(&self, address: &Pubkey) -> Result<Fetched<Escrow>> {
+        let account = self.client.get_account_with_commitment(address, self.client.commitment()).await?.value;
+        decode_fetched(address, account)
+    }
+
+    pub async fn fetch_receipt(&self, address: &Pubkey) -> Result<Fetched<FillReceipt>> {
+        let account = self.client.get_account_with_commitment(address, self.client.commitment()).await?.value;
+        decode_fetched(address, account)
+    }
+
+    pub async fn fetch_escrows(&self, filter: &EscrowFilter) -> Result<Vec<Fetched<Escrow>>> {
+        let config = filter.program_accounts_config(self.client.commitment());
+
+        self.client
+            .get_program_accounts_with_config(&PROGRAM_ID, config)
+            .await?
+            .into_iter()
+            .map(|(address, account)| Ok(Fetched { address, account: decode(&address, &account.data)? }))
+            .collect()
+    }
+
+    /// Async [`accounts::find_open_escrows`](crate::accounts::find_open_escrows)
+    pub async fn find_open_escrows(&self, mint_a: &Pubkey, mint_b: &Pubkey) -> Result<Vec<Fetched<Escrow>>> {
+        let mut escrows = self.fetch_escrows(&EscrowFilter::open_pair(*mint_a, *mint_b)).await?;
+        sort_by_price(&mut escrows);
+        Ok(escrows)
+    }
+
+    /// Async [`accounts::find_expired`](crate::accounts::find_expired)
+    pub async fn find_expired(&self, now: i64) -> Result<Vec<Fetched<Escrow>>> {
+        Ok(expired(self.fetch_escrows(&EscrowFilter::default()).await?, now))
+    }
+
+    /// Unix timestamp of the latest block at the client's commitment, the time the
+    /// program compares expiries against
+    pub async fn cluster_time(&self) -> Result<i64> {
+        let slot = self.client.get_slot().await?;
+        Ok(self.client.get_block_time(slot).await?)
+    }
+
+    /// Async [`accounts::find_by_maker`](crate::accounts::find_by_maker)
+    pub async fn find_by_maker(&self, maker: &Pubkey) -> Result<Vec<Fetched<Escrow>>> {
+        let mut escrows = self.fetch_escrows(&EscrowFilter::by_maker(*maker)).await?;
+        sort_by_price(&mut escrows);
+        Ok(escrows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let retry = RetryConfig {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(700),
+            ..RetryConfig::default()
+        };
+
+        let delays: Vec<u64> = (1..=5).map(|attempt| retry.backoff(attempt).as_millis() as u64).collect();
+        assert_eq!(delays, vec![100, 200, 400, 700, 700]);
+
+        // Far-off attempts saturate instead of overflowing
+        assert_eq!(retry.backoff(u32::MAX), retry.max_backoff);
+    }
+}
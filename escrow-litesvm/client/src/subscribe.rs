@@ -0,0 +1,315 @@
+//! Live escrow updates over websocket.
+//!
+//! `programSubscribe` reports escrows as they are made and changed, and each known
+//! escrow also gets an `accountSubscribe` so closes are seen even once the account
+//! falls out of the program filter. Every (re)connect starts from a
+//! `getProgramAccounts` snapshot diffed against what was already reported, so
+//! nothing missed while disconnected is lost.
+//!
+//! [`subscribe_events`] follows the program's logs instead, for what happened rather
+//! than what the accounts look like now: a closed escrow was either taken or refunded,
+//! and only the events say which.
+
+use {
+    crate::{
+        accounts::{decode, EscrowFilter, Fetched},
+        error::Result,
+        events::{decode_logs, ProgramEvent},
+        rpc::RetryConfig,
+        PROGRAM_ID,
+    },
+    anchor_lang::prelude::Pubkey,
+    escrow_interface::accounts::Escrow,
+    futures::{stream::{BoxStream, SelectAll}, Stream, StreamExt},
+    solana_account::Account,
+    solana_account_decoder_client_types::{UiAccount, UiAccountEncoding},
+    solana_commitment_config::CommitmentConfig,
+    solana_pubsub_client::nonblocking::pubsub_client::{PubsubClient, UnsubscribeFn},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::{
+        config::{RpcAccountInfoConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+        response::Response as RpcResponse,
+    },
+    solana_signature::Signature,
+    std::{collections::HashMap, str::FromStr},
+    tokio::sync::mpsc::UnboundedSender,
+    tokio_stream::wrappers::UnboundedReceiverStream,
+};
+
+/// A program event as logged by a successful transaction
+pub struct LoggedEvent {
+    pub signature: Signature,
+    pub slot: u64,
+    /// Position among the transaction's events, which with the signature identifies it
+    pub index: u32,
+    pub event: ProgramEvent,
+}
+
+#[derive(Clone)]
+pub enum EscrowEvent {
+    Created(Fetched<Escrow>),
+    Updated(Fetched<Escrow>),
+    Closed(Pubkey),
+}
+
+/// Websocket URL for `rpc_url` by the Solana CLI's convention: same host, ws(s)
+/// scheme, and the RPC port plus one when it has one
+pub fn websocket_url(rpc_url: &str) -> String {
+    let url = rpc_url.replacen("https://", "wss://", 1).replacen("http://", "ws://", 1);
+    match url.rsplit_once(':').map(|(host, port)| (host, port.trim_end_matches('/').parse::<u16>())) {
+        Some((host, Ok(port))) => format!("{}:{}", host, port + 1),
+        _ => url,
+    }
+}
+
+/// Streams every change to the escrows matching `filter`, starting with a `Created`
+/// for each one that already exists. Reconnects with backoff whenever the websocket
+/// drops, and ends only once the stream is dropped.
+pub fn subscribe_escrows(rpc_url: impl Into<String>, ws_url: impl Into<String>, filter: EscrowFilter) -> impl Stream<Item = EscrowEvent> {
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    let rpc_client = RpcClient::new_with_commitment(rpc_url.into(), CommitmentConfig::confirmed());
+    tokio::spawn(run(rpc_client, ws_url.into(), filter, sender));
+    UnboundedReceiverStream::new(receiver)
+}
+
+async fn run(rpc_client: RpcClient, ws_url: String, filter: EscrowFilter, sender: UnboundedSender<EscrowEvent>) {
+    let retry = RetryConfig::default();
+    let mut book = Book { known: HashMap::new(), sender };
+    let mut failures = 0;
+
+    while !book.sender.is_closed() {
+        if failures > 0 {
+            tokio::time::sleep(retry.backoff(failures)).await;
+        }
+
+        // A connection that got as far as streaming resets the backoff when it drops
+        match watch(&rpc_client, &ws_url, &filter, &mut book).await {
+            Ok(()) => failures = 1,
+            Err(_) => failures += 1,
+        }
+    }
+}
+
+// Data last reported for each escrow, so repeated notifications are only reported once
+struct Book {
+    known: HashMap<Pubkey, Vec<u8>>,
+    sender: UnboundedSender<EscrowEvent>,
+}
+
+impl Book {
+    fn apply(&mut self, address: Pubkey, account: Option<Account>) -> Option<EscrowEvent> {
+        let live = account.filter(|account| account.lamports > 0 && account.owner == PROGRAM_ID);
+        let escrow = live.as_ref().and_then(|account| decode::<Escrow>(&address, &account.data).ok());
+
+        let event = match (escrow, self.known.contains_key(&address)) {
+            (None, true) => {
+                self.known.remove(&address);
+                EscrowEvent::Closed(address)
+            }
+            (None, false) => return None,
+            (Some(escrow), known) => {
+                let data = live.unwrap().data;
+                if self.known.get(&address) == Some(&data) {
+                    return None;
+                }
+                self.known.insert(address, data);
+
+                let fetched = Fetched { address, account: escrow };
+                if known { EscrowEvent::Updated(fetched) } else { EscrowEvent::Created(fetched) }
+            }
+        };
+
+        let _ = self.sender.send(event.clone());
+        Some(event)
+    }
+}
+
+fn account_config() -> RpcAccountInfoConfig {
+    RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        ..RpcAccountInfoConfig::default()
+    }
+}
+
+// accountSubscribe notifications don't carry the address, so pair each with it
+fn tag<'a>(address: Pubkey, updates: BoxStream<'a, RpcResponse<UiAccount>>) -> BoxStream<'a, (Pubkey, Option<Account>)> {
+    updates.map(move |update| (address, update.value.decode::<Account>())).boxed()
+}
+
+// Returns once the connection has been established and later dropped
+async fn watch(rpc_client: &RpcClient, ws_url: &str, filter: &EscrowFilter, book: &mut Book) -> Result<()> {
+    let pubsub = PubsubClient::new(ws_url).await?;
+    let (mut program_updates, _unsubscribe) = pubsub
+        .program_subscribe(&PROGRAM_ID, Some(filter.program_accounts_config(CommitmentConfig::confirmed())))
+        .await?;
+
+    let mut account_updates: SelectAll<BoxStream<'_, (Pubkey, Option<Account>)>> = SelectAll::new();
+    let mut unsubscribes: HashMap<Pubkey, UnsubscribeFn> = HashMap::new();
+
+    // Subscribing before taking the snapshot means no change can fall in between
+    let snapshot: HashMap<Pubkey, Account> = rpc_client
+        .get_program_accounts_with_config(&PROGRAM_ID, filter.program_accounts_config(CommitmentConfig::confirmed()))
+        .await?
+        .into_iter()
+        .collect();
+
+    let gone: Vec<Pubkey> = book.known.keys().filter(|address| !snapshot.contains_key(address)).copied().collect();
+    for address in gone {
+        book.apply(address, None);
+    }
+    for (address, account) in snapshot {
+        book.apply(address, Some(account));
+    }
+
+    for address in book.known.keys().copied().collect::<Vec<_>>() {
+        let (updates, unsubscribe) = pubsub.account_subscribe(&address, Some(account_config())).await?;
+        account_updates.push(tag(address, updates));
+        unsubscribes.insert(address, unsubscribe);
+    }
+
+    loop {
+        let (address, account) = tokio::select! {
+            Some(update) = program_updates.next() => {
+                let Ok(address) = Pubkey::from_str(&update.value.pubkey) else { continue };
+                (address, update.value.account.decode::<Account>())
+            }
+            Some(update) = account_updates.next(), if !account_updates.is_empty() => update,
+            else => return Ok(()),
+        };
+
+        if book.sender.is_closed() {
+            return Ok(());
+        }
+
+        match book.apply(address, account) {
+            Some(EscrowEvent::Created(_)) => {
+                let (updates, unsubscribe) = pubsub.account_subscribe(&address, Some(account_config())).await?;
+                account_updates.push(tag(address, updates));
+                unsubscribes.insert(address, unsubscribe);
+            }
+            Some(EscrowEvent::Closed(_)) => {
+                if let Some(unsubscribe) = unsubscribes.remove(&address) {
+                    unsubscribe().await;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Streams the events of every successful escrow transaction. Reconnects with backoff
+/// whenever the websocket drops; events logged while disconnected are not replayed.
+pub fn subscribe_events(ws_url: impl Into<String>) -> impl Stream<Item = LoggedEvent> {
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(run_events(ws_url.into(), sender));
+    UnboundedReceiverStream::new(receiver)
+}
+
+async fn run_events(ws_url: String, sender: UnboundedSender<LoggedEvent>) {
+    let retry = RetryConfig::default();
+    let mut failures = 0;
+
+    while !sender.is_closed() {
+        if failures > 0 {
+            tokio::time::sleep(retry.backoff(failures)).await;
+        }
+
+        match watch_events(&ws_url, &sender).await {
+            Ok(()) => failures = 1,
+            Err(_) => failures += 1,
+        }
+    }
+}
+
+async fn watch_events(ws_url: &str, sender: &UnboundedSender<LoggedEvent>) -> Result<()> {
+    let pubsub = PubsubClient::new(ws_url).await?;
+    let (mut logs, _unsubscribe) = pubsub
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![PROGRAM_ID.to_string()]),
+            RpcTransactionLogsConfig { commitment: Some(CommitmentConfig::confirmed()) },
+        )
+        .await?;
+
+    while let Some(update) = logs.next().await {
+        // A failed transaction's events were rolled back with everything else
+        if update.value.err.is_some() {
+            continue;
+        }
+        let Ok(signature) = Signature::from_str(&update.value.signature) else { continue };
+
+        for (index, event) in decode_logs(&update.value.logs).into_iter().enumerate() {
+            let logged = LoggedEvent { signature, slot: update.context.slot, index: index as u32, event };
+            if sender.send(logged).is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, anchor_lang::AccountSerialize};
+
+    fn escrow_account(remaining: u64) -> Account {
+        let escrow = ::anchor_escrow::state::Escrow {
+            seed: 1,
+            maker: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            status: ::anchor_escrow::state::EscrowStatus::Open,
+            receive: 10,
+            deposit_total: 10,
+            filled: 10 - remaining,
+            remaining,
+            created_at: 0,
+            expires_at: 0,
+            starts_at: 0,
+            bump: 255,
+            gate: ::anchor_escrow::state::TakerGate::None,
+            operator: Pubkey::default(),
+            fill_or_kill: false,
+            reserved_for: Pubkey::default(),
+            nonce: 0,
+            metadata: Vec::new(),
+        };
+        let mut data = Vec::new();
+        escrow.try_serialize(&mut data).unwrap();
+        Account { lamports: 1_000_000, data, owner: PROGRAM_ID, executable: false, rent_epoch: 0 }
+    }
+
+    #[test]
+    fn reports_each_change_once() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut book = Book { known: HashMap::new(), sender };
+        let address = Pubkey::new_unique();
+        let open = escrow_account(10);
+
+        assert!(matches!(book.apply(address, Some(open.clone())), Some(EscrowEvent::Created(_))));
+        // The same data from the other subscription is a duplicate
+        assert!(book.apply(address, Some(open.clone())).is_none());
+        assert!(matches!(book.apply(address, Some(escrow_account(4))), Some(EscrowEvent::Updated(_))));
+
+        // Anchor's close leaves zero lamports behind
+        let closed = Account { lamports: 0, data: vec![0; open.data.len()], ..open };
+        assert!(matches!(book.apply(address, Some(closed)), Some(EscrowEvent::Closed(_))));
+        assert!(book.apply(address, None).is_none());
+
+        let sent: Vec<&str> = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|event| match event {
+                EscrowEvent::Created(_) => "created",
+                EscrowEvent::Updated(_) => "updated",
+                EscrowEvent::Closed(_) => "closed",
+            })
+            .collect();
+        assert_eq!(sent, vec!["created", "updated", "closed"]);
+    }
+
+    #[test]
+    fn derives_websocket_url() {
+        assert_eq!(websocket_url("https://api.devnet.solana.com"), "wss://api.devnet.solana.com");
+        assert_eq!(websocket_url("http://127.0.0.1:8899"), "ws://127.0.0.1:8900");
+    }
+}
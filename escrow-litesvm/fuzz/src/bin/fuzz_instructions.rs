@@ -0,0 +1,29 @@
+//! Run with `cargo hfuzz run fuzz_instructions` from the `fuzz` directory after `anchor build`.
+
+use {
+    anchor_escrow_fuzz::{FuzzEnv, FuzzInstruction},
+    arbitrary::{Arbitrary, Unstructured},
+    honggfuzz::fuzz,
+};
+
+// Caps the sequence length so each input stays cheap to replay
+const MAX_INSTRUCTIONS: usize = 32;
+
+fn main() {
+    loop {
+        fuzz!(|data: &[u8]| {
+            let mut unstructured = Unstructured::new(data);
+            let Ok(instructions) = Vec::<FuzzInstruction>::arbitrary(&mut unstructured) else {
+                return;
+            };
+
+            let mut env = FuzzEnv::new();
+            env.check_invariants();
+
+            for instruction in instructions.iter().take(MAX_INSTRUCTIONS) {
+                env.execute(instruction);
+                env.check_invariants();
+            }
+        });
+    }
+}
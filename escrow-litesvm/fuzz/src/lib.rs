@@ -0,0 +1,307 @@
+use {
+    anchor_escrow::constants::{ESCROW_SEED, MAKER_COUNTER_SEED},
+    anchor_lang::{
+        solana_program::{clock::Clock, program_pack::Pack},
+        AccountDeserialize,
+        InstructionData,
+        ToAccountMetas
+    },
+    anchor_spl::{
+        associated_token::{
+            self,
+            spl_associated_token_account
+        },
+        token::spl_token
+    },
+    arbitrary::Arbitrary,
+    litesvm::LiteSVM,
+    litesvm_token::{
+        spl_token::ID as TOKEN_PROGRAM_ID,
+        CreateAssociatedTokenAccount,
+        CreateMint,
+        MintTo
+    },
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_message::Message,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+};
+
+const PROGRAM_ID: Pubkey = anchor_escrow::ID;
+const SYSTEM_PROGRAM_ID: Pubkey = anchor_lang::system_program::ID;
+
+// Few users and seeds so that sequences keep hitting the same escrows
+const USERS: usize = 2;
+const SEEDS: u64 = 4;
+const INITIAL_BALANCE: u64 = 1_000_000;
+
+#[derive(Arbitrary, Debug)]
+pub enum FuzzInstruction {
+    Make { user: u8, seed: u8, deposit: u64, receive: u64, expires_in: i32 },
+    Take { user: u8, maker: u8, seed: u8, mutation: AccountMutation },
+    Refund { user: u8, maker: u8, seed: u8, mutation: AccountMutation },
+    Mutated { kind: u8, user: u8, maker: u8, seed: u8, data: Vec<u8>, truncate_to: Option<u8>, mutation: AccountMutation },
+    Warp { seconds: u32 },
+}
+
+/// Permutes the account metas of an otherwise valid instruction
+#[derive(Arbitrary, Debug)]
+pub struct AccountMutation {
+    swaps: Vec<(u8, u8)>,
+    toggle_writable: Option<u8>,
+    // Another user pays and signs, so the intended signer's signature is missing
+    foreign_payer: Option<u8>,
+}
+
+impl AccountMutation {
+    fn apply(&self, ix: &mut Instruction) {
+        let len = ix.accounts.len();
+        for (a, b) in &self.swaps {
+            ix.accounts.swap(*a as usize % len, *b as usize % len);
+        }
+        if let Some(index) = self.toggle_writable {
+            let meta = &mut ix.accounts[index as usize % len];
+            meta.is_writable = !meta.is_writable;
+        }
+        if self.foreign_payer.is_some() {
+            for meta in ix.accounts.iter_mut() {
+                meta.is_signer = false;
+            }
+        }
+    }
+
+    fn payer(&self, user: u8) -> u8 {
+        self.foreign_payer.unwrap_or(user)
+    }
+}
+
+pub struct FuzzEnv {
+    svm: LiteSVM,
+    users: Vec<Keypair>,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+}
+
+impl FuzzEnv {
+    /// Loads the compiled program and gives every user both mints to trade with.
+    /// Unlike the program tests, nothing is fetched from devnet.
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+
+        let program_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../programs/anchor-escrow");
+        svm.add_program(PROGRAM_ID, &test_utils::artifact::program_bytes("anchor_escrow", program_dir));
+
+        let users: Vec<Keypair> = (0..USERS).map(|_| Keypair::new()).collect();
+        for user in &users {
+            svm.airdrop(&user.pubkey(), 100 * LAMPORTS_PER_SOL).unwrap();
+        }
+
+        let authority = &users[0];
+        let mint_a = CreateMint::new(&mut svm, authority).decimals(6).send().unwrap();
+        let mint_b = CreateMint::new(&mut svm, authority).decimals(6).send().unwrap();
+
+        for user in &users {
+            for mint in [mint_a, mint_b] {
+                let ata = CreateAssociatedTokenAccount::new(&mut svm, user, &mint)
+                    .owner(&user.pubkey())
+                    .send()
+                    .unwrap();
+                MintTo::new(&mut svm, authority, &mint, &ata, INITIAL_BALANCE).send().unwrap();
+            }
+        }
+
+        Self { svm, users, mint_a, mint_b }
+    }
+
+    fn user(&self, index: u8) -> &Keypair {
+        &self.users[index as usize % USERS]
+    }
+
+    fn escrow(&self, maker: u8, seed: u8) -> (Pubkey, u64) {
+        let seed = seed as u64 % SEEDS;
+        let maker = self.user(maker).pubkey();
+        let escrow = Pubkey::find_program_address(&[ESCROW_SEED, maker.as_ref(), &seed.to_le_bytes()], &PROGRAM_ID).0;
+        (escrow, seed)
+    }
+
+    fn ata(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        associated_token::get_associated_token_address(owner, mint)
+    }
+
+    fn make_ix(&self, user: u8, seed: u8, deposit: u64, receive: u64, expires_at: i64) -> Instruction {
+        let maker = self.user(user).pubkey();
+        let (escrow, seed) = self.escrow(user, seed);
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: anchor_escrow::accounts::Make {
+                maker,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                maker_ata_a: self.ata(&maker, &self.mint_a),
+                escrow,
+                vault: self.ata(&escrow, &self.mint_a),
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+                maker_counter: Pubkey::find_program_address(&[MAKER_COUNTER_SEED, maker.as_ref()], &PROGRAM_ID).0,
+            }.to_account_metas(None),
+            data: anchor_escrow::instruction::Make { seed, deposit, receive, expires_at, starts_at: 0 }.data(),
+        }
+    }
+
+    fn take_ix(&self, user: u8, maker: u8, seed: u8) -> Instruction {
+        let taker = self.user(user).pubkey();
+        let maker_address = self.user(maker).pubkey();
+        let (escrow, _) = self.escrow(maker, seed);
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: anchor_escrow::accounts::Take {
+                taker,
+                maker: maker_address,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                taker_ata_a: self.ata(&taker, &self.mint_a),
+                taker_ata_b: self.ata(&taker, &self.mint_b),
+                maker_ata_b: Some(self.ata(&maker_address, &self.mint_b)),
+                maker_wsol: None,
+                escrow,
+                vault: self.ata(&escrow, &self.mint_a),
+                receipt: Pubkey::find_program_address(
+                    &[anchor_escrow::constants::RECEIPT_SEED, escrow.as_ref(), taker.as_ref()],
+                    &PROGRAM_ID
+                ).0,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+            }.to_account_metas(None),
+            data: anchor_escrow::instruction::Take {}.data(),
+        }
+    }
+
+    fn refund_ix(&self, user: u8, maker: u8, seed: u8) -> Instruction {
+        // `user` signs while the escrow belongs to `maker`, covering non-maker refunds
+        let signer = self.user(user).pubkey();
+        let (escrow, _) = self.escrow(maker, seed);
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: anchor_escrow::accounts::Refund {
+                maker: signer,
+                mint_a: self.mint_a,
+                maker_ata_a: self.ata(&signer, &self.mint_a),
+                escrow,
+                vault: self.ata(&escrow, &self.mint_a),
+                token_program: TOKEN_PROGRAM_ID,
+            }.to_account_metas(None),
+            data: anchor_escrow::instruction::Refund {}.data(),
+        }
+    }
+
+    pub fn execute(&mut self, instruction: &FuzzInstruction) {
+        let (ix, payer) = match instruction {
+            FuzzInstruction::Make { user, seed, deposit, receive, expires_in } => {
+                let now = self.svm.get_sysvar::<Clock>().unix_timestamp;
+                let expires_at = if *expires_in == 0 { 0 } else { now + *expires_in as i64 };
+                (self.make_ix(*user, *seed, *deposit, *receive, expires_at), *user)
+            }
+            FuzzInstruction::Take { user, maker, seed, mutation } => {
+                let mut ix = self.take_ix(*user, *maker, *seed);
+                mutation.apply(&mut ix);
+                (ix, mutation.payer(*user))
+            }
+            FuzzInstruction::Refund { user, maker, seed, mutation } => {
+                let mut ix = self.refund_ix(*user, *maker, *seed);
+                mutation.apply(&mut ix);
+                (ix, mutation.payer(*user))
+            }
+            FuzzInstruction::Mutated { kind, user, maker, seed, data, truncate_to, mutation } => {
+                let mut ix = match kind % 3 {
+                    0 => self.make_ix(*user, *seed, 1, 1, 0),
+                    1 => self.take_ix(*user, *maker, *seed),
+                    _ => self.refund_ix(*user, *maker, *seed),
+                };
+                // Flip bits anywhere in the discriminator and encoded arguments
+                let len = ix.data.len();
+                for (i, byte) in data.iter().enumerate() {
+                    ix.data[i % len] ^= byte;
+                }
+                if let Some(truncate_to) = truncate_to {
+                    ix.data.truncate(*truncate_to as usize);
+                }
+                mutation.apply(&mut ix);
+                (ix, mutation.payer(*user))
+            }
+            FuzzInstruction::Warp { seconds } => {
+                let mut clock = self.svm.get_sysvar::<Clock>();
+                clock.unix_timestamp += *seconds as i64;
+                self.svm.set_sysvar::<Clock>(&clock);
+                return;
+            }
+        };
+
+        let payer = self.user(payer);
+        let message = Message::new(&[ix], Some(&payer.pubkey()));
+        let transaction = Transaction::new(&[payer], message, self.svm.latest_blockhash());
+
+        if let Err(failed) = self.svm.send_transaction(transaction) {
+            // Rejections are expected, a panic inside the program never is
+            assert!(
+                !failed.meta.logs.iter().any(|log| log.contains("panicked")),
+                "Program panicked on {:?}: {:#?}", instruction, failed.meta.logs
+            );
+        }
+
+        // Identical instructions in a row must not be deduplicated as the same transaction
+        self.svm.expire_blockhash();
+    }
+
+    fn token_amount(&self, address: &Pubkey) -> u64 {
+        self.svm
+            .get_account(address)
+            .filter(|account| account.owner == TOKEN_PROGRAM_ID && !account.data.is_empty())
+            .map(|account| spl_token::state::Account::unpack(&account.data).unwrap().amount)
+            .unwrap_or(0)
+    }
+
+    /// No instruction sequence may mint, burn or strand tokens, or leave an escrow
+    /// whose accounting disagrees with its vault.
+    pub fn check_invariants(&self) {
+        let mut total_a = 0u64;
+        let mut total_b = 0u64;
+
+        for (index, user) in self.users.iter().enumerate() {
+            total_a += self.token_amount(&self.ata(&user.pubkey(), &self.mint_a));
+            total_b += self.token_amount(&self.ata(&user.pubkey(), &self.mint_b));
+
+            for seed in 0..SEEDS as u8 {
+                let (escrow, _) = self.escrow(index as u8, seed);
+                let vault = self.ata(&escrow, &self.mint_a);
+                let vault_amount = self.token_amount(&vault);
+                total_a += vault_amount;
+
+                match self.svm.get_account(&escrow).filter(|account| account.owner == PROGRAM_ID) {
+                    Some(account) => {
+                        let escrow_data = anchor_escrow::state::Escrow::try_deserialize(&mut account.data.as_ref())
+                            .expect("Escrow account is corrupted");
+                        assert_eq!(escrow_data.maker, user.pubkey());
+                        assert_eq!(escrow_data.filled + escrow_data.remaining, escrow_data.deposit_total);
+                        assert!(vault_amount >= escrow_data.remaining, "Vault holds less than the escrow owes");
+                    }
+                    None => assert_eq!(vault_amount, 0, "Vault outlived its escrow"),
+                }
+            }
+        }
+
+        let minted = INITIAL_BALANCE * USERS as u64;
+        assert_eq!(total_a, minted, "Mint A tokens were created or lost");
+        assert_eq!(total_b, minted, "Mint B tokens were created or lost");
+    }
+}
+
+impl Default for FuzzEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,80 @@
+-- Amounts are u64 on chain, which overflows BIGINT, so they are NUMERIC(20, 0).
+-- Addresses and signatures are base58 text.
+
+CREATE TABLE escrows (
+    address           TEXT PRIMARY KEY,
+    maker             TEXT NOT NULL,
+    seed              NUMERIC(20, 0) NOT NULL,
+    mint_a            TEXT NOT NULL,
+    mint_b            TEXT NOT NULL,
+    deposit           NUMERIC(20, 0) NOT NULL,
+    receive           NUMERIC(20, 0) NOT NULL,
+    filled            NUMERIC(20, 0) NOT NULL DEFAULT 0,
+    remaining         NUMERIC(20, 0) NOT NULL,
+    -- open, filled, refunded or expired
+    status            TEXT NOT NULL,
+    -- NULL when the escrow never expires
+    expires_at        BIGINT,
+    created_signature TEXT NOT NULL,
+    created_slot      BIGINT NOT NULL,
+    created_at        BIGINT,
+    closed_signature  TEXT,
+    -- Slot of the last event applied, so an older event replayed late can't undo a newer one
+    updated_slot      BIGINT NOT NULL
+);
+
+CREATE INDEX escrows_pair ON escrows (mint_a, mint_b);
+CREATE INDEX escrows_maker ON escrows (maker);
+
+CREATE TABLE fills (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    escrow      TEXT NOT NULL,
+    maker       TEXT NOT NULL,
+    taker       TEXT NOT NULL,
+    amount      NUMERIC(20, 0) NOT NULL,
+    price       NUMERIC(20, 0) NOT NULL,
+    remaining   NUMERIC(20, 0) NOT NULL,
+    slot        BIGINT NOT NULL,
+    -- NULL until the transaction is seen through RPC rather than the websocket
+    block_time  BIGINT,
+    indexed_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE INDEX fills_escrow ON fills (escrow);
+CREATE INDEX fills_slot ON fills (slot);
+
+-- Refunds by the maker and expired escrows closed by a cranker
+CREATE TABLE refunds (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    escrow      TEXT NOT NULL,
+    maker       TEXT NOT NULL,
+    amount      NUMERIC(20, 0) NOT NULL,
+    -- Set for close_expired, NULL for the maker's own refund
+    cranker     TEXT,
+    slot        BIGINT NOT NULL,
+    block_time  BIGINT,
+    indexed_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE INDEX refunds_escrow ON refunds (escrow);
+
+-- Newest transaction the RPC catch-up has processed
+CREATE TABLE cursor (
+    id        BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    signature TEXT NOT NULL,
+    slot      BIGINT NOT NULL
+);
+
+CREATE MATERIALIZED VIEW open_book AS
+    SELECT address, maker, mint_a, mint_b, remaining, receive, deposit, expires_at,
+           receive / deposit AS price
+    FROM escrows
+    WHERE status = 'open' AND deposit > 0;
+
+-- REFRESH ... CONCURRENTLY needs a unique index
+CREATE UNIQUE INDEX open_book_address ON open_book (address);
+CREATE INDEX open_book_pair ON open_book (mint_a, mint_b, price);
@@ -0,0 +1,8 @@
+-- Events a webhook notification was sent for, so an event stored twice, once from the
+-- websocket and once from catch-up, is only announced once
+CREATE TABLE notifications (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    sent_at     TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (signature, event_index)
+);
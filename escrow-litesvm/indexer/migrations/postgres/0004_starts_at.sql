@@ -0,0 +1,15 @@
+-- A timelocked escrow can't be taken before starts_at, so it stays out of open_book
+-- until a refresh after that. NULL when the escrow can be taken right away.
+ALTER TABLE escrows ADD COLUMN starts_at BIGINT;
+
+DROP MATERIALIZED VIEW open_book;
+
+CREATE MATERIALIZED VIEW open_book AS
+    SELECT address, maker, mint_a, mint_b, remaining, receive, deposit, expires_at,
+           receive / deposit AS price
+    FROM escrows
+    WHERE status = 'open' AND deposit > 0
+      AND (starts_at IS NULL OR starts_at <= extract(epoch FROM now()));
+
+CREATE UNIQUE INDEX open_book_address ON open_book (address);
+CREATE INDEX open_book_pair ON open_book (mint_a, mint_b, price);
@@ -0,0 +1,14 @@
+-- An expired escrow can't be taken, only closed, so it leaves open_book at the first
+-- refresh after expires_at even while it waits to be closed.
+DROP MATERIALIZED VIEW open_book;
+
+CREATE MATERIALIZED VIEW open_book AS
+    SELECT address, maker, mint_a, mint_b, remaining, receive, deposit, expires_at,
+           receive / deposit AS price
+    FROM escrows
+    WHERE status = 'open' AND deposit > 0
+      AND (starts_at IS NULL OR starts_at <= extract(epoch FROM now()))
+      AND (expires_at IS NULL OR expires_at > extract(epoch FROM now()));
+
+CREATE UNIQUE INDEX open_book_address ON open_book (address);
+CREATE INDEX open_book_pair ON open_book (mint_a, mint_b, price);
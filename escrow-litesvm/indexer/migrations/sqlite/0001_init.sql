@@ -0,0 +1,78 @@
+-- Amounts are u64 on chain, which overflows INTEGER, so they are decimal TEXT and
+-- any arithmetic on them happens in the indexer. Addresses and signatures are base58.
+
+CREATE TABLE escrows (
+    address           TEXT PRIMARY KEY,
+    maker             TEXT NOT NULL,
+    seed              TEXT NOT NULL,
+    mint_a            TEXT NOT NULL,
+    mint_b            TEXT NOT NULL,
+    deposit           TEXT NOT NULL,
+    receive           TEXT NOT NULL,
+    filled            TEXT NOT NULL DEFAULT '0',
+    remaining         TEXT NOT NULL,
+    -- open, filled, refunded or expired
+    status            TEXT NOT NULL,
+    -- NULL when the escrow never expires
+    expires_at        INTEGER,
+    created_signature TEXT NOT NULL,
+    created_slot      INTEGER NOT NULL,
+    created_at        INTEGER,
+    closed_signature  TEXT,
+    -- Slot of the last event applied, so an older event replayed late can't undo a newer one
+    updated_slot      INTEGER NOT NULL
+);
+
+CREATE INDEX escrows_pair ON escrows (mint_a, mint_b);
+CREATE INDEX escrows_maker ON escrows (maker);
+
+CREATE TABLE fills (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    escrow      TEXT NOT NULL,
+    maker       TEXT NOT NULL,
+    taker       TEXT NOT NULL,
+    amount      TEXT NOT NULL,
+    price       TEXT NOT NULL,
+    remaining   TEXT NOT NULL,
+    slot        INTEGER NOT NULL,
+    -- NULL until the transaction is seen through RPC rather than the websocket
+    block_time  INTEGER,
+    indexed_at  TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE INDEX fills_escrow ON fills (escrow);
+CREATE INDEX fills_slot ON fills (slot);
+
+-- Refunds by the maker and expired escrows closed by a cranker
+CREATE TABLE refunds (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    escrow      TEXT NOT NULL,
+    maker       TEXT NOT NULL,
+    amount      TEXT NOT NULL,
+    -- Set for close_expired, NULL for the maker's own refund
+    cranker     TEXT,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    indexed_at  TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE INDEX refunds_escrow ON refunds (escrow);
+
+-- Newest transaction the RPC catch-up has processed
+CREATE TABLE cursor (
+    id        INTEGER PRIMARY KEY CHECK (id = 1),
+    signature TEXT NOT NULL,
+    slot      INTEGER NOT NULL
+);
+
+-- Events a webhook notification was sent for
+CREATE TABLE notifications (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    sent_at     TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (signature, event_index)
+);
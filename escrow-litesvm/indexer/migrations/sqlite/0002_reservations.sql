@@ -0,0 +1,3 @@
+-- Reserving an escrow sets its status to reserved, which keeps it out of the open book
+-- until the maker releases it. NULL while the escrow is open to every taker.
+ALTER TABLE escrows ADD COLUMN reserved_for TEXT;
@@ -0,0 +1,3 @@
+-- A timelocked escrow can't be taken before starts_at, so the open book skips it until
+-- then. NULL when the escrow can be taken right away.
+ALTER TABLE escrows ADD COLUMN starts_at INTEGER;
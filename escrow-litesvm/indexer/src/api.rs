@@ -0,0 +1,134 @@
+//! Read-only HTTP API over the indexed data, for frontends that should not need RPC.
+//!
+//! ```text
+//! GET /escrows?mint_a=<MINT>&mint_b=<MINT>   takable escrows, cheapest first
+//! GET /makers/<MAKER>/escrows                every escrow of a maker, newest first
+//! GET /fills?escrow=&maker=&taker=&limit=    fills, newest first
+//! ```
+//!
+//! Amounts are decimal strings, since u64 doesn't survive a JavaScript number.
+
+use {
+    crate::store::{EscrowRow, FillFilter, FillRow, OpenEscrow, Repository},
+    axum::{
+        extract::{Path, Query, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::get,
+        Json, Router,
+    },
+    serde::Deserialize,
+    solana_pubkey::Pubkey,
+    std::{net::SocketAddr, str::FromStr, sync::Arc},
+};
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 1_000;
+
+pub enum ApiError {
+    BadRequest(String),
+    Internal(anyhow::Error),
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::Internal(err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            Self::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            Self::Internal(err) => {
+                tracing::error!(error = format!("{:#}", err), "API request failed");
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal error".to_string())
+            }
+        };
+        (status, Json(serde_json::json!({ "error": message }))).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+fn pubkey(name: &str, value: Option<String>) -> Result<Option<String>, ApiError> {
+    value
+        .map(|value| match Pubkey::from_str(&value) {
+            Ok(_) => Ok(value),
+            Err(_) => Err(ApiError::BadRequest(format!("{} is not a valid address", name))),
+        })
+        .transpose()
+}
+
+fn limit(limit: Option<i64>) -> Result<i64, ApiError> {
+    match limit.unwrap_or(DEFAULT_LIMIT) {
+        limit @ 1..=MAX_LIMIT => Ok(limit),
+        _ => Err(ApiError::BadRequest(format!("limit must be between 1 and {}", MAX_LIMIT))),
+    }
+}
+
+pub fn router(store: Arc<dyn Repository>) -> Router {
+    Router::new()
+        .route("/escrows", get(open_escrows))
+        .route("/makers/{maker}/escrows", get(maker_escrows))
+        .route("/fills", get(fills))
+        .with_state(store)
+}
+
+pub async fn serve(listen: SocketAddr, store: Arc<dyn Repository>) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(listen).await?;
+    axum::serve(listener, router(store)).await?;
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct PairQuery {
+    mint_a: Option<String>,
+    mint_b: Option<String>,
+}
+
+async fn open_escrows(State(store): State<Arc<dyn Repository>>, Query(query): Query<PairQuery>) -> ApiResult<Vec<OpenEscrow>> {
+    let (mint_a, mint_b) = (pubkey("mint_a", query.mint_a)?, pubkey("mint_b", query.mint_b)?);
+    Ok(Json(store.open_escrows(mint_a.as_deref(), mint_b.as_deref()).await?))
+}
+
+async fn maker_escrows(State(store): State<Arc<dyn Repository>>, Path(maker): Path<String>) -> ApiResult<Vec<EscrowRow>> {
+    let maker = pubkey("maker", Some(maker))?.unwrap();
+    Ok(Json(store.maker_escrows(&maker).await?))
+}
+
+#[derive(Deserialize)]
+struct FillQuery {
+    escrow: Option<String>,
+    maker: Option<String>,
+    taker: Option<String>,
+    limit: Option<i64>,
+}
+
+async fn fills(State(store): State<Arc<dyn Repository>>, Query(query): Query<FillQuery>) -> ApiResult<Vec<FillRow>> {
+    let filter = FillFilter {
+        escrow: pubkey("escrow", query.escrow)?,
+        maker: pubkey("maker", query.maker)?,
+        taker: pubkey("taker", query.taker)?,
+        limit: limit(query.limit)?,
+    };
+    Ok(Json(store.fills(&filter).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_query_parameters() {
+        assert!(matches!(pubkey("maker", Some("nope".to_string())), Err(ApiError::BadRequest(_))));
+        assert_eq!(pubkey("maker", None).ok().unwrap(), None);
+        let address = Pubkey::new_unique().to_string();
+        assert_eq!(pubkey("maker", Some(address.clone())).ok().unwrap(), Some(address));
+
+        assert_eq!(limit(None).ok(), Some(DEFAULT_LIMIT));
+        assert_eq!(limit(Some(MAX_LIMIT)).ok(), Some(MAX_LIMIT));
+        assert!(limit(Some(0)).is_err());
+        assert!(limit(Some(MAX_LIMIT + 1)).is_err());
+    }
+}
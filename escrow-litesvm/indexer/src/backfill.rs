@@ -0,0 +1,48 @@
+//! Rebuilds the database from the program's full transaction history.
+//!
+//! Transactions are fetched `concurrency` at a time but applied strictly oldest first,
+//! so every escrow passes through the same states it did on chain. Writes are
+//! idempotent, so an interrupted backfill is simply run again.
+
+use {
+    crate::{history, store::Repository},
+    futures::{stream, StreamExt},
+    tracing::{info, instrument},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+};
+
+const PROGRESS_EVERY: usize = 500;
+
+#[instrument(skip_all)]
+pub async fn run(rpc: &RpcClient, store: &dyn Repository, concurrency: usize) -> anyhow::Result<()> {
+    let signatures = history::all_signatures(rpc).await?;
+    info!(transactions = signatures.len(), "backfilling");
+
+    let mut fetched = stream::iter(&signatures)
+        .map(|(signature, _)| history::events(rpc, signature))
+        .buffered(concurrency);
+
+    let mut events = 0;
+    let mut done = 0;
+    while let Some(transaction_events) = fetched.next().await {
+        for event in transaction_events? {
+            store.apply(&event).await?;
+            events += 1;
+        }
+
+        done += 1;
+        if done % PROGRESS_EVERY == 0 {
+            info!(done, transactions = signatures.len(), events, "backfill progress");
+        }
+    }
+
+    // A live indexer picks up from the newest transaction, one that already has a
+    // cursor keeps it
+    if let (None, Some((signature, slot))) = (store.cursor().await?, signatures.last()) {
+        store.set_cursor(signature, *slot).await?;
+    }
+    store.refresh_open_book().await?;
+
+    info!(transactions = signatures.len(), events, "backfilled");
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! Live events from a Yellowstone gRPC (Geyser) endpoint, in place of the websocket.
+//!
+//! The subscription streams every successful transaction that mentions the program,
+//! with its full status meta, so `emit_cpi!` events arrive live as well as logged
+//! ones and the event indexes match what catch-up derives from `getTransaction`.
+//! Escrow accounts need no subscription of their own, since every instruction that
+//! changes one emits an event. Like the websocket stream it reconnects with backoff.
+
+use {
+    escrow_client::{
+        events::{decode_inner_instruction, decode_logs},
+        rpc::RetryConfig,
+        LoggedEvent, PROGRAM_ID,
+    },
+    futures::{SinkExt, Stream, StreamExt},
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    std::collections::HashMap,
+    tokio::sync::mpsc::UnboundedSender,
+    tokio_stream::wrappers::UnboundedReceiverStream,
+    yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient},
+    yellowstone_grpc_proto::geyser::{
+        subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest, SubscribeRequestFilterTransactions,
+        SubscribeRequestPing, SubscribeUpdateTransactionInfo,
+    },
+};
+
+/// Streams program events from `endpoint`, authenticating with `x_token` when set
+pub fn subscribe_events(endpoint: String, x_token: Option<String>) -> impl Stream<Item = LoggedEvent> {
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(run(endpoint, x_token, sender));
+    UnboundedReceiverStream::new(receiver)
+}
+
+async fn run(endpoint: String, x_token: Option<String>, sender: UnboundedSender<LoggedEvent>) {
+    let retry = RetryConfig::default();
+    let mut failures = 0;
+
+    while !sender.is_closed() {
+        if failures > 0 {
+            tokio::time::sleep(retry.backoff(failures)).await;
+        }
+
+        match watch(&endpoint, x_token.clone(), &sender).await {
+            Ok(()) => failures = 1,
+            Err(err) => {
+                failures += 1;
+                tracing::warn!(error = format!("{:#}", err), "Geyser stream failed");
+            }
+        }
+    }
+}
+
+fn request() -> SubscribeRequest {
+    let transactions = SubscribeRequestFilterTransactions {
+        vote: Some(false),
+        // A failed transaction's events were rolled back with everything else
+        failed: Some(false),
+        account_include: vec![PROGRAM_ID.to_string()],
+        ..Default::default()
+    };
+    SubscribeRequest {
+        transactions: HashMap::from([("escrow".to_string(), transactions)]),
+        commitment: Some(CommitmentLevel::Confirmed as i32),
+        ..Default::default()
+    }
+}
+
+async fn watch(endpoint: &str, x_token: Option<String>, sender: &UnboundedSender<LoggedEvent>) -> anyhow::Result<()> {
+    let mut client = GeyserGrpcClient::build_from_shared(endpoint.to_string())?
+        .x_token(x_token)?
+        .tls_config(ClientTlsConfig::new().with_native_roots())?
+        .connect()
+        .await?;
+    let (mut requests, mut updates) = client.subscribe_with_request(Some(request())).await?;
+
+    while let Some(update) = updates.next().await {
+        match update?.update_oneof {
+            Some(UpdateOneof::Transaction(update)) => {
+                let Some(transaction) = update.transaction else { continue };
+                for logged in decode(&transaction, update.slot) {
+                    if sender.send(logged).is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+            // Load balancers drop streams that stay quiet, so pings are answered
+            Some(UpdateOneof::Ping(_)) => {
+                let ping = SubscribeRequest { ping: Some(SubscribeRequestPing { id: 1 }), ..Default::default() };
+                requests.send(ping).await?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Events of one transaction, logged ones first as `decode_transaction` orders them
+fn decode(transaction: &SubscribeUpdateTransactionInfo, slot: u64) -> Vec<LoggedEvent> {
+    let Ok(signature) = Signature::try_from(transaction.signature.as_slice()) else { return Vec::new() };
+    let Some(meta) = &transaction.meta else { return Vec::new() };
+    if meta.err.is_some() {
+        return Vec::new();
+    }
+
+    // Loaded addresses follow the static keys, writable first, as inner instructions index them
+    let static_keys = transaction.transaction.iter().flat_map(|tx| &tx.message).flat_map(|message| &message.account_keys);
+    let keys: Vec<Pubkey> = static_keys
+        .chain(&meta.loaded_writable_addresses)
+        .chain(&meta.loaded_readonly_addresses)
+        .filter_map(|key| Pubkey::try_from(key.as_slice()).ok())
+        .collect();
+
+    let mut events = decode_logs(&meta.log_messages);
+    for instruction in meta.inner_instructions.iter().flat_map(|inner| &inner.instructions) {
+        let Some(program_id) = keys.get(instruction.program_id_index as usize) else { continue };
+        events.extend(decode_inner_instruction(program_id, &instruction.data));
+    }
+
+    events
+        .into_iter()
+        .enumerate()
+        .map(|(index, event)| LoggedEvent { signature, slot, index: index as u32, event })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, yellowstone_grpc_proto::prelude::TransactionStatusMeta};
+
+    #[test]
+    fn subscribes_to_successful_program_transactions() {
+        let request = request();
+        let filter = &request.transactions["escrow"];
+        assert_eq!(filter.account_include, vec![PROGRAM_ID.to_string()]);
+        assert_eq!(filter.failed, Some(false));
+        assert_eq!(filter.vote, Some(false));
+    }
+
+    #[test]
+    fn skips_failed_transactions() {
+        let transaction = SubscribeUpdateTransactionInfo {
+            signature: Signature::new_unique().as_ref().to_vec(),
+            meta: Some(TransactionStatusMeta {
+                err: Some(Default::default()),
+                log_messages: vec![format!("Program {} invoke [1]", PROGRAM_ID)],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(decode(&transaction, 1).is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+//! Catch-up over RPC: every successful program transaction newer than the cursor,
+//! oldest first, with its events. This picks up whatever the websocket missed while
+//! disconnected, and events logged through `emit_cpi!` that logs alone don't carry.
+//!
+//! [`all_signatures`] walks the whole history instead, for a backfill. Escrow state is
+//! rebuilt from events alone, which every instruction that changes an escrow emits.
+
+use {
+    crate::Indexed,
+    escrow_client::{events::decode_transaction_with_keys, PROGRAM_ID},
+    solana_commitment_config::CommitmentConfig,
+    solana_rpc_client::{nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config},
+    solana_rpc_client_api::{config::RpcTransactionConfig, response::RpcConfirmedTransactionStatusWithSignature},
+    solana_signature::Signature,
+    solana_transaction_status_client_types::{
+        option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiMessage,
+        UiTransactionEncoding,
+    },
+    std::str::FromStr,
+};
+
+// `getSignaturesForAddress` page size limit
+const PAGE_SIZE: usize = 1_000;
+
+/// Signatures of successful program transactions after `until`, oldest first. With no
+/// `until` only the newest is returned, so a fresh database starts from now.
+pub async fn signatures_since(client: &RpcClient, until: Option<Signature>) -> anyhow::Result<Vec<(Signature, u64)>> {
+    let limit = if until.is_some() { PAGE_SIZE } else { 1 };
+    let mut before = None;
+    let mut newest_first = Vec::new();
+
+    loop {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until,
+            limit: Some(limit),
+            commitment: Some(CommitmentConfig::confirmed()),
+        };
+        let page = client.get_signatures_for_address_with_config(&PROGRAM_ID, config).await?;
+        let full = page.len() == PAGE_SIZE;
+        before = page.last().and_then(|status| Signature::from_str(&status.signature).ok());
+        newest_first.extend(page);
+
+        if !full || until.is_none() {
+            break;
+        }
+    }
+
+    Ok(successful_oldest_first(newest_first))
+}
+
+/// Signatures of every successful program transaction since the deployment, oldest first
+pub async fn all_signatures(client: &RpcClient) -> anyhow::Result<Vec<(Signature, u64)>> {
+    let mut before = None;
+    let mut newest_first = Vec::new();
+
+    loop {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until: None,
+            limit: Some(PAGE_SIZE),
+            commitment: Some(CommitmentConfig::confirmed()),
+        };
+        let page = client.get_signatures_for_address_with_config(&PROGRAM_ID, config).await?;
+        let full = page.len() == PAGE_SIZE;
+        before = page.last().and_then(|status| Signature::from_str(&status.signature).ok());
+        newest_first.extend(page);
+
+        if !full {
+            break;
+        }
+    }
+
+    Ok(successful_oldest_first(newest_first))
+}
+
+fn successful_oldest_first(newest_first: Vec<RpcConfirmedTransactionStatusWithSignature>) -> Vec<(Signature, u64)> {
+    newest_first
+        .into_iter()
+        .rev()
+        .filter(|status| status.err.is_none())
+        .filter_map(|status| Some((Signature::from_str(&status.signature).ok()?, status.slot)))
+        .collect()
+}
+
+/// The events of one transaction
+pub async fn events(client: &RpcClient, signature: &Signature) -> anyhow::Result<Vec<Indexed>> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let transaction = client.get_transaction_with_config(signature, config).await?;
+    Ok(decode(signature, transaction))
+}
+
+fn decode(signature: &Signature, transaction: EncodedConfirmedTransactionWithStatusMeta) -> Vec<Indexed> {
+    let Some(meta) = transaction.transaction.meta else { return Vec::new() };
+    if meta.err.is_some() {
+        return Vec::new();
+    }
+
+    // Loaded addresses follow the static keys, writable first, as inner instructions index them
+    let mut keys = match transaction.transaction.transaction {
+        EncodedTransaction::Json(ui) => match ui.message {
+            UiMessage::Raw(raw) => raw.account_keys,
+            UiMessage::Parsed(parsed) => parsed.account_keys.into_iter().map(|key| key.pubkey).collect(),
+        },
+        _ => Vec::new(),
+    };
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        keys.extend(loaded.writable.iter().chain(&loaded.readonly).cloned());
+    }
+
+    decode_transaction_with_keys(&meta, &keys)
+        .into_iter()
+        .enumerate()
+        .map(|(index, event)| Indexed {
+            signature: *signature,
+            slot: transaction.slot,
+            block_time: transaction.block_time,
+            index: index as u32,
+            event,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_transaction_error::TransactionError, RpcConfirmedTransactionStatusWithSignature as Status};
+
+    fn status(signature: Signature, slot: u64, failed: bool) -> Status {
+        Status {
+            signature: signature.to_string(),
+            slot,
+            err: failed.then_some(TransactionError::AccountInUse),
+            memo: None,
+            block_time: None,
+            confirmation_status: None,
+        }
+    }
+
+    #[test]
+    fn orders_successful_signatures_oldest_first() {
+        let signatures: Vec<Signature> = (0..3).map(|_| Signature::new_unique()).collect();
+        let newest_first = vec![
+            status(signatures[2], 30, false),
+            status(signatures[1], 20, true),
+            status(signatures[0], 10, false),
+        ];
+
+        assert_eq!(successful_oldest_first(newest_first), vec![(signatures[0], 10), (signatures[2], 30)]);
+    }
+}
@@ -0,0 +1,246 @@
+//! `escrow-indexer`, which keeps a database copy of every escrow, fill and refund.
+//!
+//! ```text
+//! escrow-indexer --db postgres://localhost/escrow --url https://api.devnet.solana.com
+//! escrow-indexer --db sqlite://escrow.db
+//! ```
+//!
+//! Events arrive over the websocket as they are logged, or from a Yellowstone gRPC
+//! endpoint with `--geyser-url`, see [`geyser`], and a periodic catch-up walks
+//! `getSignaturesForAddress` from the last transaction it processed, filling in
+//! whatever the websocket missed and the block times it does not report. A fresh
+//! database starts from the program's newest transaction. The `open_book` view,
+//! open escrows with their price, is refreshed on its own interval.
+//!
+//! With `--api-listen` the indexer also serves the data over HTTP, see [`api`], and
+//! with `--metrics-listen` Prometheus metrics, see [`metrics`]. `--webhook` announces
+//! fills, refunds and expiries to the maker's systems, see [`notify`].
+//! Logs go through `tracing`, and `--otlp-endpoint` exports the spans, see
+//! [`telemetry`]. `escrow-indexer backfill` rebuilds everything from the program's first transaction,
+//! see [`backfill`].
+
+mod api;
+mod backfill;
+mod geyser;
+mod history;
+mod metrics;
+mod notify;
+mod store;
+mod telemetry;
+
+use {
+    anyhow::Context as _,
+    clap::{Parser, Subcommand},
+    escrow_client::{subscribe::websocket_url, subscribe_events, LoggedEvent, ProgramEvent},
+    futures::StreamExt,
+    metrics::{Metrics, MINUTE_OF_SLOTS},
+    notify::{Notification, Webhooks},
+    solana_commitment_config::CommitmentConfig,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_signature::Signature,
+    std::{net::SocketAddr, sync::Arc, time::Duration},
+    tracing::{error, info, instrument, warn},
+    store::Repository,
+};
+
+#[derive(Parser)]
+#[command(name = "escrow-indexer", version, about = "Index escrow events into Postgres or SQLite")]
+struct Args {
+    /// postgres://... or sqlite://<path>
+    #[arg(long = "db", visible_alias = "database-url", env = "DATABASE_URL")]
+    database_url: String,
+    /// JSON RPC URL of the cluster
+    #[arg(long, short = 'u', env = "INDEXER_URL", default_value = "https://api.devnet.solana.com")]
+    url: String,
+    /// Websocket URL, derived from the RPC URL by default
+    #[arg(long, env = "INDEXER_WS_URL")]
+    ws_url: Option<String>,
+    /// Yellowstone gRPC endpoint to take live events from instead of the websocket
+    #[arg(long, env = "INDEXER_GEYSER_URL")]
+    geyser_url: Option<String>,
+    /// x-token sent to the gRPC endpoint
+    #[arg(long, env = "INDEXER_GEYSER_TOKEN", hide_env_values = true, requires = "geyser_url")]
+    geyser_token: Option<String>,
+    /// Seconds between catch-up passes over RPC
+    #[arg(long, default_value_t = 60)]
+    catch_up_interval: u64,
+    /// Seconds between refreshes of the open_book view
+    #[arg(long, default_value_t = 10)]
+    refresh_interval: u64,
+    /// Address to serve the REST API on, e.g. 0.0.0.0:8080, off when omitted
+    #[arg(long, env = "INDEXER_API_LISTEN")]
+    api_listen: Option<SocketAddr>,
+    /// Address to serve Prometheus metrics on, e.g. 0.0.0.0:9100, off when omitted
+    #[arg(long, env = "INDEXER_METRICS_LISTEN")]
+    metrics_listen: Option<SocketAddr>,
+    /// URL to POST lifecycle notifications to, repeat for several
+    #[arg(long = "webhook", env = "INDEXER_WEBHOOKS", value_delimiter = ',')]
+    webhooks: Vec<String>,
+    /// Key to sign webhook payloads with, unsigned when omitted
+    #[arg(long, env = "INDEXER_WEBHOOK_SECRET", hide_env_values = true)]
+    webhook_secret: Option<String>,
+    /// OTLP/gRPC collector to export traces to, e.g. http://localhost:4317
+    #[arg(long, env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index the program's whole history, then exit
+    Backfill {
+        /// Transactions fetched at once
+        #[arg(long, default_value_t = 8)]
+        concurrency: usize,
+    },
+}
+
+/// An event and where it was logged
+pub struct Indexed {
+    pub signature: Signature,
+    pub slot: u64,
+    /// Only known for events fetched over RPC
+    pub block_time: Option<i64>,
+    pub index: u32,
+    pub event: ProgramEvent,
+}
+
+impl From<LoggedEvent> for Indexed {
+    fn from(logged: LoggedEvent) -> Self {
+        Self { signature: logged.signature, slot: logged.slot, block_time: None, index: logged.index, event: logged.event }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let _telemetry = telemetry::init("escrow-indexer", args.otlp_endpoint.as_deref())?;
+    let store = store::connect(&args.database_url).await.context("Failed to open the database")?;
+    if let Some(listen) = args.api_listen {
+        let store = store.clone();
+        tokio::spawn(async move {
+            if let Err(err) = api::serve(listen, store).await {
+                error!(error = format!("{:#}", err), "API server stopped");
+            }
+        });
+    }
+    let metrics = Arc::new(Metrics::new()?);
+    if let Some(listen) = args.metrics_listen {
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            if let Err(err) = metrics::serve(listen, metrics).await {
+                error!(error = format!("{:#}", err), "metrics server stopped");
+            }
+        });
+    }
+    let rpc = RpcClient::new_with_commitment(args.url.clone(), CommitmentConfig::confirmed());
+    let webhooks = match args.webhooks.is_empty() {
+        true => None,
+        false => Some(Arc::new(Webhooks::new(args.webhooks, args.webhook_secret)?)),
+    };
+    let sink = Sink { store: &*store, metrics: &metrics, webhooks };
+
+    if let Some(Command::Backfill { concurrency }) = args.command {
+        return backfill::run(&rpc, &*store, concurrency.max(1)).await;
+    }
+
+    // Subscribed before the first catch-up, so nothing falls in between
+    let mut live = match args.geyser_url {
+        Some(endpoint) => geyser::subscribe_events(endpoint, args.geyser_token).boxed(),
+        None => subscribe_events(args.ws_url.clone().unwrap_or_else(|| websocket_url(&args.url))).boxed(),
+    };
+    let mut catch_up = tokio::time::interval(Duration::from_secs(args.catch_up_interval));
+    let mut refresh = tokio::time::interval(Duration::from_secs(args.refresh_interval));
+    for interval in [&mut catch_up, &mut refresh] {
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    }
+
+    loop {
+        tokio::select! {
+            Some(logged) = live.next() => {
+                if let Err(err) = sink.record(&Indexed::from(logged), "live").await {
+                    // Catch-up writes it again later
+                    warn!(error = format!("{:#}", err), "failed to store a live event");
+                }
+            }
+            _ = catch_up.tick() => {
+                if let Err(err) = catch_up_once(&rpc, &sink).await {
+                    error!(error = format!("{:#}", err), "catch-up failed");
+                }
+            }
+            _ = refresh.tick() => {
+                if let Err(err) = refresh_once(&rpc, &*store, &metrics).await {
+                    error!(error = format!("{:#}", err), "refresh failed");
+                }
+            }
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// Where every event goes once decoded
+struct Sink<'a> {
+    store: &'a dyn Repository,
+    metrics: &'a Metrics,
+    webhooks: Option<Arc<Webhooks>>,
+}
+
+impl Sink<'_> {
+    // Announced only once stored, so a receiver that looks the escrow up finds it
+    #[instrument(skip_all, fields(signature = %indexed.signature, index = indexed.index, source = %source))]
+    async fn record(&self, indexed: &Indexed, source: &str) -> anyhow::Result<()> {
+        self.store.apply(indexed).await?;
+        self.metrics.events.with_label_values(&[source]).inc();
+
+        if let (Some(webhooks), Some(notification)) = (&self.webhooks, Notification::of(indexed)) {
+            if self.store.claim_notification(indexed).await? {
+                webhooks.send(&notification)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// The cursor only moves past a transaction once all of its events are stored
+#[instrument(skip_all)]
+async fn catch_up_once(rpc: &RpcClient, sink: &Sink<'_>) -> anyhow::Result<()> {
+    let Sink { store, metrics, .. } = sink;
+    let tip = metrics.timed("get_slot", rpc.get_slot()).await?;
+    let cursor = store.cursor().await?;
+    let signatures = metrics.timed("get_signatures_for_address", history::signatures_since(rpc, cursor)).await?;
+
+    for (signature, slot) in &signatures {
+        for event in metrics.timed("get_transaction", history::events(rpc, signature)).await? {
+            sink.record(&event, "catch_up").await?;
+        }
+        store.set_cursor(signature, *slot).await?;
+    }
+    metrics.caught_up_slot.set(tip as i64);
+
+    if !signatures.is_empty() {
+        info!(transactions = signatures.len(), "caught up");
+    }
+    Ok(())
+}
+
+// The gauges follow the view, so they move on the same interval
+async fn refresh_once(rpc: &RpcClient, store: &dyn Repository, metrics: &Metrics) -> anyhow::Result<()> {
+    store.refresh_open_book().await?;
+    metrics.open_escrows.set(store.open_escrow_count().await?);
+
+    let tip = metrics.timed("get_slot", rpc.get_slot()).await?;
+    metrics.set_tip(tip);
+    metrics.fills_per_minute.set(store.fills_since(tip.saturating_sub(MINUTE_OF_SLOTS)).await?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, clap::CommandFactory};
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Args::command().debug_assert();
+    }
+}
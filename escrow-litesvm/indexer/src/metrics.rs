@@ -0,0 +1,116 @@
+//! Prometheus metrics, served at `/metrics` with `--metrics-listen`.
+//!
+//! ```text
+//! escrow_indexer_open_escrows               rows of open_book at its last refresh
+//! escrow_indexer_fills_per_minute           fills in the last 150 slots, about a minute
+//! escrow_indexer_events_total{source}       events stored, from live or catch_up
+//! escrow_indexer_tip_slot                   the cluster's slot
+//! escrow_indexer_caught_up_slot             tip when the last successful catch-up began
+//! escrow_indexer_slot_lag                   the two apart, grows while catch-up fails
+//! escrow_indexer_rpc_latency_seconds{method} RPC round trips
+//! ```
+//!
+//! Events seen over both the websocket and catch-up are counted once per source.
+
+use {
+    axum::{extract::State, http::header, response::IntoResponse, routing::get, Router},
+    prometheus::{HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder},
+    std::{future::Future, net::SocketAddr, sync::Arc},
+};
+
+/// Slots in about a minute at 400ms each
+pub const MINUTE_OF_SLOTS: u64 = 150;
+
+pub struct Metrics {
+    registry: Registry,
+    pub open_escrows: IntGauge,
+    pub fills_per_minute: IntGauge,
+    pub events: IntCounterVec,
+    pub tip_slot: IntGauge,
+    pub caught_up_slot: IntGauge,
+    pub slot_lag: IntGauge,
+    rpc_latency: HistogramVec,
+}
+
+impl Metrics {
+    pub fn new() -> anyhow::Result<Self> {
+        let registry = Registry::new();
+        let metrics = Self {
+            open_escrows: IntGauge::new("escrow_indexer_open_escrows", "Open escrows at the last open_book refresh")?,
+            fills_per_minute: IntGauge::new(
+                "escrow_indexer_fills_per_minute",
+                "Fills in the last 150 slots, about a minute",
+            )?,
+            events: IntCounterVec::new(Opts::new("escrow_indexer_events_total", "Events stored by source"), &["source"])?,
+            tip_slot: IntGauge::new("escrow_indexer_tip_slot", "Slot of the cluster")?,
+            caught_up_slot: IntGauge::new(
+                "escrow_indexer_caught_up_slot",
+                "Slot of the cluster when the last successful catch-up began",
+            )?,
+            slot_lag: IntGauge::new("escrow_indexer_slot_lag", "Slots the indexer is behind the cluster")?,
+            rpc_latency: HistogramVec::new(
+                HistogramOpts::new("escrow_indexer_rpc_latency_seconds", "RPC round trips by method"),
+                &["method"],
+            )?,
+            registry,
+        };
+
+        metrics.registry.register(Box::new(metrics.open_escrows.clone()))?;
+        metrics.registry.register(Box::new(metrics.fills_per_minute.clone()))?;
+        metrics.registry.register(Box::new(metrics.events.clone()))?;
+        metrics.registry.register(Box::new(metrics.tip_slot.clone()))?;
+        metrics.registry.register(Box::new(metrics.caught_up_slot.clone()))?;
+        metrics.registry.register(Box::new(metrics.slot_lag.clone()))?;
+        metrics.registry.register(Box::new(metrics.rpc_latency.clone()))?;
+        Ok(metrics)
+    }
+
+    /// Awaits `call`, recording how long it took under `method`
+    pub async fn timed<T>(&self, method: &str, call: impl Future<Output = T>) -> T {
+        let _timer = self.rpc_latency.with_label_values(&[method]).start_timer();
+        call.await
+    }
+
+    pub fn set_tip(&self, tip: u64) {
+        self.tip_slot.set(tip as i64);
+        self.slot_lag.set((tip as i64 - self.caught_up_slot.get()).max(0));
+    }
+
+    pub fn encode(&self) -> anyhow::Result<String> {
+        Ok(TextEncoder::new().encode_to_string(&self.registry.gather())?)
+    }
+}
+
+pub async fn serve(listen: SocketAddr, metrics: Arc<Metrics>) -> anyhow::Result<()> {
+    let router = Router::new().route("/metrics", get(scrape)).with_state(metrics);
+    let listener = tokio::net::TcpListener::bind(listen).await?;
+    axum::serve(listener, router).await?;
+    Ok(())
+}
+
+async fn scrape(State(metrics): State<Arc<Metrics>>) -> impl IntoResponse {
+    match metrics.encode() {
+        Ok(body) => ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response(),
+        Err(err) => {
+            tracing::error!(error = format!("{:#}", err), "failed to encode metrics");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lag_is_measured_from_the_last_catch_up() {
+        let metrics = Metrics::new().unwrap();
+        metrics.caught_up_slot.set(1_000);
+        metrics.set_tip(1_250);
+        assert_eq!(metrics.slot_lag.get(), 250);
+
+        let text = metrics.encode().unwrap();
+        assert!(text.contains("escrow_indexer_slot_lag 250"));
+        assert!(text.contains("escrow_indexer_tip_slot 1250"));
+    }
+}
@@ -0,0 +1,225 @@
+//! Webhook notifications when an escrow is taken, expires or is refunded.
+//!
+//! Each event is POSTed as JSON to every `--webhook` URL:
+//!
+//! ```text
+//! {"type": "escrow.taken", "escrow": "...", "maker": "...", "signature": "...", "slot": 1,
+//!  "taker": "...", "amount": "10", "price": "25", "remaining": "90"}
+//! ```
+//!
+//! With `--webhook-secret` the request carries `X-Escrow-Timestamp` and
+//! `X-Escrow-Signature: sha256=<hex>`, an HMAC-SHA256 of `<timestamp>.<body>`, so a
+//! receiver can check the payload came from the indexer and is not a replay.
+//!
+//! Deliveries run in the background and are retried with backoff on network errors,
+//! 429 and 5xx. An event is announced at most once, even though it is usually stored
+//! twice, and a delivery that still fails after the last attempt is dropped.
+
+use {
+    crate::Indexed,
+    escrow_client::ProgramEvent,
+    hmac::{Hmac, Mac},
+    reqwest::StatusCode,
+    serde::Serialize,
+    sha2::Sha256,
+    std::{
+        sync::Arc,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+};
+
+const ATTEMPTS: u32 = 5;
+const FIRST_BACKOFF: Duration = Duration::from_secs(1);
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+pub enum Notification {
+    #[serde(rename = "escrow.taken")]
+    Taken {
+        #[serde(flatten)]
+        common: Common,
+        taker: String,
+        amount: String,
+        price: String,
+        remaining: String,
+    },
+    #[serde(rename = "escrow.expired")]
+    Expired {
+        #[serde(flatten)]
+        common: Common,
+        cranker: String,
+        amount: String,
+    },
+    #[serde(rename = "escrow.refunded")]
+    Refunded {
+        #[serde(flatten)]
+        common: Common,
+        amount: String,
+    },
+}
+
+#[derive(Serialize)]
+pub struct Common {
+    pub escrow: String,
+    pub maker: String,
+    pub signature: String,
+    pub slot: u64,
+}
+
+impl Notification {
+    /// The notification for `indexed`, none for events makers aren't told about
+    pub fn of(indexed: &Indexed) -> Option<Self> {
+        let common = |escrow: &dyn ToString, maker: &dyn ToString| Common {
+            escrow: escrow.to_string(),
+            maker: maker.to_string(),
+            signature: indexed.signature.to_string(),
+            slot: indexed.slot,
+        };
+
+        match &indexed.event {
+            ProgramEvent::Taken(taken) => Some(Self::Taken {
+                common: common(&taken.escrow, &taken.maker),
+                taker: taken.taker.to_string(),
+                amount: taken.amount.to_string(),
+                price: taken.price.to_string(),
+                remaining: taken.remaining.to_string(),
+            }),
+            ProgramEvent::ExpiredClosed(closed) => Some(Self::Expired {
+                common: common(&closed.escrow, &closed.maker),
+                cranker: closed.cranker.to_string(),
+                amount: closed.amount.to_string(),
+            }),
+            ProgramEvent::Refunded(refunded) => Some(Self::Refunded {
+                common: common(&refunded.escrow, &refunded.maker),
+                amount: refunded.amount.to_string(),
+            }),
+            ProgramEvent::Made(_) | ProgramEvent::TermsUpdated(_) | ProgramEvent::StatusChanged(_) => None,
+        }
+    }
+}
+
+pub struct Webhooks {
+    http: reqwest::Client,
+    urls: Vec<String>,
+    secret: Option<Vec<u8>>,
+}
+
+impl Webhooks {
+    pub fn new(urls: Vec<String>, secret: Option<String>) -> anyhow::Result<Self> {
+        let http = reqwest::Client::builder().timeout(TIMEOUT).build()?;
+        Ok(Self { http, urls, secret: secret.map(String::into_bytes) })
+    }
+
+    /// Delivers `notification` to every URL in the background
+    pub fn send(self: &Arc<Self>, notification: &Notification) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(notification)?;
+        for url in &self.urls {
+            let (webhooks, url, body) = (self.clone(), url.clone(), body.clone());
+            tokio::spawn(async move {
+                if let Err(err) = webhooks.deliver(&url, body).await {
+                    tracing::warn!(%url, error = format!("{:#}", err), "webhook failed");
+                }
+            });
+        }
+        Ok(())
+    }
+
+    async fn deliver(&self, url: &str, body: Vec<u8>) -> anyhow::Result<()> {
+        let mut backoff = FIRST_BACKOFF;
+        for attempt in 1..=ATTEMPTS {
+            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+            let mut request = self.http.post(url).header("Content-Type", "application/json");
+            if let Some(secret) = &self.secret {
+                request = request
+                    .header("X-Escrow-Timestamp", timestamp)
+                    .header("X-Escrow-Signature", format!("sha256={}", sign(secret, timestamp, &body)));
+            }
+
+            let failure = match request.body(body.clone()).send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) if !retryable(response.status()) => {
+                    anyhow::bail!("{} rejected the notification", response.status())
+                }
+                Ok(response) => anyhow::anyhow!("{}", response.status()),
+                Err(err) => err.into(),
+            };
+
+            if attempt == ATTEMPTS {
+                return Err(failure.context(format!("gave up after {} attempts", ATTEMPTS)));
+            }
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+        unreachable!()
+    }
+}
+
+fn retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Hex HMAC-SHA256 of `<timestamp>.<body>` under `secret`
+pub fn sign(secret: &[u8], timestamp: u64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC takes keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        escrow_client::interface::events::{EscrowMade, EscrowTaken},
+        solana_pubkey::Pubkey,
+        solana_signature::Signature,
+    };
+
+    fn indexed(event: ProgramEvent) -> Indexed {
+        Indexed { signature: Signature::default(), slot: 7, block_time: None, index: 0, event }
+    }
+
+    #[test]
+    fn announces_fills_but_not_new_escrows() {
+        let (escrow, maker, taker) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let taken = EscrowTaken { escrow, maker, taker, amount: 10, price: 25, remaining: 90 };
+
+        let json = serde_json::to_value(Notification::of(&indexed(ProgramEvent::Taken(taken))).unwrap()).unwrap();
+        assert_eq!(json["type"], "escrow.taken");
+        assert_eq!(json["escrow"], escrow.to_string());
+        assert_eq!(json["taker"], taker.to_string());
+        assert_eq!(json["remaining"], "90");
+        assert_eq!(json["slot"], 7);
+
+        let made = EscrowMade {
+            escrow,
+            maker,
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            seed: 1,
+            deposit: 100,
+            receive: 250,
+            expires_at: 0,
+            starts_at: 0,
+        };
+        assert!(Notification::of(&indexed(ProgramEvent::Made(made))).is_none());
+    }
+
+    #[test]
+    fn signs_timestamp_and_body() {
+        let signature = sign(b"secret", 1_700_000_000, b"{}");
+        assert_eq!(signature.len(), 64);
+        assert_eq!(signature, sign(b"secret", 1_700_000_000, b"{}"));
+        assert_ne!(signature, sign(b"secret", 1_700_000_001, b"{}"));
+        assert_ne!(signature, sign(b"other", 1_700_000_000, b"{}"));
+    }
+
+    #[test]
+    fn retries_only_transient_failures() {
+        assert!(retryable(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!retryable(StatusCode::BAD_REQUEST));
+    }
+}
@@ -0,0 +1,296 @@
+//! Persistence behind [`Repository`], in Postgres or, for small deployments and local
+//! development, an SQLite file. Every write is idempotent and keyed by signature and
+//! event index, so the same event arriving over the websocket and again through
+//! catch-up is only counted once.
+
+mod postgres;
+mod sqlite;
+
+use {
+    crate::Indexed,
+    async_trait::async_trait,
+    rust_decimal::Decimal,
+    serde::Serialize,
+    solana_signature::Signature,
+    sqlx::FromRow,
+    std::sync::Arc,
+};
+
+pub use {postgres::PgStore, sqlite::SqliteStore};
+
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Newest transaction catch-up has processed
+    async fn cursor(&self) -> anyhow::Result<Option<Signature>>;
+    async fn set_cursor(&self, signature: &Signature, at_slot: u64) -> anyhow::Result<()>;
+    /// Recomputes the open book, where the backend keeps it apart from the escrows
+    async fn refresh_open_book(&self) -> anyhow::Result<()>;
+    /// Open escrows as of the last `refresh_open_book`
+    async fn open_escrow_count(&self) -> anyhow::Result<i64>;
+    /// Fills logged at `from_slot` or later
+    async fn fills_since(&self, from_slot: u64) -> anyhow::Result<i64>;
+    /// Open escrows of the pair, cheapest first, as of the last `refresh_open_book`
+    async fn open_escrows(&self, mint_a: Option<&str>, mint_b: Option<&str>) -> anyhow::Result<Vec<OpenEscrow>>;
+    /// Every escrow of `maker`, newest first
+    async fn maker_escrows(&self, maker: &str) -> anyhow::Result<Vec<EscrowRow>>;
+    async fn fills(&self, filter: &FillFilter) -> anyhow::Result<Vec<FillRow>>;
+    /// Whether `indexed` is the first time its event is announced, marking it as such
+    async fn claim_notification(&self, indexed: &Indexed) -> anyhow::Result<bool>;
+    /// Records `event` and applies it to its escrow
+    async fn apply(&self, indexed: &Indexed) -> anyhow::Result<()>;
+}
+
+/// Opens `sqlite://<path>` or `postgres://...` and brings the schema up to date
+pub async fn connect(database_url: &str) -> anyhow::Result<Arc<dyn Repository>> {
+    if database_url.starts_with("sqlite:") {
+        Ok(Arc::new(SqliteStore::connect(database_url).await?))
+    } else {
+        Ok(Arc::new(PgStore::connect(database_url).await?))
+    }
+}
+
+/// A row of the `open_book` view
+#[derive(Serialize, FromRow)]
+pub struct OpenEscrow {
+    pub address: String,
+    pub maker: String,
+    pub mint_a: String,
+    pub mint_b: String,
+    pub remaining: Decimal,
+    pub receive: Decimal,
+    pub deposit: Decimal,
+    pub expires_at: Option<i64>,
+    pub price: Decimal,
+}
+
+#[derive(Serialize, FromRow)]
+pub struct EscrowRow {
+    pub address: String,
+    pub maker: String,
+    pub seed: Decimal,
+    pub mint_a: String,
+    pub mint_b: String,
+    pub deposit: Decimal,
+    pub receive: Decimal,
+    pub filled: Decimal,
+    pub remaining: Decimal,
+    pub status: String,
+    pub expires_at: Option<i64>,
+    pub created_signature: String,
+    pub created_slot: i64,
+    pub created_at: Option<i64>,
+    pub closed_signature: Option<String>,
+    /// The only taker while `status` is reserved
+    pub reserved_for: Option<String>,
+    /// When it can first be taken, NULL if right away
+    pub starts_at: Option<i64>,
+}
+
+#[derive(Serialize, FromRow)]
+pub struct FillRow {
+    pub signature: String,
+    pub escrow: String,
+    pub maker: String,
+    pub taker: String,
+    pub amount: Decimal,
+    pub price: Decimal,
+    pub remaining: Decimal,
+    pub slot: i64,
+    pub block_time: Option<i64>,
+}
+
+/// Fills matching every field that is set, newest first
+pub struct FillFilter {
+    pub escrow: Option<String>,
+    pub maker: Option<String>,
+    pub taker: Option<String>,
+    pub limit: i64,
+}
+
+pub(crate) fn amount(value: u64) -> Decimal {
+    Decimal::from(value)
+}
+
+// Slots and timestamps fit i64 for the foreseeable future
+pub(crate) fn slot(value: u64) -> i64 {
+    value as i64
+}
+
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        escrow_client::{
+            interface::{
+                events::{EscrowMade, EscrowRefunded, EscrowStatusChanged, EscrowTaken, EscrowTermsUpdated},
+                types::EscrowStatus,
+            },
+            ProgramEvent,
+        },
+        solana_pubkey::Pubkey,
+    };
+
+    fn at(slot: u64, event: ProgramEvent) -> Indexed {
+        Indexed { signature: Signature::new_unique(), slot, block_time: None, index: 0, event }
+    }
+
+    // Fresh addresses every run, so a Postgres database can be reused between runs
+    async fn exercise(store: &dyn Repository) {
+        let (escrow, maker, mint_a, mint_b) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (a, b) = (mint_a.to_string(), mint_b.to_string());
+
+        let made = at(10, ProgramEvent::Made(EscrowMade {
+            escrow,
+            maker,
+            mint_a,
+            mint_b,
+            seed: 1,
+            deposit: 100,
+            receive: 250,
+            expires_at: 0,
+            starts_at: 0,
+        }));
+        store.apply(&made).await.unwrap();
+        store.apply(&made).await.unwrap();
+
+        // Timelocked, an escrow of the same pair stays out of the book until it starts
+        let pending = at(10, ProgramEvent::Made(EscrowMade {
+            escrow: Pubkey::new_unique(),
+            maker: Pubkey::new_unique(),
+            mint_a,
+            mint_b,
+            seed: 1,
+            deposit: 100,
+            receive: 100,
+            expires_at: 0,
+            starts_at: i64::MAX,
+        }));
+        store.apply(&pending).await.unwrap();
+
+        // Expired but not yet closed, one can no longer be taken either
+        let expired = at(10, ProgramEvent::Made(EscrowMade {
+            escrow: Pubkey::new_unique(),
+            maker: Pubkey::new_unique(),
+            mint_a,
+            mint_b,
+            seed: 1,
+            deposit: 100,
+            receive: 100,
+            expires_at: 1,
+            starts_at: 0,
+        }));
+        store.apply(&expired).await.unwrap();
+        store.refresh_open_book().await.unwrap();
+
+        let open = store.open_escrows(Some(&a), Some(&b)).await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].price, Decimal::from_str_exact("2.5").unwrap());
+
+        // Reserved for one taker, the escrow leaves the book until the maker releases it
+        let reserved_for = Pubkey::new_unique();
+        let reserved = at(10, ProgramEvent::StatusChanged(EscrowStatusChanged {
+            escrow,
+            status: EscrowStatus::Reserved,
+            reserved_for,
+        }));
+        store.apply(&reserved).await.unwrap();
+        store.refresh_open_book().await.unwrap();
+        assert!(store.open_escrows(Some(&a), Some(&b)).await.unwrap().is_empty());
+        let escrows = store.maker_escrows(&maker.to_string()).await.unwrap();
+        assert_eq!(escrows[0].status, "reserved");
+        assert_eq!(escrows[0].reserved_for, Some(reserved_for.to_string()));
+
+        let released = at(10, ProgramEvent::StatusChanged(EscrowStatusChanged {
+            escrow,
+            status: EscrowStatus::Open,
+            reserved_for: Pubkey::default(),
+        }));
+        store.apply(&released).await.unwrap();
+        store.refresh_open_book().await.unwrap();
+        assert_eq!(store.open_escrows(Some(&a), Some(&b)).await.unwrap().len(), 1);
+        assert_eq!(store.maker_escrows(&maker.to_string()).await.unwrap()[0].reserved_for, None);
+
+        let taker = Pubkey::new_unique();
+        let taken = at(11, ProgramEvent::Taken(EscrowTaken { escrow, maker, taker, amount: 60, price: 150, remaining: 40 }));
+        store.apply(&taken).await.unwrap();
+        store.apply(&taken).await.unwrap();
+        let filter = FillFilter { escrow: Some(escrow.to_string()), maker: None, taker: None, limit: 10 };
+        let fills = store.fills(&filter).await.unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].taker, taker.to_string());
+        assert!(store.fills_since(11).await.unwrap() >= 1);
+
+        // Replayed late, an older update must not undo the fill
+        let stale = at(5, ProgramEvent::TermsUpdated(EscrowTermsUpdated { escrow, receive: 999, expires_at: 0 }));
+        store.apply(&stale).await.unwrap();
+        let escrows = store.maker_escrows(&maker.to_string()).await.unwrap();
+        assert_eq!(escrows.len(), 1);
+        assert_eq!((escrows[0].filled, escrows[0].remaining), (Decimal::from(60), Decimal::from(40)));
+        assert_eq!((escrows[0].receive, escrows[0].status.as_str()), (Decimal::from(250), "open"));
+
+        let refunded = at(12, ProgramEvent::Refunded(EscrowRefunded { escrow, maker, amount: 40 }));
+        store.apply(&refunded).await.unwrap();
+        store.refresh_open_book().await.unwrap();
+        assert!(store.open_escrows(Some(&a), Some(&b)).await.unwrap().is_empty());
+        let escrows = store.maker_escrows(&maker.to_string()).await.unwrap();
+        assert_eq!((escrows[0].status.as_str(), escrows[0].remaining), ("refunded", Decimal::ZERO));
+        assert_eq!(escrows[0].closed_signature, Some(refunded.signature.to_string()));
+
+        // A release applied after the refund, even in its slot, leaves it closed
+        let late = at(12, ProgramEvent::StatusChanged(EscrowStatusChanged {
+            escrow,
+            status: EscrowStatus::Open,
+            reserved_for: Pubkey::default(),
+        }));
+        store.apply(&late).await.unwrap();
+        store.refresh_open_book().await.unwrap();
+        assert!(store.open_escrows(Some(&a), Some(&b)).await.unwrap().is_empty());
+        assert_eq!(store.maker_escrows(&maker.to_string()).await.unwrap()[0].status, "refunded");
+
+        // Making the same seed again reuses the address and starts the row over, and
+        // the first make replayed late leaves it alone
+        let remade = at(13, ProgramEvent::Made(EscrowMade {
+            escrow,
+            maker,
+            mint_a,
+            mint_b,
+            seed: 1,
+            deposit: 50,
+            receive: 200,
+            expires_at: 0,
+            starts_at: 0,
+        }));
+        store.apply(&remade).await.unwrap();
+        store.apply(&made).await.unwrap();
+        store.refresh_open_book().await.unwrap();
+        let open = store.open_escrows(Some(&a), Some(&b)).await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].price, Decimal::from(4));
+        let escrows = store.maker_escrows(&maker.to_string()).await.unwrap();
+        assert_eq!(escrows.len(), 1);
+        assert_eq!((escrows[0].status.as_str(), escrows[0].filled, escrows[0].remaining), ("open", Decimal::ZERO, Decimal::from(50)));
+        assert_eq!((escrows[0].deposit, escrows[0].receive), (Decimal::from(50), Decimal::from(200)));
+        assert_eq!(escrows[0].created_signature, remade.signature.to_string());
+        assert_eq!(escrows[0].closed_signature, None);
+
+        assert!(store.claim_notification(&taken).await.unwrap());
+        assert!(!store.claim_notification(&taken).await.unwrap());
+
+        store.set_cursor(&refunded.signature, 12).await.unwrap();
+        assert_eq!(store.cursor().await.unwrap(), Some(refunded.signature));
+    }
+
+    #[tokio::test]
+    async fn sqlite_backend() {
+        exercise(&SqliteStore::connect("sqlite::memory:").await.unwrap()).await;
+    }
+
+    // Needs a scratch database, e.g. TEST_DATABASE_URL=postgres://localhost/escrow_test
+    #[tokio::test]
+    async fn postgres_backend() {
+        let Ok(url) = std::env::var("TEST_DATABASE_URL") else { return };
+        exercise(&PgStore::connect(&url).await.unwrap()).await;
+    }
+}
@@ -0,0 +1,363 @@
+//! The SQLite backend, for a single indexer without a database server. Amounts are
+//! decimal text, so the arithmetic Postgres does in SQL (filled amounts, prices) is
+//! done here, and the open book is read straight from `escrows`.
+
+use {
+    super::{slot, EscrowRow, FillFilter, FillRow, OpenEscrow, Repository},
+    crate::Indexed,
+    async_trait::async_trait,
+    escrow_client::{interface::types::EscrowStatus, ProgramEvent},
+    rust_decimal::Decimal,
+    solana_signature::Signature,
+    sqlx::{
+        sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteRow},
+        QueryBuilder, Row, Sqlite, SqlitePool, Transaction,
+    },
+    std::str::FromStr,
+    tracing::instrument,
+};
+
+pub struct SqliteStore {
+    pool: SqlitePool,
+}
+
+impl SqliteStore {
+    /// Opens `sqlite://<path>`, creating the file if needed, and brings the schema up
+    /// to date
+    pub async fn connect(database_url: &str) -> anyhow::Result<Self> {
+        let options = SqliteConnectOptions::from_str(database_url)?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        // One writer at a time is all SQLite allows, and `sqlite::memory:` is private
+        // to its connection
+        let pool = SqlitePoolOptions::new().max_connections(1).connect_with(options).await?;
+        sqlx::migrate!("migrations/sqlite").run(&pool).await?;
+        Ok(Self { pool })
+    }
+}
+
+// The open book: open escrows with something left that have started and not expired
+const OPEN: &str = "status = 'open' AND deposit != '0'
+     AND (starts_at IS NULL OR starts_at <= CAST(strftime('%s', 'now') AS INTEGER))
+     AND (expires_at IS NULL OR expires_at > CAST(strftime('%s', 'now') AS INTEGER))";
+
+fn decimal(row: &SqliteRow, column: &str) -> anyhow::Result<Decimal> {
+    Ok(Decimal::from_str(row.try_get(column)?)?)
+}
+
+fn open_escrow(row: SqliteRow) -> anyhow::Result<OpenEscrow> {
+    let (receive, deposit) = (decimal(&row, "receive")?, decimal(&row, "deposit")?);
+    Ok(OpenEscrow {
+        address: row.try_get("address")?,
+        maker: row.try_get("maker")?,
+        mint_a: row.try_get("mint_a")?,
+        mint_b: row.try_get("mint_b")?,
+        remaining: decimal(&row, "remaining")?,
+        receive,
+        deposit,
+        expires_at: row.try_get("expires_at")?,
+        price: receive / deposit,
+    })
+}
+
+fn escrow_row(row: SqliteRow) -> anyhow::Result<EscrowRow> {
+    Ok(EscrowRow {
+        address: row.try_get("address")?,
+        maker: row.try_get("maker")?,
+        seed: decimal(&row, "seed")?,
+        mint_a: row.try_get("mint_a")?,
+        mint_b: row.try_get("mint_b")?,
+        deposit: decimal(&row, "deposit")?,
+        receive: decimal(&row, "receive")?,
+        filled: decimal(&row, "filled")?,
+        remaining: decimal(&row, "remaining")?,
+        status: row.try_get("status")?,
+        expires_at: row.try_get("expires_at")?,
+        created_signature: row.try_get("created_signature")?,
+        created_slot: row.try_get("created_slot")?,
+        created_at: row.try_get("created_at")?,
+        closed_signature: row.try_get("closed_signature")?,
+        reserved_for: row.try_get("reserved_for")?,
+        starts_at: row.try_get("starts_at")?,
+    })
+}
+
+fn fill_row(row: SqliteRow) -> anyhow::Result<FillRow> {
+    Ok(FillRow {
+        signature: row.try_get("signature")?,
+        escrow: row.try_get("escrow")?,
+        maker: row.try_get("maker")?,
+        taker: row.try_get("taker")?,
+        amount: decimal(&row, "amount")?,
+        price: decimal(&row, "price")?,
+        remaining: decimal(&row, "remaining")?,
+        slot: row.try_get("slot")?,
+        block_time: row.try_get("block_time")?,
+    })
+}
+
+#[async_trait]
+impl Repository for SqliteStore {
+    async fn cursor(&self) -> anyhow::Result<Option<Signature>> {
+        let row = sqlx::query("SELECT signature FROM cursor").fetch_optional(&self.pool).await?;
+        Ok(row.map(|row| Signature::from_str(row.get("signature"))).transpose()?)
+    }
+
+    #[instrument(name = "db.set_cursor", skip_all, fields(backend = "sqlite"))]
+    async fn set_cursor(&self, signature: &Signature, at_slot: u64) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT INTO cursor (id, signature, slot) VALUES (1, ?1, ?2)
+             ON CONFLICT (id) DO UPDATE SET signature = excluded.signature, slot = excluded.slot",
+        )
+        .bind(signature.to_string())
+        .bind(slot(at_slot))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    // The open book is read live, there is nothing to refresh
+    async fn refresh_open_book(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn open_escrow_count(&self) -> anyhow::Result<i64> {
+        Ok(sqlx::query_scalar(&format!("SELECT count(*) FROM escrows WHERE {}", OPEN))
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    async fn fills_since(&self, from_slot: u64) -> anyhow::Result<i64> {
+        Ok(sqlx::query_scalar("SELECT count(*) FROM fills WHERE slot >= ?1")
+            .bind(slot(from_slot))
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    async fn open_escrows(&self, mint_a: Option<&str>, mint_b: Option<&str>) -> anyhow::Result<Vec<OpenEscrow>> {
+        let rows = sqlx::query(&format!(
+            "SELECT address, maker, mint_a, mint_b, remaining, receive, deposit, expires_at FROM escrows
+             WHERE {} AND (?1 IS NULL OR mint_a = ?1) AND (?2 IS NULL OR mint_b = ?2)",
+            OPEN,
+        ))
+        .bind(mint_a)
+        .bind(mint_b)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut escrows = rows.into_iter().map(open_escrow).collect::<anyhow::Result<Vec<_>>>()?;
+        escrows.sort_by(|a, b| a.price.cmp(&b.price).then_with(|| a.address.cmp(&b.address)));
+        Ok(escrows)
+    }
+
+    async fn maker_escrows(&self, maker: &str) -> anyhow::Result<Vec<EscrowRow>> {
+        let rows = sqlx::query(
+            "SELECT address, maker, seed, mint_a, mint_b, deposit, receive, filled, remaining, status, expires_at,
+                    created_signature, created_slot, created_at, closed_signature, reserved_for, starts_at
+             FROM escrows WHERE maker = ?1 ORDER BY created_slot DESC, address",
+        )
+        .bind(maker)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter().map(escrow_row).collect()
+    }
+
+    async fn fills(&self, filter: &FillFilter) -> anyhow::Result<Vec<FillRow>> {
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "SELECT signature, escrow, maker, taker, amount, price, remaining, slot, block_time FROM fills WHERE 1",
+        );
+        for (column, value) in [("escrow", &filter.escrow), ("maker", &filter.maker), ("taker", &filter.taker)] {
+            if let Some(value) = value {
+                query.push(format!(" AND {} = ", column)).push_bind(value.clone());
+            }
+        }
+        query.push(" ORDER BY slot DESC, signature, event_index LIMIT ").push_bind(filter.limit);
+
+        query.build().fetch_all(&self.pool).await?.into_iter().map(fill_row).collect()
+    }
+
+    #[instrument(name = "db.claim_notification", skip_all, fields(backend = "sqlite"))]
+    async fn claim_notification(&self, indexed: &Indexed) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            "INSERT INTO notifications (signature, event_index) VALUES (?1, ?2) ON CONFLICT DO NOTHING",
+        )
+        .bind(indexed.signature.to_string())
+        .bind(indexed.index as i32)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[instrument(name = "db.apply", skip_all, fields(backend = "sqlite"))]
+    async fn apply(&self, indexed: &Indexed) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+        match &indexed.event {
+            ProgramEvent::Made(made) => {
+                sqlx::query(
+                    "INSERT INTO escrows (address, maker, seed, mint_a, mint_b, deposit, receive, remaining, status,
+                                          expires_at, created_signature, created_slot, created_at, updated_slot, starts_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?6, 'open', ?8, ?9, ?10, ?11, ?10, ?12)
+                     ON CONFLICT (address) DO UPDATE SET created_at = COALESCE(escrows.created_at, excluded.created_at)",
+                )
+                .bind(made.escrow.to_string())
+                .bind(made.maker.to_string())
+                .bind(made.seed.to_string())
+                .bind(made.mint_a.to_string())
+                .bind(made.mint_b.to_string())
+                .bind(made.deposit.to_string())
+                .bind(made.receive.to_string())
+                .bind((made.expires_at != 0).then_some(made.expires_at))
+                .bind(indexed.signature.to_string())
+                .bind(slot(indexed.slot))
+                .bind(indexed.block_time)
+                .bind((made.starts_at != 0).then_some(made.starts_at))
+                .execute(&mut *tx)
+                .await?;
+
+                // Refunding and making the same seed again reuses the address, so a newer
+                // make starts the row over with its own terms
+                sqlx::query(
+                    "UPDATE escrows SET maker = ?2, seed = ?3, mint_a = ?4, mint_b = ?5, deposit = ?6, receive = ?7,
+                         filled = '0', remaining = ?6, status = 'open', expires_at = ?8, created_signature = ?9,
+                         created_slot = ?10, created_at = ?11, closed_signature = NULL, reserved_for = NULL, starts_at = ?12,
+                         updated_slot = ?10
+                     WHERE address = ?1 AND created_slot < ?10",
+                )
+                .bind(made.escrow.to_string())
+                .bind(made.maker.to_string())
+                .bind(made.seed.to_string())
+                .bind(made.mint_a.to_string())
+                .bind(made.mint_b.to_string())
+                .bind(made.deposit.to_string())
+                .bind(made.receive.to_string())
+                .bind((made.expires_at != 0).then_some(made.expires_at))
+                .bind(indexed.signature.to_string())
+                .bind(slot(indexed.slot))
+                .bind(indexed.block_time)
+                .bind((made.starts_at != 0).then_some(made.starts_at))
+                .execute(&mut *tx)
+                .await?;
+            }
+            ProgramEvent::Taken(taken) => {
+                sqlx::query(
+                    "INSERT INTO fills (signature, event_index, escrow, maker, taker, amount, price, remaining, slot, block_time)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                     ON CONFLICT (signature, event_index) DO UPDATE SET block_time = COALESCE(fills.block_time, excluded.block_time)",
+                )
+                .bind(indexed.signature.to_string())
+                .bind(indexed.index as i32)
+                .bind(taken.escrow.to_string())
+                .bind(taken.maker.to_string())
+                .bind(taken.taker.to_string())
+                .bind(taken.amount.to_string())
+                .bind(taken.price.to_string())
+                .bind(taken.remaining.to_string())
+                .bind(slot(indexed.slot))
+                .bind(indexed.block_time)
+                .execute(&mut *tx)
+                .await?;
+
+                let deposit: Option<String> = sqlx::query_scalar("SELECT deposit FROM escrows WHERE address = ?1")
+                    .bind(taken.escrow.to_string())
+                    .fetch_optional(&mut *tx)
+                    .await?;
+                if let Some(deposit) = deposit {
+                    let filled = u64::from_str(&deposit)?.saturating_sub(taken.remaining);
+                    // `remaining` is absolute, so applying the same fill twice changes nothing
+                    sqlx::query(
+                        "UPDATE escrows SET remaining = ?2, filled = ?3, updated_slot = ?4,
+                             status = CASE WHEN ?2 = '0' THEN 'filled' ELSE status END,
+                             closed_signature = CASE WHEN ?2 = '0' THEN ?5 ELSE closed_signature END
+                         WHERE address = ?1 AND updated_slot <= ?4",
+                    )
+                    .bind(taken.escrow.to_string())
+                    .bind(taken.remaining.to_string())
+                    .bind(filled.to_string())
+                    .bind(slot(indexed.slot))
+                    .bind(indexed.signature.to_string())
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+            ProgramEvent::Refunded(refunded) => {
+                insert_refund(&mut tx, indexed, &refunded.escrow.to_string(), &refunded.maker.to_string(), refunded.amount, None).await?;
+                close(&mut tx, indexed, &refunded.escrow.to_string(), "refunded").await?;
+            }
+            ProgramEvent::ExpiredClosed(closed) => {
+                let cranker = closed.cranker.to_string();
+                insert_refund(&mut tx, indexed, &closed.escrow.to_string(), &closed.maker.to_string(), closed.amount, Some(&cranker)).await?;
+                close(&mut tx, indexed, &closed.escrow.to_string(), "expired").await?;
+            }
+            ProgramEvent::TermsUpdated(updated) => {
+                sqlx::query(
+                    "UPDATE escrows SET receive = ?2, expires_at = ?3, updated_slot = ?4
+                     WHERE address = ?1 AND updated_slot <= ?4",
+                )
+                .bind(updated.escrow.to_string())
+                .bind(updated.receive.to_string())
+                .bind((updated.expires_at != 0).then_some(updated.expires_at))
+                .bind(slot(indexed.slot))
+                .execute(&mut *tx)
+                .await?;
+            }
+            ProgramEvent::StatusChanged(changed) => {
+                let (status, reserved_for) = match changed.status {
+                    EscrowStatus::Open => ("open", None),
+                    EscrowStatus::Reserved => ("reserved", Some(changed.reserved_for.to_string())),
+                };
+                sqlx::query(
+                    "UPDATE escrows SET status = ?2, reserved_for = ?3, updated_slot = ?4
+                     WHERE address = ?1 AND updated_slot <= ?4 AND status IN ('open', 'reserved')",
+                )
+                .bind(changed.escrow.to_string())
+                .bind(status)
+                .bind(reserved_for)
+                .bind(slot(indexed.slot))
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+async fn insert_refund(
+    tx: &mut Transaction<'_, Sqlite>,
+    indexed: &Indexed,
+    escrow: &str,
+    maker: &str,
+    refunded: u64,
+    cranker: Option<&str>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO refunds (signature, event_index, escrow, maker, amount, cranker, slot, block_time)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+         ON CONFLICT (signature, event_index) DO UPDATE SET block_time = COALESCE(refunds.block_time, excluded.block_time)",
+    )
+    .bind(indexed.signature.to_string())
+    .bind(indexed.index as i32)
+    .bind(escrow)
+    .bind(maker)
+    .bind(refunded.to_string())
+    .bind(cranker)
+    .bind(slot(indexed.slot))
+    .bind(indexed.block_time)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+async fn close(tx: &mut Transaction<'_, Sqlite>, indexed: &Indexed, escrow: &str, status: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE escrows SET status = ?2, remaining = '0', closed_signature = ?3, updated_slot = ?4
+         WHERE address = ?1 AND updated_slot <= ?4",
+    )
+    .bind(escrow)
+    .bind(status)
+    .bind(indexed.signature.to_string())
+    .bind(slot(indexed.slot))
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,46 @@
+//! Logging through `tracing`, filtered by `RUST_LOG` (info by default), and with
+//! `--otlp-endpoint` the same spans exported over OTLP/gRPC, e.g. to a collector at
+//! http://localhost:4317. Event spans carry the transaction signature, which ties
+//! them to the client spans of the transaction that emitted them.
+
+use {
+    opentelemetry::trace::TracerProvider as _,
+    opentelemetry_otlp::WithExportConfig,
+    opentelemetry_sdk::{trace::SdkTracerProvider, Resource},
+    tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter},
+};
+
+/// Flushes exported spans when dropped
+pub struct Telemetry {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        if let Some(provider) = &self.provider {
+            if let Err(err) = provider.shutdown() {
+                eprintln!("Failed to flush traces: {}", err);
+            }
+        }
+    }
+}
+
+pub fn init(service: &'static str, otlp_endpoint: Option<&str>) -> anyhow::Result<Telemetry> {
+    let provider = otlp_endpoint
+        .map(|endpoint| -> anyhow::Result<_> {
+            let exporter = opentelemetry_otlp::SpanExporter::builder().with_tonic().with_endpoint(endpoint).build()?;
+            Ok(SdkTracerProvider::builder()
+                .with_batch_exporter(exporter)
+                .with_resource(Resource::builder().with_service_name(service).build())
+                .build())
+        })
+        .transpose()?;
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .with(tracing_subscriber::fmt::layer())
+        .with(provider.as_ref().map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer(service))))
+        .try_init()?;
+
+    Ok(Telemetry { provider })
+}
@@ -0,0 +1,88 @@
+//! Client-side interface for the `anchor_escrow` program.
+//!
+//! Everything here is generated from `idls/anchor_escrow.json` by `declare_program!`,
+//! so downstream services and programs can build instructions, make CPIs and decode
+//! accounts without linking the program crate itself. Refresh the IDL with
+//! `anchor build` and copy `target/idl/anchor_escrow.json` over the committed one
+//! whenever the program's interface changes.
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+
+declare_program!(anchor_escrow);
+
+pub use anchor_escrow::*;
+
+/// Byte offsets of `Escrow` fields for memcmp filters, discriminator included.
+/// These mirror `anchor_escrow::constants` and are checked against it in tests.
+pub mod layout {
+    pub const DISCRIMINATOR_OFFSET: usize = 0;
+    pub const MAKER_OFFSET: usize = 16;
+    pub const MINT_A_OFFSET: usize = 48;
+    pub const MINT_B_OFFSET: usize = 80;
+    pub const STATUS_OFFSET: usize = 112;
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Discriminator;
+
+    // The committed IDL must stay in sync with the program it describes, and every
+    // instruction it lists must be one of the checked ones
+    #[test]
+    fn idl_matches_program() {
+        assert_eq!(super::ID, ::anchor_escrow::ID);
+
+        macro_rules! discriminators {
+            ($($name:ident),* $(,)?) => {
+                vec![$({
+                    assert_eq!(
+                        super::client::args::$name::DISCRIMINATOR,
+                        ::anchor_escrow::instruction::$name::DISCRIMINATOR,
+                        "{} discriminator differs", stringify!($name)
+                    );
+                    ::anchor_escrow::instruction::$name::DISCRIMINATOR.to_vec()
+                }),*]
+            };
+        }
+
+        let checked = discriminators![
+            Make,
+            Refund,
+            Take,
+            MakeCnft,
+            RefundCnft,
+            TakeCnft,
+            AppendMetadata,
+            TakePartial,
+            UpdateTerms,
+            CloseExpired,
+            TakeSol,
+            TakeQuote,
+            SetGate,
+            SetOperator,
+            OperatorRefund,
+            SetFillOrKill,
+            Reserve,
+            Release,
+        ];
+
+        let idl: serde_json::Value = serde_json::from_str(include_str!("../idls/anchor_escrow.json")).unwrap();
+        let instructions = idl["instructions"].as_array().unwrap();
+        assert_eq!(instructions.len(), checked.len());
+        for instruction in instructions {
+            let discriminator: Vec<u8> = serde_json::from_value(instruction["discriminator"].clone()).unwrap();
+            assert!(checked.contains(&discriminator), "{} is not checked", instruction["name"]);
+        }
+    }
+
+    #[test]
+    fn layout_matches_program() {
+        use ::anchor_escrow::constants;
+
+        assert_eq!(super::layout::MAKER_OFFSET, constants::ESCROW_MAKER_OFFSET);
+        assert_eq!(super::layout::MINT_A_OFFSET, constants::ESCROW_MINT_A_OFFSET);
+        assert_eq!(super::layout::MINT_B_OFFSET, constants::ESCROW_MINT_B_OFFSET);
+        assert_eq!(super::layout::STATUS_OFFSET, constants::ESCROW_STATUS_OFFSET);
+    }
+}
@@ -0,0 +1,97 @@
+//! Submission through a Jito block engine, so cranks still land when the leader's
+//! queue is full. Each crank goes out as a one-transaction bundle carrying its own
+//! tip, paid to one of the block engine's tip accounts.
+
+use {
+    anyhow::{anyhow, bail, Context as _},
+    serde_json::{json, Value},
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    solana_system_interface::instruction::transfer,
+    std::{cell::Cell, str::FromStr},
+};
+
+pub struct Jito {
+    http: reqwest::Client,
+    bundles_url: String,
+    tip_lamports: u64,
+    tip_accounts: Vec<Pubkey>,
+    // Tips rotate through the accounts, so concurrent bundles don't all write-lock one
+    next_tip_account: Cell<usize>,
+}
+
+impl Jito {
+    /// Connects to the block engine at `url`, e.g. `https://mainnet.block-engine.jito.wtf`,
+    /// and fetches its tip accounts
+    pub async fn connect(url: &str, tip_lamports: u64) -> anyhow::Result<Self> {
+        let mut jito = Self {
+            http: reqwest::Client::new(),
+            bundles_url: format!("{}/api/v1/bundles", url.trim_end_matches('/')),
+            tip_lamports,
+            tip_accounts: Vec::new(),
+            next_tip_account: Cell::new(0),
+        };
+
+        let accounts = jito.call("getTipAccounts", json!([])).await.context("Failed to fetch Jito tip accounts")?;
+        jito.tip_accounts = parse_tip_accounts(&accounts)?;
+        Ok(jito)
+    }
+
+    /// Transfer of the tip from `payer`, to include in the bundled transaction
+    pub fn tip_instruction(&self, payer: &Pubkey) -> Instruction {
+        let index = self.next_tip_account.get();
+        self.next_tip_account.set((index + 1) % self.tip_accounts.len());
+        transfer(payer, &self.tip_accounts[index], self.tip_lamports)
+    }
+
+    /// Sends base64 wire-format transactions as one bundle, returning the bundle id
+    pub async fn send_bundle(&self, transactions: &[String]) -> anyhow::Result<String> {
+        let result = self.call("sendBundle", json!([transactions, { "encoding": "base64" }])).await?;
+        result.as_str().map(str::to_string).ok_or_else(|| anyhow!("Unexpected sendBundle result {}", result))
+    }
+
+    async fn call(&self, method: &str, params: Value) -> anyhow::Result<Value> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self.http.post(&self.bundles_url).json(&request).send().await?.error_for_status()?.json().await?;
+        result(response)
+    }
+}
+
+fn result(mut response: Value) -> anyhow::Result<Value> {
+    if let Some(error) = response.get("error") {
+        bail!("Block engine error: {}", error.get("message").and_then(Value::as_str).unwrap_or(&error.to_string()));
+    }
+    response.get_mut("result").map(Value::take).ok_or_else(|| anyhow!("Block engine response has no result"))
+}
+
+fn parse_tip_accounts(accounts: &Value) -> anyhow::Result<Vec<Pubkey>> {
+    let accounts: Vec<Pubkey> = accounts
+        .as_array()
+        .ok_or_else(|| anyhow!("Unexpected getTipAccounts result {}", accounts))?
+        .iter()
+        .map(|account| account.as_str().and_then(|account| Pubkey::from_str(account).ok()).ok_or_else(|| anyhow!("Invalid tip account {}", account)))
+        .collect::<anyhow::Result<_>>()?;
+
+    if accounts.is_empty() {
+        bail!("The block engine has no tip accounts");
+    }
+    Ok(accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_block_engine_responses() {
+        let tip_account = Pubkey::new_unique();
+        let response = json!({ "jsonrpc": "2.0", "id": 1, "result": [tip_account.to_string()] });
+        assert_eq!(parse_tip_accounts(&result(response).unwrap()).unwrap(), vec![tip_account]);
+
+        let error = json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32602, "message": "bundle contains an expired blockhash" } });
+        assert_eq!(result(error).unwrap_err().to_string(), "Block engine error: bundle contains an expired blockhash");
+
+        assert!(parse_tip_accounts(&json!([])).is_err());
+        assert!(parse_tip_accounts(&json!(["not a pubkey"])).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use {
+    crate::{jito::Jito, metrics::Metrics},
+    escrow_client::{
+        interface::{accounts::Escrow, constants::CLOSE_EXPIRED_BOUNTY},
+        ClientError, CloseExpiredBuilder, EscrowClientError, EscrowRpc, Fetched,
+    },
+    futures::{stream, StreamExt},
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    std::{
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+        fmt,
+        sync::Arc,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+    tracing::{error, info, instrument, warn},
+};
+
+/// What happened to one expired escrow
+pub enum Outcome {
+    Closed(String),
+    /// Someone else closed, refunded or took it first
+    Gone,
+    /// The maker closed their ATA, and recreating it costs more rent than the bounty pays
+    Skipped,
+    Failed(ClientError),
+}
+
+impl Outcome {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Closed(_) => "closed",
+            Self::Gone => "gone",
+            Self::Skipped => "skipped",
+            Self::Failed(_) => "failed",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Summary {
+    pub expired: usize,
+    pub closed: usize,
+    pub gone: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    /// Lamports of bounty earned, before fees and tips
+    pub bounties: u64,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} expired, {} closed, {} already gone, {} skipped, {} failed, {} lamports of bounty",
+            self.expired, self.closed, self.gone, self.skipped, self.failed, self.bounties
+        )
+    }
+}
+
+pub struct Keeper {
+    rpc: EscrowRpc,
+    cranker: Keypair,
+    concurrency: usize,
+    jito: Option<Jito>,
+    metrics: Arc<Metrics>,
+    // Mint A never changes token program, so each one is only looked up once
+    token_programs: RefCell<HashMap<Pubkey, Pubkey>>,
+}
+
+impl Keeper {
+    pub fn new(rpc: EscrowRpc, cranker: Keypair, concurrency: usize, metrics: Arc<Metrics>) -> Self {
+        Self { rpc, cranker, concurrency, jito: None, metrics, token_programs: RefCell::new(HashMap::new()) }
+    }
+
+    /// Submits cranks as Jito bundles first, falling back to plain RPC when one fails
+    /// to land
+    pub fn with_jito(mut self, jito: Jito) -> Self {
+        self.jito = Some(jito);
+        self
+    }
+
+    /// Closes every escrow expired by cluster time, `concurrency` at a time
+    #[instrument(skip_all)]
+    pub async fn round(&self) -> anyhow::Result<Summary> {
+        let summary = self.crank_expired().await;
+        match &summary {
+            Ok(summary) => self.metrics.expired.set(summary.expired as i64),
+            Err(_) => self.metrics.scan_failures.inc(),
+        }
+        let finished = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        self.metrics.last_round.set(finished.as_secs() as i64);
+        summary
+    }
+
+    async fn crank_expired(&self) -> anyhow::Result<Summary> {
+        let now = self.metrics.timed("cluster_time", self.rpc.cluster_time()).await?;
+        let expired = self.metrics.timed("find_expired", self.rpc.find_expired(now)).await?;
+        self.resolve_token_programs(&expired).await?;
+
+        // Recreating a maker's ATA costs its full rent, several times the bounty, so those
+        // cranks are left to the maker's own refund
+        let cranks: Vec<_> = expired.iter().map(|escrow| self.builder(escrow)).collect();
+        let missing_atas = &self.missing_accounts(cranks.iter().map(CloseExpiredBuilder::maker_ata_a)).await?;
+
+        let mut summary = Summary { expired: expired.len(), ..Summary::default() };
+        let mut outcomes = stream::iter(&cranks)
+            .map(|crank| async move {
+                let outcome =
+                    if missing_atas.contains(&crank.maker_ata_a()) { Outcome::Skipped } else { self.close(crank).await };
+                (crank.escrow(), outcome)
+            })
+            .buffer_unordered(self.concurrency);
+
+        while let Some((escrow, outcome)) = outcomes.next().await {
+            self.metrics.cranks.with_label_values(&[outcome.label()]).inc();
+            match outcome {
+                Outcome::Closed(signature) => {
+                    summary.closed += 1;
+                    summary.bounties += CLOSE_EXPIRED_BOUNTY;
+                    self.metrics.bounties.inc_by(CLOSE_EXPIRED_BOUNTY);
+                    info!(%escrow, %signature, "closed");
+                }
+                Outcome::Gone => summary.gone += 1,
+                Outcome::Skipped => {
+                    summary.skipped += 1;
+                    info!(%escrow, "skipped, the maker's ATA would have to be recreated");
+                }
+                Outcome::Failed(err) => {
+                    summary.failed += 1;
+                    error!(%escrow, error = %err, "failed to close");
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    fn builder(&self, escrow: &Fetched<Escrow>) -> CloseExpiredBuilder {
+        let token_program = self.token_programs.borrow()[&escrow.account.mint_a];
+        CloseExpiredBuilder::for_escrow(self.cranker.pubkey(), escrow).token_program(token_program)
+    }
+
+    #[instrument(skip_all, fields(escrow = %builder.escrow()))]
+    async fn close(&self, builder: &CloseExpiredBuilder) -> Outcome {
+        if let Some(jito) = &self.jito {
+            match self.close_in_bundle(jito, builder).await {
+                Ok(signature) => return Outcome::Closed(signature),
+                Err(err) => {
+                    self.metrics.bundle_fallbacks.inc();
+                    warn!(error = format!("{:#}", err), "bundle did not land, sending over RPC");
+                }
+            }
+        }
+
+        match self.rpc.close_expired(builder, &self.cranker).await {
+            Ok(signature) => Outcome::Closed(signature.to_string()),
+            Err(err) => classify(err),
+        }
+    }
+
+    // Once the bundle's blockhash expires it can no longer land, so falling back to
+    // RPC afterwards can never close the escrow twice
+    #[instrument(skip_all)]
+    async fn close_in_bundle(&self, jito: &Jito, builder: &CloseExpiredBuilder) -> anyhow::Result<String> {
+        let payer = self.cranker.pubkey();
+        let mut transaction = self.rpc.prepare(&[builder.instruction(), jito.tip_instruction(&payer)], &payer).await?;
+        transaction.sign(&self.cranker)?;
+
+        jito.send_bundle(&[transaction.to_base64()]).await?;
+        Ok(self.rpc.await_confirmation(&transaction.into_signed()?).await?.to_string())
+    }
+
+    async fn missing_accounts(&self, addresses: impl Iterator<Item = Pubkey>) -> anyhow::Result<HashSet<Pubkey>> {
+        let addresses: Vec<Pubkey> = addresses.collect();
+        let mut missing = HashSet::new();
+
+        for chunk in addresses.chunks(100) {
+            let accounts =
+                self.metrics.timed("get_multiple_accounts", self.rpc.client().get_multiple_accounts(chunk)).await?;
+            missing.extend(chunk.iter().zip(accounts).filter(|(_, account)| account.is_none()).map(|(address, _)| *address));
+        }
+        Ok(missing)
+    }
+
+    async fn resolve_token_programs(&self, escrows: &[Fetched<Escrow>]) -> anyhow::Result<()> {
+        let mut unknown: Vec<Pubkey> = escrows
+            .iter()
+            .map(|escrow| escrow.account.mint_a)
+            .filter(|mint| !self.token_programs.borrow().contains_key(mint))
+            .collect();
+        unknown.sort();
+        unknown.dedup();
+
+        for chunk in unknown.chunks(100) {
+            let accounts =
+                self.metrics.timed("get_multiple_accounts", self.rpc.client().get_multiple_accounts(chunk)).await?;
+            for (mint, account) in chunk.iter().zip(accounts) {
+                let account = account.ok_or_else(|| anyhow::anyhow!("Mint {} not found", mint))?;
+                self.token_programs.borrow_mut().insert(*mint, account.owner);
+            }
+        }
+        Ok(())
+    }
+}
+
+// Losing a race to another keeper, the maker's refund or a last-second take leaves
+// the escrow closed, and the maker's `update_terms` can push its expiry back. Neither
+// is worth reporting as a failure.
+fn classify(err: ClientError) -> Outcome {
+    match err.program_error() {
+        Some(EscrowClientError::AccountNotInitialized | EscrowClientError::EscrowNotExpired) => Outcome::Gone,
+        _ => Outcome::Failed(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lost_races_are_not_failures() {
+        let program = |error| ClientError::Program { error, signature: None, logs: Vec::new() };
+
+        assert!(matches!(classify(program(EscrowClientError::AccountNotInitialized)), Outcome::Gone));
+        assert!(matches!(classify(program(EscrowClientError::EscrowNotExpired)), Outcome::Gone));
+        assert!(matches!(classify(program(EscrowClientError::InvalidStatusTransition)), Outcome::Failed(_)));
+        assert!(matches!(classify(program(EscrowClientError::TokenAccountFrozen)), Outcome::Failed(_)));
+        assert!(matches!(classify(ClientError::RetriesExhausted { attempts: 5 }), Outcome::Failed(_)));
+    }
+}
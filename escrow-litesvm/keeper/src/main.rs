@@ -0,0 +1,145 @@
+//! `escrow-keeper`, a bot cranking `close_expired` on every escrow past its expiry.
+//!
+//! ```text
+//! escrow-keeper --url https://api.mainnet-beta.solana.com --keypair keeper.json --concurrency 8
+//! ```
+//!
+//! Each round fetches the escrows that have expired by cluster time and closes them,
+//! a few at a time. The crank returns the deposit and most of the rent to the maker
+//! and pays the cranker `CLOSE_EXPIRED_BOUNTY` out of the escrow's rent, which covers
+//! the transaction fee and a tip. Escrows whose maker closed their ATA are skipped,
+//! recreating it would cost more rent than the bounty pays.
+//!
+//! With `--jito-url` each crank is sent as a tipped Jito bundle first, and over plain
+//! RPC when the bundle does not land.
+//!
+//! With `--metrics-listen` Prometheus metrics are served at `/metrics`, see [`metrics`].
+//! Logs go through `tracing`, and `--otlp-endpoint` exports the spans, see [`telemetry`].
+
+mod jito;
+mod keeper;
+mod metrics;
+mod telemetry;
+
+use {
+    anyhow::{anyhow, Context as _},
+    clap::Parser,
+    escrow_client::{interface::constants::CLOSE_EXPIRED_BOUNTY, EscrowRpc, PriorityFee},
+    jito::Jito,
+    keeper::Keeper,
+    metrics::Metrics,
+    solana_commitment_config::CommitmentConfig,
+    solana_keypair::read_keypair_file,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    std::{net::SocketAddr, path::PathBuf, str::FromStr, sync::Arc, time::Duration},
+    tracing::{error, info},
+};
+
+#[derive(Parser)]
+#[command(name = "escrow-keeper", version, about = "Close expired escrows")]
+struct Args {
+    /// JSON RPC URL of the cluster
+    #[arg(long, short = 'u', env = "KEEPER_URL", default_value = "https://api.devnet.solana.com")]
+    url: String,
+    /// Keypair that signs and pays for the cranks, the Solana CLI default when omitted
+    #[arg(long, short = 'k', env = "KEEPER_KEYPAIR")]
+    keypair: Option<PathBuf>,
+    #[arg(long, default_value = "confirmed")]
+    commitment: String,
+    /// Cranks in flight at once
+    #[arg(long, default_value_t = 4)]
+    concurrency: usize,
+    /// Seconds between scans
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+    /// Percentile of recent priority fees to pay, none when omitted
+    #[arg(long)]
+    priority_fee_percentile: Option<u8>,
+    /// Jito block engine to send bundles to, e.g. https://mainnet.block-engine.jito.wtf
+    #[arg(long, env = "KEEPER_JITO_URL")]
+    jito_url: Option<String>,
+    /// Tip paid with each bundle, in lamports
+    #[arg(long, default_value_t = 10_000, requires = "jito_url")]
+    jito_tip_lamports: u64,
+    /// Address to serve Prometheus metrics on, e.g. 0.0.0.0:9100, off when omitted
+    #[arg(long, env = "KEEPER_METRICS_LISTEN")]
+    metrics_listen: Option<SocketAddr>,
+    /// OTLP/gRPC collector to export traces to, e.g. http://localhost:4317
+    #[arg(long, env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+    /// Run a single round and exit
+    #[arg(long)]
+    once: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let _telemetry = telemetry::init("escrow-keeper", args.otlp_endpoint.as_deref())?;
+
+    let commitment = CommitmentConfig::from_str(&args.commitment)
+        .map_err(|_| anyhow!("Unknown commitment {}, use processed, confirmed or finalized", args.commitment))?;
+    let mut rpc = EscrowRpc::from_client(RpcClient::new_with_commitment(args.url.clone(), commitment));
+    if let Some(percentile) = args.priority_fee_percentile {
+        anyhow::ensure!(percentile <= 100, "Priority fee percentile must be between 0 and 100");
+        rpc = rpc.with_priority_fee(PriorityFee::percentile(percentile));
+    }
+
+    let keypair_path = match args.keypair {
+        Some(path) => path,
+        None => dirs::home_dir().ok_or_else(|| anyhow!("No home directory, pass --keypair"))?.join(".config/solana/id.json"),
+    };
+    let cranker = read_keypair_file(&keypair_path)
+        .map_err(|err| anyhow!("{}", err))
+        .with_context(|| format!("Failed to read keypair {}", keypair_path.display()))?;
+
+    let metrics = Arc::new(Metrics::new()?);
+    if let Some(listen) = args.metrics_listen {
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            if let Err(err) = metrics::serve(listen, metrics).await {
+                error!(error = format!("{:#}", err), "metrics server stopped");
+            }
+        });
+    }
+
+    let mut keeper = Keeper::new(rpc, cranker, args.concurrency.max(1), metrics);
+    if let Some(url) = &args.jito_url {
+        anyhow::ensure!(
+            args.jito_tip_lamports < CLOSE_EXPIRED_BOUNTY,
+            "A {} lamport tip would cost more than the {} lamport bounty each crank earns",
+            args.jito_tip_lamports,
+            CLOSE_EXPIRED_BOUNTY
+        );
+        keeper = keeper.with_jito(Jito::connect(url, args.jito_tip_lamports).await?);
+    }
+    let mut interval = tokio::time::interval(Duration::from_secs(args.interval));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+
+        match keeper.round().await {
+            Ok(summary) => info!(%summary, "round finished"),
+            // A failed scan is retried next round rather than stopping the keeper
+            Err(err) => error!(error = format!("{:#}", err), "scan failed"),
+        }
+
+        if args.once {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, clap::CommandFactory};
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Args::command().debug_assert();
+    }
+}
@@ -0,0 +1,116 @@
+//! Prometheus metrics, served at `/metrics` with `--metrics-listen`.
+//!
+//! ```text
+//! escrow_keeper_expired_escrows              escrows found expired by the last round
+//! escrow_keeper_cranks_total{outcome}        closed, gone, skipped or failed
+//! escrow_keeper_bounty_lamports_total        close_expired bounties earned, before fees
+//! escrow_keeper_bundle_fallbacks_total       bundles that did not land and went over RPC
+//! escrow_keeper_scan_failures_total          rounds that could not list expired escrows
+//! escrow_keeper_last_round_timestamp_seconds when the last round finished
+//! escrow_keeper_rpc_latency_seconds{method}  RPC round trips
+//! ```
+
+use {
+    axum::{extract::State, http::header, response::IntoResponse, routing::get, Router},
+    prometheus::{HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder},
+    std::{future::Future, net::SocketAddr, sync::Arc},
+};
+
+pub struct Metrics {
+    registry: Registry,
+    pub expired: IntGauge,
+    pub cranks: IntCounterVec,
+    pub bounties: IntCounter,
+    pub bundle_fallbacks: IntCounter,
+    pub scan_failures: IntCounter,
+    pub last_round: IntGauge,
+    rpc_latency: HistogramVec,
+}
+
+impl Metrics {
+    pub fn new() -> anyhow::Result<Self> {
+        let registry = Registry::new();
+        let metrics = Self {
+            expired: IntGauge::new("escrow_keeper_expired_escrows", "Escrows found expired by the last round")?,
+            cranks: IntCounterVec::new(
+                Opts::new("escrow_keeper_cranks_total", "close_expired cranks by outcome"),
+                &["outcome"],
+            )?,
+            bounties: IntCounter::new(
+                "escrow_keeper_bounty_lamports_total",
+                "Lamports earned in close_expired bounties, before fees and tips",
+            )?,
+            bundle_fallbacks: IntCounter::new(
+                "escrow_keeper_bundle_fallbacks_total",
+                "Jito bundles that did not land and were sent over RPC instead",
+            )?,
+            scan_failures: IntCounter::new(
+                "escrow_keeper_scan_failures_total",
+                "Rounds that failed to list expired escrows",
+            )?,
+            last_round: IntGauge::new(
+                "escrow_keeper_last_round_timestamp_seconds",
+                "Unix time the last round finished",
+            )?,
+            rpc_latency: HistogramVec::new(
+                HistogramOpts::new("escrow_keeper_rpc_latency_seconds", "RPC round trips by method"),
+                &["method"],
+            )?,
+            registry,
+        };
+
+        metrics.registry.register(Box::new(metrics.expired.clone()))?;
+        metrics.registry.register(Box::new(metrics.cranks.clone()))?;
+        metrics.registry.register(Box::new(metrics.bounties.clone()))?;
+        metrics.registry.register(Box::new(metrics.bundle_fallbacks.clone()))?;
+        metrics.registry.register(Box::new(metrics.scan_failures.clone()))?;
+        metrics.registry.register(Box::new(metrics.last_round.clone()))?;
+        metrics.registry.register(Box::new(metrics.rpc_latency.clone()))?;
+        Ok(metrics)
+    }
+
+    /// Awaits `call`, recording how long it took under `method`
+    pub async fn timed<T>(&self, method: &str, call: impl Future<Output = T>) -> T {
+        let _timer = self.rpc_latency.with_label_values(&[method]).start_timer();
+        call.await
+    }
+
+    pub fn encode(&self) -> anyhow::Result<String> {
+        Ok(TextEncoder::new().encode_to_string(&self.registry.gather())?)
+    }
+}
+
+pub async fn serve(listen: SocketAddr, metrics: Arc<Metrics>) -> anyhow::Result<()> {
+    let router = Router::new().route("/metrics", get(scrape)).with_state(metrics);
+    let listener = tokio::net::TcpListener::bind(listen).await?;
+    axum::serve(listener, router).await?;
+    Ok(())
+}
+
+async fn scrape(State(metrics): State<Arc<Metrics>>) -> impl IntoResponse {
+    match metrics.encode() {
+        Ok(body) => ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response(),
+        Err(err) => {
+            tracing::error!(error = format!("{:#}", err), "failed to encode metrics");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn exposes_every_metric() {
+        let metrics = Metrics::new().unwrap();
+        metrics.cranks.with_label_values(&["closed"]).inc();
+        metrics.timed("get_slot", async {}).await;
+
+        let text = metrics.encode().unwrap();
+        assert!(text.contains("escrow_keeper_cranks_total{outcome=\"closed\"} 1"));
+        assert!(text.contains("escrow_keeper_rpc_latency_seconds_count{method=\"get_slot\"} 1"));
+        assert!(text.contains("escrow_keeper_expired_escrows 0"));
+        assert!(text.contains("escrow_keeper_bounty_lamports_total 0"));
+    }
+}
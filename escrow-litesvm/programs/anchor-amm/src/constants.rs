@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+#[constant]
+pub const CONFIG_SEED: &[u8] = b"config";
+#[constant]
+pub const LP_SEED: &[u8] = b"lp";
+
+pub const LP_DECIMALS: u8 = 6;
+
+/// Fees are in basis points of the input amount
+pub const FEE_DENOMINATOR: u16 = 10_000;
+pub const MAX_FEE: u16 = 1_000;
@@ -0,0 +1,54 @@
+//! Constant-product math, free of accounts so it can be checked on its own.
+//!
+//! Rounding always favours the pool: deposits round up, withdrawals and swap outputs
+//! round down, so `x * y` never decreases and LP tokens are never diluted.
+
+use crate::constants::FEE_DENOMINATOR;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveError {
+    ZeroAmount,
+    EmptyPool,
+    Overflow,
+}
+
+fn narrow(value: u128) -> Result<u64, CurveError> {
+    u64::try_from(value).map_err(|_| CurveError::Overflow)
+}
+
+/// The X and Y a deposit minting `lp` tokens must add, given the current reserves and supply
+pub fn deposit_amounts(lp: u64, reserve_x: u64, reserve_y: u64, supply: u64) -> Result<(u64, u64), CurveError> {
+    if lp == 0 {
+        return Err(CurveError::ZeroAmount);
+    }
+    if supply == 0 {
+        return Err(CurveError::EmptyPool);
+    }
+    let share = |reserve: u64| narrow((lp as u128 * reserve as u128).div_ceil(supply as u128));
+    Ok((share(reserve_x)?, share(reserve_y)?))
+}
+
+/// The X and Y burning `lp` tokens releases
+pub fn withdraw_amounts(lp: u64, reserve_x: u64, reserve_y: u64, supply: u64) -> Result<(u64, u64), CurveError> {
+    if lp == 0 {
+        return Err(CurveError::ZeroAmount);
+    }
+    if supply == 0 {
+        return Err(CurveError::EmptyPool);
+    }
+    let share = |reserve: u64| narrow(lp as u128 * reserve as u128 / supply as u128);
+    Ok((share(reserve_x)?, share(reserve_y)?))
+}
+
+/// The output of swapping `amount_in` into the pool, after the fee is kept in the input reserve
+pub fn swap_output(amount_in: u64, reserve_in: u64, reserve_out: u64, fee: u16) -> Result<u64, CurveError> {
+    if amount_in == 0 {
+        return Err(CurveError::ZeroAmount);
+    }
+    if reserve_in == 0 || reserve_out == 0 {
+        return Err(CurveError::EmptyPool);
+    }
+    let in_after_fee = amount_in as u128 * (FEE_DENOMINATOR - fee) as u128 / FEE_DENOMINATOR as u128;
+    let out = reserve_out as u128 * in_after_fee / (reserve_in as u128 + in_after_fee);
+    narrow(out)
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::curve::CurveError;
+
+#[error_code]
+pub enum AmmError {
+    #[msg("Fee exceeds the maximum")]
+    InvalidFee,
+    #[msg("The pool needs two different mints")]
+    IdenticalMints,
+    #[msg("Amount must be positive")]
+    InvalidAmount,
+    #[msg("The pool has no liquidity")]
+    EmptyPool,
+    #[msg("Amount exceeds the slippage limit")]
+    SlippageExceeded,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
+
+impl From<CurveError> for AmmError {
+    fn from(error: CurveError) -> Self {
+        match error {
+            CurveError::ZeroAmount => AmmError::InvalidAmount,
+            CurveError::EmptyPool => AmmError::EmptyPool,
+            CurveError::Overflow => AmmError::MathOverflow,
+        }
+    }
+}
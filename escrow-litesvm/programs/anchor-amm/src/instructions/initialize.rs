@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{constants::{ANCHOR_DISCRIMINATOR, CONFIG_SEED, LP_DECIMALS, LP_SEED, MAX_FEE}, error::AmmError, state::Config};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = initializer,
+        seeds = [CONFIG_SEED, seed.to_le_bytes().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Config::INIT_SPACE,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        init,
+        payer = initializer,
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump,
+        mint::decimals = LP_DECIMALS,
+        mint::authority = config,
+        mint::token_program = token_program,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = initializer,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = initializer,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Initialize<'info> {
+    pub fn initialize(&mut self, seed: u64, fee: u16, bumps: &InitializeBumps) -> Result<()> {
+        require!(fee <= MAX_FEE, AmmError::InvalidFee);
+        require_keys_neq!(self.mint_x.key(), self.mint_y.key(), AmmError::IdenticalMints);
+
+        self.config.set_inner(Config {
+            seed,
+            fee,
+            mint_x: self.mint_x.key(),
+            mint_y: self.mint_y.key(),
+            config_bump: bumps.config,
+            lp_bump: bumps.mint_lp,
+        });
+
+        Ok(())
+    }
+}
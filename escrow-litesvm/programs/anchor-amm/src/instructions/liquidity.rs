@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{burn, mint_to, transfer_checked, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{constants::{CONFIG_SEED, LP_SEED}, curve, error::AmmError, state::Config};
+
+// Shared by deposit and withdraw, which move the same accounts in opposite directions
+#[derive(Accounts)]
+pub struct Liquidity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_lp,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Liquidity<'info> {
+    pub fn deposit(&mut self, amount: u64, max_x: u64, max_y: u64) -> Result<()> {
+        require!(amount > 0, AmmError::InvalidAmount);
+
+        // The first deposit prices the pool, so it adds exactly what the user offered
+        let (x, y) = if self.mint_lp.supply == 0 {
+            require!(max_x > 0 && max_y > 0, AmmError::InvalidAmount);
+            (max_x, max_y)
+        } else {
+            curve::deposit_amounts(amount, self.vault_x.amount, self.vault_y.amount, self.mint_lp.supply)
+                .map_err(AmmError::from)?
+        };
+        require!(x <= max_x && y <= max_y, AmmError::SlippageExceeded);
+
+        self.transfer_in(true, x)?;
+        self.transfer_in(false, y)?;
+        self.mint_lp_tokens(amount)
+    }
+
+    pub fn withdraw(&mut self, amount: u64, min_x: u64, min_y: u64) -> Result<()> {
+        let (x, y) = curve::withdraw_amounts(amount, self.vault_x.amount, self.vault_y.amount, self.mint_lp.supply)
+            .map_err(AmmError::from)?;
+        require!(x >= min_x && y >= min_y, AmmError::SlippageExceeded);
+
+        self.burn_lp_tokens(amount)?;
+        self.transfer_out(true, x)?;
+        self.transfer_out(false, y)
+    }
+
+    fn transfer_in(&self, is_x: bool, amount: u64) -> Result<()> {
+        let (from, to, mint) = if is_x {
+            (&self.user_x, &self.vault_x, &self.mint_x)
+        } else {
+            (&self.user_y, &self.vault_y, &self.mint_y)
+        };
+
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.user.to_account_info(),
+            mint: mint.to_account_info(),
+        };
+
+        transfer_checked(CpiContext::new(self.token_program.to_account_info(), cpi_accounts), amount, mint.decimals)
+    }
+
+    fn transfer_out(&self, is_x: bool, amount: u64) -> Result<()> {
+        let (from, to, mint) = if is_x {
+            (&self.vault_x, &self.user_x, &self.mint_x)
+        } else {
+            (&self.vault_y, &self.user_y, &self.mint_y)
+        };
+
+        let seed = self.config.seed.to_le_bytes();
+        let signer_seeds: [&[&[u8]]; 1] = [&[CONFIG_SEED, &seed, &[self.config.config_bump]]];
+
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.config.to_account_info(),
+            mint: mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, &signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, mint.decimals)
+    }
+
+    fn mint_lp_tokens(&self, amount: u64) -> Result<()> {
+        let seed = self.config.seed.to_le_bytes();
+        let signer_seeds: [&[&[u8]]; 1] = [&[CONFIG_SEED, &seed, &[self.config.config_bump]]];
+
+        let cpi_accounts = MintTo {
+            mint: self.mint_lp.to_account_info(),
+            to: self.user_lp.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+
+        mint_to(CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, &signer_seeds), amount)
+    }
+
+    fn burn_lp_tokens(&self, amount: u64) -> Result<()> {
+        let cpi_accounts = Burn {
+            mint: self.mint_lp.to_account_info(),
+            from: self.user_lp.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+
+        burn(CpiContext::new(self.token_program.to_account_info(), cpi_accounts), amount)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod initialize;
+pub mod liquidity;
+pub mod swap;
+
+pub use initialize::*;
+pub use liquidity::*;
+pub use swap::*;
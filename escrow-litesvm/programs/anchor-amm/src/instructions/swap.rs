@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{constants::CONFIG_SEED, curve, error::AmmError, state::Config};
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    // The output side may be a mint the user has never held
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_x,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_y,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Swap<'info> {
+    pub fn swap(&mut self, x_to_y: bool, amount_in: u64, min_out: u64) -> Result<()> {
+        let (user_in, vault_in, mint_in, user_out, vault_out, mint_out) = if x_to_y {
+            (&self.user_x, &self.vault_x, &self.mint_x, &self.user_y, &self.vault_y, &self.mint_y)
+        } else {
+            (&self.user_y, &self.vault_y, &self.mint_y, &self.user_x, &self.vault_x, &self.mint_x)
+        };
+
+        let amount_out = curve::swap_output(amount_in, vault_in.amount, vault_out.amount, self.config.fee)
+            .map_err(AmmError::from)?;
+        require!(amount_out > 0 && amount_out >= min_out, AmmError::SlippageExceeded);
+
+        let token_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: user_in.to_account_info(),
+            to: vault_in.to_account_info(),
+            authority: self.user.to_account_info(),
+            mint: mint_in.to_account_info(),
+        };
+
+        transfer_checked(CpiContext::new(token_program.clone(), cpi_accounts), amount_in, mint_in.decimals)?;
+
+        let seed = self.config.seed.to_le_bytes();
+        let signer_seeds: [&[&[u8]]; 1] = [&[CONFIG_SEED, &seed, &[self.config.config_bump]]];
+
+        let cpi_accounts = TransferChecked {
+            from: vault_out.to_account_info(),
+            to: user_out.to_account_info(),
+            authority: self.config.to_account_info(),
+            mint: mint_out.to_account_info(),
+        };
+
+        transfer_checked(CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds), amount_out, mint_out.decimals)
+    }
+}
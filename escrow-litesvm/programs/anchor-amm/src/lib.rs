@@ -0,0 +1,40 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod constants;
+pub mod curve;
+mod error;
+mod instructions;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+use instructions::*;
+
+declare_id!("AM5jbqdwCczjJgAfCq4JVsXVWuJ5wq4Csz6sULFvHPAx");
+
+#[program]
+pub mod anchor_amm {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, seed: u64, fee: u16) -> Result<()> {
+        ctx.accounts.initialize(seed, fee, &ctx.bumps)
+    }
+
+    /// Mints `amount` LP tokens for at most `max_x` and `max_y`. The first deposit sets
+    /// the price and deposits exactly the maximums.
+    pub fn deposit(ctx: Context<Liquidity>, amount: u64, max_x: u64, max_y: u64) -> Result<()> {
+        ctx.accounts.deposit(amount, max_x, max_y)
+    }
+
+    /// Burns `amount` LP tokens for their share of both vaults, at least `min_x` and `min_y`
+    pub fn withdraw(ctx: Context<Liquidity>, amount: u64, min_x: u64, min_y: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount, min_x, min_y)
+    }
+
+    pub fn swap(ctx: Context<Swap>, x_to_y: bool, amount_in: u64, min_out: u64) -> Result<()> {
+        ctx.accounts.swap(x_to_y, amount_in, min_out)
+    }
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+// One pool per seed. The vaults are the config's associated token accounts and
+// the LP mint is a PDA of the config, which is also its mint authority.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Config {
+    pub seed: u64,
+    pub fee: u16,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub config_bump: u8,
+    pub lp_bump: u8,
+}
@@ -0,0 +1,203 @@
+use {
+    crate::{
+        constants::{CONFIG_SEED, LP_SEED},
+        state::Config,
+    },
+    anchor_lang::{solana_program::program_pack::Pack, InstructionData, ToAccountMetas},
+    anchor_spl::{associated_token::{self, get_associated_token_address}, token::spl_token},
+    litesvm::{types::TransactionResult, LiteSVM},
+    litesvm_token::{CreateAssociatedTokenAccount, MintTo},
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, keys, send, svm_with_program, token::{self, create_mint}},
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+pub const SEED: u64 = 1;
+pub const FEE: u16 = 30;
+/// What each user starts with of both mints
+pub const BALANCE: u64 = 1_000_000_000;
+
+pub fn config_address(seed: u64) -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_SEED, &seed.to_le_bytes()], &PROGRAM_ID).0
+}
+
+pub fn lp_mint_address(config: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[LP_SEED, config.as_ref()], &PROGRAM_ID).0
+}
+
+/// An initialized, empty pool and two funded users: a liquidity provider and a trader
+pub struct AmmTestContext {
+    pub program: LiteSVM,
+    pub provider: Keypair,
+    pub trader: Keypair,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub config: Pubkey,
+    pub mint_lp: Pubkey,
+    pub vault_x: Pubkey,
+    pub vault_y: Pubkey,
+}
+
+impl AmmTestContext {
+    pub fn new() -> Self {
+        let mut ctx = Self::uninitialized();
+        ctx.initialize(FEE).unwrap();
+        ctx
+    }
+
+    pub fn uninitialized() -> Self {
+        let provider = keys::keypair("provider");
+        let trader = keys::keypair("trader");
+        let mut program = svm_with_program(&PROGRAM_ID, "anchor_amm", env!("CARGO_MANIFEST_DIR"), &[&provider, &trader]);
+
+        let mint_x = create_mint(&mut program, &provider, &keys::keypair("mint_x"), 6, None);
+        let mint_y = create_mint(&mut program, &provider, &keys::keypair("mint_y"), 6, None);
+
+        for user in [&provider, &trader] {
+            for mint in [&mint_x, &mint_y] {
+                let ata = CreateAssociatedTokenAccount::new(&mut program, user, mint)
+                    .owner(&user.pubkey())
+                    .send()
+                    .unwrap();
+                MintTo::new(&mut program, &provider, mint, &ata, BALANCE).send().unwrap();
+            }
+        }
+
+        let config = config_address(SEED);
+        let mint_lp = lp_mint_address(&config);
+
+        Self {
+            vault_x: get_associated_token_address(&config, &mint_x),
+            vault_y: get_associated_token_address(&config, &mint_y),
+            program,
+            provider,
+            trader,
+            mint_x,
+            mint_y,
+            config,
+            mint_lp,
+        }
+    }
+
+    pub fn initialize_ix(&self, fee: u16) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Initialize {
+                initializer: self.provider.pubkey(),
+                mint_x: self.mint_x,
+                mint_y: self.mint_y,
+                config: self.config,
+                mint_lp: self.mint_lp,
+                vault_x: self.vault_x,
+                vault_y: self.vault_y,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Initialize { seed: SEED, fee }.data(),
+        }
+    }
+
+    fn liquidity_accounts(&self, user: &Pubkey) -> Vec<anchor_lang::prelude::AccountMeta> {
+        crate::accounts::Liquidity {
+            user: *user,
+            mint_x: self.mint_x,
+            mint_y: self.mint_y,
+            config: self.config,
+            mint_lp: self.mint_lp,
+            vault_x: self.vault_x,
+            vault_y: self.vault_y,
+            user_x: get_associated_token_address(user, &self.mint_x),
+            user_y: get_associated_token_address(user, &self.mint_y),
+            user_lp: get_associated_token_address(user, &self.mint_lp),
+            associated_token_program: associated_token::ID,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None)
+    }
+
+    pub fn deposit_ix(&self, user: &Pubkey, amount: u64, max_x: u64, max_y: u64) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: self.liquidity_accounts(user),
+            data: crate::instruction::Deposit { amount, max_x, max_y }.data(),
+        }
+    }
+
+    pub fn withdraw_ix(&self, user: &Pubkey, amount: u64, min_x: u64, min_y: u64) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: self.liquidity_accounts(user),
+            data: crate::instruction::Withdraw { amount, min_x, min_y }.data(),
+        }
+    }
+
+    pub fn swap_ix(&self, user: &Pubkey, x_to_y: bool, amount_in: u64, min_out: u64) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Swap {
+                user: *user,
+                mint_x: self.mint_x,
+                mint_y: self.mint_y,
+                config: self.config,
+                vault_x: self.vault_x,
+                vault_y: self.vault_y,
+                user_x: get_associated_token_address(user, &self.mint_x),
+                user_y: get_associated_token_address(user, &self.mint_y),
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Swap { x_to_y, amount_in, min_out }.data(),
+        }
+    }
+
+    pub fn initialize(&mut self, fee: u16) -> TransactionResult {
+        let ix = self.initialize_ix(fee);
+        send(&mut self.program, &[ix], &self.provider)
+    }
+
+    pub fn deposit(&mut self, user: &Keypair, amount: u64, max_x: u64, max_y: u64) -> TransactionResult {
+        let ix = self.deposit_ix(&user.pubkey(), amount, max_x, max_y);
+        send(&mut self.program, &[ix], user)
+    }
+
+    pub fn withdraw(&mut self, user: &Keypair, amount: u64, min_x: u64, min_y: u64) -> TransactionResult {
+        let ix = self.withdraw_ix(&user.pubkey(), amount, min_x, min_y);
+        send(&mut self.program, &[ix], user)
+    }
+
+    pub fn swap(&mut self, user: &Keypair, x_to_y: bool, amount_in: u64, min_out: u64) -> TransactionResult {
+        let ix = self.swap_ix(&user.pubkey(), x_to_y, amount_in, min_out);
+        send(&mut self.program, &[ix], user)
+    }
+
+    /// Token balance of an account, 0 if it does not exist
+    pub fn token_amount(&self, address: &Pubkey) -> u64 {
+        token::token_amount(&self.program, address)
+    }
+
+    pub fn balance(&self, user: &Pubkey, mint: &Pubkey) -> u64 {
+        self.token_amount(&get_associated_token_address(user, mint))
+    }
+
+    pub fn lp_supply(&self) -> u64 {
+        let account = self.program.get_account(&self.mint_lp).expect("LP mint not found");
+        spl_token::state::Mint::unpack(&account.data).unwrap().supply
+    }
+
+    pub fn reserves(&self) -> (u64, u64) {
+        (self.token_amount(&self.vault_x), self.token_amount(&self.vault_y))
+    }
+
+    pub fn config_data(&self) -> Config {
+        account::anchor_account(&self.program, &self.config)
+    }
+}
@@ -0,0 +1,26 @@
+use crate::curve::{deposit_amounts, swap_output, withdraw_amounts, CurveError};
+
+#[test]
+fn should_round_deposits_up_and_withdrawals_down() {
+    // A third of a 10/10/3 pool is not a whole number of either token
+    assert_eq!(deposit_amounts(1, 10, 10, 3), Ok((4, 4)));
+    assert_eq!(withdraw_amounts(1, 10, 10, 3), Ok((3, 3)));
+}
+
+#[test]
+fn should_never_shrink_the_product_on_swaps() {
+    for (amount_in, reserve_in, reserve_out, fee) in [(1, 1_000, 1_000, 30), (999, 1_000, 7, 0), (u64::MAX / 2, u64::MAX / 2, u64::MAX, 1_000)] {
+        let out = swap_output(amount_in, reserve_in, reserve_out, fee).unwrap();
+        let before = reserve_in as u128 * reserve_out as u128;
+        let after = (reserve_in as u128 + amount_in as u128) * (reserve_out - out) as u128;
+        assert!(after >= before, "{amount_in} into {reserve_in}/{reserve_out} shrank the product");
+    }
+}
+
+#[test]
+fn should_reject_zero_amounts_and_empty_pools() {
+    assert_eq!(deposit_amounts(0, 10, 10, 10), Err(CurveError::ZeroAmount));
+    assert_eq!(deposit_amounts(1, 0, 0, 0), Err(CurveError::EmptyPool));
+    assert_eq!(swap_output(0, 10, 10, 30), Err(CurveError::ZeroAmount));
+    assert_eq!(swap_output(1, 0, 10, 30), Err(CurveError::EmptyPool));
+}
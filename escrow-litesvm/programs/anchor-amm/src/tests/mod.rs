@@ -0,0 +1,4 @@
+pub mod context;
+
+mod curve;
+mod pool;
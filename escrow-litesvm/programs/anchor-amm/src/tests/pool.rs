@@ -0,0 +1,146 @@
+use {
+    super::context::{AmmTestContext, BALANCE, FEE},
+    crate::{constants::MAX_FEE, error::AmmError},
+    solana_signer::Signer,
+    test_utils::assert_custom_error,
+};
+
+fn product(ctx: &AmmTestContext) -> u128 {
+    let (x, y) = ctx.reserves();
+    x as u128 * y as u128
+}
+
+/// A pool holding 1_000_000 X and 4_000_000 Y against 2_000_000 LP tokens
+fn seeded() -> AmmTestContext {
+    let mut ctx = AmmTestContext::new();
+    let provider = ctx.provider.insecure_clone();
+    ctx.deposit(&provider, 2_000_000, 1_000_000, 4_000_000).unwrap();
+    ctx
+}
+
+#[test]
+fn should_initialize_an_empty_pool() {
+    let ctx = AmmTestContext::new();
+
+    let config = ctx.config_data();
+    assert_eq!((config.mint_x, config.mint_y, config.fee), (ctx.mint_x, ctx.mint_y, FEE));
+    assert_eq!(ctx.reserves(), (0, 0));
+    assert_eq!(ctx.lp_supply(), 0);
+}
+
+#[test]
+fn should_reject_fees_above_the_maximum() {
+    let mut ctx = AmmTestContext::uninitialized();
+
+    assert_custom_error!(ctx.initialize(MAX_FEE + 1), AmmError::InvalidFee);
+}
+
+#[test]
+fn should_price_the_pool_with_the_first_deposit() {
+    let ctx = seeded();
+    let provider = ctx.provider.pubkey();
+
+    assert_eq!(ctx.reserves(), (1_000_000, 4_000_000));
+    assert_eq!(ctx.lp_supply(), 2_000_000);
+    assert_eq!(ctx.balance(&provider, &ctx.mint_lp), 2_000_000);
+    assert_eq!(ctx.balance(&provider, &ctx.mint_x), BALANCE - 1_000_000);
+}
+
+#[test]
+fn should_mint_lp_in_proportion_to_the_reserves() {
+    let mut ctx = seeded();
+    let trader = ctx.trader.insecure_clone();
+
+    // A quarter of the supply costs a quarter of each reserve
+    ctx.deposit(&trader, 500_000, 250_000, 1_000_000).unwrap();
+
+    assert_eq!(ctx.reserves(), (1_250_000, 5_000_000));
+    assert_eq!(ctx.lp_supply(), 2_500_000);
+    assert_eq!(ctx.balance(&trader.pubkey(), &ctx.mint_lp), 500_000);
+    assert_eq!(ctx.balance(&trader.pubkey(), &ctx.mint_y), BALANCE - 1_000_000);
+}
+
+#[test]
+fn should_reject_deposits_above_the_maximums() {
+    let mut ctx = seeded();
+    let trader = ctx.trader.insecure_clone();
+
+    assert_custom_error!(ctx.deposit(&trader, 500_000, 249_999, 1_000_000), AmmError::SlippageExceeded);
+    assert_custom_error!(ctx.deposit(&trader, 500_000, 250_000, 999_999), AmmError::SlippageExceeded);
+}
+
+#[test]
+fn should_swap_with_fee_and_grow_the_product() {
+    let mut ctx = seeded();
+    let trader = ctx.trader.insecure_clone();
+    let before = product(&ctx);
+
+    // 10_000 X less 0.3% is 9_970, worth floor(4_000_000 * 9_970 / 1_009_970) = 39_486 Y
+    ctx.swap(&trader, true, 10_000, 39_486).unwrap();
+
+    assert_eq!(ctx.reserves(), (1_010_000, 4_000_000 - 39_486));
+    assert_eq!(ctx.balance(&trader.pubkey(), &ctx.mint_y), BALANCE + 39_486);
+    assert!(product(&ctx) > before, "The fee should stay in the pool");
+
+    // And back the other way
+    let before = product(&ctx);
+    ctx.program.expire_blockhash();
+    ctx.swap(&trader, false, 39_486, 1).unwrap();
+    assert!(product(&ctx) > before);
+    assert!(ctx.balance(&trader.pubkey(), &ctx.mint_x) < BALANCE, "A round trip should cost the fee");
+}
+
+#[test]
+fn should_reject_swaps_below_the_minimum_output() {
+    let mut ctx = seeded();
+    let trader = ctx.trader.insecure_clone();
+
+    assert_custom_error!(ctx.swap(&trader, true, 10_000, 39_487), AmmError::SlippageExceeded);
+    assert_eq!(ctx.reserves(), (1_000_000, 4_000_000));
+}
+
+#[test]
+fn should_reject_swaps_into_an_empty_pool() {
+    let mut ctx = AmmTestContext::new();
+    let trader = ctx.trader.insecure_clone();
+
+    assert_custom_error!(ctx.swap(&trader, true, 10_000, 0), AmmError::EmptyPool);
+}
+
+#[test]
+fn should_return_swap_fees_to_providers_on_withdraw() {
+    let mut ctx = seeded();
+    let provider = ctx.provider.insecure_clone();
+    let trader = ctx.trader.insecure_clone();
+
+    ctx.swap(&trader, true, 100_000, 0).unwrap();
+    ctx.program.expire_blockhash();
+    ctx.swap(&trader, false, 300_000, 0).unwrap();
+    let (x, y) = ctx.reserves();
+    assert!(x as u128 * y as u128 > 1_000_000 * 4_000_000, "Fees should grow the provider's reserves");
+
+    // Burning the whole supply releases both reserves, fees included
+    ctx.withdraw(&provider, 2_000_000, x, y).unwrap();
+
+    assert_eq!(ctx.reserves(), (0, 0));
+    assert_eq!(ctx.lp_supply(), 0);
+    let provider_x = ctx.balance(&provider.pubkey(), &ctx.mint_x);
+    let provider_y = ctx.balance(&provider.pubkey(), &ctx.mint_y);
+    assert_eq!(provider_x + ctx.balance(&trader.pubkey(), &ctx.mint_x), 2 * BALANCE, "No X should be created or lost");
+    assert_eq!(provider_y + ctx.balance(&trader.pubkey(), &ctx.mint_y), 2 * BALANCE, "No Y should be created or lost");
+}
+
+#[test]
+fn should_withdraw_a_proportional_share() {
+    let mut ctx = seeded();
+    let provider = ctx.provider.insecure_clone();
+
+    ctx.withdraw(&provider, 500_000, 250_000, 1_000_000).unwrap();
+
+    assert_eq!(ctx.reserves(), (750_000, 3_000_000));
+    assert_eq!(ctx.lp_supply(), 1_500_000);
+    assert_eq!(ctx.balance(&provider.pubkey(), &ctx.mint_lp), 1_500_000);
+
+    ctx.program.expire_blockhash();
+    assert_custom_error!(ctx.withdraw(&provider, 500_000, 250_001, 0), AmmError::SlippageExceeded);
+}
@@ -0,0 +1,174 @@
+//! Throughput of the paths clients and indexers run for every escrow they touch:
+//! building account metas, encoding instruction data and decoding escrow accounts.
+//!
+//! ```text
+//! cargo bench -p anchor-escrow
+//! ```
+
+use {
+    anchor_escrow::{accounts, instruction, state::{Escrow, EscrowStatus, TakerGate}, ID as PROGRAM_ID},
+    anchor_lang::{prelude::Pubkey, AccountDeserialize, AccountSerialize, InstructionData, ToAccountMetas},
+    criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput},
+};
+
+fn key(label: u8) -> Pubkey {
+    Pubkey::new_from_array([label; 32])
+}
+
+fn take_accounts() -> accounts::Take {
+    accounts::Take {
+        taker: key(1),
+        maker: key(2),
+        mint_a: key(3),
+        mint_b: key(4),
+        taker_ata_a: key(5),
+        taker_ata_b: key(6),
+        maker_ata_b: Some(key(7)),
+        maker_wsol: None,
+        escrow: key(8),
+        vault: key(9),
+        receipt: key(10),
+        associated_token_program: anchor_spl::associated_token::ID,
+        token_program: anchor_spl::token::ID,
+        system_program: anchor_lang::system_program::ID,
+    }
+}
+
+fn escrow_data(metadata_len: usize) -> Vec<u8> {
+    let escrow = Escrow {
+        seed: 42,
+        maker: key(2),
+        mint_a: key(3),
+        mint_b: key(4),
+        status: EscrowStatus::Open,
+        receive: 1_000_000,
+        deposit_total: 500_000,
+        filled: 100_000,
+        remaining: 400_000,
+        created_at: 1_700_000_000,
+        expires_at: 1_700_086_400,
+        starts_at: 1_700_000_000,
+        bump: 254,
+        gate: TakerGate::None,
+        operator: Pubkey::default(),
+        fill_or_kill: false,
+        reserved_for: Pubkey::default(),
+        nonce: 0,
+        metadata: vec![7; metadata_len],
+    };
+
+    let mut data = Vec::new();
+    escrow.try_serialize(&mut data).unwrap();
+    data
+}
+
+fn account_metas(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_account_metas");
+
+    group.bench_function("make", |b| {
+        let make = accounts::Make {
+            maker: key(2),
+            mint_a: key(3),
+            mint_b: key(4),
+            maker_ata_a: key(5),
+            escrow: key(8),
+            vault: key(9),
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+            maker_counter: key(12),
+        };
+        b.iter(|| black_box(&make).to_account_metas(None))
+    });
+
+    // Optional accounts resolve to the program id when absent, so both shapes are measured
+    group.bench_function("take", |b| {
+        let take = take_accounts();
+        b.iter(|| black_box(&take).to_account_metas(None))
+    });
+
+    group.bench_function("take_unwrap", |b| {
+        let take = accounts::Take { maker_ata_b: None, maker_wsol: Some(key(11)), ..take_accounts() };
+        b.iter(|| black_box(&take).to_account_metas(None))
+    });
+
+    group.bench_function("take_sol", |b| {
+        let take = accounts::TakeSol {
+            taker: key(1),
+            maker: key(2),
+            mint_a: key(3),
+            taker_ata_a: key(5),
+            escrow: key(8),
+            vault: key(9),
+            receipt: key(10),
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+        };
+        b.iter(|| black_box(&take).to_account_metas(None))
+    });
+
+    group.finish();
+}
+
+fn instruction_data(c: &mut Criterion) {
+    let mut group = c.benchmark_group("instruction_data");
+
+    group.bench_function("make", |b| {
+        b.iter(|| {
+            instruction::Make {
+                seed: black_box(42),
+                deposit: black_box(500_000),
+                receive: black_box(1_000_000),
+                expires_at: black_box(1_700_086_400),
+                starts_at: black_box(0),
+            }
+            .data()
+        })
+    });
+
+    group.bench_function("take_partial", |b| {
+        b.iter(|| instruction::TakePartial { amount: black_box(100_000) }.data())
+    });
+
+    group.bench_function("take", |b| b.iter(|| instruction::Take {}.data()));
+
+    group.bench_function("full_instruction", |b| {
+        let take = take_accounts();
+        b.iter(|| anchor_lang::solana_program::instruction::Instruction {
+            program_id: PROGRAM_ID,
+            accounts: black_box(&take).to_account_metas(None),
+            data: instruction::Take {}.data(),
+        })
+    });
+
+    group.finish();
+}
+
+fn escrow_deserialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Escrow::try_deserialize");
+
+    // Metadata is reallocated onto the account, so decoding cost grows with it
+    for metadata_len in [0, 256, 4_096] {
+        let data = escrow_data(metadata_len);
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_function(format!("metadata_{}", metadata_len), |b| {
+            b.iter(|| Escrow::try_deserialize(&mut black_box(&data[..])).unwrap())
+        });
+    }
+
+    // An indexer decodes a whole getProgramAccounts page at once
+    let page: Vec<Vec<u8>> = (0..1_000).map(|_| escrow_data(0)).collect();
+    group.throughput(Throughput::Elements(page.len() as u64));
+    group.bench_function("page_of_1000", |b| {
+        b.iter_batched(
+            || page.clone(),
+            |page| page.iter().map(|data| Escrow::try_deserialize(&mut &data[..]).unwrap()).count(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, account_metas, instruction_data, escrow_deserialize);
+criterion_main!(benches);
@@ -0,0 +1,95 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
+
+// Metaplex Bubblegum and the SPL programs it relies on to store compressed NFTs
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+pub const COMPRESSION_PROGRAM_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+const TRANSFER_DISCRIMINATOR: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+
+/// Identifies a compressed NFT leaf in a merkle tree. The root has to be fresh,
+/// everything else stays the same for the lifetime of the asset.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LeafArgs {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+}
+
+pub fn get_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"asset", merkle_tree.as_ref(), nonce.to_le_bytes().as_ref()],
+        &BUBBLEGUM_PROGRAM_ID,
+    )
+    .0
+}
+
+pub struct TransferAccounts<'a, 'info> {
+    pub tree_config: &'a AccountInfo<'info>,
+    pub leaf_owner: &'a AccountInfo<'info>,
+    pub new_leaf_owner: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub bubblegum_program: &'a AccountInfo<'info>,
+}
+
+pub fn transfer_data(leaf: &LeafArgs) -> Vec<u8> {
+    let mut data = TRANSFER_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&leaf.root);
+    data.extend_from_slice(&leaf.data_hash);
+    data.extend_from_slice(&leaf.creator_hash);
+    data.extend_from_slice(&leaf.nonce.to_le_bytes());
+    data.extend_from_slice(&leaf.index.to_le_bytes());
+    data
+}
+
+// Bubblegum asks the compression program to replace the leaf, which only succeeds
+// if the leaf and proof hash up to the supplied root. A successful transfer is
+// therefore also the proof that the cNFT described by `leaf` exists and is owned
+// by `leaf_owner`.
+pub fn transfer<'info>(
+    accounts: TransferAccounts<'_, 'info>,
+    leaf: &LeafArgs,
+    proof: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut metas = vec![
+        AccountMeta::new_readonly(accounts.tree_config.key(), false),
+        AccountMeta::new_readonly(accounts.leaf_owner.key(), true),
+        AccountMeta::new_readonly(accounts.leaf_owner.key(), false),
+        AccountMeta::new_readonly(accounts.new_leaf_owner.key(), false),
+        AccountMeta::new(accounts.merkle_tree.key(), false),
+        AccountMeta::new_readonly(accounts.log_wrapper.key(), false),
+        AccountMeta::new_readonly(accounts.compression_program.key(), false),
+        AccountMeta::new_readonly(accounts.system_program.key(), false),
+    ];
+    metas.extend(proof.iter().map(|node| AccountMeta::new_readonly(node.key(), false)));
+
+    let mut infos = vec![
+        accounts.tree_config.clone(),
+        accounts.leaf_owner.clone(),
+        accounts.leaf_owner.clone(),
+        accounts.new_leaf_owner.clone(),
+        accounts.merkle_tree.clone(),
+        accounts.log_wrapper.clone(),
+        accounts.compression_program.clone(),
+        accounts.system_program.clone(),
+        accounts.bubblegum_program.clone(),
+    ];
+    infos.extend_from_slice(proof);
+
+    let ix = Instruction {
+        program_id: BUBBLEGUM_PROGRAM_ID,
+        accounts: metas,
+        data: transfer_data(leaf),
+    };
+
+    invoke_signed(&ix, &infos, signer_seeds).map_err(Into::into)
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+pub const MAX_METADATA_LEN: usize = 512;
+
+#[constant]
+pub const ESCROW_SEED: &[u8] = b"escrow";
+#[constant]
+pub const CNFT_ESCROW_SEED: &[u8] = b"cnft_escrow";
+#[constant]
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+#[constant]
+pub const WSOL_SEED: &[u8] = b"wsol";
+#[constant]
+pub const MAKER_COUNTER_SEED: &[u8] = b"maker_counter";
+
+/// Lamports `close_expired` pays its cranker out of the escrow's rent, enough to cover
+/// the fee and a tip so keepers are not out of pocket for cleaning up
+#[constant]
+pub const CLOSE_EXPIRED_BOUNTY: u64 = 500_000;
+
+/// Prefixed to every signed quote, so a maker's signature over one can never be
+/// replayed as a signature over anything else
+#[constant]
+pub const QUOTE_DOMAIN: &[u8] = b"anchor-escrow:quote:v1";
+
+// Byte offsets of fields within a serialized Escrow account, discriminator included.
+// Used as memcmp offsets in getProgramAccounts filters, so they must never move.
+pub const ESCROW_MAKER_OFFSET: usize = ANCHOR_DISCRIMINATOR + 8;
+pub const ESCROW_MINT_A_OFFSET: usize = ESCROW_MAKER_OFFSET + 32;
+pub const ESCROW_MINT_B_OFFSET: usize = ESCROW_MINT_A_OFFSET + 32;
+pub const ESCROW_STATUS_OFFSET: usize = ESCROW_MINT_B_OFFSET + 32;
@@ -0,0 +1,52 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+
+use crate::error::EscrowError;
+
+// Layout of the ed25519 precompile's instruction data: a signature count and a padding
+// byte, then one set of seven u16 offsets per signature
+const OFFSETS_START: usize = 2;
+const OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+// An instruction index meaning the precompile instruction's own data
+const THIS_INSTRUCTION: u16 = u16::MAX;
+
+/// Checks that the instruction before the current one is an ed25519 precompile
+/// verification of `message` signed by `signer`. The runtime has already checked the
+/// signature itself, or the transaction would have failed before reaching the program.
+pub fn verify_preceding(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = load_current_index_checked(instructions)?;
+    let previous = current.checked_sub(1).ok_or(EscrowError::MissingQuoteSignature)?;
+    let instruction = load_instruction_at_checked(previous as usize, instructions)?;
+
+    require_keys_eq!(instruction.program_id, ed25519_program::ID, EscrowError::MissingQuoteSignature);
+    require!(verifies(&instruction.data, signer, message), EscrowError::InvalidQuoteSignature);
+    Ok(())
+}
+
+fn verifies(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let Some(offsets) = data.get(OFFSETS_START..OFFSETS_START + OFFSETS_SIZE) else {
+        return false;
+    };
+    if data[0] != 1 {
+        return false;
+    }
+
+    let offset = |index: usize| u16::from_le_bytes([offsets[2 * index], offsets[2 * index + 1]]);
+    let (signature_ix, pubkey_offset, pubkey_ix) = (offset(1), offset(2) as usize, offset(3));
+    let (message_offset, message_size, message_ix) = (offset(4) as usize, offset(5) as usize, offset(6));
+
+    // Offsets into other instructions would let the precompile verify bytes this
+    // check never looks at
+    if [signature_ix, pubkey_ix, message_ix].iter().any(|&index| index != THIS_INSTRUCTION) {
+        return false;
+    }
+
+    data.get(pubkey_offset..pubkey_offset + PUBKEY_SIZE) == Some(signer.as_ref())
+        && data.get(message_offset..message_offset + message_size) == Some(message)
+}
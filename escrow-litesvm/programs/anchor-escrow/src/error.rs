@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum EscrowError {
+    #[msg("Unwrapping proceeds requires mint_b to be the native mint")]
+    MintNotNative,
+    #[msg("No destination was provided for the maker's proceeds")]
+    MissingMakerDestination,
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Escrow has expired")]
+    EscrowExpired,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Escrow status does not allow this action")]
+    InvalidStatusTransition,
+    #[msg("Escrow metadata exceeds the maximum length")]
+    MetadataTooLong,
+    #[msg("Fill amount must be positive and at most the remaining deposit")]
+    InvalidFillAmount,
+    #[msg("Escrow has not expired yet")]
+    EscrowNotExpired,
+    #[msg("Escrow terms can only change while it is open and unfilled")]
+    TermsLocked,
+    #[msg("Token account is frozen")]
+    TokenAccountFrozen,
+    #[msg("Quote must be signed by the maker in the instruction before this one")]
+    MissingQuoteSignature,
+    #[msg("The ed25519 instruction does not verify this quote by the maker")]
+    InvalidQuoteSignature,
+    #[msg("Quote is for another escrow, taker or fill state")]
+    QuoteMismatch,
+    #[msg("Quote has expired")]
+    QuoteExpired,
+    #[msg("The escrow's taker gate needs accounts that were not passed")]
+    MissingGateAccounts,
+    #[msg("Taker does not hold a verified NFT of the escrow's collection")]
+    NotInCollection,
+    #[msg("Taker has no valid attestation from the escrow's issuer")]
+    InvalidAttestation,
+    #[msg("Signer is not the escrow's operator")]
+    NotOperator,
+    #[msg("Start time must be before the expiry")]
+    InvalidStartTime,
+    #[msg("Escrow cannot be taken before its start time")]
+    EscrowNotStarted,
+    #[msg("Escrow is fill-or-kill, take the whole remaining amount")]
+    PartialFillNotAllowed,
+    #[msg("Escrow is reserved for another taker")]
+    ReservedForAnotherTaker,
+    #[msg("Mints with a transfer fee cannot be escrowed")]
+    TransferFeeMint,
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::state::EscrowStatus;
+
+#[event]
+pub struct EscrowMade {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub seed: u64,
+    pub deposit: u64,
+    pub receive: u64,
+    pub expires_at: i64,
+    // 0 if the escrow can be taken right away
+    pub starts_at: i64,
+}
+
+// Emitted for every fill, partial or full. `remaining` is what is left after it.
+#[event]
+pub struct EscrowTaken {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub amount: u64,
+    pub price: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct EscrowRefunded {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EscrowTermsUpdated {
+    pub escrow: Pubkey,
+    pub receive: u64,
+    pub expires_at: i64,
+}
+
+// Emitted by `reserve` and `release`. `reserved_for` is Pubkey::default() once open again.
+#[event]
+pub struct EscrowStatusChanged {
+    pub escrow: Pubkey,
+    pub status: EscrowStatus,
+    pub reserved_for: Pubkey,
+}
+
+#[event]
+pub struct ExpiredEscrowClosed {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub cranker: Pubkey,
+    pub amount: u64,
+    pub bounty: u64,
+}
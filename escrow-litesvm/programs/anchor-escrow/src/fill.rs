@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{close_account, transfer_checked, CloseAccount, TokenAccount, TransferChecked};
+
+use crate::{
+    error::EscrowError,
+    events::EscrowTaken,
+    instructions::take::mint_decimals,
+    state::{Escrow, FillReceipt},
+};
+
+/// The accounts every take variant settles a fill with once the maker is paid: the
+/// fill goes on the taker's receipt, mint A leaves the vault, and the escrow closes
+/// when nothing remains. The variants only differ in how they pay the maker.
+pub struct Fill<'a, 'info> {
+    pub taker: &'a Signer<'info>,
+    pub maker: &'a SystemAccount<'info>,
+    pub mint_a: &'a UncheckedAccount<'info>,
+    pub taker_ata_a: &'a InterfaceAccount<'info, TokenAccount>,
+    pub escrow: &'a mut Account<'info, Escrow>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub receipt: &'a mut Account<'info, FillReceipt>,
+    pub receipt_bump: u8,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'a, 'info> Fill<'a, 'info> {
+    /// Settles `amount` of mint A, for which the maker was paid `price`
+    pub fn settle(&mut self, amount: u64, price: u64, now: i64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        self.record(amount, price, now)?;
+        self.withdraw(amount, signer_seeds)?;
+        self.close_if_filled(signer_seeds)
+    }
+
+    fn record(&mut self, amount: u64, price: u64, now: i64) -> Result<()> {
+        let escrow = self.escrow.key();
+        let receipt = &mut *self.receipt;
+
+        if receipt.escrow == Pubkey::default() || receipt.escrow_nonce != self.escrow.nonce {
+            **receipt = FillReceipt {
+                escrow,
+                escrow_nonce: self.escrow.nonce,
+                maker: self.escrow.maker,
+                taker: self.taker.key(),
+                mint_a: self.escrow.mint_a,
+                mint_b: self.escrow.mint_b,
+                amount_a: 0,
+                amount_b: 0,
+                fills: 0,
+                last_filled_at: 0,
+                bump: self.receipt_bump,
+            };
+        }
+
+        receipt.amount_a = receipt.amount_a
+            .checked_add(amount)
+            .ok_or(EscrowError::MathOverflow)?;
+        receipt.amount_b = receipt.amount_b
+            .checked_add(price)
+            .ok_or(EscrowError::MathOverflow)?;
+        receipt.fills = receipt.fills
+            .checked_add(1)
+            .ok_or(EscrowError::MathOverflow)?;
+        receipt.last_filled_at = now;
+
+        emit!(EscrowTaken {
+            escrow,
+            maker: self.escrow.maker,
+            taker: self.taker.key(),
+            amount,
+            price,
+            remaining: self.escrow.remaining
+                .checked_sub(amount)
+                .ok_or(EscrowError::MathOverflow)?,
+        });
+
+        Ok(())
+    }
+
+    fn withdraw(&mut self, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        self.escrow.filled = self.escrow.filled
+            .checked_add(amount)
+            .ok_or(EscrowError::MathOverflow)?;
+        self.escrow.remaining = self.escrow.remaining
+            .checked_sub(amount)
+            .ok_or(EscrowError::MathOverflow)?;
+
+        // Tokens sent straight to the vault are not part of the offer, but the vault
+        // can only be closed once empty, so the last fill sweeps them to the taker.
+        let withdrawal = if self.escrow.remaining == 0 {
+            self.vault.amount
+        } else {
+            amount
+        };
+
+        let cpi_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            to: self.taker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new_with_signer(self.token_program.clone(), cpi_accounts, signer_seeds);
+
+        transfer_checked(cpi_context, withdrawal, mint_decimals(self.mint_a)?)
+    }
+
+    fn close_if_filled(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        if self.escrow.remaining > 0 {
+            return Ok(());
+        }
+
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new_with_signer(self.token_program.clone(), cpi_accounts, signer_seeds);
+
+        close_account(cpi_context)?;
+
+        self.escrow.close(self.maker.to_account_info())
+    }
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{token::ID as TOKEN_PROGRAM_ID, token_2022::ID as TOKEN_2022_PROGRAM_ID, token_interface::TokenAccount};
+
+use crate::{error::EscrowError, state::TakerGate};
+
+// Metaplex Token Metadata, which records the collection an NFT belongs to
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+// Solana Attestation Service, where issuers record credentials about wallets
+pub const ATTESTATION_PROGRAM_ID: Pubkey = pubkey!("22zoJMtdu4tQc2PzL74ZUT7FrwgB1Udec8DdW4yw4BdG");
+
+// `Key::MetadataV1`, the first byte of every metadata account
+const METADATA_V1_KEY: u8 = 4;
+// The first byte of every attestation account
+const ATTESTATION_DISCRIMINATOR: u8 = 2;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub(crate) struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub(crate) struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+/// The leading fields of a Token Metadata `Metadata` account, up to its collection.
+/// Everything after it is left unread.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub(crate) struct Metadata {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<Collection>,
+}
+
+/// An attestation account of the Solana Attestation Service. Its `nonce` is the wallet
+/// the attestation is about, and an `expiry` of 0 never expires.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub(crate) struct Attestation {
+    pub discriminator: u8,
+    pub nonce: Pubkey,
+    pub credential: Pubkey,
+    pub schema: Pubkey,
+    pub data: Vec<u8>,
+    pub signer: Pubkey,
+    pub expiry: i64,
+    pub token_account: Pubkey,
+}
+
+/// Checks that `taker` satisfies the escrow's gate at `now`, given the accounts the
+/// taker passed after the instruction's own.
+pub fn check(gate: &TakerGate, taker: &Pubkey, proof: &[AccountInfo], now: i64) -> Result<()> {
+    match gate {
+        TakerGate::None => Ok(()),
+        TakerGate::Collection { mint } => {
+            let [nft, metadata, ..] = proof else {
+                return err!(EscrowError::MissingGateAccounts);
+            };
+            check_collection(mint, taker, nft, metadata)
+        }
+        TakerGate::Attestation { credential, schema } => {
+            let [attestation, ..] = proof else {
+                return err!(EscrowError::MissingGateAccounts);
+            };
+            check_attestation(credential, schema, taker, attestation, now)
+        }
+    }
+}
+
+// Only Token Metadata writes accounts it owns, and it keeps a mint's metadata at that
+// mint's PDA, so an owned MetadataV1 account naming the NFT's mint is its metadata
+// without deriving the address again.
+fn check_collection(collection: &Pubkey, taker: &Pubkey, nft: &AccountInfo, metadata: &AccountInfo) -> Result<()> {
+    require!(
+        *nft.owner == TOKEN_PROGRAM_ID || *nft.owner == TOKEN_2022_PROGRAM_ID,
+        EscrowError::NotInCollection
+    );
+    let nft = TokenAccount::try_deserialize(&mut &nft.try_borrow_data()?[..])?;
+    require!(nft.owner == *taker && nft.amount > 0, EscrowError::NotInCollection);
+
+    require_keys_eq!(*metadata.owner, TOKEN_METADATA_PROGRAM_ID, EscrowError::NotInCollection);
+    let metadata = Metadata::deserialize(&mut &metadata.try_borrow_data()?[..])
+        .map_err(|_| error!(EscrowError::NotInCollection))?;
+    require!(metadata.key == METADATA_V1_KEY && metadata.mint == nft.mint, EscrowError::NotInCollection);
+
+    match metadata.collection {
+        Some(Collection { verified: true, key }) if key == *collection => Ok(()),
+        _ => err!(EscrowError::NotInCollection),
+    }
+}
+
+// Only the attestation program writes accounts it owns, and only a signer authorized
+// by the credential can create an attestation under it, so the owner and the fields
+// are enough. Closed attestations are gone, which is how issuers revoke them.
+fn check_attestation(credential: &Pubkey, schema: &Pubkey, taker: &Pubkey, attestation: &AccountInfo, now: i64) -> Result<()> {
+    require_keys_eq!(*attestation.owner, ATTESTATION_PROGRAM_ID, EscrowError::InvalidAttestation);
+    let attestation = Attestation::deserialize(&mut &attestation.try_borrow_data()?[..])
+        .map_err(|_| error!(EscrowError::InvalidAttestation))?;
+
+    require!(
+        attestation.discriminator == ATTESTATION_DISCRIMINATOR
+            && attestation.credential == *credential
+            && attestation.schema == *schema
+            && attestation.nonce == *taker,
+        EscrowError::InvalidAttestation
+    );
+    require!(attestation.expiry == 0 || now < attestation.expiry, EscrowError::InvalidAttestation);
+    Ok(())
+}
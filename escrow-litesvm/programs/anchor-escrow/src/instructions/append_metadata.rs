@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, ESCROW_SEED, MAX_METADATA_LEN},
+    error::EscrowError,
+    state::Escrow,
+};
+
+#[derive(Accounts)]
+#[instruction(data: Vec<u8>)]
+pub struct AppendMetadata<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.metadata.len() + data.len() <= MAX_METADATA_LEN @ EscrowError::MetadataTooLong,
+        realloc = ANCHOR_DISCRIMINATOR + Escrow::INIT_SPACE + escrow.metadata.len() + data.len(),
+        realloc::payer = maker,
+        realloc::zero = false,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AppendMetadata<'info> {
+    pub fn append_metadata(&mut self, data: Vec<u8>) -> Result<()> {
+        self.escrow.metadata.extend_from_slice(&data);
+
+        Ok(())
+    }
+}
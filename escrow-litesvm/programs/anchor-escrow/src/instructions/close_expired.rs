@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{constants::{CLOSE_EXPIRED_BOUNTY, ESCROW_SEED}, error::EscrowError, events::ExpiredEscrowClosed, state::Escrow, unwind::Unwind};
+
+// Permissionless crank: once an escrow has expired anyone may return the deposit
+// to the maker and close it. The cranker earns `CLOSE_EXPIRED_BOUNTY` out of the
+// escrow's rent and the rest of the rent goes back to the maker.
+#[derive(Accounts)]
+pub struct CloseExpired<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    // The maker may have closed their ATA since making the offer, in which case
+    // the cranker recreates it unless the program is built with `strict-atas`.
+    #[cfg_attr(not(feature = "strict-atas"), account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        constraint = !maker_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
+    ))]
+    #[cfg_attr(feature = "strict-atas", account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        constraint = !maker_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
+    ))]
+    pub maker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        constraint = !vault.is_frozen() @ EscrowError::TokenAccountFrozen,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseExpired<'info> {
+    pub fn refund_and_close_vault(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(self.escrow.is_expired(now), EscrowError::EscrowNotExpired);
+
+        let event = ExpiredEscrowClosed {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            cranker: self.cranker.key(),
+            amount: self.vault.amount,
+            bounty: CLOSE_EXPIRED_BOUNTY,
+        };
+
+        Unwind {
+            maker: self.maker.to_account_info(),
+            mint_a: &self.mint_a,
+            maker_ata_a: &self.maker_ata_a,
+            escrow: &self.escrow,
+            vault: &self.vault,
+            token_program: self.token_program.to_account_info(),
+        }
+        .refund_and_close_vault(event)?;
+
+        // The escrow holds at least its rent-exempt minimum, far above the bounty, and
+        // is closed to the maker right after
+        self.escrow.sub_lamports(CLOSE_EXPIRED_BOUNTY)?;
+        self.cranker.add_lamports(CLOSE_EXPIRED_BOUNTY)?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked}};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::spl_token_2022::{self, extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions}},
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
+};
 
-use crate::state::Escrow;
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, ESCROW_SEED, MAKER_COUNTER_SEED},
+    error::EscrowError,
+    events::EscrowMade,
+    state::{Escrow, EscrowStatus, MakerCounter, TakerGate},
+};
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
@@ -14,14 +23,15 @@ pub struct Make<'info> {
         mut,
         associated_token::mint = mint_a,
         associated_token::authority = maker,
+        constraint = !maker_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
     )]
     pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
     #[account(
         init,
         payer = maker,
-        seeds = [b"escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        seeds = [ESCROW_SEED, maker.key().as_ref(), seed.to_le_bytes().as_ref()],
         bump,
-        space = 8 + Escrow::INIT_SPACE,
+        space = ANCHOR_DISCRIMINATOR + Escrow::INIT_SPACE,
     )]
     pub escrow: Account<'info, Escrow>,
     #[account(
@@ -34,17 +44,62 @@ pub struct Make<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+    // Numbers the maker's escrows. Last, so the accounts before it keep their positions.
+    #[account(
+        init_if_needed,
+        payer = maker,
+        seeds = [MAKER_COUNTER_SEED, maker.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + MakerCounter::INIT_SPACE,
+    )]
+    pub maker_counter: Account<'info, MakerCounter>,
+}
+
+// The vault would receive less than the deposit, and could never be closed while it holds
+// the withheld fee, so the escrow's amounts would not match what it can pay out
+fn has_transfer_fee(mint: &AccountInfo) -> Result<bool> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(false);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(mint.get_extension::<TransferFeeConfig>().is_ok())
 }
 
 impl<'info> Make<'info> {
-    pub fn init_escrow(&mut self, seed: u64, receive: u64, bumps: &MakeBumps) -> Result<()> {
+    pub fn init_escrow(&mut self, seed: u64, deposit: u64, receive: u64, expires_at: i64, starts_at: i64, bumps: &MakeBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at == 0 || expires_at > now, EscrowError::InvalidExpiry);
+        require!(expires_at == 0 || starts_at < expires_at, EscrowError::InvalidStartTime);
+        require!(!has_transfer_fee(&self.mint_a.to_account_info())?, EscrowError::TransferFeeMint);
+
+        let nonce = self.maker_counter.escrows_made;
+        self.maker_counter.escrows_made = nonce
+            .checked_add(1)
+            .ok_or(EscrowError::MathOverflow)?;
+        self.maker_counter.bump = bumps.maker_counter;
+
         self.escrow.set_inner(Escrow {
             seed,
             maker: self.maker.key(),
             mint_a: self.mint_a.key(),
             mint_b: self.mint_b.key(),
+            status: EscrowStatus::Open,
             receive,
+            deposit_total: deposit,
+            filled: 0,
+            remaining: deposit,
+            created_at: now,
+            expires_at,
+            starts_at,
             bump: bumps.escrow,
+            gate: TakerGate::None,
+            operator: Pubkey::default(),
+            fill_or_kill: false,
+            reserved_for: Pubkey::default(),
+            nonce,
+            metadata: Vec::new(),
         });
 
         Ok(())
@@ -64,6 +119,18 @@ impl<'info> Make<'info> {
 
         transfer_checked(cpi_ctx, deposit, self.mint_a.decimals)?;
 
+        emit!(EscrowMade {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            seed: self.escrow.seed,
+            deposit,
+            receive: self.escrow.receive,
+            expires_at: self.escrow.expires_at,
+            starts_at: self.escrow.starts_at,
+        });
+
         Ok(())
     }
 }
\ No newline at end of file
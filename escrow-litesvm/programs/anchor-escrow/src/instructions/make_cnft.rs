@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    bubblegum::{self, get_asset_id, LeafArgs, TransferAccounts, BUBBLEGUM_PROGRAM_ID, COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID},
+    constants::{ANCHOR_DISCRIMINATOR, CNFT_ESCROW_SEED},
+    state::CnftEscrow,
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeCnft<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = maker,
+        seeds = [CNFT_ESCROW_SEED, maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + CnftEscrow::INIT_SPACE,
+    )]
+    pub escrow: Account<'info, CnftEscrow>,
+    /// CHECK: Bubblegum tree config PDA, validated by Bubblegum
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = BUBBLEGUM_PROGRAM_ID,
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: Concurrent merkle tree, validated by the compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: Address checked
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: Address checked
+    #[account(address = COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    /// CHECK: Address checked
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeCnft<'info> {
+    pub fn init_escrow(&mut self, seed: u64, receive: u64, leaf: &LeafArgs, bumps: &MakeCnftBumps) -> Result<()> {
+        self.escrow.set_inner(CnftEscrow {
+            seed,
+            maker: self.maker.key(),
+            merkle_tree: self.merkle_tree.key(),
+            asset_id: get_asset_id(&self.merkle_tree.key(), leaf.nonce),
+            data_hash: leaf.data_hash,
+            creator_hash: leaf.creator_hash,
+            nonce: leaf.nonce,
+            index: leaf.index,
+            mint_b: self.mint_b.key(),
+            receive,
+            bump: bumps.escrow,
+            tree_config_bump: bumps.tree_config,
+        });
+
+        Ok(())
+    }
+
+    // The maker signs as leaf owner, the escrow PDA becomes the new owner
+    pub fn deposit(&mut self, leaf: &LeafArgs, proof: &[AccountInfo<'info>]) -> Result<()> {
+        let accounts = TransferAccounts {
+            tree_config: &self.tree_config.to_account_info(),
+            leaf_owner: &self.maker.to_account_info(),
+            new_leaf_owner: &self.escrow.to_account_info(),
+            merkle_tree: &self.merkle_tree.to_account_info(),
+            log_wrapper: &self.log_wrapper.to_account_info(),
+            compression_program: &self.compression_program.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            bubblegum_program: &self.bubblegum_program.to_account_info(),
+        };
+
+        bubblegum::transfer(accounts, leaf, proof, &[])
+    }
+}
@@ -1,7 +1,33 @@
+pub mod append_metadata;
+pub mod close_expired;
 pub mod make;
+pub mod make_cnft;
+pub mod operator_refund;
 pub mod refund;
+pub mod refund_cnft;
+pub mod reserve;
+pub mod set_fill_or_kill;
+pub mod set_gate;
+pub mod set_operator;
 pub mod take;
+pub mod take_cnft;
+pub mod take_quote;
+pub mod take_sol;
+pub mod update_terms;
 
+pub use append_metadata::*;
+pub use close_expired::*;
 pub use make::*;
+pub use make_cnft::*;
+pub use operator_refund::*;
 pub use refund::*;
-pub use take::*;
\ No newline at end of file
+pub use refund_cnft::*;
+pub use reserve::*;
+pub use set_fill_or_kill::*;
+pub use set_gate::*;
+pub use set_operator::*;
+pub use take::*;
+pub use take_cnft::*;
+pub use take_quote::*;
+pub use take_sol::*;
+pub use update_terms::*;
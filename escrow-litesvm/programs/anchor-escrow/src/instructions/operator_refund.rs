@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{constants::ESCROW_SEED, error::EscrowError, events::EscrowRefunded, state::Escrow, unwind::Unwind};
+
+// `Refund` signed by the operator the maker registered instead of the maker, so a
+// custodial platform can clean up without holding the maker's key. Everything is
+// still returned to the maker, the operator only pays the fee.
+#[derive(Accounts)]
+pub struct OperatorRefund<'info> {
+    pub operator: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        constraint = !maker_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = operator @ EscrowError::NotOperator,
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        constraint = !vault.is_frozen() @ EscrowError::TokenAccountFrozen,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> OperatorRefund<'info> {
+    pub fn refund_and_close_vault(&mut self) -> Result<()> {
+        let event = EscrowRefunded {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            amount: self.vault.amount,
+        };
+
+        Unwind {
+            maker: self.maker.to_account_info(),
+            mint_a: &self.mint_a,
+            maker_ata_a: &self.maker_ata_a,
+            escrow: &self.escrow,
+            vault: &self.vault,
+            token_program: self.token_program.to_account_info(),
+        }
+        .refund_and_close_vault(event)
+    }
+}
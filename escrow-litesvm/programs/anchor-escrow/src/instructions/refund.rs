@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked, CloseAccount, close_account};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::Escrow;
+use crate::{constants::ESCROW_SEED, error::EscrowError, events::EscrowRefunded, state::Escrow, unwind::Unwind};
 
 #[derive(Accounts)]
 pub struct Refund<'info> {
@@ -12,6 +12,7 @@ pub struct Refund<'info> {
         mut,
         associated_token::mint = mint_a,
         associated_token::authority = maker,
+        constraint = !maker_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
     )]
     maker_ata_a: InterfaceAccount<'info, TokenAccount>,
     #[account(
@@ -19,7 +20,7 @@ pub struct Refund<'info> {
         close = maker,
         has_one = mint_a,
         has_one = maker,
-        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
         bump = escrow.bump,
     )]
     pub escrow: Account<'info, Escrow>,
@@ -27,46 +28,28 @@ pub struct Refund<'info> {
         mut,
         associated_token::mint = mint_a,
         associated_token::authority = escrow,
+        constraint = !vault.is_frozen() @ EscrowError::TokenAccountFrozen,
     )]
     vault: InterfaceAccount<'info, TokenAccount>,
     token_program: Interface<'info, TokenInterface>,
-    system_program: Program<'info, System>,
 }
 
 impl<'info> Refund<'info> {
     pub fn refund_and_close_vault(&mut self) -> Result<()> {
-        let signer_seeds: [&[&[u8]]; 1] = [&[
-            b"escrow",
-            self.maker.key.as_ref(),
-            &self.escrow.seed.to_le_bytes()[..],
-            &[self.escrow.bump]
-        ]];
-        
-        let cpi_program = self.token_program.to_account_info();
-
-        let cpi_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            to: self.maker_ata_a.to_account_info(),
-            mint: self.mint_a.to_account_info(),
-            authority: self.escrow.to_account_info(),
-        };
-
-        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds);
-
-        transfer_checked(cpi_context, self.vault.amount, self.mint_a.decimals)?;
-
-        let cpi_program = self.token_program.to_account_info();
-
-        let cpi_accounts = CloseAccount {
-            account: self.vault.to_account_info(),
-            destination: self.maker.to_account_info(),
-            authority: self.escrow.to_account_info(),
+        let event = EscrowRefunded {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            amount: self.vault.amount,
         };
 
-        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds);
-
-        close_account(cpi_context)?;
-        
-        Ok(())
+        Unwind {
+            maker: self.maker.to_account_info(),
+            mint_a: &self.mint_a,
+            maker_ata_a: &self.maker_ata_a,
+            escrow: &self.escrow,
+            vault: &self.vault,
+            token_program: self.token_program.to_account_info(),
+        }
+        .refund_and_close_vault(event)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    bubblegum::{self, LeafArgs, TransferAccounts, BUBBLEGUM_PROGRAM_ID, COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID},
+    constants::CNFT_ESCROW_SEED,
+    state::CnftEscrow,
+};
+
+#[derive(Accounts)]
+pub struct RefundCnft<'info> {
+    #[account(mut)]
+    maker: Signer<'info>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = merkle_tree,
+        seeds = [CNFT_ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, CnftEscrow>,
+    /// CHECK: Bubblegum tree config PDA, validated by Bubblegum
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump = escrow.tree_config_bump,
+        seeds::program = BUBBLEGUM_PROGRAM_ID,
+    )]
+    tree_config: UncheckedAccount<'info>,
+    /// CHECK: Concurrent merkle tree, validated by the compression program
+    #[account(mut)]
+    merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: Address checked
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: Address checked
+    #[account(address = COMPRESSION_PROGRAM_ID)]
+    compression_program: UncheckedAccount<'info>,
+    /// CHECK: Address checked
+    #[account(address = NOOP_PROGRAM_ID)]
+    log_wrapper: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> RefundCnft<'info> {
+    pub fn refund(&mut self, root: [u8; 32], proof: &[AccountInfo<'info>]) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            CNFT_ESCROW_SEED,
+            self.maker.key.as_ref(),
+            &self.escrow.seed.to_le_bytes()[..],
+            &[self.escrow.bump]
+        ]];
+
+        let leaf = LeafArgs {
+            root,
+            data_hash: self.escrow.data_hash,
+            creator_hash: self.escrow.creator_hash,
+            nonce: self.escrow.nonce,
+            index: self.escrow.index,
+        };
+
+        let accounts = TransferAccounts {
+            tree_config: &self.tree_config.to_account_info(),
+            leaf_owner: &self.escrow.to_account_info(),
+            new_leaf_owner: &self.maker.to_account_info(),
+            merkle_tree: &self.merkle_tree.to_account_info(),
+            log_wrapper: &self.log_wrapper.to_account_info(),
+            compression_program: &self.compression_program.to_account_info(),
+            system_program: &self.system_program.to_account_info(),
+            bubblegum_program: &self.bubblegum_program.to_account_info(),
+        };
+
+        bubblegum::transfer(accounts, &leaf, proof, &signer_seeds)
+    }
+}
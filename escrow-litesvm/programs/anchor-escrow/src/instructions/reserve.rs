@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::ESCROW_SEED,
+    events::EscrowStatusChanged,
+    state::{Escrow, EscrowStatus},
+};
+
+// Shared by `reserve` and `release`
+#[derive(Accounts)]
+pub struct Reserve<'info> {
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> Reserve<'info> {
+    // A reserved escrow can still be partly filled by its taker, refunded or closed
+    // once expired, only other takers are turned away
+    pub fn reserve(&mut self, taker: Pubkey) -> Result<()> {
+        self.escrow.transition(EscrowStatus::Reserved)?;
+        self.escrow.reserved_for = taker;
+
+        self.emit_status_changed();
+        Ok(())
+    }
+
+    pub fn release(&mut self) -> Result<()> {
+        self.escrow.transition(EscrowStatus::Open)?;
+        self.escrow.reserved_for = Pubkey::default();
+
+        self.emit_status_changed();
+        Ok(())
+    }
+
+    fn emit_status_changed(&self) {
+        emit!(EscrowStatusChanged {
+            escrow: self.escrow.key(),
+            status: self.escrow.status,
+            reserved_for: self.escrow.reserved_for,
+        });
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::ESCROW_SEED,
+    error::EscrowError,
+    state::{Escrow, EscrowStatus},
+};
+
+#[derive(Accounts)]
+pub struct SetFillOrKill<'info> {
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> SetFillOrKill<'info> {
+    // Locked after the first fill like the terms, a partly filled escrow can no longer be
+    // filled whole
+    pub fn set_fill_or_kill(&mut self, fill_or_kill: bool) -> Result<()> {
+        require!(
+            self.escrow.status == EscrowStatus::Open && self.escrow.filled == 0,
+            EscrowError::TermsLocked
+        );
+
+        self.escrow.fill_or_kill = fill_or_kill;
+
+        Ok(())
+    }
+}
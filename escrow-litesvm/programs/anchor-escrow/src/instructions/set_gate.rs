@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::ESCROW_SEED,
+    error::EscrowError,
+    state::{Escrow, EscrowStatus, TakerGate},
+};
+
+#[derive(Accounts)]
+pub struct SetGate<'info> {
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> SetGate<'info> {
+    // Locked after the first fill like the terms, so every fill is held to the same gate
+    pub fn set_gate(&mut self, gate: TakerGate) -> Result<()> {
+        require!(
+            self.escrow.status == EscrowStatus::Open && self.escrow.filled == 0,
+            EscrowError::TermsLocked
+        );
+
+        self.escrow.gate = gate;
+
+        Ok(())
+    }
+}
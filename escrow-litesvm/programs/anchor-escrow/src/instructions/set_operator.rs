@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::ESCROW_SEED, state::Escrow};
+
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> SetOperator<'info> {
+    // Unlike the terms this can change at any time, it only decides who may clean up
+    pub fn set_operator(&mut self, operator: Pubkey) -> Result<()> {
+        self.escrow.operator = operator;
+
+        Ok(())
+    }
+}
@@ -1,108 +1,211 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account}};
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{associated_token::AssociatedToken, token::spl_token, token_2022::spl_token_2022, token_interface::{TokenAccount, TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account}};
 
-use crate::state::Escrow;
+use crate::{constants::{ANCHOR_DISCRIMINATOR, ESCROW_SEED, RECEIPT_SEED, WSOL_SEED}, error::EscrowError, fill::Fill, gate, state::{Escrow, FillReceipt}};
 
 //Create context
+// Every deserialized account is boxed to keep it off the 4KB BPF stack frame,
+// and the frozen checks run in `validate_accounts` rather than as constraints, which
+// keeps the generated `try_accounts` frame small enough for all optional accounts.
+// The mints are never deserialized: the escrow pins their addresses and only their
+// decimals are read, straight from the account data.
 #[derive(Accounts)]
 pub struct Take<'info> {
     #[account(mut)]
     pub taker: Signer<'info>,
     #[account(mut)]
     pub maker: SystemAccount<'info>,
-    pub mint_a: InterfaceAccount<'info, Mint>,
-    pub mint_b: InterfaceAccount<'info, Mint>,
-    #[account(
+    /// CHECK: Matched against the escrow by `has_one`, and the token program checks its decimals
+    pub mint_a: UncheckedAccount<'info>,
+    /// CHECK: Matched against the escrow by `has_one`, and the token program checks its decimals
+    pub mint_b: UncheckedAccount<'info>,
+    // Destination ATAs are created on the fly unless the program is built with
+    // `strict-atas`, in which case they must already exist.
+    #[cfg_attr(not(feature = "strict-atas"), account(
         init_if_needed,
         payer = taker,
         associated_token::mint = mint_a,
         associated_token::authority = taker,
-    )]
-    pub taker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    ))]
+    #[cfg_attr(feature = "strict-atas", account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+    ))]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = mint_b,
         associated_token::authority = taker,
     )]
-    pub taker_ata_b: InterfaceAccount<'info, TokenAccount>,
-    #[account(
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[cfg_attr(not(feature = "strict-atas"), account(
         init_if_needed,
         payer = taker,
         associated_token::mint = mint_b,
         associated_token::authority = maker,
+    ))]
+    #[cfg_attr(feature = "strict-atas", account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+    ))]
+    pub maker_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    // Temporary wSOL account used to unwrap the maker's proceeds into native SOL.
+    // Pass it instead of maker_ata_b when mint_b is the native mint.
+    #[account(
+        init,
+        payer = taker,
+        seeds = [WSOL_SEED, escrow.key().as_ref()],
+        bump,
+        token::mint = mint_b,
+        token::authority = escrow,
+        token::token_program = token_program,
+        constraint = is_native_mint(&mint_b.key()) @ EscrowError::MintNotNative,
     )]
-    pub maker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    pub maker_wsol: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    // Closed by the handler once the last unit is filled, so partial fills keep it open
     #[account(
         mut,
-        close = maker,
         has_one = maker,
         has_one = mint_a,
         has_one = mint_b,
-        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
         bump = escrow.bump,
     )]
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: Box<Account<'info, Escrow>>,
     #[account(
         mut,
         associated_token::mint = mint_a,
         associated_token::authority = escrow,
     )]
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        seeds = [RECEIPT_SEED, escrow.key().as_ref(), taker.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + FillReceipt::INIT_SPACE,
+    )]
+    pub receipt: Box<Account<'info, FillReceipt>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
+pub(crate) fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == spl_token::native_mint::ID || *mint == spl_token_2022::native_mint::ID
+}
+
+// Same offset in the Token-2022 base mint, extensions only follow it
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+pub(crate) fn mint_decimals(mint: &AccountInfo) -> Result<u8> {
+    let data = mint.try_borrow_data()?;
+    data.get(MINT_DECIMALS_OFFSET).copied().ok_or_else(|| ErrorCode::AccountDidNotDeserialize.into())
+}
+
 //Deposit tokens from taker to maker
-//Transfer tokens from vault to taker
-//Close vault account
+//Then settle the fill as every take variant does
 impl<'info> Take<'info> {
-    pub fn deposit(&mut self) -> Result<()> {
-        let cpi_program = self.token_program.to_account_info();
+    // The clock, the escrow's signer seeds and the token program are resolved once
+    // and shared by every CPI of the fill rather than rebuilt for each one.
+    // `gate_proof` holds the remaining accounts, checked against the escrow's gate.
+    pub fn fill(&mut self, amount: u64, bumps: &TakeBumps, gate_proof: &[AccountInfo]) -> Result<()> {
+        self.validate_accounts()?;
+        let now = Clock::get()?.unix_timestamp;
+        self.escrow.validate_fill(&self.taker.key(), amount, now)?;
+        gate::check(&self.escrow.gate, &self.taker.key(), gate_proof, now)?;
+        log!("Filling {} of {} remaining", amount, self.escrow.remaining);
+
+        let seed = self.escrow.seed.to_le_bytes();
+        let bump = [self.escrow.bump];
+        let maker = self.maker.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[ESCROW_SEED, maker.as_ref(), &seed, &bump]];
+        let token_program = self.token_program.to_account_info();
+
+        let price = self.deposit(amount, &token_program, &signer_seeds)?;
+
+        Fill {
+            taker: &self.taker,
+            maker: &self.maker,
+            mint_a: &self.mint_a,
+            taker_ata_a: &self.taker_ata_a,
+            escrow: &mut self.escrow,
+            vault: &self.vault,
+            receipt: &mut self.receipt,
+            receipt_bump: bumps.receipt,
+            token_program,
+        }
+        .settle(amount, price, now, &signer_seeds)
+    }
+
+    #[inline(never)]
+    fn validate_accounts(&self) -> Result<()> {
+        let maker_ata_b_frozen = self.maker_ata_b.as_ref().is_some_and(|maker_ata_b| maker_ata_b.is_frozen());
+        require!(
+            !(self.taker_ata_a.is_frozen() || self.taker_ata_b.is_frozen() || maker_ata_b_frozen || self.vault.is_frozen()),
+            EscrowError::TokenAccountFrozen
+        );
+        Ok(())
+    }
+
+    // Returns the amount of mint B paid for the fill
+    fn deposit(&self, amount: u64, token_program: &AccountInfo<'info>, signer_seeds: &[&[&[u8]]]) -> Result<u64> {
+        let price = self.escrow.quote(amount)?;
+
+        let destination = match (&self.maker_wsol, &self.maker_ata_b) {
+            (Some(maker_wsol), _) => maker_wsol.to_account_info(),
+            (None, Some(maker_ata_b)) => maker_ata_b.to_account_info(),
+            (None, None) => return err!(EscrowError::MissingMakerDestination),
+        };
 
         let cpi_accounts = TransferChecked {
             from: self.taker_ata_b.to_account_info(),
-            to: self.maker_ata_b.to_account_info(),
+            to: destination,
             authority: self.taker.to_account_info(),
             mint: self.mint_b.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
 
-        transfer_checked(cpi_ctx, self.escrow.receive, self.mint_b.decimals)
-    }
+        transfer_checked(cpi_ctx, price, mint_decimals(&self.mint_b)?)?;
 
-    pub fn withdraw_and_close_vault(&mut self) -> Result<()> {
-        let signer_seeds: [&[&[u8]]; 1] = [&[
-            b"escrow",
-            self.maker.key.as_ref(),
-            &self.escrow.seed.to_le_bytes()[..],
-            &[self.escrow.bump]
-        ]];
+        if self.maker_wsol.is_some() {
+            self.unwrap_to_maker(price, token_program, signer_seeds)?;
+        }
 
-        let cpi_program = self.token_program.to_account_info();
+        Ok(price)
+    }
 
-        let cpi_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            to: self.taker_ata_a.to_account_info(),
+    // Closing the temporary wSOL account releases the wrapped amount plus its rent as
+    // native lamports. They are returned to the taker, who paid that rent, and the
+    // taker then forwards exactly the price in lamports to the maker.
+    fn unwrap_to_maker(&self, price: u64, token_program: &AccountInfo<'info>, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let Some(maker_wsol) = &self.maker_wsol else {
+            return err!(EscrowError::MissingMakerDestination);
+        };
+
+        let cpi_accounts = CloseAccount {
+            account: maker_wsol.to_account_info(),
+            destination: self.taker.to_account_info(),
             authority: self.escrow.to_account_info(),
-            mint: self.mint_a.to_account_info(),
         };
 
-        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds);
+        let cpi_context = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
 
-        transfer_checked(cpi_context, self.vault.amount, self.mint_a.decimals)?;
+        close_account(cpi_context)?;
 
-        let cpi_program = self.token_program.to_account_info();
+        let cpi_program = self.system_program.to_account_info();
 
-        let cpi_accounts = CloseAccount {
-            account: self.vault.to_account_info(),
-            destination: self.maker.to_account_info(),
-            authority: self.escrow.to_account_info(),
+        let cpi_accounts = Transfer {
+            from: self.taker.to_account_info(),
+            to: self.maker.to_account_info(),
         };
 
-        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds);
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-        close_account(cpi_context)
+        transfer(cpi_ctx, price)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{TokenAccount, TokenInterface, TransferChecked, transfer_checked}};
+
+use crate::{constants::{ANCHOR_DISCRIMINATOR, ESCROW_SEED, RECEIPT_SEED}, ed25519, error::EscrowError, fill::Fill, state::{Escrow, FillReceipt, Quote}};
+
+use super::take::mint_decimals;
+
+// `Take` at a price the maker signed off-chain rather than the escrow's own terms.
+// The maker pays nothing to quote, and the instructions sysvar lets the program check
+// the ed25519 verification the taker put in front of this instruction.
+#[derive(Accounts)]
+pub struct TakeQuote<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    /// CHECK: Matched against the escrow by `has_one`, and the token program checks its decimals
+    pub mint_a: UncheckedAccount<'info>,
+    /// CHECK: Matched against the escrow by `has_one`, and the token program checks its decimals
+    pub mint_b: UncheckedAccount<'info>,
+    // Destination ATAs follow `Take`: created on the fly unless built with `strict-atas`
+    #[cfg_attr(not(feature = "strict-atas"), account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+    ))]
+    #[cfg_attr(feature = "strict-atas", account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+    ))]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[cfg_attr(not(feature = "strict-atas"), account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+    ))]
+    #[cfg_attr(feature = "strict-atas", account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+    ))]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        seeds = [RECEIPT_SEED, escrow.key().as_ref(), taker.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + FillReceipt::INIT_SPACE,
+    )]
+    pub receipt: Box<Account<'info, FillReceipt>>,
+    /// CHECK: The address is the instructions sysvar, read through its own accessors
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakeQuote<'info> {
+    pub fn fill(&mut self, quote: Quote, bumps: &TakeQuoteBumps) -> Result<()> {
+        require!(
+            !(self.taker_ata_a.is_frozen() || self.taker_ata_b.is_frozen() || self.maker_ata_b.is_frozen() || self.vault.is_frozen()),
+            EscrowError::TokenAccountFrozen
+        );
+        let now = Clock::get()?.unix_timestamp;
+        self.escrow.validate_fill(&self.taker.key(), quote.amount, now)?;
+        self.validate_quote(&quote, now)?;
+        log!("Filling {} of {} remaining at a quoted {}", quote.amount, self.escrow.remaining, quote.price);
+
+        let seed = self.escrow.seed.to_le_bytes();
+        let bump = [self.escrow.bump];
+        let maker = self.maker.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[ESCROW_SEED, maker.as_ref(), &seed, &bump]];
+
+        self.deposit(quote.price)?;
+
+        Fill {
+            taker: &self.taker,
+            maker: &self.maker,
+            mint_a: &self.mint_a,
+            taker_ata_a: &self.taker_ata_a,
+            escrow: &mut self.escrow,
+            vault: &self.vault,
+            receipt: &mut self.receipt,
+            receipt_bump: bumps.receipt,
+            token_program: self.token_program.to_account_info(),
+        }
+        .settle(quote.amount, quote.price, now, &signer_seeds)
+    }
+
+    // The escrow's own fill checks still apply, the quote only replaces its price.
+    // Its gate does not: the maker already picked the taker by signing the quote.
+    fn validate_quote(&self, quote: &Quote, now: i64) -> Result<()> {
+        require!(
+            quote.escrow == self.escrow.key()
+                && quote.nonce == self.escrow.nonce
+                && quote.taker == self.taker.key()
+                && quote.filled == self.escrow.filled,
+            EscrowError::QuoteMismatch
+        );
+        require!(now < quote.expires_at, EscrowError::QuoteExpired);
+
+        ed25519::verify_preceding(&self.instructions, &self.escrow.maker, &quote.message())
+    }
+
+    fn deposit(&self, price: u64) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.taker_ata_b.to_account_info(),
+            to: self.maker_ata_b.to_account_info(),
+            authority: self.taker.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, price, mint_decimals(&self.mint_b)?)
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+use crate::{constants::{ANCHOR_DISCRIMINATOR, ESCROW_SEED, RECEIPT_SEED}, error::EscrowError, fill::Fill, gate, state::{Escrow, FillReceipt}};
+
+use super::take::is_native_mint;
+
+// `Take` for escrows asking native SOL. The taker pays the maker with a plain system
+// transfer, so there is no mint B, no maker ATA, no wSOL round trip and no ATA program.
+// The taker's ATA for mint A must already exist.
+#[derive(Accounts)]
+pub struct TakeSol<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    /// CHECK: Matched against the escrow by `has_one`, and the token program checks its decimals
+    pub mint_a: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = mint_a,
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = is_native_mint(&escrow.mint_b) @ EscrowError::MintNotNative,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        seeds = [RECEIPT_SEED, escrow.key().as_ref(), taker.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + FillReceipt::INIT_SPACE,
+    )]
+    pub receipt: Box<Account<'info, FillReceipt>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakeSol<'info> {
+    pub fn fill(&mut self, amount: u64, bumps: &TakeSolBumps, gate_proof: &[AccountInfo]) -> Result<()> {
+        require!(!(self.taker_ata_a.is_frozen() || self.vault.is_frozen()), EscrowError::TokenAccountFrozen);
+        let now = Clock::get()?.unix_timestamp;
+        self.escrow.validate_fill(&self.taker.key(), amount, now)?;
+        gate::check(&self.escrow.gate, &self.taker.key(), gate_proof, now)?;
+        log!("Filling {} of {} remaining for SOL", amount, self.escrow.remaining);
+
+        let price = self.escrow.quote(amount)?;
+        self.pay_maker(price)?;
+
+        let seed = self.escrow.seed.to_le_bytes();
+        let bump = [self.escrow.bump];
+        let maker = self.maker.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[ESCROW_SEED, maker.as_ref(), &seed, &bump]];
+
+        Fill {
+            taker: &self.taker,
+            maker: &self.maker,
+            mint_a: &self.mint_a,
+            taker_ata_a: &self.taker_ata_a,
+            escrow: &mut self.escrow,
+            vault: &self.vault,
+            receipt: &mut self.receipt,
+            receipt_bump: bumps.receipt,
+            token_program: self.token_program.to_account_info(),
+        }
+        .settle(amount, price, now, &signer_seeds)
+    }
+
+    fn pay_maker(&self, price: u64) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: self.taker.to_account_info(),
+            to: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, price)
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::ESCROW_SEED,
+    error::EscrowError,
+    events::EscrowTermsUpdated,
+    state::{Escrow, EscrowStatus},
+};
+
+#[derive(Accounts)]
+pub struct UpdateTerms<'info> {
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [ESCROW_SEED, maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> UpdateTerms<'info> {
+    // Takers who already filled did so at the old price, so terms are frozen after the first fill
+    pub fn update_terms(&mut self, receive: u64, expires_at: i64) -> Result<()> {
+        require!(
+            self.escrow.status == EscrowStatus::Open && self.escrow.filled == 0,
+            EscrowError::TermsLocked
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at == 0 || expires_at > now, EscrowError::InvalidExpiry);
+        require!(expires_at == 0 || self.escrow.starts_at < expires_at, EscrowError::InvalidStartTime);
+
+        self.escrow.receive = receive;
+        self.escrow.expires_at = expires_at;
+
+        emit!(EscrowTermsUpdated {
+            escrow: self.escrow.key(),
+            receive,
+            expires_at,
+        });
+
+        Ok(())
+    }
+}
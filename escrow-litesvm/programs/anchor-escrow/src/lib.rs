@@ -3,11 +3,31 @@
 
 use anchor_lang::prelude::*;
 
-mod state;
+/// `msg!` that compiles away without the `verbose-logs` feature, so mainnet fills don't
+/// pay for diagnostics. Events are emitted either way.
+macro_rules! log {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "verbose-logs")]
+        ::anchor_lang::prelude::msg!($($arg)*);
+    };
+}
+
+mod bubblegum;
+pub mod constants;
+mod ed25519;
+mod error;
+pub mod events;
+mod fill;
+mod gate;
+pub mod state;
 mod instructions;
+mod unwind;
+#[cfg(test)]
 mod tests;
 
+use bubblegum::LeafArgs;
 use instructions::*;
+use state::{Quote, TakerGate};
 
 declare_id!("FircrADQ2wgGuvpm8qneNCfKM7o5zoHTWnDQxngpTQ3J");
 
@@ -15,8 +35,10 @@ declare_id!("FircrADQ2wgGuvpm8qneNCfKM7o5zoHTWnDQxngpTQ3J");
 pub mod anchor_escrow {
     use super::*;
 
-    pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64) -> Result<()> {
-        ctx.accounts.init_escrow(seed, receive, &ctx.bumps)?;
+    /// `starts_at` lets an escrow be funded ahead of a launch, it cannot be taken before
+    /// then. 0 makes it takeable right away, as an `expires_at` of 0 never expires it.
+    pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64, expires_at: i64, starts_at: i64) -> Result<()> {
+        ctx.accounts.init_escrow(seed, deposit, receive, expires_at, starts_at, &ctx.bumps)?;
         ctx.accounts.deposit(deposit)
     }
 
@@ -25,7 +47,76 @@ pub mod anchor_escrow {
     }
 
     pub fn take(ctx: Context<Take>) -> Result<()> {
+        let amount = ctx.accounts.escrow.remaining;
+        ctx.accounts.fill(amount, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn append_metadata(ctx: Context<AppendMetadata>, data: Vec<u8>) -> Result<()> {
+        ctx.accounts.append_metadata(data)
+    }
+
+    pub fn make_cnft<'info>(ctx: Context<'_, '_, 'info, 'info, MakeCnft<'info>>, seed: u64, receive: u64, leaf: LeafArgs) -> Result<()> {
+        ctx.accounts.init_escrow(seed, receive, &leaf, &ctx.bumps)?;
+        ctx.accounts.deposit(&leaf, ctx.remaining_accounts)
+    }
+
+    pub fn refund_cnft<'info>(ctx: Context<'_, '_, 'info, 'info, RefundCnft<'info>>, root: [u8; 32]) -> Result<()> {
+        ctx.accounts.refund(root, ctx.remaining_accounts)
+    }
+
+    pub fn take_cnft<'info>(ctx: Context<'_, '_, 'info, 'info, TakeCnft<'info>>, root: [u8; 32]) -> Result<()> {
         ctx.accounts.deposit()?;
-        ctx.accounts.withdraw_and_close_vault()
+        ctx.accounts.withdraw(root, ctx.remaining_accounts)
+    }
+
+    pub fn take_partial(ctx: Context<Take>, amount: u64) -> Result<()> {
+        ctx.accounts.fill(amount, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn update_terms(ctx: Context<UpdateTerms>, receive: u64, expires_at: i64) -> Result<()> {
+        ctx.accounts.update_terms(receive, expires_at)
+    }
+
+    pub fn close_expired(ctx: Context<CloseExpired>) -> Result<()> {
+        ctx.accounts.refund_and_close_vault()
+    }
+
+    pub fn take_sol(ctx: Context<TakeSol>, amount: u64) -> Result<()> {
+        ctx.accounts.fill(amount, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    /// Fills at a price the maker signed off-chain. The transaction must verify the
+    /// maker's signature over `quote` with an ed25519 instruction right before this one.
+    pub fn take_quote(ctx: Context<TakeQuote>, quote: Quote) -> Result<()> {
+        ctx.accounts.fill(quote, &ctx.bumps)
+    }
+    /// Restricts who may take the escrow. Takes then pass the accounts proving the
+    /// taker qualifies after their own, e.g. the taker's NFT and its metadata, or
+    /// the taker's attestation.
+    pub fn set_gate(ctx: Context<SetGate>, gate: TakerGate) -> Result<()> {
+        ctx.accounts.set_gate(gate)
+    }
+    /// Lets `operator` refund the escrow on the maker's behalf, or nobody when it is
+    /// `Pubkey::default()`. The deposit and rent still only ever go to the maker.
+    pub fn set_operator(ctx: Context<SetOperator>, operator: Pubkey) -> Result<()> {
+        ctx.accounts.set_operator(operator)
+    }
+
+    pub fn operator_refund(ctx: Context<OperatorRefund>) -> Result<()> {
+        ctx.accounts.refund_and_close_vault()
+    }
+
+    /// Makes every take, partial or quoted, fill the whole remaining deposit or fail.
+    pub fn set_fill_or_kill(ctx: Context<SetFillOrKill>, fill_or_kill: bool) -> Result<()> {
+        ctx.accounts.set_fill_or_kill(fill_or_kill)
+    }
+
+    /// Holds an open escrow for `taker`, every other taker is rejected until `release`
+    pub fn reserve(ctx: Context<Reserve>, taker: Pubkey) -> Result<()> {
+        ctx.accounts.reserve(taker)
+    }
+
+    pub fn release(ctx: Context<Reserve>) -> Result<()> {
+        ctx.accounts.release()
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct CnftEscrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub asset_id: Pubkey,
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    pub bump: u8,
+    // Bubblegum tree config bump, stored so take/refund don't re-derive it
+    pub tree_config_bump: u8,
+}
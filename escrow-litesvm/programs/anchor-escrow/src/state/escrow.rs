@@ -1,5 +1,37 @@
 use anchor_lang::prelude::*;
 
+use crate::error::EscrowError;
+
+/// Status of a live escrow. Filling or refunding one closes the account in the same
+/// instruction, so there is no terminal status to store: how it ended is in its events.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EscrowStatus {
+    Open,
+    /// Only `reserved_for` may take it until the maker releases it
+    Reserved,
+}
+
+impl EscrowStatus {
+    // The maker reserves an open escrow and releases it back to open
+    pub fn can_transition_to(self, next: EscrowStatus) -> bool {
+        use EscrowStatus::*;
+
+        matches!((self, next), (Open, Reserved) | (Reserved, Open))
+    }
+}
+
+/// Who may take an escrow besides paying its price. Takes of a gated escrow pass the
+/// accounts proving the taker qualifies as remaining accounts, see `crate::gate`.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TakerGate {
+    None,
+    /// The taker must hold an NFT verified as part of the collection with this mint
+    Collection { mint: Pubkey },
+    /// The taker must hold an unexpired Solana Attestation Service attestation of
+    /// `schema`, issued under the issuer's `credential`
+    Attestation { credential: Pubkey, schema: Pubkey },
+}
+
 #[account]
 #[derive(InitSpace, Debug)]
 pub struct Escrow {
@@ -7,6 +39,84 @@ pub struct Escrow {
     pub maker: Pubkey,
     pub mint_a: Pubkey,
     pub mint_b: Pubkey,
+    pub status: EscrowStatus,
     pub receive: u64,
+    // Amount of mint_a deposited at make, and how much of it has been filled so far.
+    // These are authoritative, unlike the vault balance which anyone can top up.
+    pub deposit_total: u64,
+    pub filled: u64,
+    pub remaining: u64,
+    pub created_at: i64,
+    // Unix timestamp after which the escrow can no longer be taken, 0 if it never expires
+    pub expires_at: i64,
+    // Unix timestamp before which the escrow cannot be taken yet, 0 if it can be right away
+    pub starts_at: i64,
     pub bump: u8,
+    pub gate: TakerGate,
+    // May refund the escrow on the maker's behalf, Pubkey::default() if nobody may
+    pub operator: Pubkey,
+    // Takes must fill the whole remaining deposit at once
+    pub fill_or_kill: bool,
+    // The only taker while the escrow is reserved, Pubkey::default() while it is open
+    pub reserved_for: Pubkey,
+    // How many escrows the maker had made before this one. Unlike the address, which a
+    // refunded escrow's seed hands on, or `created_at`, which two escrows made in the
+    // same second share, it tells every escrow of a maker apart.
+    pub nonce: u64,
+    // Free-form notes/tags appended after creation, the account is reallocated to fit
+    #[max_len(0)]
+    pub metadata: Vec<u8>,
+}
+
+impl Escrow {
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    pub fn has_started(&self, now: i64) -> bool {
+        now >= self.starts_at
+    }
+
+    /// Checks every take variant shares: the escrow is open to `taker` at `now`, and
+    /// `amount` is a fill of it the maker accepts
+    pub fn validate_fill(&self, taker: &Pubkey, amount: u64, now: i64) -> Result<()> {
+        require!(self.has_started(now), EscrowError::EscrowNotStarted);
+        require!(!self.is_expired(now), EscrowError::EscrowExpired);
+        require!(
+            self.status == EscrowStatus::Open || self.reserved_for == *taker,
+            EscrowError::ReservedForAnotherTaker
+        );
+        require!(amount > 0 && amount <= self.remaining, EscrowError::InvalidFillAmount);
+        require!(!self.fill_or_kill || amount == self.remaining, EscrowError::PartialFillNotAllowed);
+        Ok(())
+    }
+
+    /// Mint B owed for the first `filled` units of mint A, rounded up in the maker's favour
+    pub fn price_of(&self, filled: u64) -> Result<u64> {
+        let total = (filled as u128) * (self.receive as u128);
+        let deposit_total = self.deposit_total as u128;
+
+        let price = total
+            .checked_add(deposit_total.saturating_sub(1))
+            .and_then(|total| total.checked_div(deposit_total))
+            .ok_or(EscrowError::MathOverflow)?;
+
+        u64::try_from(price).map_err(|_| error!(EscrowError::MathOverflow))
+    }
+
+    /// Mint B a taker pays to fill `amount` more units of mint A. Quoting against the
+    /// cumulative fill means the partial payments always add up to exactly `receive`.
+    pub fn quote(&self, amount: u64) -> Result<u64> {
+        let filled = self.filled
+            .checked_add(amount)
+            .ok_or(EscrowError::MathOverflow)?;
+
+        Ok(self.price_of(filled)? - self.price_of(self.filled)?)
+    }
+
+    pub fn transition(&mut self, next: EscrowStatus) -> Result<()> {
+        require!(self.status.can_transition_to(next), EscrowError::InvalidStatusTransition);
+        self.status = next;
+        Ok(())
+    }
 }
\ No newline at end of file
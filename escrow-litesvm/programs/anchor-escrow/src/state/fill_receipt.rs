@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+// Survives the escrow being closed, so fills can be looked up without an indexer
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct FillReceipt {
+    pub escrow: Pubkey,
+    // The escrow's `nonce`. Its address is reused when the maker refunds and makes the
+    // same seed again, possibly in the same second, and that escrow's fills start a new receipt.
+    pub escrow_nonce: u64,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    // Totals across every fill of this escrow by this taker
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub fills: u32,
+    pub last_filled_at: i64,
+    pub bump: u8,
+}
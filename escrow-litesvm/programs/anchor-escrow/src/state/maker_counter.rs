@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+// Created with the maker's first escrow and never closed, so the count only grows
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct MakerCounter {
+    pub escrows_made: u64,
+    pub bump: u8,
+}
@@ -1,3 +1,11 @@
+pub mod cnft_escrow;
 pub mod escrow;
+pub mod fill_receipt;
+pub mod maker_counter;
+pub mod quote;
 
-pub use escrow::*;
\ No newline at end of file
+pub use cnft_escrow::*;
+pub use escrow::*;
+pub use fill_receipt::*;
+pub use maker_counter::*;
+pub use quote::*;
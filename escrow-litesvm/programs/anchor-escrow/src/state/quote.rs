@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::QUOTE_DOMAIN;
+
+/// A price for filling part of an escrow that the maker signs off-chain instead of
+/// sending `update_terms`. It names the taker, and is pinned to the escrow's `filled`
+/// amount so it stops being valid as soon as anything, itself included, fills the escrow.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Quote {
+    pub escrow: Pubkey,
+    /// The escrow's `nonce`. The address alone is reused when the maker refunds and
+    /// makes the same seed again, and the quote must not carry over to new terms.
+    pub nonce: u64,
+    pub taker: Pubkey,
+    /// Mint A the taker receives
+    pub amount: u64,
+    /// Mint B the taker pays for it
+    pub price: u64,
+    pub filled: u64,
+    pub expires_at: i64,
+}
+
+impl Quote {
+    /// The bytes the maker signs
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = QUOTE_DOMAIN.to_vec();
+        // Serializing into a Vec cannot fail
+        self.serialize(&mut message).unwrap();
+        message
+    }
+}
@@ -0,0 +1,133 @@
+use {
+    super::context::EscrowTestContext,
+    litesvm::types::TransactionResult,
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_message::Message,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    solana_transaction_error::TransactionError,
+    test_utils::clock::warp_slots,
+};
+
+// A blockhash is only accepted for this many slots after it was produced
+const BLOCKHASH_VALIDITY_SLOTS: u64 = 150;
+
+fn sign(ctx: &EscrowTestContext, instructions: &[Instruction], payer: &Keypair) -> Transaction {
+    let message = Message::new(instructions, Some(&payer.pubkey()));
+    Transaction::new(&[payer], message, ctx.program.latest_blockhash())
+}
+
+// LiteSVM only accepts its latest blockhash, so moving past the validity window
+// means warping the slot and producing a new one, as the cluster would
+fn outlive_blockhash(ctx: &mut EscrowTestContext) {
+    warp_slots(&mut ctx.program, BLOCKHASH_VALIDITY_SLOTS + 1);
+    ctx.program.expire_blockhash();
+}
+
+/// Sends a transaction signed by `sign_with`, re-signing against the latest blockhash
+/// whenever the cluster no longer knows the one it was built with. This mirrors what
+/// a client does on `BlockhashNotFound`, and returns the number of attempts taken.
+fn send_with_resubmission(
+    ctx: &mut EscrowTestContext,
+    mut transaction: Transaction,
+    sign_with: &Keypair,
+    max_attempts: usize,
+) -> (TransactionResult, usize) {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        match ctx.program.send_transaction(transaction.clone()) {
+            Err(failed) if failed.err == TransactionError::BlockhashNotFound && attempts < max_attempts => {
+                transaction.sign(&[sign_with], ctx.program.latest_blockhash());
+            }
+            result => return (result, attempts),
+        }
+    }
+}
+
+#[test]
+fn should_reject_take_signed_against_an_expired_blockhash() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let taker_lamports = ctx.lamports(&ctx.taker.pubkey());
+
+    let stale = sign(&ctx, &[ctx.take_ix()], &ctx.taker);
+    outlive_blockhash(&mut ctx);
+
+    let failed = ctx.program.send_transaction(stale).expect_err("A stale blockhash must be rejected");
+    assert_eq!(failed.err, TransactionError::BlockhashNotFound);
+
+    // Rejected before execution: nothing moved and no fee was charged
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+    assert_eq!(ctx.escrow_data().remaining, 10);
+    assert_eq!(ctx.lamports(&ctx.taker.pubkey()), taker_lamports);
+}
+
+#[test]
+fn should_succeed_when_rebuilt_with_a_fresh_blockhash() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let stale = sign(&ctx, &[ctx.take_ix()], &ctx.taker);
+    outlive_blockhash(&mut ctx);
+    assert!(ctx.program.send_transaction(stale).is_err());
+
+    let fresh = sign(&ctx, &[ctx.take_ix()], &ctx.taker);
+    ctx.program.send_transaction(fresh).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 10);
+    assert!(ctx.program.get_account(&ctx.escrow).is_none());
+}
+
+#[test]
+fn should_resubmit_after_blockhash_expiry_and_land_once() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).build();
+
+    let transaction = sign(&ctx, &[ctx.take_partial_ix(30)], &ctx.taker);
+    outlive_blockhash(&mut ctx);
+
+    let taker = ctx.taker.insecure_clone();
+    let (result, attempts) = send_with_resubmission(&mut ctx, transaction.clone(), &taker, 3);
+    result.unwrap();
+    assert_eq!(attempts, 2);
+    assert_eq!(ctx.escrow_data().filled, 30);
+
+    // Rebroadcasting the landed transaction is deduplicated, not filled twice
+    let rebroadcast = Transaction::new(&[&taker], transaction.message.clone(), ctx.program.latest_blockhash());
+    let failed = ctx.program.send_transaction(rebroadcast).expect_err("A landed transaction must not land twice");
+    assert_eq!(failed.err, TransactionError::AlreadyProcessed);
+    assert_eq!(ctx.escrow_data().filled, 30);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 30);
+}
+
+// Why a client whose send timed out must wait for that signature to confirm or its
+// blockhash to expire: the send may have landed, and a re-signed copy is a new
+// transaction that fills again
+#[test]
+fn should_fill_twice_when_a_landed_take_is_re_signed() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).build();
+
+    let transaction = sign(&ctx, &[ctx.take_partial_ix(30)], &ctx.taker);
+    ctx.program.send_transaction(transaction.clone()).unwrap();
+
+    ctx.program.expire_blockhash();
+    let re_signed = Transaction::new(&[&ctx.taker], transaction.message.clone(), ctx.program.latest_blockhash());
+    ctx.program.send_transaction(re_signed).unwrap();
+
+    assert_eq!(ctx.escrow_data().filled, 60);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 60);
+}
+
+#[test]
+fn should_give_up_when_the_blockhash_keeps_expiring() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let transaction = sign(&ctx, &[ctx.take_ix()], &ctx.taker);
+    outlive_blockhash(&mut ctx);
+
+    let taker = ctx.taker.insecure_clone();
+    let (result, attempts) = send_with_resubmission(&mut ctx, transaction, &taker, 1);
+
+    assert_eq!(result.unwrap_err().err, TransactionError::BlockhashNotFound);
+    assert_eq!(attempts, 1);
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+}
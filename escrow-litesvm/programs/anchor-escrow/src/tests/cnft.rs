@@ -0,0 +1,453 @@
+use {
+    super::{
+        context::{setup, PROGRAM_ID},
+        fixtures::load_program,
+    },
+    crate::{
+        bubblegum::{get_asset_id, LeafArgs, BUBBLEGUM_PROGRAM_ID, COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID},
+        constants::CNFT_ESCROW_SEED,
+        state::CnftEscrow,
+    },
+    anchor_lang::{
+        solana_program::{hash, keccak},
+        AccountDeserialize,
+        InstructionData,
+        ToAccountMetas,
+    },
+    anchor_spl::associated_token,
+    litesvm::{types::TransactionResult, LiteSVM},
+    litesvm_token::{spl_token::ID as TOKEN_PROGRAM_ID, CreateAssociatedTokenAccount, MintTo},
+    solana_instruction::{AccountMeta, Instruction},
+    solana_keypair::Keypair,
+    solana_message::Message,
+    solana_pubkey::Pubkey,
+    solana_sdk_ids::system_program::ID as SYSTEM_PROGRAM_ID,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    test_utils::{keys::keypair, send, token::{create_mint, token_amount}},
+};
+
+// The tests that run Bubblegum load it, spl-account-compression and spl-noop from
+// `fixtures/<address>.so`. The dumps are not committed, fetch them once from devnet with
+//
+// ```text
+// cargo test --features refresh-fixtures cnft -- --ignored
+// ```
+//
+// after which `cargo test cnft -- --ignored` runs them offline
+
+const SEED: u64 = 42;
+const RECEIVE: u64 = 25;
+
+// The smallest tree the compression program accepts. Every test mints a single cNFT,
+// the leaf at index 0, so its proof is always the empty sibling at each level.
+const MAX_DEPTH: usize = 3;
+const MAX_BUFFER_SIZE: usize = 8;
+
+// spl-account-compression's account layout: a header, then the sequence number, active
+// index and buffer size, one changelog per buffer slot and the rightmost proof
+const TREE_HEADER_LEN: usize = 56;
+const CHANGELOG_LEN: usize = 32 + 32 * MAX_DEPTH + 8;
+const MERKLE_TREE_LEN: usize = TREE_HEADER_LEN + 24 + MAX_BUFFER_SIZE * CHANGELOG_LEN + (32 * MAX_DEPTH + 32 + 8);
+
+fn bubblegum_discriminator(name: &str) -> Vec<u8> {
+    hash::hash(format!("global:{}", name).as_bytes()).to_bytes()[..8].to_vec()
+}
+
+// Borsh encoded Bubblegum `MetadataArgs`: a mutable, uncollected NFT without creators
+fn metadata_args() -> Vec<u8> {
+    let mut args = Vec::new();
+    for field in ["Escrowed", "ESC", "https://example.com/escrowed.json"] {
+        args.extend((field.len() as u32).to_le_bytes());
+        args.extend(field.as_bytes());
+    }
+    args.extend(0_u16.to_le_bytes()); // seller_fee_basis_points
+    args.push(0); // primary_sale_happened
+    args.push(1); // is_mutable
+    args.push(0); // edition_nonce: None
+    args.extend([1, 0]); // token_standard: Some(NonFungible)
+    args.push(0); // collection: None
+    args.push(0); // uses: None
+    args.push(0); // token_program_version: Original
+    args.extend(0_u32.to_le_bytes()); // creators: []
+    args
+}
+
+// Bubblegum hashes the metadata with its seller fee, and the creators one after another
+fn data_hash() -> [u8; 32] {
+    let metadata_hash = keccak::hash(&metadata_args());
+    keccak::hashv(&[metadata_hash.as_ref(), &0_u16.to_le_bytes()]).to_bytes()
+}
+
+fn creator_hash() -> [u8; 32] {
+    keccak::hashv(&[]).to_bytes()
+}
+
+fn empty_node(level: usize) -> [u8; 32] {
+    (0..level).fold([0; 32], |node, _| keccak::hashv(&[&node, &node]).to_bytes())
+}
+
+/// Off-chain copy of the tree, enough to prove the single cNFT the tests mint
+struct Tree {
+    address: Pubkey,
+    asset_id: Pubkey,
+}
+
+impl Tree {
+    // A V1 leaf commits to its owner and delegate, Bubblegum resets the delegate to the
+    // new owner on every transfer
+    fn leaf(&self, owner: &Pubkey) -> [u8; 32] {
+        keccak::hashv(&[
+            &[1],
+            self.asset_id.as_ref(),
+            owner.as_ref(),
+            owner.as_ref(),
+            &0_u64.to_le_bytes(),
+            &data_hash(),
+            &creator_hash(),
+        ])
+        .to_bytes()
+    }
+
+    // Index 0 is the left child at every level
+    fn root(&self, owner: &Pubkey) -> [u8; 32] {
+        (0..MAX_DEPTH).fold(self.leaf(owner), |node, level| keccak::hashv(&[&node, &empty_node(level)]).to_bytes())
+    }
+
+    fn proof(&self) -> Vec<AccountMeta> {
+        (0..MAX_DEPTH)
+            .map(|level| AccountMeta::new_readonly(Pubkey::new_from_array(empty_node(level)), false))
+            .collect()
+    }
+
+    fn config(&self) -> Pubkey {
+        Pubkey::find_program_address(&[self.address.as_ref()], &BUBBLEGUM_PROGRAM_ID).0
+    }
+}
+
+struct CnftTestContext {
+    program: LiteSVM,
+    maker: Keypair,
+    taker: Keypair,
+    mint_b: Pubkey,
+    taker_ata_b: Pubkey,
+    maker_ata_b: Pubkey,
+    escrow: Pubkey,
+    tree: Tree,
+}
+
+impl CnftTestContext {
+    /// A Bubblegum tree holding one cNFT owned by the maker, and the taker's mint B
+    fn new() -> Self {
+        let (mut program, maker, taker) = setup();
+
+        program.add_program(BUBBLEGUM_PROGRAM_ID, &load_program(&BUBBLEGUM_PROGRAM_ID.to_string()));
+        program.add_program(COMPRESSION_PROGRAM_ID, &load_program(&COMPRESSION_PROGRAM_ID.to_string()));
+        program.add_program(NOOP_PROGRAM_ID, &load_program(&NOOP_PROGRAM_ID.to_string()));
+
+        let merkle_tree = keypair("merkle_tree");
+        let tree = Tree {
+            address: merkle_tree.pubkey(),
+            asset_id: get_asset_id(&merkle_tree.pubkey(), 0),
+        };
+        create_tree(&mut program, &maker, &merkle_tree, &tree);
+        mint_cnft(&mut program, &maker, &tree);
+
+        let mint_b = create_mint(&mut program, &maker, &keypair("mint_b"), 6, None);
+        let taker_ata_b = CreateAssociatedTokenAccount::new(&mut program, &taker, &mint_b)
+            .owner(&taker.pubkey())
+            .send()
+            .unwrap();
+        MintTo::new(&mut program, &maker, &mint_b, &taker_ata_b, 1_000).send().unwrap();
+
+        let escrow = Pubkey::find_program_address(
+            &[CNFT_ESCROW_SEED, maker.pubkey().as_ref(), &SEED.to_le_bytes()],
+            &PROGRAM_ID,
+        ).0;
+
+        Self {
+            maker_ata_b: associated_token::get_associated_token_address(&maker.pubkey(), &mint_b),
+            program,
+            maker,
+            taker,
+            mint_b,
+            taker_ata_b,
+            escrow,
+            tree,
+        }
+    }
+
+    fn make_ix(&self) -> Instruction {
+        let mut accounts = crate::accounts::MakeCnft {
+            maker: self.maker.pubkey(),
+            mint_b: self.mint_b,
+            escrow: self.escrow,
+            tree_config: self.tree.config(),
+            merkle_tree: self.tree.address,
+            bubblegum_program: BUBBLEGUM_PROGRAM_ID,
+            compression_program: COMPRESSION_PROGRAM_ID,
+            log_wrapper: NOOP_PROGRAM_ID,
+            system_program: SYSTEM_PROGRAM_ID,
+        }.to_account_metas(None);
+        accounts.extend(self.tree.proof());
+
+        let leaf = LeafArgs {
+            root: self.tree.root(&self.maker.pubkey()),
+            data_hash: data_hash(),
+            creator_hash: creator_hash(),
+            nonce: 0,
+            index: 0,
+        };
+
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts,
+            data: crate::instruction::MakeCnft { seed: SEED, receive: RECEIVE, leaf }.data(),
+        }
+    }
+
+    fn take_ix(&self, proof: Vec<AccountMeta>) -> Instruction {
+        let mut accounts = crate::accounts::TakeCnft {
+            taker: self.taker.pubkey(),
+            maker: self.maker.pubkey(),
+            mint_b: self.mint_b,
+            taker_ata_b: self.taker_ata_b,
+            maker_ata_b: self.maker_ata_b,
+            escrow: self.escrow,
+            tree_config: self.tree.config(),
+            merkle_tree: self.tree.address,
+            bubblegum_program: BUBBLEGUM_PROGRAM_ID,
+            compression_program: COMPRESSION_PROGRAM_ID,
+            log_wrapper: NOOP_PROGRAM_ID,
+            associated_token_program: associated_token::ID,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: SYSTEM_PROGRAM_ID,
+        }.to_account_metas(None);
+        accounts.extend(proof);
+
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts,
+            data: crate::instruction::TakeCnft { root: self.tree.root(&self.escrow) }.data(),
+        }
+    }
+
+    fn refund_ix(&self) -> Instruction {
+        let mut accounts = crate::accounts::RefundCnft {
+            maker: self.maker.pubkey(),
+            escrow: self.escrow,
+            tree_config: self.tree.config(),
+            merkle_tree: self.tree.address,
+            bubblegum_program: BUBBLEGUM_PROGRAM_ID,
+            compression_program: COMPRESSION_PROGRAM_ID,
+            log_wrapper: NOOP_PROGRAM_ID,
+            system_program: SYSTEM_PROGRAM_ID,
+        }.to_account_metas(None);
+        accounts.extend(self.tree.proof());
+
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts,
+            data: crate::instruction::RefundCnft { root: self.tree.root(&self.escrow) }.data(),
+        }
+    }
+
+    fn make(&mut self) -> TransactionResult {
+        let ix = self.make_ix();
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    fn take(&mut self) -> TransactionResult {
+        let ix = self.take_ix(self.tree.proof());
+        send(&mut self.program, &[ix], &self.taker)
+    }
+
+    fn refund(&mut self) -> TransactionResult {
+        let ix = self.refund_ix();
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    fn escrow_data(&self) -> CnftEscrow {
+        let account = self.program.get_account(&self.escrow).expect("Escrow account not found");
+        CnftEscrow::try_deserialize(&mut account.data.as_ref()).unwrap()
+    }
+
+    /// The root of the tree's latest changelog, which only matches `Tree::root` for the
+    /// current owner of the cNFT
+    fn onchain_root(&self) -> [u8; 32] {
+        let account = self.program.get_account(&self.tree.address).expect("Merkle tree not found");
+        let tree = &account.data[TREE_HEADER_LEN..];
+        let active_index = u64::from_le_bytes(tree[8..16].try_into().unwrap()) as usize;
+        let changelog = 24 + active_index * CHANGELOG_LEN;
+        tree[changelog..changelog + 32].try_into().unwrap()
+    }
+
+    fn token_amount(&self, address: &Pubkey) -> u64 {
+        token_amount(&self.program, address)
+    }
+
+    /// Lamports held by an account, 0 if it does not exist
+    fn lamports(&self, address: &Pubkey) -> u64 {
+        self.program.get_account(address).map(|a| a.lamports).unwrap_or(0)
+    }
+}
+
+// Allocates the tree account for the compression program and has Bubblegum initialize
+// it, with the maker as tree creator
+fn create_tree(program: &mut LiteSVM, maker: &Keypair, merkle_tree: &Keypair, tree: &Tree) {
+    let lamports = program.minimum_balance_for_rent_exemption(MERKLE_TREE_LEN);
+
+    let mut data = bubblegum_discriminator("create_tree");
+    data.extend((MAX_DEPTH as u32).to_le_bytes());
+    data.extend((MAX_BUFFER_SIZE as u32).to_le_bytes());
+    data.push(0); // public: None
+
+    let instructions = [
+        solana_system_interface::instruction::create_account(
+            &maker.pubkey(),
+            &merkle_tree.pubkey(),
+            lamports,
+            MERKLE_TREE_LEN as u64,
+            &COMPRESSION_PROGRAM_ID,
+        ),
+        Instruction {
+            program_id: BUBBLEGUM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(tree.config(), false),
+                AccountMeta::new(tree.address, false),
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new_readonly(maker.pubkey(), true),
+                AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+                AccountMeta::new_readonly(COMPRESSION_PROGRAM_ID, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ],
+            data,
+        },
+    ];
+
+    let message = Message::new(&instructions, Some(&maker.pubkey()));
+    let transaction = Transaction::new(&[maker, merkle_tree], message, program.latest_blockhash());
+    program.send_transaction(transaction).expect("Failed to create merkle tree");
+}
+
+// Mints the tree's first cNFT, nonce and index 0, to the maker
+fn mint_cnft(program: &mut LiteSVM, maker: &Keypair, tree: &Tree) {
+    let mut data = bubblegum_discriminator("mint_v1");
+    data.extend(metadata_args());
+
+    let ix = Instruction {
+        program_id: BUBBLEGUM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tree.config(), false),
+            AccountMeta::new_readonly(maker.pubkey(), false),
+            AccountMeta::new_readonly(maker.pubkey(), false),
+            AccountMeta::new(tree.address, false),
+            AccountMeta::new(maker.pubkey(), true),
+            AccountMeta::new_readonly(maker.pubkey(), true),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+            AccountMeta::new_readonly(COMPRESSION_PROGRAM_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data,
+    };
+
+    send(program, &[ix], maker).expect("Failed to mint cNFT");
+}
+
+#[test]
+fn should_encode_bubblegum_transfer_data() {
+    use crate::bubblegum::transfer_data;
+
+    let leaf = LeafArgs {
+        root: [1; 32],
+        data_hash: [2; 32],
+        creator_hash: [3; 32],
+        nonce: 7,
+        index: 7,
+    };
+
+    // discriminator + root + data_hash + creator_hash + nonce + index
+    let data = transfer_data(&leaf);
+    assert_eq!(data.len(), 8 + 32 * 3 + 8 + 4);
+    assert_eq!(&data[..8], &[163, 52, 200, 231, 140, 3, 69, 186]);
+    assert_eq!(&data[8..40], &leaf.root);
+    assert_eq!(&data[40..72], &leaf.data_hash);
+    assert_eq!(&data[72..104], &leaf.creator_hash);
+    assert_eq!(&data[104..112], &7_u64.to_le_bytes());
+    assert_eq!(&data[112..], &7_u32.to_le_bytes());
+
+    let merkle_tree = keypair("merkle_tree").pubkey();
+    let expected = Pubkey::find_program_address(
+        &[b"asset", merkle_tree.as_ref(), &7_u64.to_le_bytes()],
+        &BUBBLEGUM_PROGRAM_ID,
+    ).0;
+    assert_eq!(get_asset_id(&merkle_tree, 7), expected);
+}
+
+#[test]
+#[ignore = "needs the Bubblegum program fixtures"]
+fn should_make_cnft_escrow_and_move_the_cnft_to_the_escrow() {
+    let mut ctx = CnftTestContext::new();
+    assert_eq!(ctx.onchain_root(), ctx.tree.root(&ctx.maker.pubkey()));
+
+    ctx.make().unwrap();
+
+    let escrow = ctx.escrow_data();
+    assert_eq!(escrow.maker, ctx.maker.pubkey());
+    assert_eq!(escrow.merkle_tree, ctx.tree.address);
+    assert_eq!(escrow.asset_id, ctx.tree.asset_id);
+    assert_eq!(escrow.data_hash, data_hash());
+    assert_eq!(escrow.creator_hash, creator_hash());
+    assert_eq!(escrow.receive, RECEIVE);
+    assert_eq!(escrow.tree_config_bump, Pubkey::find_program_address(&[ctx.tree.address.as_ref()], &BUBBLEGUM_PROGRAM_ID).1);
+    assert_eq!(ctx.onchain_root(), ctx.tree.root(&ctx.escrow));
+}
+
+#[test]
+#[ignore = "needs the Bubblegum program fixtures"]
+fn should_take_cnft_escrow_and_pay_the_maker() {
+    let mut ctx = CnftTestContext::new();
+    ctx.make().unwrap();
+
+    ctx.take().unwrap();
+
+    assert_eq!(ctx.onchain_root(), ctx.tree.root(&ctx.taker.pubkey()));
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), RECEIVE);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_b), 1_000 - RECEIVE);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0);
+}
+
+#[test]
+#[ignore = "needs the Bubblegum program fixtures"]
+fn should_refund_cnft_escrow_to_the_maker() {
+    let mut ctx = CnftTestContext::new();
+    ctx.make().unwrap();
+
+    ctx.refund().unwrap();
+
+    assert_eq!(ctx.onchain_root(), ctx.tree.root(&ctx.maker.pubkey()));
+    assert_eq!(ctx.lamports(&ctx.escrow), 0);
+}
+
+#[test]
+#[ignore = "needs the Bubblegum program fixtures"]
+fn should_reject_take_cnft_with_a_wrong_proof() {
+    let mut ctx = CnftTestContext::new();
+    ctx.make().unwrap();
+
+    let mut proof = ctx.tree.proof();
+    proof[1] = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+    let ix = ctx.take_ix(proof);
+
+    // The compression program fails to hash the leaf up to the root, and the payment
+    // to the maker is rolled back with it
+    assert!(send(&mut ctx.program, &[ix], &ctx.taker).is_err());
+    assert_eq!(ctx.onchain_root(), ctx.tree.root(&ctx.escrow));
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_b), 1_000);
+    assert_eq!(ctx.escrow_data().asset_id, ctx.tree.asset_id);
+
+    // The same take with the right proof goes through
+    ctx.program.expire_blockhash();
+    ctx.take().unwrap();
+    assert_eq!(ctx.onchain_root(), ctx.tree.root(&ctx.taker.pubkey()));
+}
@@ -0,0 +1,117 @@
+use {
+    super::context::EscrowTestContext,
+    solana_native_token::LAMPORTS_PER_SOL,
+    test_utils::{compute::ComputeReport, send},
+};
+
+// Committed compute budgets per instruction. When a change legitimately costs more,
+// raise the budget in the same commit so the increase is reviewed. Budgets that lock in
+// an optimization are the handler's measured cost rather than a ceiling, re-measure them
+// with `cargo test compute -- --nocapture` when the handler changes.
+// Make also creates the maker's counter the first time
+const MAKE_BUDGET: u64 = 60_000;
+const TAKE_BUDGET: u64 = 86_240;
+// A fill into accounts that already exist, the steady state of a partially filled escrow
+const TAKE_PARTIAL_BUDGET: u64 = 55_020;
+const TAKE_UNWRAP_BUDGET: u64 = 105_790;
+// Settling in SOL skips the maker's ATA and the wSOL round trip, it must stay well below both
+const TAKE_SOL_BUDGET: u64 = 60_000;
+const REFUND_BUDGET: u64 = 40_000;
+
+// Allowed overshoot before a run counts as a regression. LiteSVM is deterministic, so
+// this only absorbs toolchain drift.
+const TOLERANCE_CU: u64 = 100;
+
+fn assert_within_budget(name: &str, consumed: u64, budget: u64) {
+    let limit = budget + TOLERANCE_CU;
+    println!("{} consumed {} compute units (budget {}, limit {})", name, consumed, budget, limit);
+    assert!(consumed <= limit, "{} regressed: {} CU exceeds the {} CU budget by more than {} CU", name, consumed, budget, TOLERANCE_CU);
+}
+
+// For budgets that lock in an optimization, such as Take reading only the decimals of its
+// mints. A budget left above the measured cost would let that optimization be reverted
+// without failing, so the cost must also stay within the tolerance below it.
+fn assert_pinned_to_budget(name: &str, consumed: u64, budget: u64) {
+    assert_within_budget(name, consumed, budget);
+    assert!(consumed + TOLERANCE_CU >= budget, "{} budget is stale: {} CU is more than {} CU below the {} CU budget, lower it", name, consumed, TOLERANCE_CU, budget);
+}
+
+#[test]
+fn should_keep_make_within_compute_budget() {
+    let mut ctx = EscrowTestContext::builder().without_make().build();
+
+    let tx_result = ctx.make().unwrap();
+
+    assert_within_budget("Make", tx_result.compute_units_consumed, MAKE_BUDGET);
+}
+
+#[test]
+fn should_keep_take_within_compute_budget() {
+    // The first take pays for creating both destination ATAs and the receipt,
+    // which is the most expensive path
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let tx_result = ctx.take().unwrap();
+
+    assert_pinned_to_budget("Take", tx_result.compute_units_consumed, TAKE_BUDGET);
+}
+
+#[test]
+fn should_keep_take_with_unwrap_within_compute_budget() {
+    let mut ctx = EscrowTestContext::builder()
+        .with_native_mint_b()
+        .with_receive(LAMPORTS_PER_SOL)
+        .build();
+
+    let take_ix = ctx.take_unwrap_ix();
+    let tx_result = send(&mut ctx.program, &[take_ix], &ctx.taker).unwrap();
+
+    assert_pinned_to_budget("Take with unwrap", tx_result.compute_units_consumed, TAKE_UNWRAP_BUDGET);
+}
+
+#[test]
+fn should_keep_take_sol_within_compute_budget() {
+    let mut ctx = EscrowTestContext::builder()
+        .with_native_mint_b()
+        .with_receive(LAMPORTS_PER_SOL)
+        .build();
+    ctx.create_taker_ata_a();
+
+    let take_ix = ctx.take_sol_ix(ctx.deposit);
+    let tx_result = send(&mut ctx.program, &[take_ix], &ctx.taker).unwrap();
+
+    assert_within_budget("Take in SOL", tx_result.compute_units_consumed, TAKE_SOL_BUDGET);
+    assert!(TAKE_SOL_BUDGET < TAKE_UNWRAP_BUDGET);
+}
+
+#[test]
+fn should_keep_refund_within_compute_budget() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let tx_result = ctx.refund().unwrap();
+
+    assert_within_budget("Refund", tx_result.compute_units_consumed, REFUND_BUDGET);
+}
+
+#[test]
+fn should_report_compute_per_handler_over_a_partial_fill_lifecycle() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).without_make().build();
+    let mut report = ComputeReport::new("Escrow lifecycle");
+
+    let ix = ctx.make_ix();
+    report.send("make", &mut ctx.program, &[ix], &ctx.maker).unwrap();
+
+    let ix = ctx.update_terms_ix(200, 0);
+    report.send("update_terms", &mut ctx.program, &[ix], &ctx.maker).unwrap();
+
+    let ix = ctx.take_partial_ix(40);
+    report.send("take_partial (first fill)", &mut ctx.program, &[ix], &ctx.taker).unwrap();
+
+    let ix = ctx.take_partial_ix(20);
+    report.send("take_partial", &mut ctx.program, &[ix], &ctx.taker).unwrap();
+
+    let ix = ctx.take_ix();
+    report.send("take (closes)", &mut ctx.program, &[ix], &ctx.taker).unwrap();
+
+    assert_pinned_to_budget("Take partial", report.compute_units("take_partial"), TAKE_PARTIAL_BUDGET);
+}
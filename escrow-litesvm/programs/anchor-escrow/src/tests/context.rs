@@ -0,0 +1,691 @@
+use {
+    super::fixtures::load_account,
+    anchor_lang::{
+        prelude::msg,
+        solana_program::{clock::Clock, program_pack::Pack},
+        InstructionData,
+        ToAccountMetas
+    },
+    anchor_spl::{
+        associated_token::{
+            self,
+            spl_associated_token_account
+        },
+        token::spl_token::{
+            self,
+            native_mint::ID as NATIVE_MINT
+        }
+    },
+    litesvm::{
+        types::TransactionResult,
+        LiteSVM
+    },
+    litesvm_token::{
+        spl_token::ID as TOKEN_PROGRAM_ID,
+        CreateAssociatedTokenAccount,
+        MintTo
+    },
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_sdk_ids::system_program::ID as SYSTEM_PROGRAM_ID,
+    solana_signer::Signer,
+    test_utils::{account, artifact, keys, send, token::create_mint}
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+// Setup function to initialize LiteSVM and create a payer keypair
+pub fn setup() -> (LiteSVM, Keypair, Keypair) {
+    // Initialize LiteSVM and payer
+    let mut program = LiteSVM::new();
+    let payer = keys::keypair("maker");
+    let taker = keys::keypair("taker");
+
+    // Shown with the output of a failing test, rerun with it to get the same keys
+    println!("TEST_SEED={}", keys::seed());
+
+    // Load program SO file
+    program.add_program(PROGRAM_ID, &artifact::program_bytes("anchor_escrow", env!("CARGO_MANIFEST_DIR")));
+
+    // Example on how to load an account from devnet. LiteSVM has no network access,
+    // so the account is read from a committed fixture (see `fixtures::load_account`)
+    let fetched_account = load_account("DRYvf71cbF2s5wgaJQvAGkghMkRcp5arvsK2w97vXhi2");
+    let lamports = fetched_account.lamports;
+
+    // Set the fetched account in the LiteSVM environment
+    // This allows us to simulate interactions with this account during testing
+    program.set_account(payer.pubkey(), fetched_account).unwrap();
+
+    msg!("Lamports of fetched account: {}", lamports);
+
+    // Airdrop some SOL to the payer keypair
+    program
+        .airdrop(&payer.pubkey(), 100 * LAMPORTS_PER_SOL)
+        .expect("Failed to airdrop SOL to payer");
+
+    program
+        .airdrop(&taker.pubkey(), 100 * LAMPORTS_PER_SOL)
+        .expect("Failed to airdrop SOL to taker");
+
+    // Return the LiteSVM instance and payer keypair
+    (program, payer, taker)
+}
+
+pub fn escrow_address(maker: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[crate::constants::ESCROW_SEED, maker.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID
+    ).0
+}
+
+pub fn receipt_address(escrow: &Pubkey, taker: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[crate::constants::RECEIPT_SEED, escrow.as_ref(), taker.as_ref()],
+        &PROGRAM_ID
+    ).0
+}
+
+pub fn maker_counter_address(maker: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[crate::constants::MAKER_COUNTER_SEED, maker.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn wsol_address(escrow: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[crate::constants::WSOL_SEED, escrow.as_ref()], &PROGRAM_ID).0
+}
+
+/// Everything a test needs after an escrow has been made: the SVM, both parties,
+/// the mints and every derived account the instructions take.
+pub struct EscrowTestContext {
+    pub program: LiteSVM,
+    pub maker: Keypair,
+    pub taker: Keypair,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub maker_ata_a: Pubkey,
+    pub maker_ata_b: Pubkey,
+    pub taker_ata_a: Pubkey,
+    pub taker_ata_b: Pubkey,
+    pub escrow: Pubkey,
+    pub vault: Pubkey,
+    pub receipt: Pubkey,
+    pub seed: u64,
+    pub deposit: u64,
+    pub receive: u64,
+    pub expires_at: i64,
+    pub starts_at: i64,
+}
+
+pub struct EscrowTestContextBuilder {
+    decimals_a: u8,
+    decimals_b: u8,
+    deposit: u64,
+    receive: u64,
+    seed: u64,
+    expires_in: Option<i64>,
+    starts_in: Option<i64>,
+    native_mint_b: bool,
+    freeze_authority: bool,
+    make: bool,
+}
+
+impl Default for EscrowTestContextBuilder {
+    fn default() -> Self {
+        Self {
+            decimals_a: 6,
+            decimals_b: 6,
+            deposit: 10,
+            receive: 10,
+            seed: 123,
+            expires_in: None,
+            starts_in: None,
+            native_mint_b: false,
+            freeze_authority: false,
+            make: true,
+        }
+    }
+}
+
+impl EscrowTestContextBuilder {
+    pub fn with_decimals(mut self, decimals_a: u8, decimals_b: u8) -> Self {
+        self.decimals_a = decimals_a;
+        self.decimals_b = decimals_b;
+        self
+    }
+
+    pub fn with_deposit(mut self, deposit: u64) -> Self {
+        self.deposit = deposit;
+        self
+    }
+
+    pub fn with_receive(mut self, receive: u64) -> Self {
+        self.receive = receive;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Expire the escrow `seconds` after the current cluster time
+    pub fn with_expiry(mut self, seconds: i64) -> Self {
+        self.expires_in = Some(seconds);
+        self
+    }
+
+    /// Open the escrow to takers `seconds` after the current cluster time
+    pub fn with_start(mut self, seconds: i64) -> Self {
+        self.starts_in = Some(seconds);
+        self
+    }
+
+    /// Lock the escrow against takes for `seconds`, the same as `with_start`
+    pub fn with_timelock(self, seconds: i64) -> Self {
+        self.with_start(seconds)
+    }
+
+    /// Use wrapped SOL as mint B and fund the taker with wSOL instead of minted tokens
+    pub fn with_native_mint_b(mut self) -> Self {
+        self.native_mint_b = true;
+        self
+    }
+
+    /// Make the maker the freeze authority of both mints, see `freeze`
+    pub fn with_freeze_authority(mut self) -> Self {
+        self.freeze_authority = true;
+        self
+    }
+
+    /// Set up all accounts but leave sending the make transaction to the test
+    pub fn without_make(mut self) -> Self {
+        self.make = false;
+        self
+    }
+
+    pub fn build(self) -> EscrowTestContext {
+        let (mut program, maker, taker) = setup();
+        let freeze_authority = self.freeze_authority.then(|| maker.pubkey());
+
+        let mint_a = create_mint(&mut program, &maker, &keys::keypair("mint_a"), self.decimals_a, freeze_authority.as_ref());
+
+        let mint_b = if self.native_mint_b {
+            NATIVE_MINT
+        } else {
+            create_mint(&mut program, &maker, &keys::keypair("mint_b"), self.decimals_b, freeze_authority.as_ref())
+        };
+
+        let maker_ata_a = CreateAssociatedTokenAccount::new(&mut program, &maker, &mint_a)
+            .owner(&maker.pubkey())
+            .send()
+            .unwrap();
+
+        MintTo::new(&mut program, &maker, &mint_a, &maker_ata_a, self.deposit.max(10_u64.pow(9)))
+            .send()
+            .unwrap();
+
+        let taker_ata_b = CreateAssociatedTokenAccount::new(&mut program, &taker, &mint_b)
+            .owner(&taker.pubkey())
+            .send()
+            .unwrap();
+
+        if self.native_mint_b {
+            // Wrap SOL: fund the token account and sync its token amount
+            program
+                .airdrop(&taker_ata_b, 2 * self.receive)
+                .expect("Failed to fund taker wSOL account");
+
+            let sync_ix = spl_token::instruction::sync_native(&TOKEN_PROGRAM_ID, &taker_ata_b).unwrap();
+            send(&mut program, &[sync_ix], &taker).unwrap();
+        } else {
+            MintTo::new(&mut program, &maker, &mint_b, &taker_ata_b, self.receive.max(10_u64.pow(9)))
+                .send()
+                .unwrap();
+        }
+
+        // Take creates the destination ATAs itself, so only derive their addresses.
+        // The `strict-atas` build requires them to exist beforehand.
+        let taker_ata_a = associated_token::get_associated_token_address(&taker.pubkey(), &mint_a);
+        let maker_ata_b = associated_token::get_associated_token_address(&maker.pubkey(), &mint_b);
+
+        #[cfg(feature = "strict-atas")]
+        {
+            CreateAssociatedTokenAccount::new(&mut program, &maker, &mint_a)
+                .owner(&taker.pubkey())
+                .send()
+                .unwrap();
+            if !self.native_mint_b {
+                CreateAssociatedTokenAccount::new(&mut program, &maker, &mint_b)
+                    .owner(&maker.pubkey())
+                    .send()
+                    .unwrap();
+            }
+        }
+
+        let escrow = escrow_address(&maker.pubkey(), self.seed);
+        let vault = associated_token::get_associated_token_address(&escrow, &mint_a);
+        let receipt = receipt_address(&escrow, &taker.pubkey());
+
+        let now = program.get_sysvar::<Clock>().unix_timestamp;
+        let expires_at = self.expires_in.map(|seconds| now + seconds).unwrap_or(0);
+        let starts_at = self.starts_in.map(|seconds| now + seconds).unwrap_or(0);
+
+        let mut ctx = EscrowTestContext {
+            program,
+            maker,
+            taker,
+            mint_a,
+            mint_b,
+            maker_ata_a,
+            maker_ata_b,
+            taker_ata_a,
+            taker_ata_b,
+            escrow,
+            vault,
+            receipt,
+            seed: self.seed,
+            deposit: self.deposit,
+            receive: self.receive,
+            expires_at,
+            starts_at,
+        };
+
+        if self.make {
+            ctx.make().expect("Make transaction failed");
+        }
+
+        ctx
+    }
+}
+
+impl EscrowTestContext {
+    pub fn builder() -> EscrowTestContextBuilder {
+        EscrowTestContextBuilder::default()
+    }
+
+    pub fn make_ix(&self) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Make {
+                maker: self.maker.pubkey(),
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                maker_ata_a: self.maker_ata_a,
+                escrow: self.escrow,
+                vault: self.vault,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+                maker_counter: maker_counter_address(&self.maker.pubkey()),
+            }.to_account_metas(None),
+            data: crate::instruction::Make {
+                seed: self.seed,
+                deposit: self.deposit,
+                receive: self.receive,
+                expires_at: self.expires_at,
+                starts_at: self.starts_at,
+            }.data(),
+        }
+    }
+
+    pub fn take_ix(&self) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Take {
+                taker: self.taker.pubkey(),
+                maker: self.maker.pubkey(),
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                taker_ata_a: self.taker_ata_a,
+                taker_ata_b: self.taker_ata_b,
+                maker_ata_b: Some(self.maker_ata_b),
+                maker_wsol: None,
+                escrow: self.escrow,
+                vault: self.vault,
+                receipt: self.receipt,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+            }.to_account_metas(None),
+            data: crate::instruction::Take {}.data(),
+        }
+    }
+
+    /// Take that unwraps the maker's wSOL proceeds instead of paying into maker_ata_b
+    pub fn take_unwrap_ix(&self) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Take {
+                taker: self.taker.pubkey(),
+                maker: self.maker.pubkey(),
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                taker_ata_a: self.taker_ata_a,
+                taker_ata_b: self.taker_ata_b,
+                maker_ata_b: None,
+                maker_wsol: Some(wsol_address(&self.escrow)),
+                escrow: self.escrow,
+                vault: self.vault,
+                receipt: self.receipt,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+            }.to_account_metas(None),
+            data: crate::instruction::Take {}.data(),
+        }
+    }
+
+    pub fn take_partial_ix(&self, amount: u64) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Take {
+                taker: self.taker.pubkey(),
+                maker: self.maker.pubkey(),
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                taker_ata_a: self.taker_ata_a,
+                taker_ata_b: self.taker_ata_b,
+                maker_ata_b: Some(self.maker_ata_b),
+                maker_wsol: None,
+                escrow: self.escrow,
+                vault: self.vault,
+                receipt: self.receipt,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+            }.to_account_metas(None),
+            data: crate::instruction::TakePartial { amount }.data(),
+        }
+    }
+
+    /// `take_sol` of `amount`, only valid when mint B is the native mint
+    pub fn take_sol_ix(&self, amount: u64) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::TakeSol {
+                taker: self.taker.pubkey(),
+                maker: self.maker.pubkey(),
+                mint_a: self.mint_a,
+                taker_ata_a: self.taker_ata_a,
+                escrow: self.escrow,
+                vault: self.vault,
+                receipt: self.receipt,
+                token_program: TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+            }.to_account_metas(None),
+            data: crate::instruction::TakeSol { amount }.data(),
+        }
+    }
+
+    /// `take_quote` of `quote`, which needs `quote_signature_ix` right before it
+    pub fn take_quote_ix(&self, quote: crate::state::Quote) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::TakeQuote {
+                taker: self.taker.pubkey(),
+                maker: self.maker.pubkey(),
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                taker_ata_a: self.taker_ata_a,
+                taker_ata_b: self.taker_ata_b,
+                maker_ata_b: self.maker_ata_b,
+                escrow: self.escrow,
+                vault: self.vault,
+                receipt: self.receipt,
+                instructions: anchor_lang::solana_program::sysvar::instructions::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+            }.to_account_metas(None),
+            data: crate::instruction::TakeQuote { quote }.data(),
+        }
+    }
+
+    /// The ed25519 precompile instruction verifying `signer`'s signature over `quote`
+    pub fn quote_signature_ix(&self, quote: &crate::state::Quote, signer: &Keypair) -> Instruction {
+        let message = quote.message();
+        let signature = <[u8; 64]>::from(signer.sign_message(&message));
+        solana_ed25519_program::new_ed25519_instruction_with_signature(&message, &signature, &signer.pubkey().to_bytes())
+    }
+
+    /// A quote from the maker to the taker against the escrow as it stands, valid for an hour
+    pub fn quote(&self, amount: u64, price: u64) -> crate::state::Quote {
+        let escrow = self.escrow_data();
+        crate::state::Quote {
+            escrow: self.escrow,
+            nonce: escrow.nonce,
+            taker: self.taker.pubkey(),
+            amount,
+            price,
+            filled: escrow.filled,
+            expires_at: self.now() + 3600,
+        }
+    }
+
+    pub fn update_terms_ix(&self, receive: u64, expires_at: i64) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::UpdateTerms {
+                maker: self.maker.pubkey(),
+                escrow: self.escrow,
+            }.to_account_metas(None),
+            data: crate::instruction::UpdateTerms { receive, expires_at }.data(),
+        }
+    }
+
+    pub fn set_gate_ix(&self, gate: crate::state::TakerGate) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::SetGate {
+                maker: self.maker.pubkey(),
+                escrow: self.escrow,
+            }.to_account_metas(None),
+            data: crate::instruction::SetGate { gate }.data(),
+        }
+    }
+
+    pub fn set_fill_or_kill_ix(&self, fill_or_kill: bool) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::SetFillOrKill {
+                maker: self.maker.pubkey(),
+                escrow: self.escrow,
+            }.to_account_metas(None),
+            data: crate::instruction::SetFillOrKill { fill_or_kill }.data(),
+        }
+    }
+
+    pub fn set_operator_ix(&self, operator: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::SetOperator {
+                maker: self.maker.pubkey(),
+                escrow: self.escrow,
+            }.to_account_metas(None),
+            data: crate::instruction::SetOperator { operator: *operator }.data(),
+        }
+    }
+
+    pub fn reserve_ix(&self, taker: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Reserve {
+                maker: self.maker.pubkey(),
+                escrow: self.escrow,
+            }.to_account_metas(None),
+            data: crate::instruction::Reserve { taker: *taker }.data(),
+        }
+    }
+
+    pub fn release_ix(&self) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Reserve {
+                maker: self.maker.pubkey(),
+                escrow: self.escrow,
+            }.to_account_metas(None),
+            data: crate::instruction::Release {}.data(),
+        }
+    }
+
+    pub fn operator_refund_ix(&self, operator: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::OperatorRefund {
+                operator: *operator,
+                maker: self.maker.pubkey(),
+                mint_a: self.mint_a,
+                maker_ata_a: self.maker_ata_a,
+                escrow: self.escrow,
+                vault: self.vault,
+                token_program: TOKEN_PROGRAM_ID,
+            }.to_account_metas(None),
+            data: crate::instruction::OperatorRefund {}.data(),
+        }
+    }
+
+    pub fn close_expired_ix(&self, cranker: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::CloseExpired {
+                cranker: *cranker,
+                maker: self.maker.pubkey(),
+                mint_a: self.mint_a,
+                maker_ata_a: self.maker_ata_a,
+                escrow: self.escrow,
+                vault: self.vault,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: TOKEN_PROGRAM_ID,
+                system_program: SYSTEM_PROGRAM_ID,
+            }.to_account_metas(None),
+            data: crate::instruction::CloseExpired {}.data(),
+        }
+    }
+
+    pub fn refund_ix(&self) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Refund {
+                maker: self.maker.pubkey(),
+                mint_a: self.mint_a,
+                maker_ata_a: self.maker_ata_a,
+                escrow: self.escrow,
+                vault: self.vault,
+                token_program: TOKEN_PROGRAM_ID,
+            }.to_account_metas(None),
+            data: crate::instruction::Refund {}.data(),
+        }
+    }
+
+    pub fn make(&mut self) -> TransactionResult {
+        let ix = self.make_ix();
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    pub fn take(&mut self) -> TransactionResult {
+        let ix = self.take_ix();
+        send(&mut self.program, &[ix], &self.taker)
+    }
+
+    pub fn take_partial(&mut self, amount: u64) -> TransactionResult {
+        let ix = self.take_partial_ix(amount);
+        send(&mut self.program, &[ix], &self.taker)
+    }
+
+    /// Signs `quote` as the maker and takes it in the same transaction
+    pub fn take_quote(&mut self, quote: crate::state::Quote) -> TransactionResult {
+        let ixs = [self.quote_signature_ix(&quote, &self.maker), self.take_quote_ix(quote)];
+        send(&mut self.program, &ixs, &self.taker)
+    }
+
+    pub fn update_terms(&mut self, receive: u64, expires_at: i64) -> TransactionResult {
+        let ix = self.update_terms_ix(receive, expires_at);
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    pub fn set_gate(&mut self, gate: crate::state::TakerGate) -> TransactionResult {
+        let ix = self.set_gate_ix(gate);
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    pub fn set_fill_or_kill(&mut self, fill_or_kill: bool) -> TransactionResult {
+        let ix = self.set_fill_or_kill_ix(fill_or_kill);
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    pub fn set_operator(&mut self, operator: &Pubkey) -> TransactionResult {
+        let ix = self.set_operator_ix(operator);
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    pub fn reserve(&mut self, taker: &Pubkey) -> TransactionResult {
+        let ix = self.reserve_ix(taker);
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    pub fn release(&mut self) -> TransactionResult {
+        let ix = self.release_ix();
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    pub fn refund(&mut self) -> TransactionResult {
+        let ix = self.refund_ix();
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    /// Freezes a token account of either mint, requires `with_freeze_authority`
+    pub fn freeze(&mut self, account: &Pubkey, mint: &Pubkey) -> TransactionResult {
+        let ix = spl_token::instruction::freeze_account(&TOKEN_PROGRAM_ID, account, mint, &self.maker.pubkey(), &[]).unwrap();
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    pub fn thaw(&mut self, account: &Pubkey, mint: &Pubkey) -> TransactionResult {
+        let ix = spl_token::instruction::thaw_account(&TOKEN_PROGRAM_ID, account, mint, &self.maker.pubkey(), &[]).unwrap();
+        send(&mut self.program, &[ix], &self.maker)
+    }
+
+    /// Point the context at the maker's escrow for `seed`, which may not exist yet
+    pub fn select_escrow(&mut self, seed: u64) {
+        self.seed = seed;
+        self.escrow = escrow_address(&self.maker.pubkey(), seed);
+        self.vault = associated_token::get_associated_token_address(&self.escrow, &self.mint_a);
+        self.receipt = receipt_address(&self.escrow, &self.taker.pubkey());
+    }
+
+    pub fn escrow_data(&self) -> crate::state::Escrow {
+        account::anchor_account(&self.program, &self.escrow)
+    }
+
+    pub fn receipt_data(&self) -> crate::state::FillReceipt {
+        account::anchor_account(&self.program, &self.receipt)
+    }
+
+    /// `take_sol` never creates the taker's ATA for mint A, so tests using it create it first
+    pub fn create_taker_ata_a(&mut self) {
+        if self.program.get_account(&self.taker_ata_a).is_none() {
+            CreateAssociatedTokenAccount::new(&mut self.program, &self.taker, &self.mint_a)
+                .owner(&self.taker.pubkey())
+                .send()
+                .unwrap();
+        }
+    }
+
+    pub fn token_account(&self, address: &Pubkey) -> spl_token::state::Account {
+        let account = self.program.get_account(address).expect("Token account not found");
+        spl_token::state::Account::unpack(&account.data).unwrap()
+    }
+
+    pub fn token_amount(&self, address: &Pubkey) -> u64 {
+        self.token_account(address).amount
+    }
+
+    /// Lamports held by an account, 0 if it does not exist
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.program, address)
+    }
+
+    pub fn now(&self) -> i64 {
+        self.program.get_sysvar::<Clock>().unix_timestamp
+    }
+}
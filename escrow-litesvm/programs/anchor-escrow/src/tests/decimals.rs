@@ -0,0 +1,70 @@
+use super::context::EscrowTestContext;
+
+// Asymmetric pairs are where amount and price conversions usually go wrong
+const DECIMAL_PAIRS: [(u8, u8); 4] = [(0, 6), (6, 9), (9, 0), (0, 0)];
+
+// 3 whole tokens of A for 2 whole tokens of B
+fn build(decimals_a: u8, decimals_b: u8) -> EscrowTestContext {
+    EscrowTestContext::builder()
+        .with_decimals(decimals_a, decimals_b)
+        .with_deposit(3 * 10_u64.pow(decimals_a as u32))
+        .with_receive(2 * 10_u64.pow(decimals_b as u32))
+        .without_make()
+        .build()
+}
+
+fn run_take_flow(decimals_a: u8, decimals_b: u8) {
+    let mut ctx = build(decimals_a, decimals_b);
+    let (deposit, receive) = (ctx.deposit, ctx.receive);
+    let one_a = 10_u64.pow(decimals_a as u32);
+    let case = format!("decimals {}/{}", decimals_a, decimals_b);
+
+    let maker_a = ctx.token_amount(&ctx.maker_ata_a);
+    let taker_b = ctx.token_amount(&ctx.taker_ata_b);
+
+    ctx.make().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.vault), deposit, "{}", case);
+
+    // One whole token of A costs a third of `receive`, rounded up
+    ctx.take_partial(one_a).unwrap();
+    let first_price = (2 * 10_u64.pow(decimals_b as u32)).div_ceil(3);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), one_a, "{}", case);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), first_price, "{}", case);
+
+    // Taking the rest settles the offer at exactly `receive`
+    ctx.take().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), deposit, "{}", case);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), receive, "{}", case);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_b), taker_b - receive, "{}", case);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_a - deposit, "{}", case);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "{}", case);
+    assert_eq!(ctx.lamports(&ctx.vault), 0, "{}", case);
+}
+
+fn run_refund_flow(decimals_a: u8, decimals_b: u8) {
+    let mut ctx = build(decimals_a, decimals_b);
+    let case = format!("decimals {}/{}", decimals_a, decimals_b);
+
+    let maker_a = ctx.token_amount(&ctx.maker_ata_a);
+
+    ctx.make().unwrap();
+    ctx.refund().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_a, "{}", case);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "{}", case);
+    assert_eq!(ctx.lamports(&ctx.vault), 0, "{}", case);
+}
+
+#[test]
+fn should_take_across_decimal_matrix() {
+    for (decimals_a, decimals_b) in DECIMAL_PAIRS {
+        run_take_flow(decimals_a, decimals_b);
+    }
+}
+
+#[test]
+fn should_refund_across_decimal_matrix() {
+    for (decimals_a, decimals_b) in DECIMAL_PAIRS {
+        run_refund_flow(decimals_a, decimals_b);
+    }
+}
@@ -0,0 +1,107 @@
+use {
+    super::context::EscrowTestContext,
+    anchor_lang::solana_program::program_option::COption,
+    anchor_spl::token::spl_token,
+    litesvm_token::spl_token::ID as TOKEN_PROGRAM_ID,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{keys::keypair, send},
+};
+
+const ALLOWANCE: u64 = 5;
+
+fn approve(ctx: &mut EscrowTestContext, account: &Pubkey, owner: &Keypair, delegate: &Pubkey) {
+    let ix = spl_token::instruction::approve(&TOKEN_PROGRAM_ID, account, delegate, &owner.pubkey(), &[], ALLOWANCE).unwrap();
+    send(&mut ctx.program, &[ix], owner).unwrap();
+}
+
+/// The escrow flow neither consumed nor revoked the approval
+fn assert_delegate_intact(ctx: &EscrowTestContext, account: &Pubkey, delegate: &Pubkey) {
+    let token_account = ctx.token_account(account);
+    assert_eq!(token_account.delegate, COption::Some(*delegate));
+    assert_eq!(token_account.delegated_amount, ALLOWANCE);
+}
+
+#[test]
+fn should_make_and_refund_with_a_delegate_on_the_maker_ata() {
+    let mut ctx = EscrowTestContext::builder().without_make().build();
+    let delegate = keypair("delegate").pubkey();
+    let (maker_ata_a, maker) = (ctx.maker_ata_a, ctx.maker.insecure_clone());
+    let balance = ctx.token_amount(&maker_ata_a);
+
+    approve(&mut ctx, &maker_ata_a, &maker, &delegate);
+
+    // The maker signs the deposit as owner, so the allowance is not drawn down
+    ctx.make().unwrap();
+    assert_eq!(ctx.token_amount(&maker_ata_a), balance - 10);
+    assert_delegate_intact(&ctx, &maker_ata_a, &delegate);
+
+    ctx.refund().unwrap();
+    assert_eq!(ctx.token_amount(&maker_ata_a), balance);
+    assert_delegate_intact(&ctx, &maker_ata_a, &delegate);
+}
+
+#[test]
+fn should_take_with_a_delegate_on_the_taker_ata() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let delegate = keypair("delegate").pubkey();
+    let (taker_ata_b, taker) = (ctx.taker_ata_b, ctx.taker.insecure_clone());
+    let balance = ctx.token_amount(&taker_ata_b);
+
+    approve(&mut ctx, &taker_ata_b, &taker, &delegate);
+
+    ctx.take().unwrap();
+
+    assert_eq!(ctx.token_amount(&taker_ata_b), balance - 10);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 10);
+    assert_delegate_intact(&ctx, &taker_ata_b, &delegate);
+}
+
+#[test]
+fn should_leave_delegates_alone_across_partial_fills() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).with_receive(100).build();
+    let delegate = keypair("delegate").pubkey();
+    let (maker_ata_a, maker) = (ctx.maker_ata_a, ctx.maker.insecure_clone());
+    let (taker_ata_b, taker) = (ctx.taker_ata_b, ctx.taker.insecure_clone());
+
+    approve(&mut ctx, &maker_ata_a, &maker, &delegate);
+    approve(&mut ctx, &taker_ata_b, &taker, &delegate);
+
+    ctx.take_partial(30).unwrap();
+    assert_delegate_intact(&ctx, &taker_ata_b, &delegate);
+
+    // The maker's delegate also survives the remainder coming back on refund
+    ctx.refund().unwrap();
+    assert_delegate_intact(&ctx, &maker_ata_a, &delegate);
+    assert_delegate_intact(&ctx, &taker_ata_b, &delegate);
+}
+
+#[test]
+#[cfg(not(feature = "strict-atas"))]
+fn should_not_let_the_delegate_take_on_the_owners_behalf() {
+    use {
+        super::context::receipt_address,
+        anchor_lang::error::ErrorCode,
+        anchor_spl::associated_token,
+        test_utils::assert_custom_error,
+    };
+
+    let mut ctx = EscrowTestContext::builder().build();
+    let delegate = keypair("delegate");
+    ctx.program.airdrop(&delegate.pubkey(), 1_000_000_000).unwrap();
+    let (taker_ata_b, taker) = (ctx.taker_ata_b, ctx.taker.insecure_clone());
+
+    approve(&mut ctx, &taker_ata_b, &taker, &delegate.pubkey());
+
+    // Only the owner can be the taker, the delegate signing instead is not enough
+    let mut take_ix = ctx.take_ix();
+    take_ix.accounts[0].pubkey = delegate.pubkey();
+    take_ix.accounts[4].pubkey = associated_token::get_associated_token_address(&delegate.pubkey(), &ctx.mint_a);
+    take_ix.accounts[10].pubkey = receipt_address(&ctx.escrow, &delegate.pubkey());
+
+    let result = send(&mut ctx.program, &[take_ix], &delegate);
+    assert_custom_error!(result, ErrorCode::ConstraintTokenOwner);
+    assert_delegate_intact(&ctx, &taker_ata_b, &delegate.pubkey());
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+}
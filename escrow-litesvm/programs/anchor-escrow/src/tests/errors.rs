@@ -0,0 +1,141 @@
+use {
+    super::context::EscrowTestContext,
+    crate::error::EscrowError,
+    anchor_lang::error::ErrorCode,
+    anchor_spl::associated_token,
+    litesvm_token::CreateAssociatedTokenAccount,
+    solana_instruction::AccountMeta,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_system_interface::error::SystemError,
+    test_utils::{assert_custom_error, send},
+};
+
+// Creates `owner`'s ATA for `mint` unless the build already did
+fn ensure_ata(ctx: &mut EscrowTestContext, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let ata = associated_token::get_associated_token_address(owner, mint);
+    if ctx.program.get_account(&ata).is_none() {
+        CreateAssociatedTokenAccount::new(&mut ctx.program, &ctx.maker, mint)
+            .owner(owner)
+            .send()
+            .unwrap();
+    }
+    ata
+}
+
+#[test]
+fn should_reject_refund_with_wrong_mint() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let maker = ctx.maker.pubkey();
+    let mint_b = ctx.mint_b;
+
+    // Consistent accounts for mint B, so only the escrow's has_one catches it
+    let mut refund_ix = ctx.refund_ix();
+    refund_ix.accounts[1].pubkey = mint_b;
+    refund_ix.accounts[2].pubkey = ensure_ata(&mut ctx, &maker, &mint_b);
+
+    let result = send(&mut ctx.program, &[refund_ix], &ctx.maker);
+    assert_custom_error!(result, ErrorCode::ConstraintHasOne);
+}
+
+#[test]
+fn should_reject_refund_with_wrong_vault() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // A second escrow's vault has the right mint but the wrong authority
+    ctx.select_escrow(456);
+    ctx.make().unwrap();
+    let other_vault = ctx.vault;
+    ctx.select_escrow(123);
+
+    let mut refund_ix = ctx.refund_ix();
+    refund_ix.accounts[4].pubkey = other_vault;
+
+    let result = send(&mut ctx.program, &[refund_ix], &ctx.maker);
+    assert_custom_error!(result, ErrorCode::ConstraintTokenOwner);
+}
+
+#[test]
+fn should_reject_refund_from_non_maker() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let taker = ctx.taker.pubkey();
+    let mint_a = ctx.mint_a;
+
+    let mut refund_ix = ctx.refund_ix();
+    refund_ix.accounts[0].pubkey = taker;
+    refund_ix.accounts[2].pubkey = ensure_ata(&mut ctx, &taker, &mint_a);
+
+    // The escrow's seeds include the maker, so they fail before has_one is checked
+    let result = send(&mut ctx.program, &[refund_ix], &ctx.taker);
+    assert_custom_error!(result, ErrorCode::ConstraintSeeds);
+
+    // The vault is untouched
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+}
+
+#[test]
+fn should_reject_take_without_taker_signature() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // The maker pays, the taker is passed but does not sign
+    let mut take_ix = ctx.take_ix();
+    take_ix.accounts[0].is_signer = false;
+
+    let result = send(&mut ctx.program, &[take_ix], &ctx.maker);
+    assert_custom_error!(result, ErrorCode::AccountNotSigner);
+}
+
+#[test]
+fn should_reject_make_with_reused_seed() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // Otherwise the duplicate would be rejected as an already processed transaction
+    ctx.program.expire_blockhash();
+
+    assert_custom_error!(ctx.make(), SystemError::AccountAlreadyInUse as u32);
+}
+
+#[test]
+fn should_reject_take_without_maker_destination() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // Pass maker_ata_b as None, so neither destination is provided
+    let mut take_ix = ctx.take_ix();
+    take_ix.accounts[6] = AccountMeta::new_readonly(crate::ID, false);
+
+    let result = send(&mut ctx.program, &[take_ix], &ctx.taker);
+    assert_custom_error!(result, EscrowError::MissingMakerDestination);
+}
+
+#[test]
+fn should_reject_unwrap_for_non_native_mint() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let take_ix = ctx.take_unwrap_ix();
+    let result = send(&mut ctx.program, &[take_ix], &ctx.taker);
+    assert_custom_error!(result, EscrowError::MintNotNative);
+}
+
+// Receipts are created once per taker, so their bump is derived on chain: any other
+// address of the same seeds would hide fills from clients looking up the canonical one
+#[test]
+fn should_reject_take_into_a_non_canonical_receipt() {
+    use {super::context::PROGRAM_ID, crate::constants::RECEIPT_SEED};
+
+    let mut ctx = EscrowTestContext::builder().build();
+    let taker = ctx.taker.pubkey();
+    let seeds: [&[u8]; 3] = [RECEIPT_SEED, ctx.escrow.as_ref(), taker.as_ref()];
+    let (canonical, canonical_bump) = Pubkey::find_program_address(&seeds, &PROGRAM_ID);
+    let non_canonical = (0..canonical_bump)
+        .rev()
+        .find_map(|bump| Pubkey::create_program_address(&[seeds[0], seeds[1], seeds[2], &[bump]], &PROGRAM_ID).ok())
+        .unwrap();
+
+    let mut take_ix = ctx.take_ix();
+    for meta in take_ix.accounts.iter_mut().filter(|meta| meta.pubkey == canonical) {
+        meta.pubkey = non_canonical;
+    }
+
+    assert_custom_error!(send(&mut ctx.program, &[take_ix], &ctx.taker), ErrorCode::ConstraintSeeds);
+    assert!(ctx.program.get_account(&non_canonical).is_none());
+}
@@ -0,0 +1,102 @@
+use {
+    super::context::EscrowTestContext,
+    crate::{
+        events::{EscrowMade, EscrowRefunded, EscrowStatusChanged, EscrowTaken, EscrowTermsUpdated, ExpiredEscrowClosed},
+        state::EscrowStatus,
+    },
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{
+        clock::warp_to_timestamp,
+        events::{event, events},
+        keys::keypair,
+        send,
+    },
+};
+
+#[test]
+fn should_emit_escrow_made() {
+    let mut ctx = EscrowTestContext::builder().without_make().build();
+
+    let tx_result = ctx.make().unwrap();
+
+    let EscrowMade { escrow, maker, mint_a, mint_b, seed, deposit, receive, expires_at, starts_at } = event(&tx_result);
+    assert_eq!((escrow, maker), (ctx.escrow, ctx.maker.pubkey()));
+    assert_eq!((mint_a, mint_b), (ctx.mint_a, ctx.mint_b));
+    assert_eq!((seed, deposit, receive, expires_at, starts_at), (123, 10, 10, 0, 0));
+}
+
+#[test]
+fn should_emit_the_start_of_a_timelocked_escrow() {
+    let mut ctx = EscrowTestContext::builder().without_make().with_start(60).build();
+
+    let tx_result = ctx.make().unwrap();
+
+    let EscrowMade { starts_at, .. } = event(&tx_result);
+    assert_eq!(starts_at, ctx.starts_at);
+}
+
+#[test]
+fn should_emit_escrow_taken_for_each_fill() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let tx_result = ctx.take_partial(4).unwrap();
+    let EscrowTaken { escrow, taker, amount, price, remaining, .. } = event(&tx_result);
+    assert_eq!((escrow, taker), (ctx.escrow, ctx.taker.pubkey()));
+    assert_eq!((amount, price, remaining), (4, 4, 6));
+
+    let tx_result = ctx.take().unwrap();
+    let EscrowTaken { amount, price, remaining, .. } = event(&tx_result);
+    assert_eq!((amount, price, remaining), (6, 6, 0));
+    assert!(events::<EscrowMade>(&tx_result).is_empty());
+}
+
+#[test]
+fn should_emit_escrow_refunded() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let tx_result = ctx.refund().unwrap();
+
+    let EscrowRefunded { escrow, maker, amount } = event(&tx_result);
+    assert_eq!((escrow, maker, amount), (ctx.escrow, ctx.maker.pubkey(), 10));
+}
+
+#[test]
+fn should_emit_terms_updated() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let tx_result = ctx.update_terms(25, 0).unwrap();
+
+    let EscrowTermsUpdated { escrow, receive, expires_at } = event(&tx_result);
+    assert_eq!((escrow, receive, expires_at), (ctx.escrow, 25, 0));
+}
+
+#[test]
+fn should_emit_status_changed_on_reserve_and_release() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let taker = ctx.taker.pubkey();
+
+    let tx_result = ctx.reserve(&taker).unwrap();
+    let EscrowStatusChanged { escrow, status, reserved_for } = event(&tx_result);
+    assert_eq!((escrow, status, reserved_for), (ctx.escrow, EscrowStatus::Reserved, taker));
+
+    let tx_result = ctx.release().unwrap();
+    let EscrowStatusChanged { escrow, status, reserved_for } = event(&tx_result);
+    assert_eq!((escrow, status, reserved_for), (ctx.escrow, EscrowStatus::Open, Pubkey::default()));
+}
+
+#[test]
+fn should_emit_expired_escrow_closed() {
+    let mut ctx = EscrowTestContext::builder().with_expiry(60).build();
+    let cranker = keypair("cranker");
+    ctx.program.airdrop(&cranker.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    warp_to_timestamp(&mut ctx.program, ctx.expires_at);
+    let close_ix = ctx.close_expired_ix(&cranker.pubkey());
+    let tx_result = send(&mut ctx.program, &[close_ix], &cranker).unwrap();
+
+    let ExpiredEscrowClosed { escrow, maker, cranker: closed_by, amount, bounty } = event(&tx_result);
+    assert_eq!((escrow, maker, closed_by, amount), (ctx.escrow, ctx.maker.pubkey(), cranker.pubkey(), 10));
+    assert_eq!(bounty, crate::constants::CLOSE_EXPIRED_BOUNTY);
+}
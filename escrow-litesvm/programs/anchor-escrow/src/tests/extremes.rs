@@ -0,0 +1,157 @@
+use {
+    super::context::EscrowTestContext,
+    crate::{
+        error::EscrowError,
+        state::{Escrow, EscrowStatus, TakerGate},
+    },
+    solana_pubkey::Pubkey,
+    test_utils::assert_custom_error,
+};
+
+fn escrow_with(deposit_total: u64, receive: u64, filled: u64) -> Escrow {
+    Escrow {
+        seed: 0,
+        maker: Pubkey::default(),
+        mint_a: Pubkey::default(),
+        mint_b: Pubkey::default(),
+        status: EscrowStatus::Open,
+        receive,
+        deposit_total,
+        filled,
+        remaining: deposit_total - filled,
+        created_at: 0,
+        expires_at: 0,
+        starts_at: 0,
+        bump: 0,
+        gate: TakerGate::None,
+        operator: Pubkey::default(),
+        fill_or_kill: false,
+        reserved_for: Pubkey::default(),
+        nonce: 0,
+        metadata: Vec::new(),
+    }
+}
+
+fn build(deposit: u64, receive: u64) -> EscrowTestContext {
+    EscrowTestContext::builder()
+        .with_deposit(deposit)
+        .with_receive(receive)
+        .build()
+}
+
+#[test]
+fn should_quote_without_overflow_at_u64_max() {
+    // u64::MAX * u64::MAX still fits the u128 intermediate
+    let escrow = escrow_with(u64::MAX, u64::MAX, 0);
+    assert_eq!(escrow.quote(u64::MAX).unwrap(), u64::MAX);
+    assert_eq!(escrow.quote(1).unwrap(), 1);
+
+    // A single unit of a huge deposit still costs at least one unit, rounded up
+    let escrow = escrow_with(u64::MAX, 1, 0);
+    assert_eq!(escrow.quote(1).unwrap(), 1);
+    assert_eq!(escrow.quote(u64::MAX).unwrap(), 1);
+
+    // One unit of A priced at every unit of B there can be
+    let escrow = escrow_with(1, u64::MAX, 0);
+    assert_eq!(escrow.quote(1).unwrap(), u64::MAX);
+}
+
+#[test]
+fn should_sum_partial_quotes_to_receive_at_the_boundaries() {
+    for (deposit_total, receive) in [(u64::MAX, u64::MAX - 1), (u64::MAX, 1), (u64::MAX - 1, u64::MAX), (3, u64::MAX)] {
+        let mut escrow = escrow_with(deposit_total, receive, 0);
+        let mut paid = 0u64;
+
+        for amount in [1, deposit_total / 3, deposit_total / 3, escrow.remaining] {
+            let amount = amount.min(escrow.remaining);
+            paid = paid.checked_add(escrow.quote(amount).unwrap()).unwrap();
+            escrow.filled += amount;
+            escrow.remaining -= amount;
+        }
+
+        assert_eq!(escrow.remaining, 0);
+        assert_eq!(paid, receive, "Partial quotes for {} A / {} B do not add up", deposit_total, receive);
+    }
+}
+
+#[test]
+fn should_reject_filling_past_u64_max() {
+    let escrow = escrow_with(u64::MAX, u64::MAX, u64::MAX);
+    assert!(escrow.quote(1).is_err(), "Filled + amount overflowing u64 must be rejected");
+
+    // Without a deposit there is nothing to price against
+    let escrow = escrow_with(0, 1, 0);
+    assert!(escrow.price_of(0).is_err());
+}
+
+#[test]
+fn should_take_u64_max_deposit_for_u64_max_receive() {
+    let mut ctx = build(u64::MAX, u64::MAX);
+    assert_eq!(ctx.token_amount(&ctx.vault), u64::MAX);
+
+    ctx.take().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), u64::MAX);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), u64::MAX);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_b), 0);
+    assert!(ctx.program.get_account(&ctx.escrow).is_none());
+
+    let receipt = ctx.receipt_data();
+    assert_eq!((receipt.amount_a, receipt.amount_b, receipt.fills), (u64::MAX, u64::MAX, 1));
+}
+
+#[test]
+fn should_charge_a_single_unit_once_across_partial_fills_of_a_huge_deposit() {
+    let mut ctx = build(u64::MAX, 1);
+
+    // The first unit rounds up to the whole price, every later fill is free
+    ctx.take_partial(1).unwrap();
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 1);
+
+    ctx.take_partial(u64::MAX / 2).unwrap();
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 1);
+
+    ctx.take().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), u64::MAX);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 1);
+
+    let receipt = ctx.receipt_data();
+    assert_eq!((receipt.amount_a, receipt.amount_b, receipt.fills), (u64::MAX, 1, 3));
+}
+
+#[test]
+fn should_settle_a_one_unit_vault() {
+    let mut ctx = build(1, u64::MAX);
+    assert_eq!(ctx.token_amount(&ctx.vault), 1);
+
+    // A single unit cannot be split, nor overfilled
+    assert_custom_error!(ctx.take_partial(0), EscrowError::InvalidFillAmount);
+    assert_custom_error!(ctx.take_partial(2), EscrowError::InvalidFillAmount);
+
+    ctx.take_partial(1).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 1);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), u64::MAX);
+    assert!(ctx.program.get_account(&ctx.escrow).is_none());
+    assert!(ctx.program.get_account(&ctx.vault).is_none());
+}
+
+#[test]
+fn should_settle_one_unit_for_one_unit() {
+    let mut ctx = build(1, 1);
+
+    ctx.take().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 1);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 1);
+}
+
+#[test]
+fn should_refund_a_u64_max_deposit() {
+    let mut ctx = build(u64::MAX, 1);
+
+    ctx.refund().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), u64::MAX);
+    assert!(ctx.program.get_account(&ctx.vault).is_none());
+}
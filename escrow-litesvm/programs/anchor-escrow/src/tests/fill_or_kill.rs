@@ -0,0 +1,86 @@
+use {
+    super::context::EscrowTestContext,
+    crate::error::EscrowError,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, send},
+};
+
+fn fill_or_kill_escrow() -> EscrowTestContext {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).with_receive(100).build();
+    ctx.set_fill_or_kill(true).unwrap();
+    ctx
+}
+
+#[test]
+fn should_store_the_flag() {
+    let mut ctx = fill_or_kill_escrow();
+    assert!(ctx.escrow_data().fill_or_kill);
+
+    ctx.set_fill_or_kill(false).unwrap();
+    assert!(!ctx.escrow_data().fill_or_kill);
+}
+
+#[test]
+fn should_reject_a_partial_fill() {
+    let mut ctx = fill_or_kill_escrow();
+
+    assert_custom_error!(ctx.take_partial(40), EscrowError::PartialFillNotAllowed);
+    assert_eq!(ctx.escrow_data().remaining, 100);
+    assert_eq!(ctx.token_amount(&ctx.vault), 100);
+}
+
+#[test]
+fn should_take_the_whole_remaining_amount() {
+    let mut ctx = fill_or_kill_escrow();
+
+    ctx.take_partial(100).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 100);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "Escrow should be closed");
+}
+
+#[test]
+fn should_reject_a_partial_quote() {
+    let mut ctx = fill_or_kill_escrow();
+
+    let quote = ctx.quote(40, 25);
+
+    assert_custom_error!(ctx.take_quote(quote), EscrowError::PartialFillNotAllowed);
+}
+
+#[test]
+fn should_reject_a_partial_take_sol() {
+    let mut ctx = EscrowTestContext::builder()
+        .with_native_mint_b()
+        .with_deposit(100)
+        .with_receive(LAMPORTS_PER_SOL)
+        .build();
+    ctx.create_taker_ata_a();
+    ctx.set_fill_or_kill(true).unwrap();
+
+    let take_ix = ctx.take_sol_ix(40);
+    let result = send(&mut ctx.program, &[take_ix], &ctx.taker);
+
+    assert_custom_error!(result, EscrowError::PartialFillNotAllowed);
+}
+
+#[test]
+fn should_lock_the_flag_after_the_first_fill() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).with_receive(100).build();
+    ctx.take_partial(40).unwrap();
+
+    assert_custom_error!(ctx.set_fill_or_kill(true), EscrowError::TermsLocked);
+}
+
+#[test]
+fn should_only_let_the_maker_set_the_flag() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let mut set_ix = ctx.set_fill_or_kill_ix(true);
+    set_ix.accounts[0].pubkey = ctx.taker.pubkey();
+    let result = send(&mut ctx.program, &[set_ix], &ctx.taker);
+
+    assert!(result.is_err());
+    assert!(!ctx.escrow_data().fill_or_kill);
+}
@@ -0,0 +1,87 @@
+use {
+    super::context::{EscrowTestContext, PROGRAM_ID},
+    litesvm::LiteSVM,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+};
+
+// Mirrors an RPC memcmp filter: `bytes` must appear at `offset` in the account data
+struct Memcmp {
+    offset: usize,
+    bytes: Vec<u8>,
+}
+
+// LiteSVM has no getProgramAccounts, so scan a set of known addresses the same way
+fn filter_program_accounts(program: &LiteSVM, candidates: &[Pubkey], filters: &[Memcmp]) -> Vec<Pubkey> {
+    candidates
+        .iter()
+        .filter(|address| {
+            program.get_account(address).is_some_and(|account| {
+                account.owner == PROGRAM_ID
+                    && filters.iter().all(|filter| {
+                        account.data.get(filter.offset..filter.offset + filter.bytes.len()) == Some(filter.bytes.as_slice())
+                    })
+            })
+        })
+        .copied()
+        .collect()
+}
+
+#[test]
+fn should_discover_and_classify_program_accounts_with_filters() {
+    use anchor_lang::Discriminator;
+    use crate::{
+        constants::{ESCROW_MAKER_OFFSET, ESCROW_MINT_A_OFFSET, ESCROW_MINT_B_OFFSET, ESCROW_STATUS_OFFSET},
+        state::{CnftEscrow, Escrow, EscrowStatus, FillReceipt},
+    };
+
+    let mut ctx = EscrowTestContext::builder().build();
+    let (escrow, vault, receipt) = (ctx.escrow, ctx.vault, ctx.receipt);
+
+    // The first escrow gets taken, a second one stays open
+    ctx.take().unwrap();
+    ctx.select_escrow(456);
+    ctx.make().unwrap();
+    let (open_escrow, open_vault) = (ctx.escrow, ctx.vault);
+
+    let candidates = [escrow, vault, open_escrow, open_vault, receipt, ctx.maker_ata_a, ctx.taker_ata_a, ctx.taker_ata_b, ctx.maker_ata_b];
+
+    let by_discriminator = |discriminator: &[u8]| vec![Memcmp { offset: 0, bytes: discriminator.to_vec() }];
+
+    // Every program-owned account matches exactly one discriminator
+    let escrows = filter_program_accounts(&ctx.program, &candidates, &by_discriminator(Escrow::DISCRIMINATOR));
+    let receipts = filter_program_accounts(&ctx.program, &candidates, &by_discriminator(FillReceipt::DISCRIMINATOR));
+    let cnft_escrows = filter_program_accounts(&ctx.program, &candidates, &by_discriminator(CnftEscrow::DISCRIMINATOR));
+    assert_eq!(escrows, vec![open_escrow]);
+    assert_eq!(receipts, vec![receipt]);
+    assert!(cnft_escrows.is_empty());
+
+    let owned = filter_program_accounts(&ctx.program, &candidates, &[]);
+    assert_eq!(owned.len(), escrows.len() + receipts.len() + cnft_escrows.len());
+
+    // Status, maker and pair filters find the open escrow
+    let mut open_filters = by_discriminator(Escrow::DISCRIMINATOR);
+    open_filters.push(Memcmp { offset: ESCROW_STATUS_OFFSET, bytes: vec![EscrowStatus::Open as u8] });
+    open_filters.push(Memcmp { offset: ESCROW_MAKER_OFFSET, bytes: ctx.maker.pubkey().to_bytes().to_vec() });
+    open_filters.push(Memcmp { offset: ESCROW_MINT_A_OFFSET, bytes: ctx.mint_a.to_bytes().to_vec() });
+    open_filters.push(Memcmp { offset: ESCROW_MINT_B_OFFSET, bytes: ctx.mint_b.to_bytes().to_vec() });
+    assert_eq!(filter_program_accounts(&ctx.program, &candidates, &open_filters), vec![open_escrow]);
+
+    // ...and nothing matches a different status, maker or swapped pair
+    for status in [EscrowStatus::Reserved] {
+        let filters = vec![
+            Memcmp { offset: 0, bytes: Escrow::DISCRIMINATOR.to_vec() },
+            Memcmp { offset: ESCROW_STATUS_OFFSET, bytes: vec![status as u8] },
+        ];
+        assert!(filter_program_accounts(&ctx.program, &candidates, &filters).is_empty());
+    }
+
+    let other_maker = vec![Memcmp { offset: ESCROW_MAKER_OFFSET, bytes: ctx.taker.pubkey().to_bytes().to_vec() }];
+    assert!(filter_program_accounts(&ctx.program, &candidates, &other_maker).is_empty());
+
+    let swapped_pair = vec![
+        Memcmp { offset: ESCROW_MINT_A_OFFSET, bytes: ctx.mint_b.to_bytes().to_vec() },
+        Memcmp { offset: ESCROW_MINT_B_OFFSET, bytes: ctx.mint_a.to_bytes().to_vec() },
+    ];
+    assert!(filter_program_accounts(&ctx.program, &candidates, &swapped_pair).is_empty());
+}
@@ -0,0 +1,109 @@
+use {
+    serde::{Deserialize, Serialize},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    std::{path::PathBuf, str::FromStr},
+};
+
+/// On-disk copy of a cluster account, stored as `fixtures/<address>.json`
+#[derive(Serialize, Deserialize)]
+struct AccountFixture {
+    address: String,
+    lamports: u64,
+    data: Vec<u8>,
+    owner: String,
+    executable: bool,
+    rent_epoch: u64,
+}
+
+fn fixture_path(address: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tests/fixtures")
+        .join(format!("{}.json", address))
+}
+
+fn program_fixture_path(address: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tests/fixtures")
+        .join(format!("{}.so", address))
+}
+
+/// Loads a devnet account from its committed fixture. Building with
+/// `--features refresh-fixtures` fetches it from devnet and rewrites the fixture instead.
+pub fn load_account(address: &str) -> Account {
+    #[cfg(feature = "refresh-fixtures")]
+    refresh(address);
+
+    let path = fixture_path(address);
+    let json = std::fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Missing fixture {}, run with --features refresh-fixtures", path.display()));
+    let fixture: AccountFixture = serde_json::from_str(&json).expect("Invalid account fixture");
+
+    assert_eq!(fixture.address, address, "Fixture {} holds a different account", path.display());
+
+    Account {
+        lamports: fixture.lamports,
+        data: fixture.data,
+        owner: Pubkey::from_str(&fixture.owner).unwrap(),
+        executable: fixture.executable,
+        rent_epoch: fixture.rent_epoch,
+    }
+}
+
+/// Loads the ELF of a devnet program from its committed fixture, `fixtures/<address>.so`,
+/// for `LiteSVM::add_program`. Refreshed from devnet like `load_account`.
+pub fn load_program(address: &str) -> Vec<u8> {
+    #[cfg(feature = "refresh-fixtures")]
+    refresh_program(address);
+
+    let path = program_fixture_path(address);
+    std::fs::read(&path)
+        .unwrap_or_else(|_| panic!("Missing fixture {}, run with --features refresh-fixtures", path.display()))
+}
+
+#[cfg(feature = "refresh-fixtures")]
+fn refresh(address: &str) {
+    use {solana_address::Address, solana_rpc_client::rpc_client::RpcClient};
+
+    let rpc_client = RpcClient::new("https://api.devnet.solana.com");
+    let fetched_account = rpc_client
+        .get_account(&Address::from_str(address).unwrap())
+        .expect("Failed to fetch account from devnet");
+
+    let fixture = AccountFixture {
+        address: address.to_string(),
+        lamports: fetched_account.lamports,
+        data: fetched_account.data,
+        owner: fetched_account.owner.to_string(),
+        executable: fetched_account.executable,
+        rent_epoch: fetched_account.rent_epoch,
+    };
+
+    let json = serde_json::to_string_pretty(&fixture).unwrap();
+    std::fs::write(fixture_path(address), json + "\n").expect("Failed to write account fixture");
+}
+
+#[cfg(feature = "refresh-fixtures")]
+fn refresh_program(address: &str) {
+    use {solana_address::Address, solana_rpc_client::rpc_client::RpcClient, solana_sdk_ids::bpf_loader_upgradeable};
+
+    // Upgradeable programs keep their ELF in a program data account, after its metadata
+    const PROGRAM_DATA_METADATA_LEN: usize = 45;
+
+    let rpc_client = RpcClient::new("https://api.devnet.solana.com");
+    let program = rpc_client
+        .get_account(&Address::from_str(address).unwrap())
+        .expect("Failed to fetch program from devnet");
+
+    let elf = if program.owner.as_ref() == bpf_loader_upgradeable::ID.as_ref() {
+        let program_data = Address::try_from(&program.data[4..36]).unwrap();
+        let program_data = rpc_client
+            .get_account(&program_data)
+            .expect("Failed to fetch program data from devnet");
+        program_data.data[PROGRAM_DATA_METADATA_LEN..].to_vec()
+    } else {
+        program.data
+    };
+
+    std::fs::write(program_fixture_path(address), elf).expect("Failed to write program fixture");
+}
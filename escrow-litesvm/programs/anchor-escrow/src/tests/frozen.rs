@@ -0,0 +1,125 @@
+use {
+    super::context::EscrowTestContext,
+    crate::{error::EscrowError, state::EscrowStatus},
+    test_utils::assert_custom_error,
+};
+
+fn build() -> EscrowTestContext {
+    EscrowTestContext::builder()
+        .with_deposit(100)
+        .with_receive(50)
+        .with_freeze_authority()
+        .build()
+}
+
+/// Nothing settled: the vault still holds the deposit and the escrow is untouched
+fn assert_unsettled(ctx: &EscrowTestContext, filled: u64) {
+    let escrow = ctx.escrow_data();
+    assert_eq!(escrow.status, EscrowStatus::Open);
+    assert_eq!((escrow.filled, escrow.remaining), (filled, 100 - filled));
+    assert_eq!(ctx.token_amount(&ctx.vault), 100 - filled);
+}
+
+#[test]
+fn should_reject_take_and_refund_while_vault_is_frozen() {
+    let mut ctx = build();
+    let (vault, mint_a) = (ctx.vault, ctx.mint_a);
+    let taker_b = ctx.token_amount(&ctx.taker_ata_b);
+
+    ctx.freeze(&vault, &mint_a).unwrap();
+
+    assert_custom_error!(ctx.take(), EscrowError::TokenAccountFrozen);
+    assert_custom_error!(ctx.refund(), EscrowError::TokenAccountFrozen);
+    assert_unsettled(&ctx, 0);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_b), taker_b);
+
+    // Once thawed the escrow settles normally
+    ctx.thaw(&vault, &mint_a).unwrap();
+    ctx.take().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 100);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 50);
+}
+
+#[test]
+fn should_keep_partial_fill_intact_when_vault_is_frozen_mid_flow() {
+    let mut ctx = build();
+    let (vault, mint_a) = (ctx.vault, ctx.mint_a);
+
+    ctx.take_partial(40).unwrap();
+    ctx.freeze(&vault, &mint_a).unwrap();
+
+    assert_custom_error!(ctx.take(), EscrowError::TokenAccountFrozen);
+    assert_unsettled(&ctx, 40);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 40);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 20);
+
+    let receipt = ctx.receipt_data();
+    assert_eq!((receipt.amount_a, receipt.amount_b, receipt.fills), (40, 20, 1));
+}
+
+#[test]
+fn should_reject_take_when_taker_payment_account_is_frozen() {
+    let mut ctx = build();
+    let (taker_ata_b, mint_b) = (ctx.taker_ata_b, ctx.mint_b);
+    let taker_b = ctx.token_amount(&taker_ata_b);
+
+    ctx.freeze(&taker_ata_b, &mint_b).unwrap();
+
+    assert_custom_error!(ctx.take(), EscrowError::TokenAccountFrozen);
+    assert_unsettled(&ctx, 0);
+    assert_eq!(ctx.token_amount(&taker_ata_b), taker_b);
+}
+
+#[test]
+fn should_reject_take_when_a_destination_account_is_frozen() {
+    let mut ctx = build();
+    let (taker_ata_a, maker_ata_b, mint_a, mint_b) = (ctx.taker_ata_a, ctx.maker_ata_b, ctx.mint_a, ctx.mint_b);
+
+    // The destinations have to exist before they can be frozen
+    #[cfg(not(feature = "strict-atas"))]
+    {
+        use {litesvm_token::CreateAssociatedTokenAccount, solana_signer::Signer};
+
+        CreateAssociatedTokenAccount::new(&mut ctx.program, &ctx.taker, &mint_a)
+            .owner(&ctx.taker.pubkey())
+            .send()
+            .unwrap();
+        CreateAssociatedTokenAccount::new(&mut ctx.program, &ctx.maker, &mint_b)
+            .owner(&ctx.maker.pubkey())
+            .send()
+            .unwrap();
+    }
+
+    ctx.freeze(&taker_ata_a, &mint_a).unwrap();
+    assert_custom_error!(ctx.take(), EscrowError::TokenAccountFrozen);
+    assert_unsettled(&ctx, 0);
+
+    ctx.thaw(&taker_ata_a, &mint_a).unwrap();
+    ctx.freeze(&maker_ata_b, &mint_b).unwrap();
+    assert_custom_error!(ctx.take(), EscrowError::TokenAccountFrozen);
+    assert_unsettled(&ctx, 0);
+    assert_eq!(ctx.token_amount(&taker_ata_a), 0);
+    assert_eq!(ctx.token_amount(&maker_ata_b), 0);
+}
+
+#[test]
+fn should_reject_make_and_refund_when_maker_account_is_frozen() {
+    let mut ctx = EscrowTestContext::builder()
+        .with_deposit(100)
+        .with_freeze_authority()
+        .without_make()
+        .build();
+    let (maker_ata_a, mint_a) = (ctx.maker_ata_a, ctx.mint_a);
+
+    ctx.freeze(&maker_ata_a, &mint_a).unwrap();
+    assert_custom_error!(ctx.make(), EscrowError::TokenAccountFrozen);
+    assert!(ctx.program.get_account(&ctx.escrow).is_none());
+
+    ctx.thaw(&maker_ata_a, &mint_a).unwrap();
+    ctx.make().unwrap();
+
+    // Freezing the refund destination keeps the deposit in the vault
+    ctx.freeze(&maker_ata_a, &mint_a).unwrap();
+    assert_custom_error!(ctx.refund(), EscrowError::TokenAccountFrozen);
+    assert_unsettled(&ctx, 0);
+}
@@ -0,0 +1,289 @@
+use {
+    super::context::EscrowTestContext,
+    crate::{
+        error::EscrowError,
+        gate::{Attestation, Collection, Metadata, ATTESTATION_PROGRAM_ID, TOKEN_METADATA_PROGRAM_ID},
+        state::TakerGate,
+    },
+    anchor_lang::AnchorSerialize,
+    solana_account::Account,
+    solana_instruction::AccountMeta,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, keys::keypair, send, token::mint_nft},
+};
+
+fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()], &TOKEN_METADATA_PROGRAM_ID).0
+}
+
+/// An NFT held by `ctx.taker` whose metadata names `collection`, returning the
+/// taker's token account and the metadata account a take passes for the gate
+fn taker_nft(ctx: &mut EscrowTestContext, collection: Option<Collection>) -> [AccountMeta; 2] {
+    let (mint, nft) = mint_nft(&mut ctx.program, &ctx.taker, &keypair("nft"));
+    let metadata = metadata_address(&mint);
+    write_metadata(ctx, &metadata, mint, collection, TOKEN_METADATA_PROGRAM_ID);
+
+    [AccountMeta::new_readonly(nft, false), AccountMeta::new_readonly(metadata, false)]
+}
+
+fn write_metadata(ctx: &mut EscrowTestContext, address: &Pubkey, mint: Pubkey, collection: Option<Collection>, owner: Pubkey) {
+    let metadata = Metadata {
+        key: 4,
+        update_authority: ctx.maker.pubkey(),
+        mint,
+        name: "Member #1".to_string(),
+        symbol: "MBR".to_string(),
+        uri: "https://example.com/1.json".to_string(),
+        seller_fee_basis_points: 0,
+        creators: None,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(0),
+        collection,
+    };
+    let data = metadata.try_to_vec().unwrap();
+    let lamports = ctx.program.minimum_balance_for_rent_exemption(data.len());
+
+    ctx.program
+        .set_account(*address, Account { lamports, data, owner, executable: false, rent_epoch: 0 })
+        .unwrap();
+}
+
+fn verified(key: Pubkey) -> Option<Collection> {
+    Some(Collection { verified: true, key })
+}
+
+fn gated_escrow() -> (EscrowTestContext, Pubkey) {
+    let mut ctx = EscrowTestContext::builder().build();
+    let collection = keypair("collection").pubkey();
+    ctx.set_gate(TakerGate::Collection { mint: collection }).unwrap();
+    (ctx, collection)
+}
+
+fn take_with(ctx: &mut EscrowTestContext, proof: &[AccountMeta]) -> litesvm::types::TransactionResult {
+    let mut take_ix = ctx.take_ix();
+    take_ix.accounts.extend_from_slice(proof);
+    send(&mut ctx.program, &[take_ix], &ctx.taker)
+}
+
+#[test]
+fn should_store_the_gate() {
+    let (ctx, collection) = gated_escrow();
+
+    assert_eq!(ctx.escrow_data().gate, TakerGate::Collection { mint: collection });
+}
+
+#[test]
+fn should_take_with_a_verified_nft_of_the_collection() {
+    let (mut ctx, collection) = gated_escrow();
+    let proof = taker_nft(&mut ctx, verified(collection));
+
+    take_with(&mut ctx, &proof).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), ctx.deposit);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "Escrow should be closed");
+}
+
+#[test]
+fn should_reject_a_take_without_the_gate_accounts() {
+    let (mut ctx, _) = gated_escrow();
+
+    assert_custom_error!(ctx.take(), EscrowError::MissingGateAccounts);
+}
+
+#[test]
+fn should_reject_an_unverified_collection() {
+    let (mut ctx, collection) = gated_escrow();
+    let proof = taker_nft(&mut ctx, Some(Collection { verified: false, key: collection }));
+
+    assert_custom_error!(take_with(&mut ctx, &proof), EscrowError::NotInCollection);
+}
+
+#[test]
+fn should_reject_an_nft_of_another_collection() {
+    let (mut ctx, _) = gated_escrow();
+    let proof = taker_nft(&mut ctx, verified(Pubkey::new_unique()));
+
+    assert_custom_error!(take_with(&mut ctx, &proof), EscrowError::NotInCollection);
+}
+
+#[test]
+fn should_reject_an_nft_held_by_someone_else() {
+    let (mut ctx, collection) = gated_escrow();
+    let (mint, nft) = mint_nft(&mut ctx.program, &ctx.maker, &keypair("nft"));
+    let metadata = metadata_address(&mint);
+    write_metadata(&mut ctx, &metadata, mint, verified(collection), TOKEN_METADATA_PROGRAM_ID);
+
+    let proof = [AccountMeta::new_readonly(nft, false), AccountMeta::new_readonly(metadata, false)];
+
+    assert_custom_error!(take_with(&mut ctx, &proof), EscrowError::NotInCollection);
+}
+
+#[test]
+fn should_reject_metadata_not_owned_by_token_metadata() {
+    let (mut ctx, collection) = gated_escrow();
+    let [nft, _] = taker_nft(&mut ctx, verified(collection));
+    let mint = ctx.token_account(&nft.pubkey).mint;
+
+    // Same bytes as genuine metadata, written by anyone
+    let forged = Pubkey::new_unique();
+    write_metadata(&mut ctx, &forged, mint, verified(collection), crate::ID);
+
+    let proof = [nft, AccountMeta::new_readonly(forged, false)];
+    assert_custom_error!(take_with(&mut ctx, &proof), EscrowError::NotInCollection);
+}
+
+#[test]
+fn should_gate_take_sol() {
+    let mut ctx = EscrowTestContext::builder()
+        .with_native_mint_b()
+        .with_receive(LAMPORTS_PER_SOL)
+        .build();
+    ctx.create_taker_ata_a();
+    let collection = keypair("collection").pubkey();
+    ctx.set_gate(TakerGate::Collection { mint: collection }).unwrap();
+
+    let mut take_ix = ctx.take_sol_ix(ctx.deposit);
+    let result = send(&mut ctx.program, &[take_ix.clone()], &ctx.taker);
+    assert_custom_error!(result, EscrowError::MissingGateAccounts);
+
+    let proof = taker_nft(&mut ctx, verified(collection));
+    take_ix.accounts.extend_from_slice(&proof);
+    send(&mut ctx.program, &[take_ix], &ctx.taker).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), ctx.deposit);
+}
+
+#[test]
+fn should_lock_the_gate_after_the_first_fill() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).with_receive(100).build();
+    ctx.take_partial(40).unwrap();
+
+    let gate = TakerGate::Collection { mint: keypair("collection").pubkey() };
+    assert_custom_error!(ctx.set_gate(gate), EscrowError::TermsLocked);
+}
+
+#[test]
+fn should_only_let_the_maker_set_the_gate() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let mut set_gate_ix = ctx.set_gate_ix(TakerGate::Collection { mint: Pubkey::new_unique() });
+    set_gate_ix.accounts[0].pubkey = ctx.taker.pubkey();
+    let result = send(&mut ctx.program, &[set_gate_ix], &ctx.taker);
+
+    assert!(result.is_err());
+    assert_eq!(ctx.escrow_data().gate, TakerGate::None);
+}
+
+/// Credential and schema an issuer attests under, and an escrow gated on them
+fn attested_escrow() -> (EscrowTestContext, Pubkey, Pubkey) {
+    let mut ctx = EscrowTestContext::builder().build();
+    let (credential, schema) = (keypair("credential").pubkey(), keypair("schema").pubkey());
+    ctx.set_gate(TakerGate::Attestation { credential, schema }).unwrap();
+    (ctx, credential, schema)
+}
+
+fn attestation_address(credential: &Pubkey, schema: &Pubkey, subject: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"attestation", credential.as_ref(), schema.as_ref(), subject.as_ref()], &ATTESTATION_PROGRAM_ID).0
+}
+
+/// Writes an attestation about the taker, returning the account a take passes for the gate
+fn write_attestation(ctx: &mut EscrowTestContext, attestation: Attestation, owner: Pubkey) -> [AccountMeta; 1] {
+    let address = attestation_address(&attestation.credential, &attestation.schema, &attestation.nonce);
+    let data = attestation.try_to_vec().unwrap();
+    let lamports = ctx.program.minimum_balance_for_rent_exemption(data.len());
+
+    ctx.program
+        .set_account(address, Account { lamports, data, owner, executable: false, rent_epoch: 0 })
+        .unwrap();
+
+    [AccountMeta::new_readonly(address, false)]
+}
+
+fn attestation(ctx: &EscrowTestContext, credential: Pubkey, schema: Pubkey) -> Attestation {
+    Attestation {
+        discriminator: 2,
+        nonce: ctx.taker.pubkey(),
+        credential,
+        schema,
+        data: b"kyc:passed".to_vec(),
+        signer: keypair("issuer").pubkey(),
+        expiry: ctx.now() + 3600,
+        token_account: Pubkey::default(),
+    }
+}
+
+#[test]
+fn should_take_with_an_attestation_from_the_issuer() {
+    let (mut ctx, credential, schema) = attested_escrow();
+    let attestation = attestation(&ctx, credential, schema);
+    let proof = write_attestation(&mut ctx, attestation, ATTESTATION_PROGRAM_ID);
+
+    take_with(&mut ctx, &proof).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), ctx.deposit);
+}
+
+#[test]
+fn should_take_with_an_attestation_that_never_expires() {
+    let (mut ctx, credential, schema) = attested_escrow();
+    let attestation = Attestation { expiry: 0, ..attestation(&ctx, credential, schema) };
+    let proof = write_attestation(&mut ctx, attestation, ATTESTATION_PROGRAM_ID);
+
+    take_with(&mut ctx, &proof).unwrap();
+}
+
+#[test]
+fn should_reject_a_take_without_an_attestation() {
+    let (mut ctx, _, _) = attested_escrow();
+
+    assert_custom_error!(ctx.take(), EscrowError::MissingGateAccounts);
+}
+
+#[test]
+fn should_reject_an_expired_attestation() {
+    let (mut ctx, credential, schema) = attested_escrow();
+    let attestation = Attestation { expiry: ctx.now(), ..attestation(&ctx, credential, schema) };
+    let proof = write_attestation(&mut ctx, attestation, ATTESTATION_PROGRAM_ID);
+
+    assert_custom_error!(take_with(&mut ctx, &proof), EscrowError::InvalidAttestation);
+}
+
+#[test]
+fn should_reject_an_attestation_from_another_issuer() {
+    let (mut ctx, _, schema) = attested_escrow();
+    let attestation = attestation(&ctx, Pubkey::new_unique(), schema);
+    let proof = write_attestation(&mut ctx, attestation, ATTESTATION_PROGRAM_ID);
+
+    assert_custom_error!(take_with(&mut ctx, &proof), EscrowError::InvalidAttestation);
+}
+
+#[test]
+fn should_reject_an_attestation_of_another_schema() {
+    let (mut ctx, credential, _) = attested_escrow();
+    let attestation = attestation(&ctx, credential, Pubkey::new_unique());
+    let proof = write_attestation(&mut ctx, attestation, ATTESTATION_PROGRAM_ID);
+
+    assert_custom_error!(take_with(&mut ctx, &proof), EscrowError::InvalidAttestation);
+}
+
+#[test]
+fn should_reject_an_attestation_about_someone_else() {
+    let (mut ctx, credential, schema) = attested_escrow();
+    let attestation = Attestation { nonce: ctx.maker.pubkey(), ..attestation(&ctx, credential, schema) };
+    let proof = write_attestation(&mut ctx, attestation, ATTESTATION_PROGRAM_ID);
+
+    assert_custom_error!(take_with(&mut ctx, &proof), EscrowError::InvalidAttestation);
+}
+
+#[test]
+fn should_reject_an_attestation_not_owned_by_the_attestation_program() {
+    let (mut ctx, credential, schema) = attested_escrow();
+    let attestation = attestation(&ctx, credential, schema);
+    let proof = write_attestation(&mut ctx, attestation, crate::ID);
+
+    assert_custom_error!(take_with(&mut ctx, &proof), EscrowError::InvalidAttestation);
+}
@@ -0,0 +1,66 @@
+use {
+    super::context::{EscrowTestContext, PROGRAM_ID},
+    test_utils::{assert_log_absent, assert_log_sequence},
+};
+
+#[test]
+fn should_log_token_instructions_of_take_in_order() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let tx_result = ctx.take().unwrap();
+
+    // Pay the maker, pay out the vault, then close it
+    assert_log_sequence!(tx_result.logs, [
+        "Instruction: TransferChecked",
+        "Instruction: TransferChecked",
+        "Instruction: CloseAccount",
+    ]);
+    assert_log_absent!(tx_result.logs, "panicked");
+}
+
+#[test]
+#[cfg(not(feature = "no-log-ix-name"))]
+fn should_log_instruction_name() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let tx_result = ctx.refund().unwrap();
+
+    test_utils::assert_log_contains!(tx_result.logs, "Instruction: Refund");
+    assert_log_absent!(tx_result.logs, "Instruction: Take");
+}
+
+#[test]
+#[cfg(feature = "no-log-ix-name")]
+fn should_not_log_instruction_name_when_quiet() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let tx_result = ctx.take().unwrap();
+
+    assert_log_absent!(tx_result.logs, "Instruction: Take");
+}
+
+#[test]
+#[cfg(all(not(feature = "verbose-logs"), feature = "no-log-ix-name"))]
+fn should_emit_no_program_logs_in_the_quiet_build() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).build();
+
+    let partial = ctx.take_partial(40).unwrap();
+    let take = ctx.take().unwrap();
+
+    // Only the token program logs, events go out as `Program data:`
+    for logs in [&partial.logs, &take.logs] {
+        let own = test_utils::logs::own_logs(logs, &PROGRAM_ID.to_string());
+        assert!(own.is_empty(), "Quiet build logged {:?}:\n{}", own, test_utils::logs::render(logs));
+        test_utils::assert_log_contains!(logs, "Instruction: TransferChecked");
+    }
+}
+
+#[test]
+#[cfg(feature = "verbose-logs")]
+fn should_log_fill_diagnostics_when_verbose() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).build();
+
+    let tx_result = ctx.take_partial(40).unwrap();
+
+    test_utils::assert_log_contains!(tx_result.logs, "Filling 40 of 100 remaining");
+}
@@ -0,0 +1,143 @@
+use {
+    super::context::{EscrowTestContext, PROGRAM_ID},
+    anchor_lang::ToAccountMetas,
+    anchor_spl::{associated_token::{get_associated_token_address_with_program_id, spl_associated_token_account}, token_2022::spl_token_2022},
+    litesvm::types::TransactionResult,
+    solana_instruction::Instruction,
+    solana_sdk_ids::system_program::ID as SYSTEM_PROGRAM_ID,
+    solana_signer::Signer,
+    test_utils::{
+        assert_custom_error,
+        send,
+        token_2022::{create_ata_2022, mint_to_2022, token_2022_amount, CreateMint2022, MintExtension},
+    },
+};
+
+/// Makes the escrow with a Token-2022 mint A carrying `extensions`, funded for the deposit
+fn make_with_mint_2022(ctx: &mut EscrowTestContext, extensions: &[MintExtension]) -> TransactionResult {
+    let maker = ctx.maker.insecure_clone();
+    let mint = extensions
+        .iter()
+        .fold(CreateMint2022::new(&mut ctx.program, &maker), |mint, extension| mint.extension(extension.clone()))
+        .send()
+        .unwrap();
+    ctx.mint_a = mint;
+    ctx.maker_ata_a = create_ata_2022(&mut ctx.program, &maker, &mint, &maker.pubkey());
+    ctx.vault = get_associated_token_address_with_program_id(&ctx.escrow, &mint, &spl_token_2022::ID);
+    mint_to_2022(&mut ctx.program, &maker, &mint, &ctx.maker_ata_a, ctx.deposit).unwrap();
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: crate::accounts::Make {
+            maker: maker.pubkey(),
+            mint_a: ctx.mint_a,
+            mint_b: ctx.mint_b,
+            maker_ata_a: ctx.maker_ata_a,
+            escrow: ctx.escrow,
+            vault: ctx.vault,
+            associated_token_program: spl_associated_token_account::ID,
+            token_program: spl_token_2022::ID,
+            system_program: SYSTEM_PROGRAM_ID,
+            maker_counter: super::context::maker_counter_address(&maker.pubkey()),
+        }.to_account_metas(None),
+        data: ctx.make_ix().data,
+    };
+    send(&mut ctx.program, &[ix], &maker)
+}
+
+#[test]
+fn should_create_escrow_and_vault_correctly() {
+    let ctx = EscrowTestContext::builder().build();
+
+    let vault_data = ctx.token_account(&ctx.vault);
+    assert_eq!(vault_data.amount, 10);
+    assert_eq!(vault_data.owner, ctx.escrow);
+    assert_eq!(vault_data.mint, ctx.mint_a);
+
+    let escrow_data = ctx.escrow_data();
+    assert_eq!(escrow_data.seed, 123u64);
+    assert_eq!(escrow_data.maker, ctx.maker.pubkey());
+    assert_eq!(escrow_data.mint_a, ctx.mint_a);
+    assert_eq!(escrow_data.mint_b, ctx.mint_b);
+    assert_eq!(escrow_data.nonce, 0);
+    assert_eq!(escrow_data.receive, 10);
+    assert_eq!(escrow_data.status, crate::state::EscrowStatus::Open);
+    assert_eq!(escrow_data.deposit_total, 10);
+    assert_eq!(escrow_data.filled, 0);
+    assert_eq!(escrow_data.remaining, 10);
+    assert_eq!(escrow_data.created_at, ctx.now());
+    assert_eq!(escrow_data.expires_at, 0);
+}
+
+#[test]
+fn should_store_expiry_from_builder() {
+    let ctx = EscrowTestContext::builder()
+        .with_expiry(60 * 60)
+        .build();
+
+    let escrow_data = ctx.escrow_data();
+    assert_eq!(escrow_data.created_at, ctx.now());
+    assert_eq!(escrow_data.expires_at, ctx.now() + 60 * 60);
+}
+
+#[test]
+fn should_reject_make_with_past_expiry() {
+    let mut ctx = EscrowTestContext::builder()
+        .without_make()
+        .build();
+
+    ctx.expires_at = ctx.now() - 1;
+
+    assert_custom_error!(ctx.make(), crate::error::EscrowError::InvalidExpiry);
+}
+
+#[test]
+fn should_reject_make_starting_at_or_after_expiry() {
+    let mut ctx = EscrowTestContext::builder()
+        .with_expiry(60 * 60)
+        .with_start(60 * 60)
+        .without_make()
+        .build();
+
+    assert_custom_error!(ctx.make(), crate::error::EscrowError::InvalidStartTime);
+}
+
+#[test]
+fn should_track_remaining_independently_of_vault_balance() {
+    use litesvm_token::MintTo;
+
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // Anyone can send tokens straight to the vault
+    MintTo::new(&mut ctx.program, &ctx.maker, &ctx.mint_a, &ctx.vault, 5)
+        .send()
+        .unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.vault), 15);
+
+    let escrow_data = ctx.escrow_data();
+    assert_eq!(escrow_data.deposit_total, 10);
+    assert_eq!(escrow_data.filled, 0);
+    assert_eq!(escrow_data.remaining, 10);
+}
+
+#[test]
+fn should_make_with_a_token_2022_mint() {
+    let mut ctx = EscrowTestContext::builder().without_make().build();
+
+    make_with_mint_2022(&mut ctx, &[]).unwrap();
+
+    assert_eq!(token_2022_amount(&ctx.program, &ctx.vault), 10);
+    assert_eq!(ctx.escrow_data().deposit_total, 10);
+    assert_eq!(ctx.escrow_data().remaining, 10);
+}
+
+#[test]
+fn should_reject_make_with_a_transfer_fee_mint() {
+    let mut ctx = EscrowTestContext::builder().without_make().build();
+
+    // The vault would only receive the deposit less the fee, short of what takers pay for
+    let transfer_fee = MintExtension::TransferFee { basis_points: 100, maximum_fee: 1_000 };
+    assert_custom_error!(make_with_mint_2022(&mut ctx, &[transfer_fee]), crate::error::EscrowError::TransferFeeMint);
+    assert!(ctx.program.get_account(&ctx.escrow).is_none());
+}
@@ -0,0 +1,53 @@
+use {
+    super::context::{EscrowTestContext, PROGRAM_ID},
+    anchor_lang::{InstructionData, Space, ToAccountMetas},
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    solana_sdk_ids::system_program::ID as SYSTEM_PROGRAM_ID,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, send},
+};
+
+fn append_ix(maker: Pubkey, escrow: Pubkey, data: Vec<u8>) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: crate::accounts::AppendMetadata {
+            maker,
+            escrow,
+            system_program: SYSTEM_PROGRAM_ID,
+        }.to_account_metas(None),
+        data: crate::instruction::AppendMetadata { data }.data(),
+    }
+}
+
+#[test]
+fn should_append_metadata_with_realloc() {
+    use crate::constants::{ANCHOR_DISCRIMINATOR, MAX_METADATA_LEN};
+
+    let mut ctx = EscrowTestContext::builder().build();
+    let maker = ctx.maker.pubkey();
+    let taker = ctx.taker.pubkey();
+
+    let base_size = ANCHOR_DISCRIMINATOR + crate::state::Escrow::INIT_SPACE;
+    assert_eq!(ctx.program.get_account(&ctx.escrow).unwrap().data.len(), base_size);
+
+    // Two appends grow the account and concatenate
+    for chunk in [b"note:otc".to_vec(), b";tag:vip".to_vec()] {
+        send(&mut ctx.program, &[append_ix(maker, ctx.escrow, chunk)], &ctx.maker).unwrap();
+    }
+
+    assert_eq!(ctx.program.get_account(&ctx.escrow).unwrap().data.len(), base_size + 16);
+    let escrow_data = ctx.escrow_data();
+    assert_eq!(escrow_data.metadata, b"note:otc;tag:vip".to_vec());
+    assert_eq!(escrow_data.receive, 10);
+
+    // Going over the maximum is rejected
+    let result = send(&mut ctx.program, &[append_ix(maker, ctx.escrow, vec![0; MAX_METADATA_LEN - 15])], &ctx.maker);
+    assert_custom_error!(result, crate::error::EscrowError::MetadataTooLong);
+
+    // Only the maker can append: the escrow's seeds include the maker, so anyone else fails them
+    let result = send(&mut ctx.program, &[append_ix(taker, ctx.escrow, b"spam".to_vec())], &ctx.taker);
+    assert_custom_error!(result, anchor_lang::error::ErrorCode::ConstraintSeeds);
+
+    assert_eq!(ctx.program.get_account(&ctx.escrow).unwrap().data.len(), base_size + 16);
+}
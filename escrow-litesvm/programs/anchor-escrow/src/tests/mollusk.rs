@@ -0,0 +1,121 @@
+//! Per-instruction compute benchmarks on Mollusk, which executes a single instruction
+//! against an explicit account set, without the transaction overhead LiteSVM adds.
+//!
+//! Run with `cargo test --features mollusk-bench mollusk`, the report is written to
+//! `target/benches/compute_units.md`.
+
+use {
+    super::context::EscrowTestContext,
+    crate::state::Escrow,
+    anchor_lang::{AccountDeserialize, AccountSerialize},
+    mollusk_svm::{program::keyed_account_for_system_program, Mollusk},
+    mollusk_svm_bencher::MolluskComputeUnitBencher,
+    mollusk_svm_programs_token::{associated_token, token},
+    solana_account::Account,
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    std::collections::HashMap,
+    test_utils::{artifact, keys::keypair},
+};
+
+/// Account state carried from one Mollusk instruction to the next. Missing
+/// accounts are seeded from the LiteSVM context that built the instructions.
+struct MolluskEnv {
+    mollusk: Mollusk,
+    accounts: HashMap<Pubkey, Account>,
+}
+
+impl MolluskEnv {
+    fn new(ctx: &EscrowTestContext) -> Self {
+        // Mollusk appends the `.so` extension itself
+        let program = artifact::program_path("anchor_escrow", env!("CARGO_MANIFEST_DIR")).with_extension("");
+        let mut mollusk = Mollusk::new(&crate::ID, program.to_str().unwrap());
+        token::add_program(&mut mollusk);
+        associated_token::add_program(&mut mollusk);
+        mollusk.sysvars.clock.unix_timestamp = ctx.now();
+
+        let accounts = [
+            keyed_account_for_system_program(),
+            token::keyed_account(),
+            associated_token::keyed_account(),
+        ]
+        .into_iter()
+        .collect();
+
+        Self { mollusk, accounts }
+    }
+
+    fn accounts_for(&mut self, ctx: &EscrowTestContext, ix: &Instruction) -> Vec<(Pubkey, Account)> {
+        ix.accounts
+            .iter()
+            .map(|meta| {
+                let account = self
+                    .accounts
+                    .entry(meta.pubkey)
+                    .or_insert_with(|| ctx.program.get_account(&meta.pubkey).unwrap_or_default());
+                (meta.pubkey, account.clone())
+            })
+            .collect()
+    }
+
+    /// Executes `ix` and keeps its resulting accounts for the instructions after it
+    fn process(&mut self, ctx: &EscrowTestContext, ix: &Instruction) {
+        let accounts = self.accounts_for(ctx, ix);
+        let result = self.mollusk.process_instruction(ix, &accounts);
+        assert!(result.program_result.is_ok(), "Setup instruction failed: {:?}", result.program_result);
+        self.accounts.extend(result.resulting_accounts);
+    }
+}
+
+#[test]
+fn bench_instructions() {
+    let mut ctx = EscrowTestContext::builder().without_make().build();
+    let mut env = MolluskEnv::new(&ctx);
+
+    let make_ix = ctx.make_ix();
+    let make_accounts = env.accounts_for(&ctx, &make_ix);
+    env.process(&ctx, &make_ix);
+
+    // Every handler below starts from the freshly made escrow
+    let take_ix = ctx.take_ix();
+    let take_accounts = env.accounts_for(&ctx, &take_ix);
+
+    let take_partial_ix = ctx.take_partial_ix(ctx.deposit / 2);
+    let take_partial_accounts = env.accounts_for(&ctx, &take_partial_ix);
+
+    let update_terms_ix = ctx.update_terms_ix(ctx.receive * 2, 0);
+    let update_terms_accounts = env.accounts_for(&ctx, &update_terms_ix);
+
+    let refund_ix = ctx.refund_ix();
+    let refund_accounts = env.accounts_for(&ctx, &refund_ix);
+
+    let cranker = keypair("cranker");
+    ctx.program.airdrop(&cranker.pubkey(), 1_000_000_000).unwrap();
+    let close_expired_ix = ctx.close_expired_ix(&cranker.pubkey());
+    let mut close_expired_accounts = env.accounts_for(&ctx, &close_expired_ix);
+
+    // The crank needs an expired escrow, so only its copy gets an expiry and the clock
+    // is moved up to it. The other copies never expire and are unaffected by the warp.
+    let expires_at = ctx.now() + 1;
+    let (_, escrow_account) = close_expired_accounts
+        .iter_mut()
+        .find(|(address, _)| *address == ctx.escrow)
+        .unwrap();
+    let mut escrow = Escrow::try_deserialize(&mut escrow_account.data.as_ref()).unwrap();
+    escrow.expires_at = expires_at;
+    escrow_account.data.clear();
+    escrow.try_serialize(&mut escrow_account.data).unwrap();
+    env.mollusk.sysvars.clock.unix_timestamp = expires_at;
+
+    MolluskComputeUnitBencher::new(env.mollusk)
+        .bench(("make", &make_ix, &make_accounts))
+        .bench(("take", &take_ix, &take_accounts))
+        .bench(("take_partial", &take_partial_ix, &take_partial_accounts))
+        .bench(("update_terms", &update_terms_ix, &update_terms_accounts))
+        .bench(("refund", &refund_ix, &refund_accounts))
+        .bench(("close_expired", &close_expired_ix, &close_expired_accounts))
+        .must_pass(true)
+        .out_dir("../../target/benches")
+        .execute();
+}
@@ -0,0 +1,90 @@
+use {
+    super::context::EscrowTestContext,
+    crate::error::EscrowError,
+    solana_keypair::Keypair,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, keys::keypair, send, SIGNATURE_FEE},
+};
+
+fn operated_escrow() -> (EscrowTestContext, Keypair) {
+    let mut ctx = EscrowTestContext::builder().build();
+    let operator = keypair("operator");
+    ctx.program.airdrop(&operator.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    ctx.set_operator(&operator.pubkey()).unwrap();
+    (ctx, operator)
+}
+
+#[test]
+fn should_let_the_operator_refund_to_the_maker() {
+    let (mut ctx, operator) = operated_escrow();
+    let maker = ctx.maker.pubkey();
+
+    let maker_tokens = ctx.token_amount(&ctx.maker_ata_a);
+    let rent = ctx.lamports(&ctx.escrow) + ctx.lamports(&ctx.vault);
+    let maker_before = ctx.lamports(&maker);
+    let operator_before = ctx.lamports(&operator.pubkey());
+
+    let refund_ix = ctx.operator_refund_ix(&operator.pubkey());
+    send(&mut ctx.program, &[refund_ix], &operator).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_tokens + ctx.deposit);
+    assert_eq!(ctx.lamports(&maker), maker_before + rent, "All rent should go to the maker");
+    assert_eq!(ctx.lamports(&operator.pubkey()), operator_before - SIGNATURE_FEE);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "Escrow should be closed");
+    assert_eq!(ctx.lamports(&ctx.vault), 0, "Vault should be closed");
+}
+
+#[test]
+fn should_store_the_operator() {
+    let (ctx, operator) = operated_escrow();
+
+    assert_eq!(ctx.escrow_data().operator, operator.pubkey());
+}
+
+#[test]
+fn should_reject_a_refund_by_anyone_else() {
+    let (mut ctx, _) = operated_escrow();
+    let impostor = keypair("impostor");
+    ctx.program.airdrop(&impostor.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    let refund_ix = ctx.operator_refund_ix(&impostor.pubkey());
+    let result = send(&mut ctx.program, &[refund_ix], &impostor);
+
+    assert_custom_error!(result, EscrowError::NotOperator);
+    assert_eq!(ctx.escrow_data().remaining, ctx.deposit);
+}
+
+#[test]
+fn should_reject_an_operator_refund_once_the_operator_is_cleared() {
+    let (mut ctx, operator) = operated_escrow();
+    ctx.set_operator(&Pubkey::default()).unwrap();
+
+    let refund_ix = ctx.operator_refund_ix(&operator.pubkey());
+    let result = send(&mut ctx.program, &[refund_ix], &operator);
+
+    assert_custom_error!(result, EscrowError::NotOperator);
+}
+
+#[test]
+fn should_keep_the_maker_able_to_refund() {
+    let (mut ctx, _) = operated_escrow();
+
+    ctx.refund().unwrap();
+
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "Escrow should be closed");
+}
+
+#[test]
+fn should_only_let_the_maker_set_the_operator() {
+    let (mut ctx, operator) = operated_escrow();
+
+    // The operator cannot hand the role on
+    let mut set_operator_ix = ctx.set_operator_ix(&keypair("impostor").pubkey());
+    set_operator_ix.accounts[0].pubkey = operator.pubkey();
+    let result = send(&mut ctx.program, &[set_operator_ix], &operator);
+
+    assert!(result.is_err());
+    assert_eq!(ctx.escrow_data().operator, operator.pubkey());
+}
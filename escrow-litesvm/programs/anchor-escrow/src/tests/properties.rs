@@ -0,0 +1,101 @@
+use {
+    super::context::EscrowTestContext,
+    proptest::prelude::*,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+};
+
+// Every case spins up a fresh SVM, so keep the case count modest
+const CASES: u32 = 16;
+
+fn total_lamports(ctx: &EscrowTestContext, addresses: &[Pubkey]) -> u64 {
+    addresses.iter().map(|address| ctx.lamports(address)).sum()
+}
+
+fn build(decimals_a: u8, decimals_b: u8, deposit: u64, receive: u64, seed: u64) -> EscrowTestContext {
+    EscrowTestContext::builder()
+        .with_decimals(decimals_a, decimals_b)
+        .with_deposit(deposit)
+        .with_receive(receive)
+        .with_seed(seed)
+        .without_make()
+        .build()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn should_conserve_tokens_and_lamports_on_make_and_take(
+        decimals_a in 0u8..=9,
+        decimals_b in 0u8..=9,
+        deposit in 1u64..=u64::MAX,
+        receive in 1u64..=u64::MAX,
+        seed in any::<u64>(),
+    ) {
+        let mut ctx = build(decimals_a, decimals_b, deposit, receive, seed);
+        let maker = ctx.maker.pubkey();
+        let taker = ctx.taker.pubkey();
+
+        let maker_a_before = ctx.token_amount(&ctx.maker_ata_a);
+        let taker_b_before = ctx.token_amount(&ctx.taker_ata_b);
+        let maker_lamports_before = ctx.lamports(&maker);
+
+        ctx.make().unwrap();
+
+        let escrow_rent = ctx.lamports(&ctx.escrow);
+        let vault_rent = ctx.lamports(&ctx.vault);
+        prop_assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_a_before - deposit);
+        prop_assert_eq!(ctx.token_amount(&ctx.vault), deposit);
+
+        // Whatever make cost beyond the two rents is the fee for a single signature
+        let fee = maker_lamports_before - ctx.lamports(&maker) - escrow_rent - vault_rent;
+
+        let accounts = [maker, taker, ctx.escrow, ctx.vault, ctx.receipt, ctx.maker_ata_a, ctx.maker_ata_b, ctx.taker_ata_a, ctx.taker_ata_b];
+        let lamports_before_take = total_lamports(&ctx, &accounts);
+        let maker_lamports_before_take = ctx.lamports(&maker);
+
+        ctx.take().unwrap();
+
+        // Tokens only move between the two parties
+        prop_assert_eq!(ctx.token_amount(&ctx.taker_ata_a), deposit);
+        prop_assert_eq!(ctx.token_amount(&ctx.maker_ata_b), receive);
+        prop_assert_eq!(ctx.token_amount(&ctx.taker_ata_b), taker_b_before - receive);
+        prop_assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_a_before - deposit);
+
+        // Escrow and vault rent goes back to the maker and no lamports are created or lost beyond the fee
+        prop_assert_eq!(ctx.lamports(&ctx.escrow), 0);
+        prop_assert_eq!(ctx.lamports(&ctx.vault), 0);
+        prop_assert_eq!(ctx.lamports(&maker), maker_lamports_before_take + escrow_rent + vault_rent);
+        prop_assert_eq!(total_lamports(&ctx, &accounts), lamports_before_take - fee);
+    }
+
+    #[test]
+    fn should_restore_maker_exactly_on_make_and_refund(
+        decimals_a in 0u8..=9,
+        deposit in 1u64..=u64::MAX,
+        receive in 1u64..=u64::MAX,
+        seed in any::<u64>(),
+    ) {
+        let mut ctx = build(decimals_a, 6, deposit, receive, seed);
+        let maker = ctx.maker.pubkey();
+
+        let maker_a_before = ctx.token_amount(&ctx.maker_ata_a);
+        let maker_lamports_before = ctx.lamports(&maker);
+
+        ctx.make().unwrap();
+
+        let escrow_rent = ctx.lamports(&ctx.escrow);
+        let vault_rent = ctx.lamports(&ctx.vault);
+        let fee = maker_lamports_before - ctx.lamports(&maker) - escrow_rent - vault_rent;
+
+        ctx.refund().unwrap();
+
+        prop_assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_a_before);
+        prop_assert_eq!(ctx.lamports(&ctx.escrow), 0);
+        prop_assert_eq!(ctx.lamports(&ctx.vault), 0);
+
+        // The maker is only out the two transaction fees
+        prop_assert_eq!(ctx.lamports(&maker), maker_lamports_before - 2 * fee);
+    }
+}
@@ -0,0 +1,74 @@
+use {
+    super::context::{receipt_address, EscrowTestContext, PROGRAM_ID},
+    anchor_lang::{error::ErrorCode, InstructionData, ToAccountMetas},
+    anchor_spl::associated_token::{self, spl_associated_token_account},
+    litesvm_token::{spl_token::ID as TOKEN_PROGRAM_ID, CreateAssociatedTokenAccount, MintTo},
+    solana_instruction::Instruction,
+    solana_message::Message,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_sdk_ids::system_program::ID as SYSTEM_PROGRAM_ID,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    test_utils::{assert_custom_error, keys::keypair},
+};
+
+#[test]
+fn should_let_only_one_of_two_same_slot_takes_succeed() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // A second taker with their own mint B balance
+    let rival = keypair("rival");
+    ctx.program.airdrop(&rival.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+    let rival_ata_b = CreateAssociatedTokenAccount::new(&mut ctx.program, &rival, &ctx.mint_b)
+        .owner(&rival.pubkey())
+        .send()
+        .unwrap();
+    MintTo::new(&mut ctx.program, &ctx.maker, &ctx.mint_b, &rival_ata_b, 1_000).send().unwrap();
+    let rival_ata_a = associated_token::get_associated_token_address(&rival.pubkey(), &ctx.mint_a);
+
+    #[cfg(feature = "strict-atas")]
+    CreateAssociatedTokenAccount::new(&mut ctx.program, &rival, &ctx.mint_a)
+        .owner(&rival.pubkey())
+        .send()
+        .unwrap();
+
+    let rival_take_ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: crate::accounts::Take {
+            taker: rival.pubkey(),
+            maker: ctx.maker.pubkey(),
+            mint_a: ctx.mint_a,
+            mint_b: ctx.mint_b,
+            taker_ata_a: rival_ata_a,
+            taker_ata_b: rival_ata_b,
+            maker_ata_b: Some(ctx.maker_ata_b),
+            maker_wsol: None,
+            escrow: ctx.escrow,
+            vault: ctx.vault,
+            receipt: receipt_address(&ctx.escrow, &rival.pubkey()),
+            associated_token_program: spl_associated_token_account::ID,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: SYSTEM_PROGRAM_ID,
+        }.to_account_metas(None),
+        data: crate::instruction::Take {}.data(),
+    };
+
+    // Both transactions are signed against the same blockhash before either lands
+    let blockhash = ctx.program.latest_blockhash();
+    let take = Transaction::new(&[&ctx.taker], Message::new(&[ctx.take_ix()], Some(&ctx.taker.pubkey())), blockhash);
+    let rival_take = Transaction::new(&[&rival], Message::new(&[rival_take_ix], Some(&rival.pubkey())), blockhash);
+
+    let first = ctx.program.send_transaction(take);
+    let second = ctx.program.send_transaction(rival_take);
+
+    assert!(first.is_ok(), "The first take should succeed");
+
+    // The escrow was closed by the first take, so the second cannot load it
+    assert_custom_error!(second, ErrorCode::AccountNotInitialized);
+
+    // The vault paid out exactly once
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 10);
+    assert!(ctx.lamports(&rival_ata_a) == 0 || ctx.token_amount(&rival_ata_a) == 0, "Rival should receive nothing");
+    assert_eq!(ctx.token_amount(&rival_ata_b), 1_000);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 10);
+}
@@ -0,0 +1,160 @@
+use {
+    super::context::{EscrowTestContext, PROGRAM_ID},
+    crate::constants::CLOSE_EXPIRED_BOUNTY,
+    anchor_spl::associated_token::ID as ASSOCIATED_TOKEN_PROGRAM_ID,
+    litesvm_token::spl_token::ID as TOKEN_PROGRAM_ID,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{assert_log_absent, assert_log_contains, clock::warp_to_timestamp, keys::keypair, send, SIGNATURE_FEE},
+};
+
+#[test]
+fn should_refund_tokens_and_close_vault() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // Get initial maker balance
+    let initial_balance = ctx.token_amount(&ctx.maker_ata_a);
+
+    // Send transaction and ensure it succeeds
+    let _tx_result = ctx.refund().unwrap();
+
+    // Verify vault is closed
+    assert_eq!(ctx.lamports(&ctx.vault), 0, "Vault should have 0 lamports");
+
+    // Verify escrow is closed
+    println!("Escrow account after refund: {:?}", ctx.program.get_account(&ctx.escrow));
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "Escrow should have 0 lamports");
+
+    // Verify maker received tokens back
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), initial_balance + 10, "Maker should receive refunded tokens");
+}
+
+// Each bump `find_program_address` tries before the canonical one is a
+// `create_program_address` syscall
+const CREATE_PROGRAM_ADDRESS_UNITS: u64 = 1_500;
+
+// Refund on escrows whose canonical bumps are far apart. Checking the stored bump costs
+// the same whatever it is, re-deriving it costs one syscall per bump tried above it, so
+// reverting to a bare `bump` constraint makes the lower bump visibly more expensive.
+#[test]
+fn should_store_canonical_bump_and_reuse_it_on_refund() {
+    let mut ctx = EscrowTestContext::builder().without_make().build();
+    let maker = ctx.maker.pubkey();
+    let mint_a = ctx.mint_a;
+
+    let bumps = |seed: u64| {
+        let (escrow, escrow_bump) = Pubkey::find_program_address(
+            &[crate::constants::ESCROW_SEED, maker.as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID
+        );
+        let (_, vault_bump) = Pubkey::find_program_address(
+            &[escrow.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint_a.as_ref()],
+            &ASSOCIATED_TOKEN_PROGRAM_ID
+        );
+        (escrow_bump, vault_bump)
+    };
+    // The vault is found on every refund either way, so both use a vault on the first bump
+    let high_seed = (0..).find(|seed| bumps(*seed) == (255, 255)).unwrap();
+    let low_seed = (0..).find(|seed| matches!(bumps(*seed), (0..=251, 255))).unwrap();
+    let extra_attempts = u64::from(255 - bumps(low_seed).0);
+
+    let mut refund_units = |seed: u64| {
+        ctx.select_escrow(seed);
+        ctx.make().unwrap();
+        assert_eq!(ctx.escrow_data().bump, bumps(seed).0);
+        ctx.refund().unwrap().compute_units_consumed
+    };
+    let high = refund_units(high_seed);
+    let low = refund_units(low_seed);
+
+    println!(
+        "Refund consumed {} compute units on bump 255 and {} on bump {}, re-deriving would add about {}",
+        high,
+        low,
+        255 - extra_attempts,
+        extra_attempts * CREATE_PROGRAM_ADDRESS_UNITS
+    );
+    assert!(
+        low.abs_diff(high) < CREATE_PROGRAM_ADDRESS_UNITS,
+        "Refund re-derives the escrow bump: {} CU on bump 255, {} CU {} bumps lower",
+        high,
+        low,
+        extra_attempts
+    );
+}
+
+#[test]
+fn should_return_exact_rent_to_maker_on_refund() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let maker = ctx.maker.pubkey();
+    let taker = ctx.taker.pubkey();
+
+    let escrow_rent = ctx.lamports(&ctx.escrow);
+    let vault_rent = ctx.lamports(&ctx.vault);
+    let maker_before = ctx.lamports(&maker);
+    let taker_before = ctx.lamports(&taker);
+
+    ctx.refund().unwrap();
+
+    // Both rents land with the maker, who only pays the signature fee
+    assert_eq!(ctx.lamports(&maker), maker_before + escrow_rent + vault_rent - SIGNATURE_FEE);
+    assert_eq!(ctx.lamports(&taker), taker_before, "No rent should reach the taker");
+    assert_eq!(ctx.lamports(&ctx.escrow), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_skip_transfer_and_close_accounts_when_refunding_an_empty_vault() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(0).build();
+    let maker = ctx.maker.pubkey();
+    assert_eq!(ctx.token_amount(&ctx.vault), 0);
+
+    let maker_a = ctx.token_amount(&ctx.maker_ata_a);
+    let escrow_rent = ctx.lamports(&ctx.escrow);
+    let vault_rent = ctx.lamports(&ctx.vault);
+    let maker_before = ctx.lamports(&maker);
+
+    let tx_result = ctx.refund().unwrap();
+
+    // Straight to closing the vault, no zero-amount transfer on the way
+    assert_log_absent!(tx_result.logs, "Instruction: TransferChecked");
+    assert_log_contains!(tx_result.logs, "Instruction: CloseAccount");
+
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_a);
+    assert_eq!(ctx.lamports(&maker), maker_before + escrow_rent + vault_rent - SIGNATURE_FEE);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_refund_an_empty_vault_for_less_compute_than_a_funded_one() {
+    let mut funded = EscrowTestContext::builder().build();
+    let mut empty = EscrowTestContext::builder().with_deposit(0).build();
+
+    let funded_units = funded.refund().unwrap().compute_units_consumed;
+    let empty_units = empty.refund().unwrap().compute_units_consumed;
+
+    assert!(empty_units < funded_units, "Empty refund used {} CU, funded {} CU", empty_units, funded_units);
+}
+
+#[test]
+fn should_skip_transfer_when_cranking_an_expired_empty_vault() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(0).with_expiry(60).build();
+    let maker = ctx.maker.pubkey();
+
+    let cranker = keypair("cranker");
+    ctx.program.airdrop(&cranker.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    warp_to_timestamp(&mut ctx.program, ctx.expires_at);
+
+    let escrow_rent = ctx.lamports(&ctx.escrow);
+    let vault_rent = ctx.lamports(&ctx.vault);
+    let maker_before = ctx.lamports(&maker);
+
+    let close_ix = ctx.close_expired_ix(&cranker.pubkey());
+    let tx_result = send(&mut ctx.program, &[close_ix], &cranker).unwrap();
+
+    assert_log_absent!(tx_result.logs, "Instruction: TransferChecked");
+    assert_eq!(ctx.lamports(&maker), maker_before + escrow_rent + vault_rent - CLOSE_EXPIRED_BOUNTY);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
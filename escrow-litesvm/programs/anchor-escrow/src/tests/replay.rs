@@ -0,0 +1,45 @@
+use {
+    super::context::PROGRAM_ID,
+    std::env,
+    test_utils::{artifact, logs, replay::Replay},
+};
+
+const DEFAULT_RPC_URL: &str = "https://api.devnet.solana.com";
+
+/// Replays a cluster transaction against the local build of the escrow program:
+///
+/// ```text
+/// REPLAY_SIGNATURE=<signature> cargo test --features replay replay_cluster_transaction -- --ignored --nocapture
+/// ```
+///
+/// `REPLAY_RPC_URL` picks the cluster, devnet by default. Set `REPLAY_DEPLOYED=1` to run
+/// the deployed program instead, which reproduces the original outcome more closely.
+#[test]
+#[ignore = "needs network access and REPLAY_SIGNATURE"]
+fn replay_cluster_transaction() {
+    let signature = env::var("REPLAY_SIGNATURE").expect("Set REPLAY_SIGNATURE to the transaction to replay");
+    let rpc_url = env::var("REPLAY_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string());
+
+    let mut replay = Replay::fetch(&rpc_url, &signature);
+    if env::var_os("REPLAY_DEPLOYED").is_none() {
+        replay = replay.with_program(PROGRAM_ID, artifact::program_bytes("anchor_escrow", env!("CARGO_MANIFEST_DIR")));
+    }
+
+    let (replayed_err, replayed_logs) = match replay.execute() {
+        Ok(meta) => (None, meta.logs),
+        Err(failed) => (Some(format!("{:?}", failed.err)), failed.meta.logs),
+    };
+
+    println!("Replayed {} from slot {}", replay.signature, replay.slot);
+    println!("Original error: {:?}\nReplayed error: {:?}", replay.original_err, replayed_err);
+    let original: Vec<&str> = replay.original_logs.iter().map(String::as_str).collect();
+    println!("Logs, original (-) against replayed (+):\n{}", logs::diff(&original, &replayed_logs));
+
+    assert_eq!(
+        replay.original_err.is_some(),
+        replayed_err.is_some(),
+        "The replay {} where the original {}",
+        if replayed_err.is_some() { "failed" } else { "succeeded" },
+        if replay.original_err.is_some() { "failed" } else { "succeeded" },
+    );
+}
@@ -0,0 +1,169 @@
+use {
+    super::context::EscrowTestContext,
+    crate::{constants::CLOSE_EXPIRED_BOUNTY, error::EscrowError, state::EscrowStatus},
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{
+        assert_custom_error,
+        clock::{warp_seconds, warp_to_timestamp},
+        keys::keypair,
+        send,
+        SIGNATURE_FEE,
+    },
+};
+
+#[test]
+fn should_fill_partially_twice_then_refund_remainder() {
+    // 100 A for 33 B, so partial prices need rounding
+    let mut ctx = EscrowTestContext::builder()
+        .with_deposit(100)
+        .with_receive(33)
+        .without_make()
+        .build();
+
+    let maker_a = ctx.token_amount(&ctx.maker_ata_a);
+    let taker_b = ctx.token_amount(&ctx.taker_ata_b);
+
+    // Make
+    ctx.make().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_a - 100);
+    assert_eq!(ctx.token_amount(&ctx.vault), 100);
+
+    // First partial take: 30 A costs ceil(30 * 33 / 100) = 10 B
+    ctx.take_partial(30).unwrap();
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 30);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 10);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_b), taker_b - 10);
+    assert_eq!(ctx.token_amount(&ctx.vault), 70);
+
+    let escrow = ctx.escrow_data();
+    assert_eq!(escrow.status, EscrowStatus::Open);
+    assert_eq!((escrow.filled, escrow.remaining), (30, 70));
+
+    let receipt = ctx.receipt_data();
+    assert_eq!((receipt.amount_a, receipt.amount_b, receipt.fills), (30, 10, 1));
+
+    // Second partial take: 50 A filled in total costs ceil(50 * 33 / 100) = 17 B, 7 more
+    ctx.take_partial(20).unwrap();
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 50);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 17);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_b), taker_b - 17);
+    assert_eq!(ctx.token_amount(&ctx.vault), 50);
+
+    let escrow = ctx.escrow_data();
+    assert_eq!(escrow.status, EscrowStatus::Open);
+    assert_eq!((escrow.filled, escrow.remaining), (50, 50));
+
+    let receipt = ctx.receipt_data();
+    assert_eq!((receipt.amount_a, receipt.amount_b, receipt.fills), (50, 17, 2));
+
+    // Filling more than what is left is rejected
+    assert_custom_error!(ctx.take_partial(51), EscrowError::InvalidFillAmount);
+
+    // Refund the remainder
+    ctx.refund().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_a - 50);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 17);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_pay_exactly_receive_across_partial_fills() {
+    let mut ctx = EscrowTestContext::builder()
+        .with_deposit(7)
+        .with_receive(10)
+        .build();
+
+    // Single units cost 2, 1, 2, 1, 2, 1 and the last one 1, exactly 10 in total
+    for _ in 0..6 {
+        ctx.take_partial(1).unwrap();
+        ctx.program.expire_blockhash();
+    }
+    ctx.take().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 10);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 7);
+    assert_eq!(ctx.receipt_data().fills, 7);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "Escrow should close on the last fill");
+    assert_eq!(ctx.lamports(&ctx.vault), 0, "Vault should close on the last fill");
+}
+
+#[test]
+fn should_expire_then_close_by_crank() {
+    let mut ctx = EscrowTestContext::builder()
+        .with_expiry(60)
+        .without_make()
+        .build();
+
+    let maker = ctx.maker.pubkey();
+    let maker_a = ctx.token_amount(&ctx.maker_ata_a);
+
+    let cranker = keypair("cranker");
+    ctx.program.airdrop(&cranker.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    // Make
+    ctx.make().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+
+    // The crank is rejected while the escrow is live
+    let close_ix = ctx.close_expired_ix(&cranker.pubkey());
+    assert_custom_error!(send(&mut ctx.program, &[close_ix], &cranker), EscrowError::EscrowNotExpired);
+
+    // Expire
+    warp_to_timestamp(&mut ctx.program, ctx.expires_at);
+    assert_custom_error!(ctx.take(), EscrowError::EscrowExpired);
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+
+    // Anyone can crank it closed, earning the bounty, and the maker gets the deposit
+    // and the rest of both rents
+    let escrow_rent = ctx.lamports(&ctx.escrow);
+    let vault_rent = ctx.lamports(&ctx.vault);
+    let maker_lamports = ctx.lamports(&maker);
+    let cranker_lamports = ctx.lamports(&cranker.pubkey());
+
+    let close_ix = ctx.close_expired_ix(&cranker.pubkey());
+    send(&mut ctx.program, &[close_ix], &cranker).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_a);
+    assert_eq!(ctx.lamports(&maker), maker_lamports + escrow_rent + vault_rent - CLOSE_EXPIRED_BOUNTY);
+    assert_eq!(ctx.lamports(&cranker.pubkey()), cranker_lamports + CLOSE_EXPIRED_BOUNTY - SIGNATURE_FEE);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_update_terms_then_take_at_new_price() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let taker_b = ctx.token_amount(&ctx.taker_ata_b);
+
+    // Update terms
+    let expires_at = ctx.now() + 60 * 60;
+    ctx.update_terms(20, expires_at).unwrap();
+
+    let escrow = ctx.escrow_data();
+    assert_eq!(escrow.receive, 20);
+    assert_eq!(escrow.expires_at, expires_at);
+    assert_eq!(escrow.status, EscrowStatus::Open);
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+
+    // Take
+    warp_seconds(&mut ctx.program, 60);
+    ctx.take().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 10);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 20);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_b), taker_b - 20);
+    assert_eq!(ctx.receipt_data().amount_b, 20);
+}
+
+#[test]
+fn should_lock_terms_after_first_fill() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    ctx.take_partial(5).unwrap();
+
+    assert_custom_error!(ctx.update_terms(20, 0), EscrowError::TermsLocked);
+    assert_eq!(ctx.escrow_data().receive, 10);
+}
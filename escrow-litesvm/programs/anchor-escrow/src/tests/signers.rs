@@ -0,0 +1,69 @@
+use {
+    super::context::EscrowTestContext,
+    anchor_lang::error::ErrorCode,
+    litesvm::types::TransactionResult,
+    solana_message::Message,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    solana_transaction_error::TransactionError,
+    test_utils::{assert_custom_error, keys::keypair, send},
+};
+
+fn assert_signature_failure(result: TransactionResult) {
+    let failed = result.expect_err("Transaction with a missing or forged signature must fail");
+    assert_eq!(failed.err, TransactionError::SignatureFailure);
+}
+
+#[test]
+fn should_reject_refund_signed_by_taker() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // The real maker is passed but only the taker signs
+    let mut refund_ix = ctx.refund_ix();
+    refund_ix.accounts[0].is_signer = false;
+
+    let result = send(&mut ctx.program, &[refund_ix], &ctx.taker);
+    assert_custom_error!(result, ErrorCode::AccountNotSigner);
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+}
+
+#[test]
+fn should_reject_take_signed_by_maker() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // The maker pays for a take on behalf of a taker who never signed
+    let mut take_ix = ctx.take_ix();
+    take_ix.accounts[0].is_signer = false;
+
+    let result = send(&mut ctx.program, &[take_ix], &ctx.maker);
+    assert_custom_error!(result, ErrorCode::AccountNotSigner);
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+}
+
+#[test]
+fn should_reject_take_with_stripped_taker_signature() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // The message still requires the taker, but only the fee payer signs
+    let message = Message::new(&[ctx.take_ix()], Some(&ctx.maker.pubkey()));
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.partial_sign(&[&ctx.maker], ctx.program.latest_blockhash());
+
+    assert_signature_failure(ctx.program.send_transaction(transaction));
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+}
+
+#[test]
+fn should_reject_refund_with_forged_maker_signature() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let attacker = keypair("attacker");
+
+    // Sign in the maker's slot with a different key
+    let message = Message::new(&[ctx.refund_ix()], Some(&ctx.maker.pubkey()));
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.message.recent_blockhash = ctx.program.latest_blockhash();
+    transaction.signatures[0] = attacker.sign_message(&transaction.message_data());
+
+    assert_signature_failure(ctx.program.send_transaction(transaction));
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+}
@@ -0,0 +1,221 @@
+use {
+    super::context::{EscrowTestContext, PROGRAM_ID},
+    anchor_lang::{solana_program::program_pack::Pack, AccountDeserialize, InstructionData, ToAccountMetas},
+    anchor_spl::token::spl_token,
+    litesvm_token::spl_token::ID as TOKEN_PROGRAM_ID,
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    solana_sdk_ids::system_program::ID as SYSTEM_PROGRAM_ID,
+    solana_signer::Signer,
+    std::{fmt::Write, path::PathBuf},
+};
+
+// Snapshots live next to this file. Run with UPDATE_SNAPSHOTS=1 to rewrite them
+// after an intended layout or value change, then review and commit the diff.
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tests/snapshots")
+        .join(format!("{}.snap", name));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, actual).expect("Failed to write snapshot");
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Missing snapshot {}, run with UPDATE_SNAPSHOTS=1 to create it", path.display()));
+
+    assert_eq!(expected, actual, "Snapshot {} changed, run with UPDATE_SNAPSHOTS=1 if this is intended", name);
+}
+
+/// Renders accounts with every random key and timestamp replaced by a stable label,
+/// so the same run always produces the same text.
+struct Snapshot<'a> {
+    ctx: &'a EscrowTestContext,
+    out: String,
+}
+
+impl<'a> Snapshot<'a> {
+    fn new(ctx: &'a EscrowTestContext) -> Self {
+        Self { ctx, out: String::new() }
+    }
+
+    fn label(&self, key: &Pubkey) -> String {
+        let ctx = self.ctx;
+        let labels = [
+            (ctx.maker.pubkey(), "maker"),
+            (ctx.taker.pubkey(), "taker"),
+            (ctx.mint_a, "mint_a"),
+            (ctx.mint_b, "mint_b"),
+            (ctx.escrow, "escrow"),
+            (ctx.vault, "vault"),
+            (ctx.receipt, "receipt"),
+            (PROGRAM_ID, "program"),
+            (TOKEN_PROGRAM_ID, "token_program"),
+        ];
+
+        labels
+            .iter()
+            .find(|(address, _)| address == key)
+            .map(|(_, label)| label.to_string())
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    fn time(&self, timestamp: i64) -> String {
+        match timestamp - self.ctx.now() {
+            _ if timestamp == 0 => "0".to_string(),
+            0 => "now".to_string(),
+            offset => format!("now{:+}", offset),
+        }
+    }
+
+    fn bump(&self, bump: u8, seeds: &[&[u8]]) -> String {
+        let (_, canonical) = Pubkey::find_program_address(seeds, &PROGRAM_ID);
+        if bump == canonical { "canonical".to_string() } else { bump.to_string() }
+    }
+
+    fn field(&mut self, name: &str, value: impl std::fmt::Display) {
+        writeln!(self.out, "  {}: {}", name, value).unwrap();
+    }
+
+    // Returns the account data if it is still open
+    fn header(&mut self, name: &str, address: &Pubkey) -> Option<Vec<u8>> {
+        match self.ctx.program.get_account(address).filter(|account| account.lamports > 0) {
+            Some(account) => {
+                writeln!(self.out, "{}", name).unwrap();
+                self.field("owner", self.label(&account.owner));
+                self.field("lamports", account.lamports);
+                self.field("data_len", account.data.len());
+                Some(account.data)
+            }
+            None => {
+                writeln!(self.out, "{}: closed", name).unwrap();
+                None
+            }
+        }
+    }
+
+    fn escrow(mut self) -> Self {
+        let address = self.ctx.escrow;
+        if let Some(data) = self.header("escrow", &address) {
+            let escrow = crate::state::Escrow::try_deserialize(&mut data.as_ref()).unwrap();
+            let metadata: String = escrow.metadata.iter().map(|byte| format!("{:02x}", byte)).collect();
+            let bump = self.bump(escrow.bump, &[crate::constants::ESCROW_SEED, escrow.maker.as_ref(), &escrow.seed.to_le_bytes()]);
+
+            self.field("seed", escrow.seed);
+            self.field("maker", self.label(&escrow.maker));
+            self.field("mint_a", self.label(&escrow.mint_a));
+            self.field("mint_b", self.label(&escrow.mint_b));
+            self.field("status", format!("{:?}", escrow.status));
+            self.field("receive", escrow.receive);
+            self.field("deposit_total", escrow.deposit_total);
+            self.field("filled", escrow.filled);
+            self.field("remaining", escrow.remaining);
+            self.field("created_at", self.time(escrow.created_at));
+            self.field("expires_at", self.time(escrow.expires_at));
+            self.field("starts_at", self.time(escrow.starts_at));
+            let gate = match escrow.gate {
+                crate::state::TakerGate::None => "None".to_string(),
+                crate::state::TakerGate::Collection { mint } => format!("Collection({})", self.label(&mint)),
+                crate::state::TakerGate::Attestation { credential, schema } => {
+                    format!("Attestation({}, {})", self.label(&credential), self.label(&schema))
+                }
+            };
+
+            self.field("bump", bump);
+            self.field("gate", gate);
+            self.field("operator", if escrow.operator == Pubkey::default() { "none".to_string() } else { self.label(&escrow.operator) });
+            self.field("fill_or_kill", escrow.fill_or_kill);
+            self.field("reserved_for", if escrow.reserved_for == Pubkey::default() { "none".to_string() } else { self.label(&escrow.reserved_for) });
+            self.field("nonce", escrow.nonce);
+            self.field("metadata", format!("[{}]", metadata));
+        }
+        self
+    }
+
+    fn vault(mut self) -> Self {
+        let address = self.ctx.vault;
+        if let Some(data) = self.header("vault", &address) {
+            let vault = spl_token::state::Account::unpack(&data).unwrap();
+            let label = |key: Option<Pubkey>| key.map(|key| self.label(&key)).unwrap_or_else(|| "none".to_string());
+            let (delegate, close_authority) = (label(vault.delegate.into()), label(vault.close_authority.into()));
+            let is_native = Option::<u64>::from(vault.is_native).map(|rent| rent.to_string()).unwrap_or_else(|| "none".to_string());
+
+            self.field("mint", self.label(&vault.mint));
+            self.field("authority", self.label(&vault.owner));
+            self.field("amount", vault.amount);
+            self.field("delegate", delegate);
+            self.field("state", format!("{:?}", vault.state));
+            self.field("is_native", is_native);
+            self.field("delegated_amount", vault.delegated_amount);
+            self.field("close_authority", close_authority);
+        }
+        self
+    }
+
+    fn receipt(mut self) -> Self {
+        let address = self.ctx.receipt;
+        if let Some(data) = self.header("receipt", &address) {
+            let receipt = crate::state::FillReceipt::try_deserialize(&mut data.as_ref()).unwrap();
+            let bump = self.bump(receipt.bump, &[crate::constants::RECEIPT_SEED, receipt.escrow.as_ref(), receipt.taker.as_ref()]);
+
+            self.field("escrow", self.label(&receipt.escrow));
+            self.field("escrow_nonce", receipt.escrow_nonce);
+            self.field("maker", self.label(&receipt.maker));
+            self.field("taker", self.label(&receipt.taker));
+            self.field("mint_a", self.label(&receipt.mint_a));
+            self.field("mint_b", self.label(&receipt.mint_b));
+            self.field("amount_a", receipt.amount_a);
+            self.field("amount_b", receipt.amount_b);
+            self.field("fills", receipt.fills);
+            self.field("last_filled_at", self.time(receipt.last_filled_at));
+            self.field("bump", bump);
+        }
+        self
+    }
+}
+
+#[test]
+fn should_match_snapshot_after_make() {
+    let ctx = EscrowTestContext::builder().build();
+
+    let snapshot = Snapshot::new(&ctx).escrow().vault();
+    assert_snapshot("make", &snapshot.out);
+}
+
+#[test]
+fn should_match_snapshot_after_take() {
+    let mut ctx = EscrowTestContext::builder().build();
+    ctx.take().unwrap();
+
+    let snapshot = Snapshot::new(&ctx).escrow().vault().receipt();
+    assert_snapshot("take", &snapshot.out);
+}
+
+#[test]
+fn should_match_snapshot_after_refund() {
+    let mut ctx = EscrowTestContext::builder().build();
+    ctx.refund().unwrap();
+
+    let snapshot = Snapshot::new(&ctx).escrow().vault();
+    assert_snapshot("refund", &snapshot.out);
+}
+
+#[test]
+fn should_match_snapshot_after_append_metadata() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    let append_ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: crate::accounts::AppendMetadata {
+            maker: ctx.maker.pubkey(),
+            escrow: ctx.escrow,
+            system_program: SYSTEM_PROGRAM_ID,
+        }.to_account_metas(None),
+        data: crate::instruction::AppendMetadata { data: b"hello".to_vec() }.data(),
+    };
+    super::context::send(&mut ctx.program, &[append_ix], &ctx.maker).unwrap();
+
+    let snapshot = Snapshot::new(&ctx).escrow();
+    assert_snapshot("append_metadata", &snapshot.out);
+}
@@ -0,0 +1,176 @@
+use {
+    super::context::EscrowTestContext,
+    anchor_lang::error::ErrorCode,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, keys::keypair},
+};
+
+#[test]
+fn should_match_init_space_with_serialized_size() {
+    use anchor_lang::{AnchorSerialize, Space};
+
+    let escrow = crate::state::Escrow {
+        seed: 0,
+        maker: Pubkey::default(),
+        mint_a: Pubkey::default(),
+        mint_b: Pubkey::default(),
+        status: crate::state::EscrowStatus::Open,
+        receive: 0,
+        deposit_total: 0,
+        filled: 0,
+        remaining: 0,
+        created_at: 0,
+        expires_at: 0,
+        starts_at: 0,
+        bump: 0,
+        gate: crate::state::TakerGate::None,
+        operator: Pubkey::default(),
+        fill_or_kill: false,
+        reserved_for: Pubkey::default(),
+        nonce: 0,
+        metadata: Vec::new(),
+    };
+
+    assert_eq!(escrow.try_to_vec().unwrap().len(), crate::state::Escrow::INIT_SPACE);
+}
+
+#[test]
+fn should_match_memcmp_offsets_with_borsh_layout() {
+    use anchor_lang::AccountSerialize;
+    use crate::constants::{ESCROW_MAKER_OFFSET, ESCROW_MINT_A_OFFSET, ESCROW_MINT_B_OFFSET, ESCROW_STATUS_OFFSET};
+
+    let maker = keypair("maker").pubkey();
+    let mint_a = keypair("mint_a").pubkey();
+    let mint_b = keypair("mint_b").pubkey();
+
+    let escrow = crate::state::Escrow {
+        seed: u64::MAX,
+        maker,
+        mint_a,
+        mint_b,
+        status: crate::state::EscrowStatus::Reserved,
+        receive: u64::MAX,
+        deposit_total: u64::MAX,
+        filled: u64::MAX,
+        remaining: u64::MAX,
+        created_at: i64::MAX,
+        expires_at: i64::MAX,
+        starts_at: i64::MAX,
+        bump: u8::MAX,
+        gate: crate::state::TakerGate::Collection { mint: Pubkey::new_from_array([u8::MAX; 32]) },
+        operator: Pubkey::new_from_array([u8::MAX; 32]),
+        fill_or_kill: true,
+        reserved_for: Pubkey::new_from_array([u8::MAX; 32]),
+        nonce: u64::MAX,
+        metadata: vec![u8::MAX; 4],
+    };
+
+    let mut data = Vec::new();
+    escrow.try_serialize(&mut data).unwrap();
+
+    assert_eq!(&data[ESCROW_MAKER_OFFSET..ESCROW_MAKER_OFFSET + 32], maker.as_ref());
+    assert_eq!(&data[ESCROW_MINT_A_OFFSET..ESCROW_MINT_A_OFFSET + 32], mint_a.as_ref());
+    assert_eq!(&data[ESCROW_MINT_B_OFFSET..ESCROW_MINT_B_OFFSET + 32], mint_b.as_ref());
+    assert_eq!(data[ESCROW_STATUS_OFFSET], crate::state::EscrowStatus::Reserved as u8);
+}
+
+#[test]
+fn should_allow_only_legal_status_transitions() {
+    use crate::state::EscrowStatus::{self, *};
+
+    let all = [Open, Reserved];
+    let legal: [(EscrowStatus, EscrowStatus); 2] = [(Open, Reserved), (Reserved, Open)];
+
+    for from in all {
+        for to in all {
+            assert_eq!(
+                from.can_transition_to(to),
+                legal.contains(&(from, to)),
+                "Unexpected result for {:?} -> {:?}", from, to
+            );
+        }
+    }
+}
+
+#[test]
+fn should_reject_take_after_refund_and_refund_after_take() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // Refunds close the escrow, so later actions fail on the missing account
+    ctx.refund().unwrap();
+    assert_custom_error!(ctx.take(), ErrorCode::AccountNotInitialized);
+    ctx.program.expire_blockhash();
+    assert_custom_error!(ctx.refund(), ErrorCode::AccountNotInitialized);
+
+    // So do full takes
+    ctx.select_escrow(456);
+    ctx.make().unwrap();
+    ctx.take().unwrap();
+    assert_custom_error!(ctx.refund(), ErrorCode::AccountNotInitialized);
+    ctx.program.expire_blockhash();
+    assert_custom_error!(ctx.take(), ErrorCode::AccountNotInitialized);
+}
+
+#[test]
+fn should_only_let_the_reserved_taker_fill_a_reserved_escrow() {
+    use crate::{error::EscrowError, state::EscrowStatus};
+
+    let mut ctx = EscrowTestContext::builder().build();
+    let other = keypair("other_taker").pubkey();
+
+    ctx.reserve(&other).unwrap();
+    let escrow = ctx.escrow_data();
+    assert_eq!(escrow.status, EscrowStatus::Reserved);
+    assert_eq!(escrow.reserved_for, other);
+    assert_custom_error!(ctx.take_partial(4), EscrowError::ReservedForAnotherTaker);
+
+    // Open -> Reserved -> Open, then reserved for the context's taker
+    ctx.release().unwrap();
+    let taker = ctx.taker.pubkey();
+    ctx.reserve(&taker).unwrap();
+    ctx.take_partial(4).unwrap();
+
+    let escrow = ctx.escrow_data();
+    assert_eq!(escrow.status, EscrowStatus::Reserved);
+    assert_eq!(escrow.reserved_for, taker);
+    assert_eq!(escrow.remaining, 6);
+}
+
+#[test]
+fn should_reopen_a_released_escrow_to_every_taker() {
+    use crate::state::EscrowStatus;
+
+    let mut ctx = EscrowTestContext::builder().build();
+    ctx.reserve(&keypair("other_taker").pubkey()).unwrap();
+    ctx.release().unwrap();
+
+    let escrow = ctx.escrow_data();
+    assert_eq!(escrow.status, EscrowStatus::Open);
+    assert_eq!(escrow.reserved_for, Pubkey::default());
+    ctx.take().unwrap();
+}
+
+#[test]
+fn should_reject_reserving_twice_and_releasing_an_open_escrow() {
+    use crate::error::EscrowError;
+
+    let mut ctx = EscrowTestContext::builder().build();
+    assert_custom_error!(ctx.release(), EscrowError::InvalidStatusTransition);
+
+    let taker = ctx.taker.pubkey();
+    ctx.reserve(&taker).unwrap();
+    ctx.program.expire_blockhash();
+    assert_custom_error!(ctx.reserve(&taker), EscrowError::InvalidStatusTransition);
+}
+
+#[test]
+fn should_refund_a_reserved_escrow() {
+    let mut ctx = EscrowTestContext::builder().build();
+    ctx.reserve(&keypair("other_taker").pubkey()).unwrap();
+    let initial_balance = ctx.token_amount(&ctx.maker_ata_a);
+    ctx.refund().unwrap();
+
+    assert!(ctx.program.get_account(&ctx.escrow).is_none());
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), initial_balance + ctx.deposit);
+}
@@ -0,0 +1,144 @@
+use {
+    super::context::EscrowTestContext,
+    anchor_lang::error::ErrorCode,
+    litesvm_token::{CreateAssociatedTokenAccount, MintTo},
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, keys::keypair, send, token::create_mint},
+};
+
+// Account indices in the take, refund and close_expired instructions
+const TAKE_MINT_B: usize = 3;
+const TAKE_TAKER_ATA_B: usize = 5;
+const TAKE_MAKER_ATA_B: usize = 6;
+const TAKE_VAULT: usize = 9;
+const TAKE_TOKEN_PROGRAM: usize = 12;
+const REFUND_TOKEN_PROGRAM: usize = 5;
+const CLOSE_EXPIRED_VAULT: usize = 5;
+
+/// The rejected substitution moved nothing out of the escrow
+fn assert_untouched(ctx: &EscrowTestContext) {
+    assert_eq!(ctx.token_amount(&ctx.vault), ctx.deposit);
+    assert_eq!(ctx.escrow_data().remaining, ctx.deposit);
+}
+
+fn create_ata(ctx: &mut EscrowTestContext, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    CreateAssociatedTokenAccount::new(&mut ctx.program, &ctx.maker, mint)
+        .owner(owner)
+        .send()
+        .unwrap()
+}
+
+// A second escrow of the same maker and mints, whose vault is a plausible stand-in
+fn other_vault(ctx: &mut EscrowTestContext) -> Pubkey {
+    ctx.select_escrow(456);
+    ctx.make().unwrap();
+    let vault = ctx.vault;
+    ctx.select_escrow(123);
+    vault
+}
+
+#[test]
+fn should_reject_take_with_vault_of_another_escrow() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let vault = other_vault(&mut ctx);
+
+    let mut take_ix = ctx.take_ix();
+    take_ix.accounts[TAKE_VAULT].pubkey = vault;
+
+    let result = send(&mut ctx.program, &[take_ix], &ctx.taker);
+    assert_custom_error!(result, ErrorCode::ConstraintTokenOwner);
+    assert_untouched(&ctx);
+    assert_eq!(ctx.token_amount(&vault), ctx.deposit);
+}
+
+#[test]
+fn should_reject_close_expired_with_vault_of_another_escrow() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let vault = other_vault(&mut ctx);
+    let cranker = ctx.taker.pubkey();
+
+    let mut close_ix = ctx.close_expired_ix(&cranker);
+    close_ix.accounts[CLOSE_EXPIRED_VAULT].pubkey = vault;
+
+    let result = send(&mut ctx.program, &[close_ix], &ctx.taker);
+    assert_custom_error!(result, ErrorCode::ConstraintTokenOwner);
+    assert_untouched(&ctx);
+}
+
+#[test]
+fn should_reject_take_paying_from_an_ata_of_the_wrong_mint() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let taker = ctx.taker.pubkey();
+
+    // The taker owns it and it is funded, it is just not the mint B ATA
+    let mint_c = create_mint(&mut ctx.program, &ctx.maker, &keypair("mint_c"), 6, None);
+    let taker_ata_c = create_ata(&mut ctx, &taker, &mint_c);
+    MintTo::new(&mut ctx.program, &ctx.maker, &mint_c, &taker_ata_c, 1_000).send().unwrap();
+
+    let mut take_ix = ctx.take_ix();
+    take_ix.accounts[TAKE_TAKER_ATA_B].pubkey = taker_ata_c;
+
+    let result = send(&mut ctx.program, &[take_ix], &ctx.taker);
+    assert_custom_error!(result, ErrorCode::AccountNotAssociatedTokenAccount);
+    assert_untouched(&ctx);
+    assert_eq!(ctx.token_amount(&taker_ata_c), 1_000);
+}
+
+#[test]
+fn should_reject_take_with_a_different_mint_b() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let (maker, taker) = (ctx.maker.pubkey(), ctx.taker.pubkey());
+
+    // Every account is consistent with mint C, only the escrow disagrees
+    let mint_c = create_mint(&mut ctx.program, &ctx.maker, &keypair("mint_c"), 6, None);
+    let taker_ata_c = create_ata(&mut ctx, &taker, &mint_c);
+    let maker_ata_c = create_ata(&mut ctx, &maker, &mint_c);
+    MintTo::new(&mut ctx.program, &ctx.maker, &mint_c, &taker_ata_c, 1_000).send().unwrap();
+
+    let mut take_ix = ctx.take_ix();
+    take_ix.accounts[TAKE_MINT_B].pubkey = mint_c;
+    take_ix.accounts[TAKE_TAKER_ATA_B].pubkey = taker_ata_c;
+    take_ix.accounts[TAKE_MAKER_ATA_B].pubkey = maker_ata_c;
+
+    let result = send(&mut ctx.program, &[take_ix], &ctx.taker);
+    assert_custom_error!(result, ErrorCode::ConstraintHasOne);
+    assert_untouched(&ctx);
+}
+
+#[test]
+fn should_reject_take_paying_the_attacker_instead_of_the_maker() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let attacker = keypair("attacker");
+    ctx.program.airdrop(&attacker.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    let mint_b = ctx.mint_b;
+    let attacker_ata_b = create_ata(&mut ctx, &attacker.pubkey(), &mint_b);
+
+    let mut take_ix = ctx.take_ix();
+    take_ix.accounts[TAKE_MAKER_ATA_B].pubkey = attacker_ata_b;
+
+    let result = send(&mut ctx.program, &[take_ix], &ctx.taker);
+    assert_custom_error!(result, ErrorCode::ConstraintTokenOwner);
+    assert_untouched(&ctx);
+    assert_eq!(ctx.token_amount(&attacker_ata_b), 0);
+}
+
+#[test]
+fn should_reject_a_fake_token_program() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    // An executable program that is not one of the token programs
+    let mut take_ix = ctx.take_ix();
+    take_ix.accounts[TAKE_TOKEN_PROGRAM].pubkey = crate::ID;
+    let result = send(&mut ctx.program, &[take_ix], &ctx.taker);
+    assert_custom_error!(result, ErrorCode::InvalidProgramId);
+
+    let mut refund_ix = ctx.refund_ix();
+    refund_ix.accounts[REFUND_TOKEN_PROGRAM].pubkey = crate::ID;
+    let result = send(&mut ctx.program, &[refund_ix], &ctx.maker);
+    assert_custom_error!(result, ErrorCode::InvalidProgramId);
+
+    assert_untouched(&ctx);
+}
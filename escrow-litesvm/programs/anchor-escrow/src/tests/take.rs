@@ -0,0 +1,327 @@
+use {
+    super::context::{wsol_address, EscrowTestContext, PROGRAM_ID},
+    anchor_lang::{InstructionData, ToAccountMetas},
+    anchor_spl::{
+        associated_token,
+        token::spl_token::native_mint::ID as NATIVE_MINT
+    },
+    solana_instruction::Instruction,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{
+        assert_custom_error,
+        clock::{warp_seconds, warp_to_timestamp},
+        send,
+        SIGNATURE_FEE,
+    },
+};
+
+const FIVE_DAYS: i64 = 5 * 24 * 60 * 60;
+
+#[test]
+#[ignore]
+fn should_execute_take_correctly() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    ctx.take().unwrap();
+}
+
+#[test]
+#[cfg(not(feature = "strict-atas"))]
+fn should_create_missing_destination_atas_on_take() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    assert!(ctx.program.get_account(&ctx.taker_ata_a).is_none(), "Taker ATA A should not exist before take");
+    assert!(ctx.program.get_account(&ctx.maker_ata_b).is_none(), "Maker ATA B should not exist before take");
+
+    ctx.take().unwrap();
+
+    let taker_ata_a_data = ctx.token_account(&ctx.taker_ata_a);
+    assert_eq!(taker_ata_a_data.owner, ctx.taker.pubkey());
+    assert_eq!(taker_ata_a_data.amount, 10);
+
+    let maker_ata_b_data = ctx.token_account(&ctx.maker_ata_b);
+    assert_eq!(maker_ata_b_data.owner, ctx.maker.pubkey());
+    assert_eq!(maker_ata_b_data.amount, 10);
+}
+
+#[test]
+fn should_fail_when_escrow_is_still_locked() {
+    let mut ctx = EscrowTestContext::builder().with_timelock(FIVE_DAYS).build();
+
+    assert_custom_error!(ctx.take(), crate::error::EscrowError::EscrowNotStarted);
+}
+
+#[test]
+fn should_execute_take_after_5_days_when_timelock_enabled() {
+    let mut ctx = EscrowTestContext::builder().with_timelock(FIVE_DAYS).build();
+
+    // Time travel past the lock
+    warp_seconds(&mut ctx.program, FIVE_DAYS + 1);
+
+    ctx.take().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 10);
+}
+
+#[test]
+fn should_unwrap_wsol_proceeds_to_maker() {
+    // Mint A is a regular SPL token, mint B is wrapped SOL
+    let mut ctx = EscrowTestContext::builder()
+        .with_native_mint_b()
+        .with_receive(LAMPORTS_PER_SOL)
+        .build();
+
+    let maker = ctx.maker.pubkey();
+    let maker_wsol = wsol_address(&ctx.escrow);
+
+    let initial_taker_wsol = ctx.token_amount(&ctx.taker_ata_b);
+    assert_eq!(initial_taker_wsol, 2 * ctx.receive);
+
+    let initial_maker_lamports = ctx.lamports(&maker);
+    let escrow_rent = ctx.lamports(&ctx.escrow);
+    let vault_rent = ctx.lamports(&ctx.vault);
+
+    let take_ix = ctx.take_unwrap_ix();
+    send(&mut ctx.program, &[take_ix], &ctx.taker).unwrap();
+
+    // Maker receives exactly `receive` as native SOL, plus the rent of the closed accounts
+    assert_eq!(ctx.lamports(&maker), initial_maker_lamports + ctx.receive + escrow_rent + vault_rent);
+
+    // The temporary wSOL account is closed and the maker never gets a wSOL ATA
+    assert_eq!(ctx.lamports(&maker_wsol), 0, "Temporary wSOL account should be closed");
+    let maker_ata_b = associated_token::get_associated_token_address(&maker, &NATIVE_MINT);
+    assert_eq!(ctx.lamports(&maker_ata_b), 0, "Maker wSOL ATA should not be created");
+
+    // Taker paid `receive` out of their wSOL balance and received the vault
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_b), initial_taker_wsol - ctx.receive);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 10);
+}
+
+#[test]
+fn should_reject_take_after_expiry() {
+    let mut ctx = EscrowTestContext::builder().with_expiry(60 * 60).build();
+
+    let escrow_data = ctx.escrow_data();
+    assert_eq!(escrow_data.created_at, ctx.now());
+    assert_eq!(escrow_data.expires_at, ctx.expires_at);
+
+    // Time travel past the expiry
+    warp_to_timestamp(&mut ctx.program, ctx.expires_at);
+
+    assert_custom_error!(ctx.take(), crate::error::EscrowError::EscrowExpired);
+
+    // The vault is untouched
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+}
+
+#[test]
+fn should_reject_take_before_start() {
+    let mut ctx = EscrowTestContext::builder().with_start(60 * 60).build();
+
+    assert_eq!(ctx.escrow_data().starts_at, ctx.starts_at);
+
+    warp_to_timestamp(&mut ctx.program, ctx.starts_at - 1);
+
+    assert_custom_error!(ctx.take(), crate::error::EscrowError::EscrowNotStarted);
+    assert_custom_error!(ctx.take_partial(5), crate::error::EscrowError::EscrowNotStarted);
+    assert_eq!(ctx.token_amount(&ctx.vault), 10);
+}
+
+#[test]
+fn should_take_from_the_start_time() {
+    let mut ctx = EscrowTestContext::builder().with_start(60 * 60).build();
+
+    warp_to_timestamp(&mut ctx.program, ctx.starts_at);
+
+    ctx.take().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 10);
+}
+
+#[test]
+fn should_reject_moving_the_expiry_to_the_start_time() {
+    let mut ctx = EscrowTestContext::builder().with_start(60 * 60).build();
+
+    // An escrow expiring before it starts could never be taken
+    assert_custom_error!(ctx.update_terms(ctx.receive, ctx.starts_at), crate::error::EscrowError::InvalidStartTime);
+    assert_eq!(ctx.escrow_data().expires_at, 0);
+
+    ctx.update_terms(ctx.receive, ctx.starts_at + 1).unwrap();
+    assert_eq!(ctx.escrow_data().expires_at, ctx.starts_at + 1);
+}
+
+#[test]
+fn should_record_fill_receipt_on_take() {
+    let mut ctx = EscrowTestContext::builder().build();
+
+    ctx.take().unwrap();
+
+    // The escrow is gone but the receipt remains
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "Escrow should be closed");
+
+    assert_eq!(ctx.program.get_account(&ctx.receipt).unwrap().owner, PROGRAM_ID);
+    let receipt_data = ctx.receipt_data();
+    assert_eq!(receipt_data.escrow, ctx.escrow);
+    assert_eq!(receipt_data.escrow_nonce, 0);
+    assert_eq!(receipt_data.maker, ctx.maker.pubkey());
+    assert_eq!(receipt_data.taker, ctx.taker.pubkey());
+    assert_eq!(receipt_data.mint_a, ctx.mint_a);
+    assert_eq!(receipt_data.mint_b, ctx.mint_b);
+    assert_eq!(receipt_data.amount_a, 10);
+    assert_eq!(receipt_data.amount_b, 10);
+    assert_eq!(receipt_data.fills, 1);
+    assert_eq!(receipt_data.last_filled_at, ctx.now());
+}
+
+#[test]
+fn should_start_a_new_receipt_when_the_escrow_address_is_reused() {
+    let mut ctx = EscrowTestContext::builder().build();
+    ctx.take_partial(4).unwrap();
+    let first_created_at = ctx.escrow_data().created_at;
+
+    // Refunding and making the same seed again reuses the escrow and receipt addresses,
+    // within the same second
+    ctx.refund().unwrap();
+    ctx.program.expire_blockhash();
+    ctx.make().unwrap();
+    ctx.take_partial(3).unwrap();
+
+    assert_eq!(ctx.escrow_data().created_at, first_created_at);
+    let receipt = ctx.receipt_data();
+    assert_eq!(receipt.escrow_nonce, 1);
+    assert_eq!(receipt.escrow_nonce, ctx.escrow_data().nonce);
+    assert_eq!(receipt.amount_a, 3, "Fills of the refunded escrow should not carry over");
+    assert_eq!(receipt.amount_b, 3);
+    assert_eq!(receipt.fills, 1);
+}
+
+#[test]
+fn should_return_exact_rent_to_maker_on_take() {
+    let mut ctx = EscrowTestContext::builder().build();
+    let maker = ctx.maker.pubkey();
+    let taker = ctx.taker.pubkey();
+
+    let escrow_rent = ctx.lamports(&ctx.escrow);
+    let vault_rent = ctx.lamports(&ctx.vault);
+    let maker_before = ctx.lamports(&maker);
+    let taker_before = ctx.lamports(&taker);
+    let existing = [ctx.taker_ata_a, ctx.maker_ata_b, ctx.receipt].map(|address| ctx.lamports(&address));
+
+    ctx.take().unwrap();
+
+    // The maker gets both rents back to the lamport and pays nothing
+    assert_eq!(ctx.lamports(&maker), maker_before + escrow_rent + vault_rent);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+
+    // The taker pays the fee and the rent of every account the take created, nothing more
+    let created: u64 = [ctx.taker_ata_a, ctx.maker_ata_b, ctx.receipt]
+        .iter()
+        .zip(existing)
+        .map(|(address, before)| ctx.lamports(address) - before)
+        .sum();
+    assert_eq!(ctx.lamports(&taker), taker_before - SIGNATURE_FEE - created);
+}
+
+#[test]
+fn should_keep_rent_in_escrow_until_the_final_fill_returns_it_to_maker() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).build();
+    let maker = ctx.maker.pubkey();
+    let taker = ctx.taker.pubkey();
+
+    let escrow_rent = ctx.lamports(&ctx.escrow);
+    let vault_rent = ctx.lamports(&ctx.vault);
+    let maker_before = ctx.lamports(&maker);
+
+    // A partial fill closes nothing, so no rent moves anywhere
+    ctx.take_partial(60).unwrap();
+    assert_eq!(ctx.lamports(&ctx.escrow), escrow_rent);
+    assert_eq!(ctx.lamports(&ctx.vault), vault_rent);
+    assert_eq!(ctx.lamports(&maker), maker_before);
+
+    // The closing fill pays both rents to the maker, none to the taker who triggered it
+    let taker_before = ctx.lamports(&taker);
+    ctx.take().unwrap();
+
+    assert_eq!(ctx.lamports(&maker), maker_before + escrow_rent + vault_rent);
+    assert_eq!(ctx.lamports(&taker), taker_before - SIGNATURE_FEE);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_return_wsol_account_rent_to_taker_and_escrow_rent_to_maker_on_unwrap() {
+    let mut ctx = EscrowTestContext::builder()
+        .with_native_mint_b()
+        .with_receive(LAMPORTS_PER_SOL)
+        .build();
+    let maker = ctx.maker.pubkey();
+    let taker = ctx.taker.pubkey();
+
+    let escrow_rent = ctx.lamports(&ctx.escrow);
+    let vault_rent = ctx.lamports(&ctx.vault);
+    let maker_before = ctx.lamports(&maker);
+    let taker_before = ctx.lamports(&taker);
+    let existing = [ctx.taker_ata_a, ctx.receipt].map(|address| ctx.lamports(&address));
+
+    let take_ix = ctx.take_unwrap_ix();
+    send(&mut ctx.program, &[take_ix], &ctx.taker).unwrap();
+
+    // The maker gets the price in SOL and the rents of the accounts it paid for
+    assert_eq!(ctx.lamports(&maker), maker_before + ctx.receive + escrow_rent + vault_rent);
+
+    // The temporary wSOL account's rent goes back to the taker who funded it, so the
+    // taker's wallet only pays the fee and the rent of the accounts that stay open
+    let created: u64 = [ctx.taker_ata_a, ctx.receipt]
+        .iter()
+        .zip(existing)
+        .map(|(address, before)| ctx.lamports(address) - before)
+        .sum();
+    assert_eq!(ctx.lamports(&taker), taker_before - SIGNATURE_FEE - created);
+    assert_eq!(ctx.lamports(&wsol_address(&ctx.escrow)), 0);
+}
+
+#[test]
+#[cfg(not(feature = "strict-atas"))]
+fn should_take_within_stack_limits_with_every_optional_account_present() {
+    let mut ctx = EscrowTestContext::builder()
+        .with_native_mint_b()
+        .with_receive(LAMPORTS_PER_SOL)
+        .build();
+
+    // Both maker destinations at once, so every optional account is deserialized
+    // and the maker's wSOL ATA is created on the way
+    let take_ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: crate::accounts::Take {
+            taker: ctx.taker.pubkey(),
+            maker: ctx.maker.pubkey(),
+            mint_a: ctx.mint_a,
+            mint_b: ctx.mint_b,
+            taker_ata_a: ctx.taker_ata_a,
+            taker_ata_b: ctx.taker_ata_b,
+            maker_ata_b: Some(ctx.maker_ata_b),
+            maker_wsol: Some(wsol_address(&ctx.escrow)),
+            escrow: ctx.escrow,
+            vault: ctx.vault,
+            receipt: ctx.receipt,
+            associated_token_program: associated_token::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }.to_account_metas(None),
+        data: crate::instruction::Take {}.data(),
+    };
+    let tx_result = send(&mut ctx.program, &[take_ix], &ctx.taker);
+
+    let logs = match &tx_result {
+        Ok(meta) => &meta.logs,
+        Err(failed) => &failed.meta.logs,
+    };
+    assert!(
+        !logs.iter().any(|log| log.contains("Access violation in stack frame") || log.contains("Stack offset")),
+        "Take overflowed the BPF stack: {:#?}",
+        logs
+    );
+    tx_result.unwrap();
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), ctx.deposit);
+    assert_eq!(ctx.program.get_account(&ctx.escrow).map_or(0, |account| account.lamports), 0);
+}
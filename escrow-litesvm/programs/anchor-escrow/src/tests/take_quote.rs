@@ -0,0 +1,173 @@
+use {
+    super::context::EscrowTestContext,
+    crate::error::EscrowError,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, send},
+};
+
+fn quoted_escrow() -> EscrowTestContext {
+    EscrowTestContext::builder().with_deposit(100).with_receive(100).build()
+}
+
+#[test]
+fn should_fill_at_the_quoted_price() {
+    let mut ctx = quoted_escrow();
+
+    // Below the escrow's own price of 40 for 40
+    let quote = ctx.quote(40, 25);
+    ctx.take_quote(quote).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 40);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 25);
+
+    let escrow = ctx.escrow_data();
+    assert_eq!(escrow.filled, 40);
+    assert_eq!(escrow.remaining, 60);
+    assert_eq!(escrow.receive, 100, "A quote should leave the escrow's terms alone");
+
+    let receipt = ctx.receipt_data();
+    assert_eq!(receipt.amount_a, 40);
+    assert_eq!(receipt.amount_b, 25);
+}
+
+#[test]
+fn should_close_the_escrow_on_a_full_quoted_fill() {
+    let mut ctx = quoted_escrow();
+
+    let quote = ctx.quote(100, 150);
+    ctx.take_quote(quote).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 100);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), 150);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "Escrow should be closed");
+    assert_eq!(ctx.lamports(&ctx.vault), 0, "Vault should be closed");
+}
+
+#[test]
+#[cfg(not(feature = "strict-atas"))]
+fn should_create_missing_destination_atas_on_take_quote() {
+    let mut ctx = quoted_escrow();
+    assert!(ctx.program.get_account(&ctx.taker_ata_a).is_none());
+    assert!(ctx.program.get_account(&ctx.maker_ata_b).is_none());
+
+    let quote = ctx.quote(40, 25);
+    ctx.take_quote(quote).unwrap();
+
+    assert_eq!(ctx.token_account(&ctx.taker_ata_a).owner, ctx.taker.pubkey());
+    assert_eq!(ctx.token_account(&ctx.maker_ata_b).owner, ctx.maker.pubkey());
+}
+
+#[test]
+#[cfg(feature = "strict-atas")]
+fn should_require_existing_destination_atas_on_take_quote() {
+    use {anchor_lang::error::ErrorCode, solana_account::Account};
+
+    let mut ctx = quoted_escrow();
+    ctx.program.set_account(ctx.taker_ata_a, Account::default()).unwrap();
+
+    let quote = ctx.quote(40, 25);
+    assert_custom_error!(ctx.take_quote(quote), ErrorCode::AccountNotInitialized);
+}
+
+#[test]
+fn should_reject_a_replayed_quote() {
+    let mut ctx = quoted_escrow();
+
+    let quote = ctx.quote(40, 25);
+    ctx.take_quote(quote.clone()).unwrap();
+    ctx.program.expire_blockhash();
+
+    // The first fill moved `filled` past what the quote was made against
+    assert_custom_error!(ctx.take_quote(quote), EscrowError::QuoteMismatch);
+    assert_eq!(ctx.escrow_data().filled, 40);
+}
+
+#[test]
+fn should_reject_a_quote_for_an_escrow_remade_at_the_same_address() {
+    let mut ctx = quoted_escrow();
+    let quote = ctx.quote(40, 25);
+
+    // Same maker and seed within the same second, so the same address, `created_at`
+    // and `filled` of 0, but new terms
+    ctx.refund().unwrap();
+    ctx.receive = 1_000;
+    ctx.make().unwrap();
+    assert_eq!(ctx.escrow_data().created_at, ctx.now());
+    assert_eq!(ctx.escrow_data().nonce, 1);
+
+    assert_custom_error!(ctx.take_quote(quote), EscrowError::QuoteMismatch);
+    assert_eq!(ctx.escrow_data().filled, 0);
+}
+
+#[test]
+fn should_reject_a_quote_without_a_signature() {
+    let mut ctx = quoted_escrow();
+
+    let take_ix = ctx.take_quote_ix(ctx.quote(40, 25));
+    let result = send(&mut ctx.program, &[take_ix], &ctx.taker);
+
+    assert_custom_error!(result, EscrowError::MissingQuoteSignature);
+}
+
+#[test]
+fn should_reject_a_signature_placed_after_the_take() {
+    let mut ctx = quoted_escrow();
+
+    let quote = ctx.quote(40, 25);
+    let ixs = [ctx.take_quote_ix(quote.clone()), ctx.quote_signature_ix(&quote, &ctx.maker)];
+    let result = send(&mut ctx.program, &ixs, &ctx.taker);
+
+    assert_custom_error!(result, EscrowError::MissingQuoteSignature);
+}
+
+#[test]
+fn should_reject_a_quote_signed_by_someone_else() {
+    let mut ctx = quoted_escrow();
+
+    let quote = ctx.quote(40, 1);
+    let ixs = [ctx.quote_signature_ix(&quote, &ctx.taker), ctx.take_quote_ix(quote)];
+    let result = send(&mut ctx.program, &ixs, &ctx.taker);
+
+    assert_custom_error!(result, EscrowError::InvalidQuoteSignature);
+    assert_eq!(ctx.escrow_data().filled, 0);
+}
+
+#[test]
+fn should_reject_terms_the_maker_did_not_sign() {
+    let mut ctx = quoted_escrow();
+
+    let signed = ctx.quote(40, 25);
+    let submitted = crate::state::Quote { price: 1, ..signed.clone() };
+    let ixs = [ctx.quote_signature_ix(&signed, &ctx.maker), ctx.take_quote_ix(submitted)];
+    let result = send(&mut ctx.program, &ixs, &ctx.taker);
+
+    assert_custom_error!(result, EscrowError::InvalidQuoteSignature);
+}
+
+#[test]
+fn should_reject_a_quote_for_another_taker() {
+    let mut ctx = quoted_escrow();
+
+    let quote = crate::state::Quote { taker: Pubkey::new_unique(), ..ctx.quote(40, 25) };
+
+    assert_custom_error!(ctx.take_quote(quote), EscrowError::QuoteMismatch);
+}
+
+#[test]
+fn should_reject_an_expired_quote() {
+    let mut ctx = quoted_escrow();
+
+    let quote = crate::state::Quote { expires_at: ctx.now(), ..ctx.quote(40, 25) };
+
+    assert_custom_error!(ctx.take_quote(quote), EscrowError::QuoteExpired);
+}
+
+#[test]
+fn should_reject_a_quote_before_the_escrow_starts() {
+    let mut ctx = EscrowTestContext::builder().with_deposit(100).with_receive(100).with_start(60).build();
+
+    let quote = ctx.quote(40, 25);
+
+    assert_custom_error!(ctx.take_quote(quote), EscrowError::EscrowNotStarted);
+}
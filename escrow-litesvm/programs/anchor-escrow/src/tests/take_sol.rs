@@ -0,0 +1,86 @@
+use {
+    super::context::{wsol_address, EscrowTestContext},
+    crate::error::EscrowError,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, assert_log_absent, send, SIGNATURE_FEE},
+};
+
+fn sol_escrow() -> EscrowTestContext {
+    let mut ctx = EscrowTestContext::builder()
+        .with_deposit(100)
+        .with_native_mint_b()
+        .with_receive(LAMPORTS_PER_SOL)
+        .build();
+    ctx.create_taker_ata_a();
+    ctx
+}
+
+#[test]
+fn should_settle_the_maker_in_lamports() {
+    let mut ctx = sol_escrow();
+    let (maker, taker) = (ctx.maker.pubkey(), ctx.taker.pubkey());
+
+    let escrow_rent = ctx.lamports(&ctx.escrow);
+    let vault_rent = ctx.lamports(&ctx.vault);
+    let maker_before = ctx.lamports(&maker);
+    let taker_before = ctx.lamports(&taker);
+    let taker_wsol = ctx.token_amount(&ctx.taker_ata_b);
+
+    let take_ix = ctx.take_sol_ix(ctx.deposit);
+    let tx_result = send(&mut ctx.program, &[take_ix], &ctx.taker).unwrap();
+
+    // A single system transfer, no wSOL account is touched or created
+    assert_log_absent!(tx_result.logs, "Instruction: InitializeAccount");
+    assert_eq!(ctx.lamports(&wsol_address(&ctx.escrow)), 0);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_b), taker_wsol, "The taker's wSOL should be untouched");
+
+    assert_eq!(ctx.lamports(&maker), maker_before + ctx.receive + escrow_rent + vault_rent);
+    let receipt_rent = ctx.lamports(&ctx.receipt);
+    assert_eq!(ctx.lamports(&taker), taker_before - ctx.receive - receipt_rent - SIGNATURE_FEE);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), ctx.deposit);
+    assert_eq!(ctx.lamports(&ctx.escrow), 0, "Escrow should be closed");
+}
+
+#[test]
+fn should_fill_partially_in_sol() {
+    let mut ctx = sol_escrow();
+    let maker = ctx.maker.pubkey();
+    let maker_before = ctx.lamports(&maker);
+
+    let take_ix = ctx.take_sol_ix(40);
+    send(&mut ctx.program, &[take_ix], &ctx.taker).unwrap();
+
+    let escrow = ctx.escrow_data();
+    assert_eq!(escrow.remaining, 60);
+    assert_eq!(ctx.lamports(&maker), maker_before + ctx.receive * 40 / 100);
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), 40);
+
+    let receipt = ctx.receipt_data();
+    assert_eq!(receipt.amount_b, ctx.receive * 40 / 100);
+    assert_eq!(receipt.mint_b, ctx.mint_b);
+}
+
+#[test]
+fn should_reject_take_sol_when_mint_b_is_not_native() {
+    let mut ctx = EscrowTestContext::builder().build();
+    ctx.create_taker_ata_a();
+
+    let take_ix = ctx.take_sol_ix(ctx.deposit);
+    assert_custom_error!(send(&mut ctx.program, &[take_ix], &ctx.taker), EscrowError::MintNotNative);
+}
+
+#[test]
+fn should_reject_take_sol_beyond_the_remaining_amount() {
+    let mut ctx = sol_escrow();
+
+    let take_ix = ctx.take_sol_ix(ctx.deposit + 1);
+    assert_custom_error!(send(&mut ctx.program, &[take_ix], &ctx.taker), EscrowError::InvalidFillAmount);
+}
+
+#[test]
+fn should_pass_fewer_accounts_than_take_with_unwrap() {
+    let ctx = sol_escrow();
+
+    assert!(ctx.take_sol_ix(ctx.deposit).accounts.len() < ctx.take_unwrap_ix().accounts.len());
+}
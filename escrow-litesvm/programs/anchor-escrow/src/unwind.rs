@@ -0,0 +1,51 @@
+use anchor_lang::{prelude::*, Event};
+use anchor_spl::token_interface::{close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TransferChecked};
+
+use crate::{constants::ESCROW_SEED, state::Escrow};
+
+/// The accounts every refund variant unwinds an escrow with: whatever is left in the
+/// vault goes back to the maker and the vault closes to them. The variants only differ
+/// in who signs and the event they emit, and close the escrow itself with `close =`.
+pub struct Unwind<'a, 'info> {
+    pub maker: AccountInfo<'info>,
+    pub mint_a: &'a InterfaceAccount<'info, Mint>,
+    pub maker_ata_a: &'a InterfaceAccount<'info, TokenAccount>,
+    pub escrow: &'a Account<'info, Escrow>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'a, 'info> Unwind<'a, 'info> {
+    /// Returns the vault to the maker, emitting `event` between the two steps
+    pub fn refund_and_close_vault(&self, event: impl Event) -> Result<()> {
+        let seed = self.escrow.seed.to_le_bytes();
+        let signer_seeds: [&[&[u8]]; 1] = [&[ESCROW_SEED, self.escrow.maker.as_ref(), &seed, &[self.escrow.bump]]];
+
+        // An empty vault has nothing to return, so go straight to closing it
+        if self.vault.amount > 0 {
+            let cpi_accounts = TransferChecked {
+                from: self.vault.to_account_info(),
+                to: self.maker_ata_a.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let cpi_context = CpiContext::new_with_signer(self.token_program.clone(), cpi_accounts, &signer_seeds);
+
+            log!("Refunding {} to the maker", self.vault.amount);
+            transfer_checked(cpi_context, self.vault.amount, self.mint_a.decimals)?;
+        }
+
+        emit!(event);
+
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.clone(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new_with_signer(self.token_program.clone(), cpi_accounts, &signer_seeds);
+
+        close_account(cpi_context)
+    }
+}
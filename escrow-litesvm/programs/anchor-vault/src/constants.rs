@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+#[constant]
+pub const STATE_SEED: &[u8] = b"state";
+#[constant]
+pub const VAULT_SEED: &[u8] = b"vault";
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Amount must be positive")]
+    InvalidAmount,
+    #[msg("Withdrawal would leave the vault below its rent-exempt minimum")]
+    InsufficientFunds,
+}
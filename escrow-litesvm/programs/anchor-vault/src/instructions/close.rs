@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{constants::{STATE_SEED, VAULT_SEED}, state::VaultState};
+
+#[derive(Accounts)]
+pub struct Close<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        seeds = [STATE_SEED, owner.key().as_ref()],
+        bump = state.state_bump,
+    )]
+    pub state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, state.key().as_ref()],
+        bump = state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Close<'info> {
+    // Everything in the vault, rent included, goes back to the owner
+    pub fn close(&mut self) -> Result<()> {
+        let state = self.state.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[VAULT_SEED, state.as_ref(), &[self.state.vault_bump]]];
+
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.owner.to_account_info(),
+        };
+
+        transfer(
+            CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, &signer_seeds),
+            self.vault.lamports(),
+        )
+    }
+}
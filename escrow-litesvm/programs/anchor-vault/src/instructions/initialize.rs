@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{constants::{ANCHOR_DISCRIMINATOR, STATE_SEED, VAULT_SEED}, state::VaultState};
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        payer = owner,
+        seeds = [STATE_SEED, owner.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + VaultState::INIT_SPACE,
+    )]
+    pub state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, state.key().as_ref()],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Initialize<'info> {
+    pub fn initialize(&mut self, bumps: &InitializeBumps) -> Result<()> {
+        self.state.set_inner(VaultState {
+            owner: self.owner.key(),
+            state_bump: bumps.state,
+            vault_bump: bumps.vault,
+        });
+
+        // Fund the vault up to rent exemption so small deposits can't be lost to it
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        let shortfall = rent_exempt.saturating_sub(self.vault.lamports());
+        if shortfall > 0 {
+            let cpi_accounts = Transfer {
+                from: self.owner.to_account_info(),
+                to: self.vault.to_account_info(),
+            };
+            transfer(CpiContext::new(self.system_program.to_account_info(), cpi_accounts), shortfall)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod close;
+pub mod initialize;
+pub mod payment;
+
+pub use close::*;
+pub use initialize::*;
+pub use payment::*;
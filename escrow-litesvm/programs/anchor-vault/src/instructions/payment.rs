@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{constants::{STATE_SEED, VAULT_SEED}, error::VaultError, state::VaultState};
+
+// Shared by deposit and withdraw, which only differ in direction
+#[derive(Accounts)]
+pub struct Payment<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        has_one = owner,
+        seeds = [STATE_SEED, owner.key().as_ref()],
+        bump = state.state_bump,
+    )]
+    pub state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, state.key().as_ref()],
+        bump = state.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Payment<'info> {
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        let cpi_accounts = Transfer {
+            from: self.owner.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+
+        transfer(CpiContext::new(self.system_program.to_account_info(), cpi_accounts), amount)
+    }
+
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        // The vault keeps its rent-exempt minimum until it is closed
+        let available = self.vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0));
+        require!(amount <= available, VaultError::InsufficientFunds);
+
+        let state = self.state.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[VAULT_SEED, state.as_ref(), &[self.state.vault_bump]]];
+
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.owner.to_account_info(),
+        };
+
+        transfer(CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, &signer_seeds), amount)
+    }
+}
@@ -0,0 +1,36 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod constants;
+mod error;
+mod instructions;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+use instructions::*;
+
+declare_id!("8ntVGTgtu8AUvCqEq6RbdXAooNba422qY183NKMfh1Tp");
+
+#[program]
+pub mod anchor_vault {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        ctx.accounts.initialize(&ctx.bumps)
+    }
+
+    pub fn deposit(ctx: Context<Payment>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit(amount)
+    }
+
+    pub fn withdraw(ctx: Context<Payment>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount)
+    }
+
+    pub fn close(ctx: Context<Close>) -> Result<()> {
+        ctx.accounts.close()
+    }
+}
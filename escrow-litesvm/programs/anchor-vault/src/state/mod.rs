@@ -0,0 +1,3 @@
+pub mod vault_state;
+
+pub use vault_state::*;
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+// The vault itself is a system account holding lamports, this only records its owner
+// and the bumps, so neither PDA is ever re-derived with `find_program_address`
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct VaultState {
+    pub owner: Pubkey,
+    pub state_bump: u8,
+    pub vault_bump: u8,
+}
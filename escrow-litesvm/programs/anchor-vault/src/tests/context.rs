@@ -0,0 +1,139 @@
+use {
+    crate::{
+        constants::{STATE_SEED, VAULT_SEED},
+        state::VaultState,
+    },
+    anchor_lang::{InstructionData, ToAccountMetas},
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, keys, send, svm_with_program},
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+pub fn state_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[STATE_SEED, owner.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn vault_address(state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[VAULT_SEED, state.as_ref()], &PROGRAM_ID).0
+}
+
+/// A funded owner with the program loaded. The vault is initialized unless
+/// `uninitialized` is used.
+pub struct VaultTestContext {
+    pub program: LiteSVM,
+    pub owner: Keypair,
+    pub state: Pubkey,
+    pub vault: Pubkey,
+}
+
+impl VaultTestContext {
+    pub fn new() -> Self {
+        let mut ctx = Self::uninitialized();
+        ctx.initialize().unwrap();
+        ctx
+    }
+
+    pub fn uninitialized() -> Self {
+        let owner = keys::keypair("owner");
+        let program = svm_with_program(&PROGRAM_ID, "anchor_vault", env!("CARGO_MANIFEST_DIR"), &[&owner]);
+
+        let state = state_address(&owner.pubkey());
+        let vault = vault_address(&state);
+
+        Self { program, owner, state, vault }
+    }
+
+    // Instruction builders take the owner explicitly so tests can pass someone else
+
+    pub fn initialize_ix(&self, owner: &Pubkey) -> Instruction {
+        let state = state_address(owner);
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Initialize {
+                owner: *owner,
+                state,
+                vault: vault_address(&state),
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Initialize {}.data(),
+        }
+    }
+
+    fn payment_accounts(&self, owner: &Pubkey) -> Vec<anchor_lang::prelude::AccountMeta> {
+        crate::accounts::Payment {
+            owner: *owner,
+            state: self.state,
+            vault: self.vault,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None)
+    }
+
+    pub fn deposit_ix(&self, owner: &Pubkey, amount: u64) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: self.payment_accounts(owner),
+            data: crate::instruction::Deposit { amount }.data(),
+        }
+    }
+
+    pub fn withdraw_ix(&self, owner: &Pubkey, amount: u64) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: self.payment_accounts(owner),
+            data: crate::instruction::Withdraw { amount }.data(),
+        }
+    }
+
+    pub fn close_ix(&self, owner: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Close {
+                owner: *owner,
+                state: self.state,
+                vault: self.vault,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Close {}.data(),
+        }
+    }
+
+    pub fn initialize(&mut self) -> TransactionResult {
+        let ix = self.initialize_ix(&self.owner.pubkey());
+        send(&mut self.program, &[ix], &self.owner)
+    }
+
+    pub fn deposit(&mut self, amount: u64) -> TransactionResult {
+        let ix = self.deposit_ix(&self.owner.pubkey(), amount);
+        send(&mut self.program, &[ix], &self.owner)
+    }
+
+    pub fn withdraw(&mut self, amount: u64) -> TransactionResult {
+        let ix = self.withdraw_ix(&self.owner.pubkey(), amount);
+        send(&mut self.program, &[ix], &self.owner)
+    }
+
+    pub fn close(&mut self) -> TransactionResult {
+        let ix = self.close_ix(&self.owner.pubkey());
+        send(&mut self.program, &[ix], &self.owner)
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.program, address)
+    }
+
+    pub fn rent_exempt_minimum(&self) -> u64 {
+        self.program.minimum_balance_for_rent_exemption(0)
+    }
+
+    pub fn state_data(&self) -> VaultState {
+        account::anchor_account(&self.program, &self.state)
+    }
+}
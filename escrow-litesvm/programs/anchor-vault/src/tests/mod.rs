@@ -0,0 +1,3 @@
+pub mod context;
+
+mod vault;
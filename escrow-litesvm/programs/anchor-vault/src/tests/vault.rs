@@ -0,0 +1,92 @@
+use {
+    super::context::VaultTestContext,
+    crate::error::VaultError,
+    anchor_lang::error::ErrorCode,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, keys::keypair, send, SIGNATURE_FEE},
+};
+
+#[test]
+fn should_initialize_a_rent_exempt_vault() {
+    let ctx = VaultTestContext::new();
+
+    let state = ctx.state_data();
+    assert_eq!(state.owner, ctx.owner.pubkey());
+    assert_eq!(ctx.lamports(&ctx.vault), ctx.rent_exempt_minimum());
+}
+
+#[test]
+fn should_deposit_and_withdraw() {
+    let mut ctx = VaultTestContext::new();
+    let owner = ctx.owner.pubkey();
+    let rent = ctx.rent_exempt_minimum();
+
+    let before = ctx.lamports(&owner);
+    ctx.deposit(2 * LAMPORTS_PER_SOL).unwrap();
+    assert_eq!(ctx.lamports(&ctx.vault), rent + 2 * LAMPORTS_PER_SOL);
+    assert_eq!(ctx.lamports(&owner), before - 2 * LAMPORTS_PER_SOL - SIGNATURE_FEE);
+
+    let before = ctx.lamports(&owner);
+    ctx.withdraw(LAMPORTS_PER_SOL).unwrap();
+    assert_eq!(ctx.lamports(&ctx.vault), rent + LAMPORTS_PER_SOL);
+    assert_eq!(ctx.lamports(&owner), before + LAMPORTS_PER_SOL - SIGNATURE_FEE);
+}
+
+#[test]
+fn should_reject_withdrawing_into_the_rent_reserve() {
+    let mut ctx = VaultTestContext::new();
+    ctx.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    assert_custom_error!(ctx.withdraw(LAMPORTS_PER_SOL + 1), VaultError::InsufficientFunds);
+
+    // Everything above the reserve can still be taken out
+    ctx.withdraw(LAMPORTS_PER_SOL).unwrap();
+    assert_eq!(ctx.lamports(&ctx.vault), ctx.rent_exempt_minimum());
+}
+
+#[test]
+fn should_reject_zero_amounts() {
+    let mut ctx = VaultTestContext::new();
+
+    assert_custom_error!(ctx.deposit(0), VaultError::InvalidAmount);
+    assert_custom_error!(ctx.withdraw(0), VaultError::InvalidAmount);
+}
+
+#[test]
+fn should_close_and_return_everything() {
+    let mut ctx = VaultTestContext::new();
+    let owner = ctx.owner.pubkey();
+    ctx.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    let held = ctx.lamports(&ctx.vault) + ctx.lamports(&ctx.state);
+    let before = ctx.lamports(&owner);
+    ctx.close().unwrap();
+
+    assert_eq!(ctx.lamports(&owner), before + held - SIGNATURE_FEE);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+    assert_eq!(ctx.lamports(&ctx.state), 0);
+
+    // The same owner can open a fresh vault afterwards
+    ctx.program.expire_blockhash();
+    ctx.initialize().unwrap();
+    assert_eq!(ctx.lamports(&ctx.vault), ctx.rent_exempt_minimum());
+}
+
+#[test]
+fn should_reject_someone_elses_vault() {
+    let mut ctx = VaultTestContext::new();
+    ctx.deposit(LAMPORTS_PER_SOL).unwrap();
+
+    let thief = keypair("thief");
+    ctx.program.airdrop(&thief.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    // The state is derived from its owner, so another signer fails its seeds
+    let withdraw_ix = ctx.withdraw_ix(&thief.pubkey(), LAMPORTS_PER_SOL);
+    assert_custom_error!(send(&mut ctx.program, &[withdraw_ix], &thief), ErrorCode::ConstraintSeeds);
+
+    let close_ix = ctx.close_ix(&thief.pubkey());
+    assert_custom_error!(send(&mut ctx.program, &[close_ix], &thief), ErrorCode::ConstraintSeeds);
+
+    assert_eq!(ctx.lamports(&ctx.vault), ctx.rent_exempt_minimum() + LAMPORTS_PER_SOL);
+}
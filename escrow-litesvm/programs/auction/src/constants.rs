@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+#[constant]
+pub const AUCTION_SEED: &[u8] = b"auction";
+#[constant]
+pub const BIDS_SEED: &[u8] = b"bids";
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum AuctionError {
+    #[msg("Reserve must cover the bids account's rent")]
+    InvalidReserve,
+    #[msg("End time must be in the future")]
+    InvalidEndTime,
+    #[msg("Only mints with no decimals and a supply of one can be auctioned")]
+    NotAnNft,
+    #[msg("The auction has ended")]
+    AuctionEnded,
+    #[msg("The auction has not ended yet")]
+    AuctionNotEnded,
+    #[msg("Bid is below the reserve or does not beat the highest bid")]
+    BidTooLow,
+    #[msg("The previous bidder is missing or does not match")]
+    InvalidPreviousBidder,
+    #[msg("The winner does not match the highest bidder")]
+    InvalidWinner,
+}
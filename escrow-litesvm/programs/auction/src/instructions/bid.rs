@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    constants::{AUCTION_SEED, BIDS_SEED},
+    error::AuctionError,
+    state::Auction,
+};
+
+#[derive(Accounts)]
+pub struct Bid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction.seller.as_ref(), auction.mint.as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+    #[account(
+        mut,
+        seeds = [BIDS_SEED, auction.key().as_ref()],
+        bump = auction.bids_bump,
+    )]
+    pub bids: SystemAccount<'info>,
+    /// CHECK: Only needed once there is a bid to refund, and then checked against its
+    /// bidder. Any owner is taken, so a leader who reassigns their wallet cannot stop
+    /// everyone else from outbidding them.
+    #[account(mut)]
+    pub previous_bidder: Option<UncheckedAccount<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Bid<'info> {
+    pub fn bid(&mut self, amount: u64) -> Result<()> {
+        require!(!self.auction.has_ended(Clock::get()?.unix_timestamp), AuctionError::AuctionEnded);
+        require!(
+            self.auction.minimum_bid().is_some_and(|minimum| amount >= minimum),
+            AuctionError::BidTooLow
+        );
+
+        let cpi_accounts = Transfer {
+            from: self.bidder.to_account_info(),
+            to: self.bids.to_account_info(),
+        };
+
+        transfer(CpiContext::new(self.system_program.to_account_info(), cpi_accounts), amount)?;
+
+        if let Some(outbid) = self.auction.highest_bidder {
+            let previous_bidder = self.previous_bidder.as_ref().ok_or(AuctionError::InvalidPreviousBidder)?;
+            require_keys_eq!(previous_bidder.key(), outbid, AuctionError::InvalidPreviousBidder);
+            self.refund(&previous_bidder.to_account_info(), self.auction.highest_bid)?;
+        }
+
+        self.auction.highest_bid = amount;
+        self.auction.highest_bidder = Some(self.bidder.key());
+
+        Ok(())
+    }
+
+    fn refund(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let auction = self.auction.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[BIDS_SEED, auction.as_ref(), &[self.auction.bids_bump]]];
+
+        let cpi_accounts = Transfer {
+            from: self.bids.to_account_info(),
+            to: to.clone(),
+        };
+
+        transfer(CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, &signer_seeds), amount)
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, AUCTION_SEED, BIDS_SEED},
+    error::AuctionError,
+    state::Auction,
+};
+
+#[derive(Accounts)]
+pub struct Create<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.decimals == 0 && mint.supply == 1 @ AuctionError::NotAnNft,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = seller,
+        seeds = [AUCTION_SEED, seller.key().as_ref(), mint.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Auction::INIT_SPACE,
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        seeds = [BIDS_SEED, auction.key().as_ref()],
+        bump,
+    )]
+    pub bids: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Create<'info> {
+    pub fn create(&mut self, reserve: u64, end_ts: i64, bumps: &CreateBumps) -> Result<()> {
+        // The first bid opens the bids account, so it must cover its rent on its own
+        require!(reserve >= Rent::get()?.minimum_balance(0), AuctionError::InvalidReserve);
+        require!(end_ts > Clock::get()?.unix_timestamp, AuctionError::InvalidEndTime);
+
+        self.auction.set_inner(Auction {
+            seller: self.seller.key(),
+            mint: self.mint.key(),
+            reserve,
+            end_ts,
+            highest_bid: 0,
+            highest_bidder: None,
+            bump: bumps.auction,
+            bids_bump: bumps.bids,
+        });
+
+        let cpi_accounts = TransferChecked {
+            from: self.seller_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.seller.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        transfer_checked(CpiContext::new(self.token_program.to_account_info(), cpi_accounts), 1, 0)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod bid;
+pub mod create;
+pub mod settle;
+
+pub use bid::*;
+pub use create::*;
+pub use settle::*;
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    constants::{AUCTION_SEED, BIDS_SEED},
+    error::AuctionError,
+    state::Auction,
+};
+
+#[derive(Accounts)]
+pub struct Settle<'info> {
+    /// Pays for the winner's token account if it does not exist yet
+    #[account(mut)]
+    pub settler: Signer<'info>,
+    /// CHECK: Checked against the auction. Like the winner, any owner is taken so
+    /// neither can keep the auction from settling by reassigning their wallet.
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+    /// CHECK: Checked against the auction's highest bidder
+    #[account(
+        constraint = winner.key() == auction.winner() @ AuctionError::InvalidWinner,
+    )]
+    pub winner: UncheckedAccount<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = settler,
+        associated_token::mint = mint,
+        associated_token::authority = winner,
+        associated_token::token_program = token_program,
+    )]
+    pub winner_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    // The auction's rent goes back to the seller, who paid it
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        has_one = mint,
+        seeds = [AUCTION_SEED, seller.key().as_ref(), mint.key().as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mut,
+        seeds = [BIDS_SEED, auction.key().as_ref()],
+        bump = auction.bids_bump,
+    )]
+    pub bids: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Settle<'info> {
+    pub fn settle(&mut self) -> Result<()> {
+        require!(self.auction.has_ended(Clock::get()?.unix_timestamp), AuctionError::AuctionNotEnded);
+
+        if self.bids.lamports() > 0 {
+            self.pay_seller()?;
+        }
+
+        let seller = self.seller.key();
+        let mint = self.mint.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[AUCTION_SEED, seller.as_ref(), mint.as_ref(), &[self.auction.bump]]];
+
+        let cpi_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            to: self.winner_ata.to_account_info(),
+            authority: self.auction.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        transfer_checked(CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, &signer_seeds), 1, 0)?;
+
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.seller.to_account_info(),
+            authority: self.auction.to_account_info(),
+        };
+
+        close_account(CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, &signer_seeds))
+    }
+
+    // Sweeps the whole balance rather than the winning bid, since anyone can top the
+    // PDA up and whatever is left below rent would fail the transfer
+    fn pay_seller(&self) -> Result<()> {
+        let auction = self.auction.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[BIDS_SEED, auction.as_ref(), &[self.auction.bids_bump]]];
+
+        let cpi_accounts = Transfer {
+            from: self.bids.to_account_info(),
+            to: self.seller.to_account_info(),
+        };
+
+        transfer(
+            CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, &signer_seeds),
+            self.bids.lamports(),
+        )
+    }
+}
@@ -0,0 +1,34 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod constants;
+mod error;
+mod instructions;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+use instructions::*;
+
+declare_id!("B3jaLsyptj52AyoyRupQDgiGmnu4sdeufD8PgvffjM82");
+
+#[program]
+pub mod auction {
+    use super::*;
+
+    pub fn create(ctx: Context<Create>, reserve: u64, end_ts: i64) -> Result<()> {
+        ctx.accounts.create(reserve, end_ts, &ctx.bumps)
+    }
+
+    /// Takes the lead, refunding whoever held it
+    pub fn bid(ctx: Context<Bid>, amount: u64) -> Result<()> {
+        ctx.accounts.bid(amount)
+    }
+
+    /// Anyone can settle once the auction has ended
+    pub fn settle(ctx: Context<Settle>) -> Result<()> {
+        ctx.accounts.settle()
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+// The NFT sits in this account's associated token account, and the highest
+// bid in the system-owned bids PDA, until the auction is settled
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Auction {
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub reserve: u64,
+    pub end_ts: i64,
+    pub highest_bid: u64,
+    pub highest_bidder: Option<Pubkey>,
+    pub bump: u8,
+    pub bids_bump: u8,
+}
+
+impl Auction {
+    /// The first bid must meet the reserve, every later one must beat the highest
+    pub fn minimum_bid(&self) -> Option<u64> {
+        match self.highest_bidder {
+            Some(_) => self.highest_bid.checked_add(1),
+            None => Some(self.reserve),
+        }
+    }
+
+    /// Who receives the NFT on settlement: the seller gets it back if nobody bid
+    pub fn winner(&self) -> Pubkey {
+        self.highest_bidder.unwrap_or(self.seller)
+    }
+
+    pub fn has_ended(&self, now: i64) -> bool {
+        now >= self.end_ts
+    }
+}
@@ -0,0 +1,3 @@
+pub mod auction;
+
+pub use auction::*;
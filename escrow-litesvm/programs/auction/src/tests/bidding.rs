@@ -0,0 +1,123 @@
+use {
+    super::context::{AuctionTestContext, RESERVE},
+    crate::error::AuctionError,
+    anchor_lang::solana_program::clock::Clock,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::warp_to_timestamp, SIGNATURE_FEE},
+};
+
+#[test]
+fn should_escrow_the_nft_on_create() {
+    let ctx = AuctionTestContext::new();
+
+    let auction = ctx.auction_data();
+    assert_eq!(auction.seller, ctx.seller.pubkey());
+    assert_eq!(auction.mint, ctx.nft);
+    assert_eq!(auction.reserve, RESERVE);
+    assert_eq!(auction.end_ts, ctx.end_ts);
+    assert_eq!(auction.highest_bidder, None);
+    assert_eq!(ctx.token_amount(&ctx.vault), 1);
+    assert_eq!(ctx.token_amount(&ctx.seller_ata), 0);
+}
+
+#[test]
+fn should_reject_an_end_time_in_the_past() {
+    let mut ctx = AuctionTestContext::uncreated();
+    let now = ctx.program.get_sysvar::<Clock>().unix_timestamp;
+
+    assert_custom_error!(ctx.create(RESERVE, now), AuctionError::InvalidEndTime);
+    assert_eq!(ctx.token_amount(&ctx.seller_ata), 1);
+}
+
+#[test]
+fn should_reject_bids_below_the_reserve() {
+    let mut ctx = AuctionTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    assert_custom_error!(ctx.bid(&alice, RESERVE - 1, None), AuctionError::BidTooLow);
+    assert_eq!(ctx.lamports(&ctx.bids), 0);
+}
+
+#[test]
+fn should_refund_the_outbid_bidder() {
+    let mut ctx = AuctionTestContext::new();
+    let (alice, bob) = (ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+    let alice_before = ctx.lamports(&alice.pubkey());
+
+    ctx.bid(&alice, RESERVE, None).unwrap();
+    assert_eq!(ctx.lamports(&ctx.bids), RESERVE);
+    assert_eq!(ctx.lamports(&alice.pubkey()), alice_before - RESERVE - SIGNATURE_FEE);
+
+    ctx.bid(&bob, 2 * LAMPORTS_PER_SOL, Some(alice.pubkey())).unwrap();
+
+    // Only the winning bid stays behind
+    assert_eq!(ctx.lamports(&ctx.bids), 2 * LAMPORTS_PER_SOL);
+    assert_eq!(ctx.lamports(&alice.pubkey()), alice_before - SIGNATURE_FEE);
+
+    let auction = ctx.auction_data();
+    assert_eq!(auction.highest_bid, 2 * LAMPORTS_PER_SOL);
+    assert_eq!(auction.highest_bidder, Some(bob.pubkey()));
+}
+
+#[test]
+fn should_outbid_a_leader_who_reassigned_their_wallet() {
+    let mut ctx = AuctionTestContext::new();
+    let (alice, bob) = (ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.bid(&alice, RESERVE, None).unwrap();
+    ctx.reassign(&alice);
+    let alice_before = ctx.lamports(&alice.pubkey());
+
+    ctx.bid(&bob, 2 * LAMPORTS_PER_SOL, Some(alice.pubkey())).unwrap();
+
+    assert_eq!(ctx.lamports(&alice.pubkey()), alice_before + RESERVE);
+    assert_eq!(ctx.auction_data().highest_bidder, Some(bob.pubkey()));
+}
+
+#[test]
+fn should_reject_a_reserve_below_the_bids_rent() {
+    let mut ctx = AuctionTestContext::uncreated();
+    let end_ts = ctx.end_ts;
+    let rent = ctx.program.minimum_balance_for_rent_exemption(0);
+
+    assert_custom_error!(ctx.create(rent - 1, end_ts), AuctionError::InvalidReserve);
+    assert_eq!(ctx.token_amount(&ctx.seller_ata), 1);
+}
+
+#[test]
+fn should_reject_bids_that_do_not_beat_the_highest() {
+    let mut ctx = AuctionTestContext::new();
+    let (alice, bob) = (ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.bid(&alice, 2 * LAMPORTS_PER_SOL, None).unwrap();
+
+    assert_custom_error!(ctx.bid(&bob, 2 * LAMPORTS_PER_SOL, Some(alice.pubkey())), AuctionError::BidTooLow);
+    assert_eq!(ctx.auction_data().highest_bidder, Some(alice.pubkey()));
+}
+
+#[test]
+fn should_reject_outbids_without_the_previous_bidder() {
+    let mut ctx = AuctionTestContext::new();
+    let (alice, bob) = (ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+    let seller = ctx.seller.pubkey();
+
+    ctx.bid(&alice, RESERVE, None).unwrap();
+
+    assert_custom_error!(ctx.bid(&bob, 2 * LAMPORTS_PER_SOL, None), AuctionError::InvalidPreviousBidder);
+    assert_custom_error!(
+        ctx.bid(&bob, 2 * LAMPORTS_PER_SOL, Some(seller)),
+        AuctionError::InvalidPreviousBidder
+    );
+    assert_eq!(ctx.lamports(&ctx.bids), RESERVE);
+}
+
+#[test]
+fn should_reject_bids_after_the_end() {
+    let mut ctx = AuctionTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    warp_to_timestamp(&mut ctx.program, ctx.end_ts);
+
+    assert_custom_error!(ctx.bid(&alice, RESERVE, None), AuctionError::AuctionEnded);
+}
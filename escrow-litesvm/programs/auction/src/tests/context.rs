@@ -0,0 +1,157 @@
+use {
+    crate::{
+        constants::{AUCTION_SEED, BIDS_SEED},
+        state::Auction,
+    },
+    anchor_lang::{solana_program::{clock::Clock, system_instruction}, InstructionData, ToAccountMetas},
+    anchor_spl::{associated_token::{self, get_associated_token_address}, token::spl_token},
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, keys, send, svm_with_program, token},
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+pub const RESERVE: u64 = LAMPORTS_PER_SOL;
+/// How long after creation bids are accepted
+pub const DURATION: i64 = 24 * 60 * 60;
+
+pub fn auction_address(seller: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[AUCTION_SEED, seller.as_ref(), mint.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn bids_address(auction: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[BIDS_SEED, auction.as_ref()], &PROGRAM_ID).0
+}
+
+/// A seller holding one NFT and two bidders. `new` puts the NFT up for auction,
+/// `uncreated` leaves it to the test.
+pub struct AuctionTestContext {
+    pub program: LiteSVM,
+    pub seller: Keypair,
+    pub alice: Keypair,
+    pub bob: Keypair,
+    pub nft: Pubkey,
+    pub seller_ata: Pubkey,
+    pub auction: Pubkey,
+    pub bids: Pubkey,
+    pub vault: Pubkey,
+    pub end_ts: i64,
+}
+
+impl AuctionTestContext {
+    pub fn new() -> Self {
+        let mut ctx = Self::uncreated();
+        ctx.create(RESERVE, ctx.end_ts).unwrap();
+        ctx
+    }
+
+    pub fn uncreated() -> Self {
+        let seller = keys::keypair("seller");
+        let alice = keys::keypair("alice");
+        let bob = keys::keypair("bob");
+        let mut program = svm_with_program(&PROGRAM_ID, "auction", env!("CARGO_MANIFEST_DIR"), &[&seller, &alice, &bob]);
+
+        let (nft, seller_ata) = token::mint_nft(&mut program, &seller, &keys::keypair("nft"));
+        let auction = auction_address(&seller.pubkey(), &nft);
+        let end_ts = program.get_sysvar::<Clock>().unix_timestamp + DURATION;
+
+        Self {
+            bids: bids_address(&auction),
+            vault: get_associated_token_address(&auction, &nft),
+            program,
+            seller,
+            alice,
+            bob,
+            nft,
+            seller_ata,
+            auction,
+            end_ts,
+        }
+    }
+
+    pub fn ata(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.nft)
+    }
+
+    pub fn create(&mut self, reserve: u64, end_ts: i64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Create {
+                seller: self.seller.pubkey(),
+                mint: self.nft,
+                seller_ata: self.seller_ata,
+                auction: self.auction,
+                bids: self.bids,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Create { reserve, end_ts }.data(),
+        };
+        send(&mut self.program, &[ix], &self.seller)
+    }
+
+    /// `previous_bidder` stands in for the optional account, `None` leaves it out
+    pub fn bid(&mut self, bidder: &Keypair, amount: u64, previous_bidder: Option<Pubkey>) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Bid {
+                bidder: bidder.pubkey(),
+                auction: self.auction,
+                bids: self.bids,
+                previous_bidder,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Bid { amount }.data(),
+        };
+        send(&mut self.program, &[ix], bidder)
+    }
+
+    pub fn settle(&mut self, settler: &Keypair, winner: &Pubkey) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Settle {
+                settler: settler.pubkey(),
+                seller: self.seller.pubkey(),
+                winner: *winner,
+                mint: self.nft,
+                winner_ata: self.ata(winner),
+                auction: self.auction,
+                bids: self.bids,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Settle {}.data(),
+        };
+        send(&mut self.program, &[ix], settler)
+    }
+
+    /// Signs `wallet` over to another owner program, as a leader would to block outbids
+    pub fn reassign(&mut self, wallet: &Keypair) {
+        let ix = system_instruction::assign(&wallet.pubkey(), &keys::keypair("other-program").pubkey());
+        send(&mut self.program, &[ix], wallet).unwrap();
+    }
+
+    pub fn token_amount(&self, address: &Pubkey) -> u64 {
+        token::token_amount(&self.program, address)
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.program, address)
+    }
+
+    pub fn auction_data(&self) -> Auction {
+        account::anchor_account(&self.program, &self.auction)
+    }
+}
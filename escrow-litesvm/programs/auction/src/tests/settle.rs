@@ -0,0 +1,98 @@
+use {
+    super::context::{AuctionTestContext, RESERVE},
+    crate::error::AuctionError,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::warp_to_timestamp},
+};
+
+#[test]
+fn should_pay_the_seller_and_deliver_to_the_winner() {
+    let mut ctx = AuctionTestContext::new();
+    let (seller, alice, bob) = (ctx.seller.insecure_clone(), ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.bid(&alice, RESERVE, None).unwrap();
+    ctx.bid(&bob, 3 * LAMPORTS_PER_SOL, Some(alice.pubkey())).unwrap();
+    warp_to_timestamp(&mut ctx.program, ctx.end_ts);
+
+    let rent = ctx.lamports(&ctx.auction) + ctx.lamports(&ctx.vault);
+    let seller_before = ctx.lamports(&seller.pubkey());
+
+    ctx.settle(&bob, &bob.pubkey()).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.ata(&bob.pubkey())), 1);
+    assert_eq!(ctx.lamports(&seller.pubkey()), seller_before + 3 * LAMPORTS_PER_SOL + rent);
+    assert_eq!(ctx.lamports(&ctx.bids), 0);
+    assert_eq!(ctx.lamports(&ctx.auction), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_settle_to_a_winner_who_reassigned_their_wallet() {
+    let mut ctx = AuctionTestContext::new();
+    let (seller, alice) = (ctx.seller.insecure_clone(), ctx.alice.insecure_clone());
+
+    ctx.bid(&alice, RESERVE, None).unwrap();
+    ctx.reassign(&alice);
+    warp_to_timestamp(&mut ctx.program, ctx.end_ts);
+
+    ctx.settle(&seller, &alice.pubkey()).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.ata(&alice.pubkey())), 1);
+    assert_eq!(ctx.lamports(&ctx.bids), 0);
+    assert_eq!(ctx.lamports(&ctx.auction), 0);
+}
+
+#[test]
+fn should_settle_bids_topped_up_by_a_donation() {
+    let mut ctx = AuctionTestContext::new();
+    let (seller, alice) = (ctx.seller.insecure_clone(), ctx.alice.insecure_clone());
+
+    ctx.bid(&alice, RESERVE, None).unwrap();
+    ctx.program.airdrop(&ctx.bids, 1).unwrap();
+    warp_to_timestamp(&mut ctx.program, ctx.end_ts);
+
+    let rent = ctx.lamports(&ctx.auction) + ctx.lamports(&ctx.vault);
+    let seller_before = ctx.lamports(&seller.pubkey());
+
+    ctx.settle(&alice, &alice.pubkey()).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.ata(&alice.pubkey())), 1);
+    assert_eq!(ctx.lamports(&seller.pubkey()), seller_before + RESERVE + 1 + rent);
+    assert_eq!(ctx.lamports(&ctx.bids), 0);
+}
+
+#[test]
+fn should_return_the_nft_when_nobody_bid() {
+    let mut ctx = AuctionTestContext::new();
+    let seller = ctx.seller.insecure_clone();
+
+    warp_to_timestamp(&mut ctx.program, ctx.end_ts);
+    ctx.settle(&seller, &seller.pubkey()).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.seller_ata), 1);
+    assert_eq!(ctx.lamports(&ctx.auction), 0);
+}
+
+#[test]
+fn should_reject_settling_before_the_end() {
+    let mut ctx = AuctionTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    ctx.bid(&alice, RESERVE, None).unwrap();
+
+    assert_custom_error!(ctx.settle(&alice, &alice.pubkey()), AuctionError::AuctionNotEnded);
+    assert_eq!(ctx.token_amount(&ctx.vault), 1);
+}
+
+#[test]
+fn should_reject_settling_to_anyone_but_the_winner() {
+    let mut ctx = AuctionTestContext::new();
+    let (alice, bob) = (ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.bid(&alice, RESERVE, None).unwrap();
+    warp_to_timestamp(&mut ctx.program, ctx.end_ts);
+
+    assert_custom_error!(ctx.settle(&bob, &bob.pubkey()), AuctionError::InvalidWinner);
+    assert_eq!(ctx.token_amount(&ctx.vault), 1);
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+#[constant]
+pub const CAMPAIGN_SEED: &[u8] = b"campaign";
+#[constant]
+pub const CONTRIBUTION_SEED: &[u8] = b"contribution";
+#[constant]
+pub const CREATOR_COUNTER_SEED: &[u8] = b"creator_counter";
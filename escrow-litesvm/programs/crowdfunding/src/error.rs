@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum CrowdfundingError {
+    #[msg("Amount must be positive")]
+    InvalidAmount,
+    #[msg("Deadline must be in the future")]
+    InvalidDeadline,
+    #[msg("The campaign has ended")]
+    CampaignEnded,
+    #[msg("The campaign has not ended yet")]
+    CampaignNotEnded,
+    #[msg("The campaign has not met its goal")]
+    GoalNotMet,
+    #[msg("The campaign met its goal, contributions are not refundable")]
+    GoalMet,
+    #[msg("The contribution's campaign is still open")]
+    CampaignNotClosed,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
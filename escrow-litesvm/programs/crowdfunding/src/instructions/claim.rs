@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{constants::CAMPAIGN_SEED, error::CrowdfundingError, instructions::{close_vault, pay_out}, state::Campaign};
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator,
+        has_one = mint,
+        seeds = [CAMPAIGN_SEED, creator.key().as_ref(), campaign.seed.to_le_bytes().as_ref()],
+        bump = campaign.bump,
+    )]
+    pub campaign: Account<'info, Campaign>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = campaign,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Claim<'info> {
+    // A funded campaign can be claimed without waiting for the deadline. The
+    // contribution accounts are left for their contributors, who paid for them and
+    // take the rent back with `close_contribution`.
+    pub fn claim(&mut self) -> Result<()> {
+        require!(self.campaign.is_funded(), CrowdfundingError::GoalNotMet);
+
+        let token_program = self.token_program.to_account_info();
+        pay_out(&self.campaign, &self.mint, &self.vault, &self.creator_ata, token_program.clone(), self.vault.amount)?;
+
+        close_vault(&self.campaign, &self.vault, &self.creator, token_program)
+    }
+}
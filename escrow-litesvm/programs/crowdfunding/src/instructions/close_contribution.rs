@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::CONTRIBUTION_SEED, error::CrowdfundingError, state::{Campaign, Contribution}};
+
+#[derive(Accounts)]
+pub struct CloseContribution<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    /// CHECK: Gone once claimed, or holding a later campaign under the same seed, which
+    /// `close_contribution` tells apart from the one the contribution went to
+    pub campaign: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = contributor,
+        has_one = campaign,
+        has_one = contributor,
+        seeds = [CONTRIBUTION_SEED, campaign.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump,
+    )]
+    pub contribution: Account<'info, Contribution>,
+}
+
+impl<'info> CloseContribution<'info> {
+    // A campaign is only closed by its claim or its last refund, and the last refund
+    // leaves no contribution of its own behind, so whatever is left owes nothing
+    pub fn close_contribution(&mut self) -> Result<()> {
+        let campaign = self.campaign.to_account_info();
+        if campaign.owner == &crate::ID {
+            let campaign = Campaign::try_deserialize(&mut campaign.try_borrow_data()?.as_ref())?;
+            require!(campaign.nonce != self.contribution.campaign_nonce, CrowdfundingError::CampaignNotClosed);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked}};
+
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, CAMPAIGN_SEED, CONTRIBUTION_SEED},
+    error::CrowdfundingError,
+    state::{Campaign, Contribution},
+};
+
+#[derive(Accounts)]
+pub struct Contribute<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = contributor,
+        associated_token::token_program = token_program,
+    )]
+    pub contributor_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [CAMPAIGN_SEED, campaign.creator.as_ref(), campaign.seed.to_le_bytes().as_ref()],
+        bump = campaign.bump,
+    )]
+    pub campaign: Account<'info, Campaign>,
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        seeds = [CONTRIBUTION_SEED, campaign.key().as_ref(), contributor.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Contribution::INIT_SPACE,
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = campaign,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Contribute<'info> {
+    // Contributions past the goal are still accepted until the deadline or the claim
+    pub fn contribute(&mut self, amount: u64, bumps: &ContributeBumps) -> Result<()> {
+        require!(amount > 0, CrowdfundingError::InvalidAmount);
+        require!(!self.campaign.has_ended(Clock::get()?.unix_timestamp), CrowdfundingError::CampaignEnded);
+
+        if self.contribution.campaign == Pubkey::default()
+            || self.contribution.campaign_nonce != self.campaign.nonce
+        {
+            self.contribution.set_inner(Contribution {
+                campaign: self.campaign.key(),
+                campaign_nonce: self.campaign.nonce,
+                contributor: self.contributor.key(),
+                amount: 0,
+                bump: bumps.contribution,
+            });
+        }
+
+        self.contribution.amount = self.contribution.amount
+            .checked_add(amount)
+            .ok_or(CrowdfundingError::MathOverflow)?;
+        self.campaign.raised = self.campaign.raised
+            .checked_add(amount)
+            .ok_or(CrowdfundingError::MathOverflow)?;
+
+        let cpi_accounts = TransferChecked {
+            from: self.contributor_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.contributor.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, CAMPAIGN_SEED, CREATOR_COUNTER_SEED},
+    error::CrowdfundingError,
+    state::{Campaign, CreatorCounter},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct Create<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [CAMPAIGN_SEED, creator.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Campaign::INIT_SPACE,
+    )]
+    pub campaign: Account<'info, Campaign>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [CREATOR_COUNTER_SEED, creator.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + CreatorCounter::INIT_SPACE,
+    )]
+    pub creator_counter: Account<'info, CreatorCounter>,
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = campaign,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Create<'info> {
+    pub fn create(&mut self, seed: u64, goal: u64, deadline: i64, bumps: &CreateBumps) -> Result<()> {
+        require!(goal > 0, CrowdfundingError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        require!(deadline > now, CrowdfundingError::InvalidDeadline);
+
+        let nonce = self.creator_counter.campaigns_created;
+        self.creator_counter.campaigns_created = nonce
+            .checked_add(1)
+            .ok_or(CrowdfundingError::MathOverflow)?;
+        self.creator_counter.bump = bumps.creator_counter;
+
+        self.campaign.set_inner(Campaign {
+            seed,
+            creator: self.creator.key(),
+            mint: self.mint.key(),
+            goal,
+            raised: 0,
+            deadline,
+            nonce,
+            bump: bumps.campaign,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+pub mod claim;
+pub mod close_contribution;
+pub mod contribute;
+pub mod create;
+pub mod refund;
+
+pub use claim::*;
+pub use close_contribution::*;
+pub use contribute::*;
+pub use create::*;
+pub use refund::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TransferChecked};
+
+use crate::{constants::CAMPAIGN_SEED, state::Campaign};
+
+/// Sends `amount` out of the vault, signed by the campaign
+pub(crate) fn pay_out<'info>(
+    campaign: &Account<'info, Campaign>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    destination: &InterfaceAccount<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: vault.to_account_info(),
+        to: destination.to_account_info(),
+        authority: campaign.to_account_info(),
+        mint: mint.to_account_info(),
+    };
+
+    let seed = campaign.seed.to_le_bytes();
+    let signer_seeds: [&[&[u8]]; 1] = [&[CAMPAIGN_SEED, campaign.creator.as_ref(), &seed, &[campaign.bump]]];
+
+    transfer_checked(CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds), amount, mint.decimals)
+}
+
+/// Closes the emptied vault, returning its rent to the creator who paid it
+pub(crate) fn close_vault<'info>(
+    campaign: &Account<'info, Campaign>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    creator: &AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: creator.clone(),
+        authority: campaign.to_account_info(),
+    };
+
+    let seed = campaign.seed.to_le_bytes();
+    let signer_seeds: [&[&[u8]]; 1] = [&[CAMPAIGN_SEED, campaign.creator.as_ref(), &seed, &[campaign.bump]]];
+
+    close_account(CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds))
+}
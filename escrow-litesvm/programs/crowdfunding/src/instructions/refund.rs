@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{
+    constants::{CAMPAIGN_SEED, CONTRIBUTION_SEED},
+    error::CrowdfundingError,
+    instructions::{close_vault, pay_out},
+    state::{Campaign, Contribution},
+};
+
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    /// Receives the campaign's rent when the last contribution is refunded
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = contributor,
+        associated_token::token_program = token_program,
+    )]
+    pub contributor_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = creator,
+        has_one = mint,
+        seeds = [CAMPAIGN_SEED, creator.key().as_ref(), campaign.seed.to_le_bytes().as_ref()],
+        bump = campaign.bump,
+    )]
+    pub campaign: Account<'info, Campaign>,
+    #[account(
+        mut,
+        close = contributor,
+        has_one = campaign,
+        has_one = contributor,
+        seeds = [CONTRIBUTION_SEED, campaign.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump,
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = campaign,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Refund<'info> {
+    pub fn refund(&mut self) -> Result<()> {
+        // Left open by an earlier campaign under this address, which was claimed and so
+        // owes it nothing. Closing it only returns the contributor's rent.
+        if self.contribution.campaign_nonce != self.campaign.nonce {
+            return Ok(());
+        }
+
+        require!(self.campaign.has_ended(Clock::get()?.unix_timestamp), CrowdfundingError::CampaignNotEnded);
+        require!(!self.campaign.is_funded(), CrowdfundingError::GoalMet);
+
+        let amount = self.contribution.amount;
+        self.campaign.raised = self.campaign.raised
+            .checked_sub(amount)
+            .ok_or(CrowdfundingError::MathOverflow)?;
+
+        let token_program = self.token_program.to_account_info();
+
+        if self.campaign.raised > 0 {
+            return pay_out(&self.campaign, &self.mint, &self.vault, &self.contributor_ata, token_program, amount);
+        }
+
+        // The last refund also takes anything sent straight to the vault, which would
+        // otherwise keep it from closing
+        pay_out(&self.campaign, &self.mint, &self.vault, &self.contributor_ata, token_program.clone(), self.vault.amount)?;
+
+        // Nothing is left to refund, so the creator gets the campaign's rent back
+        close_vault(&self.campaign, &self.vault, &self.creator, token_program)?;
+        self.campaign.close(self.creator.to_account_info())
+    }
+}
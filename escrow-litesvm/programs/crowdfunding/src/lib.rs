@@ -0,0 +1,43 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod constants;
+mod error;
+mod instructions;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+use instructions::*;
+
+declare_id!("FjTFcKZbbmgEjXoSf2dLifwMnQzDWjT6ypbrJxXzU9Q3");
+
+#[program]
+pub mod crowdfunding {
+    use super::*;
+
+    pub fn create(ctx: Context<Create>, seed: u64, goal: u64, deadline: i64) -> Result<()> {
+        ctx.accounts.create(seed, goal, deadline, &ctx.bumps)
+    }
+
+    pub fn contribute(ctx: Context<Contribute>, amount: u64) -> Result<()> {
+        ctx.accounts.contribute(amount, &ctx.bumps)
+    }
+
+    /// Releases the vault to the creator once the goal is met
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        ctx.accounts.claim()
+    }
+
+    /// Returns a contribution to a campaign that missed its goal
+    pub fn refund(ctx: Context<Refund>) -> Result<()> {
+        ctx.accounts.refund()
+    }
+
+    /// Returns the rent of a contribution whose campaign was claimed
+    pub fn close_contribution(ctx: Context<CloseContribution>) -> Result<()> {
+        ctx.accounts.close_contribution()
+    }
+}
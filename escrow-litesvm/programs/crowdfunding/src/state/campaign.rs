@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+// Contributions sit in this account's associated token account until the creator
+// claims them or the contributors take them back
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Campaign {
+    pub seed: u64,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub goal: u64,
+    /// Contributions still held, which refunds bring back down
+    pub raised: u64,
+    pub deadline: i64,
+    /// How many campaigns the creator had created before this one. The same seed gives
+    /// the same address once a claimed or refunded campaign is created again, possibly
+    /// in the same second, and contributions tell the two apart by this.
+    pub nonce: u64,
+    pub bump: u8,
+}
+
+impl Campaign {
+    pub fn is_funded(&self) -> bool {
+        self.raised >= self.goal
+    }
+
+    pub fn has_ended(&self, now: i64) -> bool {
+        now >= self.deadline
+    }
+}
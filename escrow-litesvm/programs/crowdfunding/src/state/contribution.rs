@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+// One per contributor and campaign, adding up all of their contributions
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Contribution {
+    pub campaign: Pubkey,
+    /// The campaign's `nonce`, so a contribution left open by an earlier campaign
+    /// under the same address starts over instead of counting towards this one
+    pub campaign_nonce: u64,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+// Created with the creator's first campaign and never closed, so the count only grows
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct CreatorCounter {
+    pub campaigns_created: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,7 @@
+pub mod campaign;
+pub mod contribution;
+pub mod creator_counter;
+
+pub use campaign::*;
+pub use contribution::*;
+pub use creator_counter::*;
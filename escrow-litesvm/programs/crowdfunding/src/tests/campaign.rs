@@ -0,0 +1,94 @@
+use {
+    super::context::{CrowdfundingTestContext, BALANCE, GOAL, SEED},
+    crate::error::CrowdfundingError,
+    anchor_lang::solana_program::clock::Clock,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::warp_to_timestamp},
+};
+
+#[test]
+fn should_create_an_empty_campaign() {
+    let ctx = CrowdfundingTestContext::new();
+
+    let campaign = ctx.campaign_data();
+    assert_eq!(campaign.seed, SEED);
+    assert_eq!(campaign.creator, ctx.creator.pubkey());
+    assert_eq!(campaign.mint, ctx.mint);
+    assert_eq!(campaign.goal, GOAL);
+    assert_eq!(campaign.raised, 0);
+    assert_eq!(campaign.deadline, ctx.deadline);
+    assert_eq!(campaign.nonce, 0);
+    assert_eq!(ctx.token_amount(&ctx.vault), 0);
+}
+
+#[test]
+fn should_reject_a_deadline_in_the_past() {
+    let mut ctx = CrowdfundingTestContext::uncreated();
+    let now = ctx.program.get_sysvar::<Clock>().unix_timestamp;
+
+    assert_custom_error!(ctx.create(GOAL, now), CrowdfundingError::InvalidDeadline);
+    assert!(ctx.program.get_account(&ctx.campaign).is_none());
+}
+
+#[test]
+fn should_reject_a_zero_goal() {
+    let mut ctx = CrowdfundingTestContext::uncreated();
+    let deadline = ctx.deadline;
+
+    assert_custom_error!(ctx.create(0, deadline), CrowdfundingError::InvalidAmount);
+}
+
+#[test]
+fn should_add_up_contributions_per_contributor() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+    let bob = ctx.bob.insecure_clone();
+
+    ctx.contribute(&alice, 100_000).unwrap();
+    ctx.contribute(&alice, 100_000).unwrap();
+    ctx.contribute(&bob, 300_000).unwrap();
+
+    assert_eq!(ctx.contribution_data(&alice.pubkey()).amount, 200_000);
+    assert_eq!(ctx.contribution_data(&bob.pubkey()).amount, 300_000);
+    assert_eq!(ctx.campaign_data().raised, 500_000);
+    assert_eq!(ctx.token_amount(&ctx.vault), 500_000);
+    assert_eq!(ctx.token_amount(&ctx.ata(&alice.pubkey())), BALANCE - 200_000);
+}
+
+#[test]
+fn should_start_contributions_over_when_the_campaign_is_created_again() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let (alice, bob) = (ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.claim_and_recreate(&[(&alice, 600_000), (&bob, 400_000)]);
+
+    // Alice's contribution to the claimed campaign is still open under the same address
+    assert_eq!(ctx.campaign_data().nonce, 1);
+    assert_eq!(ctx.contribution_data(&alice.pubkey()).campaign_nonce, 0);
+
+    ctx.contribute(&alice, 100_000).unwrap();
+
+    let contribution = ctx.contribution_data(&alice.pubkey());
+    assert_eq!(contribution.campaign_nonce, 1);
+    assert_eq!(contribution.amount, 100_000, "Contributions to the claimed campaign should not carry over");
+    assert_eq!(ctx.campaign_data().raised, 100_000);
+}
+
+#[test]
+fn should_reject_contributions_after_the_deadline() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    warp_to_timestamp(&mut ctx.program, ctx.deadline);
+
+    assert_custom_error!(ctx.contribute(&alice, 100_000), CrowdfundingError::CampaignEnded);
+    assert_eq!(ctx.token_amount(&ctx.vault), 0);
+}
+
+#[test]
+fn should_reject_empty_contributions() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    assert_custom_error!(ctx.contribute(&alice, 0), CrowdfundingError::InvalidAmount);
+}
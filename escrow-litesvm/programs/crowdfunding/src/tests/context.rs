@@ -0,0 +1,217 @@
+use {
+    crate::{
+        constants::{CAMPAIGN_SEED, CONTRIBUTION_SEED, CREATOR_COUNTER_SEED},
+        state::{Campaign, Contribution},
+    },
+    anchor_lang::{solana_program::clock::Clock, InstructionData, ToAccountMetas},
+    anchor_spl::{associated_token::{self, get_associated_token_address}, token::spl_token},
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, keys, send, svm_with_program, token},
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+pub const SEED: u64 = 1;
+pub const GOAL: u64 = 1_000_000;
+/// How long after creation the campaign runs
+pub const DURATION: i64 = 7 * 24 * 60 * 60;
+/// What each contributor starts with
+pub const BALANCE: u64 = 1_000_000;
+
+pub fn campaign_address(creator: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(&[CAMPAIGN_SEED, creator.as_ref(), &seed.to_le_bytes()], &PROGRAM_ID).0
+}
+
+pub fn contribution_address(campaign: &Pubkey, contributor: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CONTRIBUTION_SEED, campaign.as_ref(), contributor.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn creator_counter_address(creator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CREATOR_COUNTER_SEED, creator.as_ref()], &PROGRAM_ID).0
+}
+
+/// A creator and two funded contributors. `new` creates the campaign, `uncreated` leaves it to the test.
+pub struct CrowdfundingTestContext {
+    pub program: LiteSVM,
+    pub creator: Keypair,
+    pub alice: Keypair,
+    pub bob: Keypair,
+    pub mint: Pubkey,
+    pub campaign: Pubkey,
+    pub vault: Pubkey,
+    pub deadline: i64,
+}
+
+impl CrowdfundingTestContext {
+    pub fn new() -> Self {
+        let mut ctx = Self::uncreated();
+        ctx.create(GOAL, ctx.deadline).unwrap();
+        ctx
+    }
+
+    pub fn uncreated() -> Self {
+        let creator = keys::keypair("creator");
+        let alice = keys::keypair("alice");
+        let bob = keys::keypair("bob");
+        let mut program = svm_with_program(&PROGRAM_ID, "crowdfunding", env!("CARGO_MANIFEST_DIR"), &[&creator, &alice, &bob]);
+
+        let mint = token::create_mint(&mut program, &creator, &keys::keypair("mint"), 6, None);
+        for contributor in [&alice, &bob] {
+            let ata = token::create_ata(&mut program, contributor, &mint, &contributor.pubkey());
+            let mint_ix = spl_token::instruction::mint_to(&spl_token::ID, &mint, &ata, &creator.pubkey(), &[], BALANCE).unwrap();
+            send(&mut program, &[mint_ix], &creator).unwrap();
+        }
+
+        let campaign = campaign_address(&creator.pubkey(), SEED);
+        let deadline = program.get_sysvar::<Clock>().unix_timestamp + DURATION;
+
+        Self {
+            vault: get_associated_token_address(&campaign, &mint),
+            program,
+            creator,
+            alice,
+            bob,
+            mint,
+            campaign,
+            deadline,
+        }
+    }
+
+    pub fn ata(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.mint)
+    }
+
+    pub fn create(&mut self, goal: u64, deadline: i64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Create {
+                creator: self.creator.pubkey(),
+                mint: self.mint,
+                campaign: self.campaign,
+                creator_counter: creator_counter_address(&self.creator.pubkey()),
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Create { seed: SEED, goal, deadline }.data(),
+        };
+        send(&mut self.program, &[ix], &self.creator)
+    }
+
+    /// Funds and claims the campaign, then creates it again under the same seed, in the
+    /// same second
+    pub fn claim_and_recreate(&mut self, contributions: &[(&Keypair, u64)]) {
+        for (contributor, amount) in contributions {
+            self.contribute(contributor, *amount).unwrap();
+        }
+        let creator = self.creator.insecure_clone();
+        self.claim(&creator).unwrap();
+
+        self.program.expire_blockhash();
+        self.create(GOAL, self.deadline).unwrap();
+    }
+
+    /// Each contribution gets a fresh blockhash, since repeated ones are identical transactions
+    pub fn contribute(&mut self, contributor: &Keypair, amount: u64) -> TransactionResult {
+        self.program.expire_blockhash();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Contribute {
+                contributor: contributor.pubkey(),
+                mint: self.mint,
+                contributor_ata: self.ata(&contributor.pubkey()),
+                campaign: self.campaign,
+                contribution: contribution_address(&self.campaign, &contributor.pubkey()),
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Contribute { amount }.data(),
+        };
+        send(&mut self.program, &[ix], contributor)
+    }
+
+    pub fn claim(&mut self, creator: &Keypair) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Claim {
+                creator: creator.pubkey(),
+                mint: self.mint,
+                creator_ata: self.ata(&creator.pubkey()),
+                campaign: self.campaign,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Claim {}.data(),
+        };
+        send(&mut self.program, &[ix], creator)
+    }
+
+    pub fn refund(&mut self, contributor: &Keypair) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Refund {
+                contributor: contributor.pubkey(),
+                creator: self.creator.pubkey(),
+                mint: self.mint,
+                contributor_ata: self.ata(&contributor.pubkey()),
+                campaign: self.campaign,
+                contribution: contribution_address(&self.campaign, &contributor.pubkey()),
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Refund {}.data(),
+        };
+        send(&mut self.program, &[ix], contributor)
+    }
+
+    pub fn close_contribution(&mut self, contributor: &Keypair) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::CloseContribution {
+                contributor: contributor.pubkey(),
+                campaign: self.campaign,
+                contribution: contribution_address(&self.campaign, &contributor.pubkey()),
+            }
+            .to_account_metas(None),
+            data: crate::instruction::CloseContribution {}.data(),
+        };
+        send(&mut self.program, &[ix], contributor)
+    }
+
+    /// Mints `amount` straight into the vault, around the program
+    pub fn donate_to_vault(&mut self, amount: u64) {
+        let ix = spl_token::instruction::mint_to(&spl_token::ID, &self.mint, &self.vault, &self.creator.pubkey(), &[], amount).unwrap();
+        send(&mut self.program, &[ix], &self.creator).unwrap();
+    }
+
+    pub fn token_amount(&self, address: &Pubkey) -> u64 {
+        token::token_amount(&self.program, address)
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.program, address)
+    }
+
+    pub fn campaign_data(&self) -> Campaign {
+        account::anchor_account(&self.program, &self.campaign)
+    }
+
+    pub fn contribution_data(&self, contributor: &Pubkey) -> Contribution {
+        account::anchor_account(&self.program, &contribution_address(&self.campaign, contributor))
+    }
+}
@@ -0,0 +1,186 @@
+use {
+    super::context::{contribution_address, CrowdfundingTestContext, BALANCE, GOAL},
+    crate::error::CrowdfundingError,
+    anchor_lang::error::ErrorCode,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::warp_to_timestamp, keys::keypair, SIGNATURE_FEE},
+};
+
+#[test]
+fn should_release_a_funded_campaign_to_the_creator() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let (creator, alice, bob) = (ctx.creator.insecure_clone(), ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.contribute(&alice, 600_000).unwrap();
+    ctx.contribute(&bob, 600_000).unwrap();
+
+    // Funded before the deadline, so there is no need to wait for it
+    ctx.claim(&creator).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.ata(&creator.pubkey())), 1_200_000);
+    assert_eq!(ctx.lamports(&ctx.campaign), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_reject_claims_short_of_the_goal() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let (creator, alice) = (ctx.creator.insecure_clone(), ctx.alice.insecure_clone());
+
+    ctx.contribute(&alice, GOAL - 1).unwrap();
+    warp_to_timestamp(&mut ctx.program, ctx.deadline);
+
+    assert_custom_error!(ctx.claim(&creator), CrowdfundingError::GoalNotMet);
+    assert_eq!(ctx.token_amount(&ctx.vault), GOAL - 1);
+}
+
+#[test]
+fn should_reject_claims_from_anyone_but_the_creator() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    ctx.contribute(&alice, GOAL).unwrap();
+
+    let stranger = keypair("stranger");
+    ctx.program.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    // The campaign is derived from its creator, so a stranger fails its seeds
+    assert_custom_error!(ctx.claim(&stranger), ErrorCode::ConstraintSeeds);
+    assert_eq!(ctx.token_amount(&ctx.vault), GOAL);
+}
+
+#[test]
+fn should_refund_contributors_of_a_missed_goal() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let (creator, alice, bob) = (ctx.creator.insecure_clone(), ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.contribute(&alice, 300_000).unwrap();
+    ctx.contribute(&bob, 200_000).unwrap();
+    warp_to_timestamp(&mut ctx.program, ctx.deadline);
+
+    ctx.refund(&alice).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.ata(&alice.pubkey())), BALANCE);
+    assert_eq!(ctx.campaign_data().raised, 200_000);
+    assert_eq!(ctx.lamports(&contribution_address(&ctx.campaign, &alice.pubkey())), 0);
+
+    // The last refund closes the campaign, returning its rent to the creator
+    let rent = ctx.lamports(&ctx.campaign) + ctx.lamports(&ctx.vault);
+    let creator_before = ctx.lamports(&creator.pubkey());
+
+    ctx.refund(&bob).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.ata(&bob.pubkey())), BALANCE);
+    assert_eq!(ctx.lamports(&ctx.campaign), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+    assert_eq!(ctx.lamports(&creator.pubkey()), creator_before + rent);
+}
+
+#[test]
+fn should_not_refund_a_claimed_campaigns_contribution_out_of_its_successor() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let (alice, bob) = (ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.claim_and_recreate(&[(&alice, 600_000), (&bob, 400_000)]);
+    ctx.contribute(&bob, 300_000).unwrap();
+    warp_to_timestamp(&mut ctx.program, ctx.deadline);
+
+    // Alice's stale contribution is closed for its rent, without paying out Bob's tokens
+    ctx.refund(&alice).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.ata(&alice.pubkey())), BALANCE - 600_000);
+    assert_eq!(ctx.lamports(&contribution_address(&ctx.campaign, &alice.pubkey())), 0);
+    assert_eq!(ctx.campaign_data().raised, 300_000);
+    assert_eq!(ctx.token_amount(&ctx.vault), 300_000);
+
+    ctx.refund(&bob).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.ata(&bob.pubkey())), BALANCE - 400_000);
+    assert_eq!(ctx.lamports(&ctx.campaign), 0);
+}
+
+#[test]
+fn should_return_the_rent_of_a_claimed_campaigns_contribution() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let (creator, alice) = (ctx.creator.insecure_clone(), ctx.alice.insecure_clone());
+
+    ctx.contribute(&alice, GOAL).unwrap();
+    ctx.claim(&creator).unwrap();
+
+    let contribution = contribution_address(&ctx.campaign, &alice.pubkey());
+    let rent = ctx.lamports(&contribution);
+    let alice_before = ctx.lamports(&alice.pubkey());
+
+    ctx.close_contribution(&alice).unwrap();
+
+    assert_eq!(ctx.lamports(&contribution), 0);
+    assert_eq!(ctx.lamports(&alice.pubkey()), alice_before + rent - SIGNATURE_FEE);
+}
+
+#[test]
+fn should_close_a_claimed_campaigns_contribution_after_it_is_created_again() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let (alice, bob) = (ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.claim_and_recreate(&[(&alice, 600_000), (&bob, 400_000)]);
+
+    // The address holds a campaign again, but not the one Alice contributed to
+    ctx.close_contribution(&alice).unwrap();
+
+    assert_eq!(ctx.lamports(&contribution_address(&ctx.campaign, &alice.pubkey())), 0);
+    assert_eq!(ctx.campaign_data().raised, 0);
+}
+
+#[test]
+fn should_keep_contributions_to_an_open_campaign() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    ctx.contribute(&alice, GOAL).unwrap();
+    warp_to_timestamp(&mut ctx.program, ctx.deadline);
+
+    // Funded but not yet claimed, so the contribution still counts towards it
+    assert_custom_error!(ctx.close_contribution(&alice), CrowdfundingError::CampaignNotClosed);
+    assert_eq!(ctx.contribution_data(&alice.pubkey()).amount, GOAL);
+}
+
+#[test]
+fn should_close_a_vault_holding_donated_tokens_on_the_last_refund() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    ctx.contribute(&alice, 300_000).unwrap();
+    ctx.donate_to_vault(5);
+    warp_to_timestamp(&mut ctx.program, ctx.deadline);
+
+    // The donated tokens go out with the last refund instead of keeping the vault open
+    ctx.refund(&alice).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.ata(&alice.pubkey())), BALANCE + 5);
+    assert_eq!(ctx.lamports(&ctx.campaign), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_reject_refunds_before_the_deadline() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    ctx.contribute(&alice, 300_000).unwrap();
+
+    assert_custom_error!(ctx.refund(&alice), CrowdfundingError::CampaignNotEnded);
+    assert_eq!(ctx.token_amount(&ctx.vault), 300_000);
+}
+
+#[test]
+fn should_reject_refunds_of_a_funded_campaign() {
+    let mut ctx = CrowdfundingTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    ctx.contribute(&alice, GOAL).unwrap();
+    warp_to_timestamp(&mut ctx.program, ctx.deadline);
+
+    assert_custom_error!(ctx.refund(&alice), CrowdfundingError::GoalMet);
+    assert_eq!(ctx.token_amount(&ctx.vault), GOAL);
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+#[constant]
+pub const MARKETPLACE_SEED: &[u8] = b"marketplace";
+#[constant]
+pub const LISTING_SEED: &[u8] = b"listing";
+
+pub const MAX_NAME_LEN: usize = 32;
+
+/// Fees and royalties are in basis points of the price
+pub const BPS_DENOMINATOR: u16 = 10_000;
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// Creator shares are in percent of the royalty
+pub const SHARE_DENOMINATOR: u64 = 100;
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MarketplaceError {
+    #[msg("Marketplace name must be between 1 and 32 bytes")]
+    InvalidName,
+    #[msg("Fee exceeds the maximum")]
+    InvalidFee,
+    #[msg("Price must be positive")]
+    InvalidPrice,
+    #[msg("Royalty and marketplace fee together exceed the price")]
+    InvalidRoyalty,
+    #[msg("Only mints with no decimals and a supply of one can be listed")]
+    NotAnNft,
+    #[msg("The NFT's creators are missing or out of order")]
+    InvalidCreator,
+    #[msg("The metadata account is not the NFT's Token Metadata")]
+    InvalidMetadata,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{constants::{LISTING_SEED, MARKETPLACE_SEED}, instructions::release_nft, state::{Listing, Marketplace}};
+
+#[derive(Accounts)]
+pub struct Delist<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [MARKETPLACE_SEED, marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Box<Account<'info, Marketplace>>,
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        has_one = mint,
+        seeds = [LISTING_SEED, marketplace.key().as_ref(), mint.key().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Delist<'info> {
+    pub fn delist(&mut self) -> Result<()> {
+        release_nft(
+            &self.listing,
+            &self.marketplace.key(),
+            &self.mint,
+            &self.vault,
+            &self.seller_ata,
+            &self.seller,
+            self.token_program.to_account_info(),
+        )
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::{ANCHOR_DISCRIMINATOR, MARKETPLACE_SEED, MAX_FEE_BPS, MAX_NAME_LEN}, error::MarketplaceError, state::Marketplace};
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [MARKETPLACE_SEED, name.as_bytes()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Marketplace::INIT_SPACE,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Initialize<'info> {
+    pub fn initialize(&mut self, name: String, fee_bps: u16, bumps: &InitializeBumps) -> Result<()> {
+        require!(!name.is_empty() && name.len() <= MAX_NAME_LEN, MarketplaceError::InvalidName);
+        require!(fee_bps <= MAX_FEE_BPS, MarketplaceError::InvalidFee);
+
+        self.marketplace.set_inner(Marketplace {
+            admin: self.admin.key(),
+            treasury: self.treasury.key(),
+            fee_bps,
+            bump: bumps.marketplace,
+            name,
+        });
+
+        Ok(())
+    }
+}
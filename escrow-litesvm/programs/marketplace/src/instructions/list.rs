@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, BPS_DENOMINATOR, LISTING_SEED, MARKETPLACE_SEED},
+    error::MarketplaceError,
+    metadata::{self, METADATA_SEED, TOKEN_METADATA_PROGRAM_ID},
+    state::{Listing, Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct List<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.decimals == 0 && mint.supply == 1 @ MarketplaceError::NotAnNft,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: The mint's Token Metadata account, read for its royalty in `list`. It is
+    /// left empty for an NFT without metadata.
+    #[account(
+        seeds = [METADATA_SEED, TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.key().as_ref()],
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+        bump,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [MARKETPLACE_SEED, marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Box<Account<'info, Marketplace>>,
+    #[account(
+        init,
+        payer = seller,
+        seeds = [LISTING_SEED, marketplace.key().as_ref(), mint.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Listing::INIT_SPACE,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> List<'info> {
+    pub fn list(&mut self, price: u64, bumps: &ListBumps) -> Result<()> {
+        require!(price > 0, MarketplaceError::InvalidPrice);
+        let royalty_bps = metadata::read(&self.metadata, &self.mint.key())?
+            .map_or(0, |metadata| metadata.seller_fee_basis_points);
+        require!(
+            royalty_bps <= BPS_DENOMINATOR - self.marketplace.fee_bps,
+            MarketplaceError::InvalidRoyalty
+        );
+
+        self.listing.set_inner(Listing {
+            seller: self.seller.key(),
+            mint: self.mint.key(),
+            price,
+            royalty_bps,
+            bump: bumps.listing,
+        });
+
+        let cpi_accounts = TransferChecked {
+            from: self.seller_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.seller.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        transfer_checked(CpiContext::new(self.token_program.to_account_info(), cpi_accounts), 1, 0)
+    }
+}
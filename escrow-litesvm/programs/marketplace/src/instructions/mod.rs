@@ -0,0 +1,46 @@
+pub mod delist;
+pub mod initialize;
+pub mod list;
+pub mod purchase;
+
+pub use delist::*;
+pub use initialize::*;
+pub use list::*;
+pub use purchase::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TransferChecked};
+
+use crate::{constants::LISTING_SEED, state::Listing};
+
+/// Moves the listed NFT out of the vault to `destination` and closes the vault,
+/// returning its rent to the seller who paid it
+pub(crate) fn release_nft<'info>(
+    listing: &Account<'info, Listing>,
+    marketplace: &Pubkey,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    destination: &InterfaceAccount<'info, TokenAccount>,
+    seller: &AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let mint_key = mint.key();
+    let signer_seeds: [&[&[u8]]; 1] = [&[LISTING_SEED, marketplace.as_ref(), mint_key.as_ref(), &[listing.bump]]];
+
+    let cpi_accounts = TransferChecked {
+        from: vault.to_account_info(),
+        to: destination.to_account_info(),
+        authority: listing.to_account_info(),
+        mint: mint.to_account_info(),
+    };
+
+    transfer_checked(CpiContext::new_with_signer(token_program.clone(), cpi_accounts, &signer_seeds), 1, 0)?;
+
+    let cpi_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: seller.clone(),
+        authority: listing.to_account_info(),
+    };
+
+    close_account(CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds))
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{LISTING_SEED, MARKETPLACE_SEED, SHARE_DENOMINATOR},
+    error::MarketplaceError,
+    instructions::release_nft,
+    metadata::{self, METADATA_SEED, TOKEN_METADATA_PROGRAM_ID},
+    state::{Listing, Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct Purchase<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    /// CHECK: Checked against the listing. Any owner is taken so the seller can't keep
+    /// the NFT from selling by reassigning their wallet.
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: The mint's Token Metadata account, read for its creators in `purchase`
+    #[account(
+        seeds = [METADATA_SEED, TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.key().as_ref()],
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+        bump,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        has_one = treasury,
+        seeds = [MARKETPLACE_SEED, marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Box<Account<'info, Marketplace>>,
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+    // The listing's rent goes back to the seller, who paid it
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        has_one = mint,
+        seeds = [LISTING_SEED, marketplace.key().as_ref(), mint.key().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Purchase<'info> {
+    pub fn purchase(&mut self, creators: &[AccountInfo<'info>]) -> Result<()> {
+        let split = self.listing.split(self.marketplace.fee_bps)?;
+
+        let mut seller_amount = split.seller;
+        if !self.pay_out(&self.treasury.to_account_info(), split.fee)? {
+            seller_amount = seller_amount
+                .checked_add(split.fee)
+                .ok_or(MarketplaceError::MathOverflow)?;
+        }
+        seller_amount = seller_amount
+            .checked_add(self.pay_creators(creators, split.royalty)?)
+            .ok_or(MarketplaceError::MathOverflow)?;
+
+        self.pay(&self.seller.to_account_info(), seller_amount)?;
+
+        release_nft(
+            &self.listing,
+            &self.marketplace.key(),
+            &self.mint,
+            &self.vault,
+            &self.buyer_ata,
+            &self.seller,
+            self.token_program.to_account_info(),
+        )
+    }
+
+    // Split by the creators' shares, with whatever rounds down or can't be paid out
+    // returned for the seller. So is the whole royalty of an NFT with no creators.
+    fn pay_creators(&self, accounts: &[AccountInfo<'info>], royalty: u64) -> Result<u64> {
+        if royalty == 0 {
+            return Ok(0);
+        }
+
+        let creators = metadata::read(&self.metadata, &self.mint.key())?
+            .and_then(|metadata| metadata.creators)
+            .unwrap_or_default();
+        require!(accounts.len() == creators.len(), MarketplaceError::InvalidCreator);
+
+        let mut unpaid = royalty;
+        for (creator, account) in creators.iter().zip(accounts) {
+            require_keys_eq!(account.key(), creator.address, MarketplaceError::InvalidCreator);
+
+            let amount = royalty
+                .checked_mul(creator.share as u64)
+                .ok_or(MarketplaceError::MathOverflow)?
+                / SHARE_DENOMINATOR;
+            if self.pay_out(account, amount)? {
+                unpaid -= amount;
+            }
+        }
+
+        Ok(unpaid)
+    }
+
+    // A system transfer can't leave a new account below the rent minimum, so a share
+    // too small to open the treasury or a creator's wallet is kept for the seller
+    // rather than failing the purchase. Returns whether `amount` was paid.
+    fn pay_out(&self, to: &AccountInfo<'info>, amount: u64) -> Result<bool> {
+        if to.lamports() == 0 && amount < Rent::get()?.minimum_balance(0) {
+            return Ok(false);
+        }
+
+        self.pay(to, amount)?;
+        Ok(true)
+    }
+
+    fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let cpi_accounts = Transfer {
+            from: self.buyer.to_account_info(),
+            to: to.clone(),
+        };
+
+        transfer(CpiContext::new(self.system_program.to_account_info(), cpi_accounts), amount)
+    }
+}
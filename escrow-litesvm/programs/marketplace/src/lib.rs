@@ -0,0 +1,38 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod constants;
+mod error;
+mod instructions;
+pub mod metadata;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+use instructions::*;
+
+declare_id!("G4nSBd2hT6KsjVwk88Nwn3riUFzCvvN1B7tkso2qD7Ui");
+
+#[program]
+pub mod marketplace {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, name: String, fee_bps: u16) -> Result<()> {
+        ctx.accounts.initialize(name, fee_bps, &ctx.bumps)
+    }
+
+    pub fn list(ctx: Context<List>, price: u64) -> Result<()> {
+        ctx.accounts.list(price, &ctx.bumps)
+    }
+
+    pub fn delist(ctx: Context<Delist>) -> Result<()> {
+        ctx.accounts.delist()
+    }
+
+    /// Takes the creators in the NFT's metadata, in order, as remaining accounts
+    pub fn purchase<'info>(ctx: Context<'_, '_, 'info, 'info, Purchase<'info>>) -> Result<()> {
+        ctx.accounts.purchase(ctx.remaining_accounts)
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+
+// Metaplex Token Metadata, which records an NFT's royalty and the creators sharing it
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// Metadata accounts are derived from this, Token Metadata's program ID and the mint
+pub const METADATA_SEED: &[u8] = b"metadata";
+
+// `Key::MetadataV1`, the first byte of every metadata account
+pub const METADATA_V1_KEY: u8 = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    /// Percent of the royalty, adding up to 100 across the creators
+    pub share: u8,
+}
+
+/// The leading fields of a Token Metadata `Metadata` account, up to its creators.
+/// Everything after them is left unread.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Metadata {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+}
+
+// `metadata` must already be pinned to the mint's metadata address, which only Token
+// Metadata can create. Left empty, the NFT has no metadata and so no royalty.
+pub(crate) fn read(metadata: &AccountInfo, mint: &Pubkey) -> Result<Option<Metadata>> {
+    if metadata.owner != &TOKEN_METADATA_PROGRAM_ID {
+        return Ok(None);
+    }
+
+    let metadata = Metadata::deserialize(&mut &metadata.try_borrow_data()?[..])
+        .map_err(|_| error!(MarketplaceError::InvalidMetadata))?;
+    require!(metadata.key == METADATA_V1_KEY && metadata.mint == *mint, MarketplaceError::InvalidMetadata);
+
+    Ok(Some(metadata))
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::BPS_DENOMINATOR, error::MarketplaceError};
+
+// The NFT sits in this account's associated token account until it is bought or delisted
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Listing {
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    /// In lamports
+    pub price: u64,
+    /// The NFT's `seller_fee_basis_points` when it was listed, paid to the creators
+    /// named in its metadata
+    pub royalty_bps: u16,
+    pub bump: u8,
+}
+
+/// Where a purchase's lamports go
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Split {
+    pub fee: u64,
+    pub royalty: u64,
+    pub seller: u64,
+}
+
+fn share(price: u64, bps: u16) -> Result<u64> {
+    let share = price as u128 * bps as u128 / BPS_DENOMINATOR as u128;
+    u64::try_from(share).map_err(|_| MarketplaceError::MathOverflow.into())
+}
+
+impl Listing {
+    pub fn split(&self, fee_bps: u16) -> Result<Split> {
+        let fee = share(self.price, fee_bps)?;
+        let royalty = share(self.price, self.royalty_bps)?;
+        let seller = self.price
+            .checked_sub(fee)
+            .and_then(|rest| rest.checked_sub(royalty))
+            .ok_or(MarketplaceError::MathOverflow)?;
+        Ok(Split { fee, royalty, seller })
+    }
+}
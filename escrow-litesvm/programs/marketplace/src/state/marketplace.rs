@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_NAME_LEN;
+
+// Fees are paid straight to the treasury wallet, so the program never holds them
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Marketplace {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub bump: u8,
+    #[max_len(MAX_NAME_LEN)]
+    pub name: String,
+}
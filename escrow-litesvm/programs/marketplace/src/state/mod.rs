@@ -0,0 +1,5 @@
+pub mod listing;
+pub mod marketplace;
+
+pub use listing::*;
+pub use marketplace::*;
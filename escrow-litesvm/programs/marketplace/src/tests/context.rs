@@ -0,0 +1,225 @@
+use {
+    crate::{
+        constants::{LISTING_SEED, MARKETPLACE_SEED},
+        metadata::{Creator, Metadata, METADATA_SEED, METADATA_V1_KEY, TOKEN_METADATA_PROGRAM_ID},
+        state::Listing,
+    },
+    anchor_lang::{solana_program::system_instruction, AnchorSerialize, InstructionData, ToAccountMetas},
+    anchor_spl::{associated_token::{self, get_associated_token_address}, token::spl_token},
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_account::Account,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_keypair::Keypair,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, keys, send, svm_with_program, token},
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+pub const NAME: &str = "turbin3";
+pub const FEE_BPS: u16 = 250;
+pub const PRICE: u64 = 2 * LAMPORTS_PER_SOL;
+
+pub fn marketplace_address(name: &str) -> Pubkey {
+    Pubkey::find_program_address(&[MARKETPLACE_SEED, name.as_bytes()], &PROGRAM_ID).0
+}
+
+pub fn listing_address(marketplace: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[LISTING_SEED, marketplace.as_ref(), mint.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[METADATA_SEED, TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()], &TOKEN_METADATA_PROGRAM_ID).0
+}
+
+pub fn creator(address: Pubkey, share: u8) -> Creator {
+    Creator { address, verified: false, share }
+}
+
+/// An initialized marketplace and a seller holding one NFT, not yet listed. The NFT
+/// has no metadata, and so no royalty, until `write_metadata`.
+pub struct MarketplaceTestContext {
+    pub program: LiteSVM,
+    pub admin: Keypair,
+    pub treasury: Pubkey,
+    pub seller: Keypair,
+    pub buyer: Keypair,
+    pub creator: Pubkey,
+    pub marketplace: Pubkey,
+    pub nft: Pubkey,
+    pub seller_ata: Pubkey,
+    pub listing: Pubkey,
+    pub vault: Pubkey,
+}
+
+impl MarketplaceTestContext {
+    pub fn new() -> Self {
+        let admin = keys::keypair("admin");
+        let seller = keys::keypair("seller");
+        let buyer = keys::keypair("buyer");
+        let mut program = svm_with_program(&PROGRAM_ID, "marketplace", env!("CARGO_MANIFEST_DIR"), &[&admin, &seller, &buyer]);
+
+        // Funded so they are rent exempt before receiving any share of a sale
+        let treasury = keys::keypair("treasury").pubkey();
+        let creator = keys::keypair("creator").pubkey();
+        for wallet in [&treasury, &creator] {
+            program.airdrop(wallet, LAMPORTS_PER_SOL).unwrap();
+        }
+
+        let (nft, seller_ata) = token::mint_nft(&mut program, &seller, &keys::keypair("nft"));
+        let marketplace = marketplace_address(NAME);
+        let listing = listing_address(&marketplace, &nft);
+
+        let mut ctx = Self {
+            vault: get_associated_token_address(&listing, &nft),
+            program,
+            admin,
+            treasury,
+            seller,
+            buyer,
+            creator,
+            marketplace,
+            nft,
+            seller_ata,
+            listing,
+        };
+
+        let ix = ctx.initialize_ix(NAME, FEE_BPS);
+        send(&mut ctx.program, &[ix], &ctx.admin).unwrap();
+        ctx
+    }
+
+    pub fn initialize_ix(&self, name: &str, fee_bps: u16) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Initialize {
+                admin: self.admin.pubkey(),
+                treasury: self.treasury,
+                marketplace: marketplace_address(name),
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Initialize { name: name.to_string(), fee_bps }.data(),
+        }
+    }
+
+    pub fn list_ix(&self, price: u64) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::List {
+                seller: self.seller.pubkey(),
+                mint: self.nft,
+                metadata: metadata_address(&self.nft),
+                seller_ata: self.seller_ata,
+                marketplace: self.marketplace,
+                listing: self.listing,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::List { price }.data(),
+        }
+    }
+
+    pub fn delist_ix(&self, seller: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Delist {
+                seller: *seller,
+                mint: self.nft,
+                seller_ata: get_associated_token_address(seller, &self.nft),
+                marketplace: self.marketplace,
+                listing: self.listing,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Delist {}.data(),
+        }
+    }
+
+    /// `creators` go after the named accounts, in the order the metadata lists them
+    pub fn purchase_ix(&self, creators: &[Pubkey]) -> Instruction {
+        let buyer = self.buyer.pubkey();
+        let mut accounts = crate::accounts::Purchase {
+            buyer,
+            seller: self.seller.pubkey(),
+            mint: self.nft,
+            metadata: metadata_address(&self.nft),
+            buyer_ata: get_associated_token_address(&buyer, &self.nft),
+            marketplace: self.marketplace,
+            treasury: self.treasury,
+            listing: self.listing,
+            vault: self.vault,
+            associated_token_program: associated_token::ID,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(creators.iter().map(|creator| AccountMeta::new(*creator, false)));
+
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts,
+            data: crate::instruction::Purchase {}.data(),
+        }
+    }
+
+    pub fn list(&mut self, price: u64) -> TransactionResult {
+        let ix = self.list_ix(price);
+        send(&mut self.program, &[ix], &self.seller)
+    }
+
+    pub fn delist(&mut self) -> TransactionResult {
+        let ix = self.delist_ix(&self.seller.pubkey());
+        send(&mut self.program, &[ix], &self.seller)
+    }
+
+    pub fn purchase(&mut self, creators: &[Pubkey]) -> TransactionResult {
+        let ix = self.purchase_ix(creators);
+        send(&mut self.program, &[ix], &self.buyer)
+    }
+
+    /// Token Metadata's account for the NFT, with a royalty of `bps` shared by `creators`
+    pub fn write_metadata(&mut self, bps: u16, creators: Vec<Creator>) {
+        let metadata = Metadata {
+            key: METADATA_V1_KEY,
+            update_authority: self.seller.pubkey(),
+            mint: self.nft,
+            name: "Listed #1".to_string(),
+            symbol: "LST".to_string(),
+            uri: "https://example.com/1.json".to_string(),
+            seller_fee_basis_points: bps,
+            creators: Some(creators),
+        };
+        let data = metadata.try_to_vec().unwrap();
+        let lamports = self.program.minimum_balance_for_rent_exemption(data.len());
+        let account = Account { lamports, data, owner: TOKEN_METADATA_PROGRAM_ID, executable: false, rent_epoch: 0 };
+
+        self.program.set_account(metadata_address(&self.nft), account).unwrap();
+    }
+
+    /// Signs `wallet` over to another owner program, as a seller would to block purchases
+    pub fn reassign(&mut self, wallet: &Keypair) {
+        let ix = system_instruction::assign(&wallet.pubkey(), &keys::keypair("other-program").pubkey());
+        send(&mut self.program, &[ix], wallet).unwrap();
+    }
+
+    pub fn token_amount(&self, address: &Pubkey) -> u64 {
+        token::token_amount(&self.program, address)
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.program, address)
+    }
+
+    pub fn listing_data(&self) -> Listing {
+        account::anchor_account(&self.program, &self.listing)
+    }
+}
@@ -0,0 +1,79 @@
+use {
+    super::context::{creator, MarketplaceTestContext, FEE_BPS, PRICE},
+    crate::{constants::BPS_DENOMINATOR, error::MarketplaceError},
+    anchor_lang::error::ErrorCode,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, send, SIGNATURE_FEE},
+};
+
+#[test]
+fn should_take_the_nft_into_custody_when_listed() {
+    let mut ctx = MarketplaceTestContext::new();
+    ctx.write_metadata(500, vec![creator(ctx.creator, 100)]);
+
+    ctx.list(PRICE).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.seller_ata), 0);
+    assert_eq!(ctx.token_amount(&ctx.vault), 1);
+
+    let listing = ctx.listing_data();
+    assert_eq!((listing.seller, listing.mint, listing.price), (ctx.seller.pubkey(), ctx.nft, PRICE));
+    assert_eq!(listing.royalty_bps, 500, "The royalty should come from the NFT's metadata");
+}
+
+#[test]
+fn should_reject_a_zero_price() {
+    let mut ctx = MarketplaceTestContext::new();
+
+    assert_custom_error!(ctx.list(0), MarketplaceError::InvalidPrice);
+}
+
+#[test]
+fn should_list_an_nft_without_metadata_royalty_free() {
+    let mut ctx = MarketplaceTestContext::new();
+
+    ctx.list(PRICE).unwrap();
+
+    assert_eq!(ctx.listing_data().royalty_bps, 0);
+}
+
+#[test]
+fn should_reject_a_royalty_that_leaves_nothing_after_the_fee() {
+    let mut ctx = MarketplaceTestContext::new();
+
+    ctx.write_metadata(BPS_DENOMINATOR - FEE_BPS + 1, vec![creator(ctx.creator, 100)]);
+    assert_custom_error!(ctx.list(PRICE), MarketplaceError::InvalidRoyalty);
+
+    // Exactly the rest of the price is allowed
+    ctx.write_metadata(BPS_DENOMINATOR - FEE_BPS, vec![creator(ctx.creator, 100)]);
+    ctx.list(PRICE).unwrap();
+}
+
+#[test]
+fn should_return_the_nft_and_rent_on_delist() {
+    let mut ctx = MarketplaceTestContext::new();
+    let seller = ctx.seller.pubkey();
+
+    ctx.list(PRICE).unwrap();
+    let held = ctx.lamports(&ctx.listing) + ctx.lamports(&ctx.vault);
+    let before = ctx.lamports(&seller);
+
+    ctx.delist().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.seller_ata), 1);
+    assert_eq!(ctx.lamports(&ctx.listing), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+    assert_eq!(ctx.lamports(&seller), before + held - SIGNATURE_FEE);
+}
+
+#[test]
+fn should_reject_delisting_by_anyone_but_the_seller() {
+    let mut ctx = MarketplaceTestContext::new();
+    ctx.list(PRICE).unwrap();
+
+    let buyer = ctx.buyer.insecure_clone();
+    let delist_ix = ctx.delist_ix(&buyer.pubkey());
+    assert_custom_error!(send(&mut ctx.program, &[delist_ix], &buyer), ErrorCode::ConstraintHasOne);
+
+    assert_eq!(ctx.token_amount(&ctx.vault), 1);
+}
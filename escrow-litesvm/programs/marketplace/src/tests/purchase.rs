@@ -0,0 +1,138 @@
+use {
+    super::context::{creator, MarketplaceTestContext, PRICE},
+    crate::error::MarketplaceError,
+    anchor_spl::associated_token::get_associated_token_address,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, keys::keypair, SIGNATURE_FEE},
+};
+
+// 2.5% of the 2 SOL price
+const FEE: u64 = 50_000_000;
+
+#[test]
+fn should_pay_the_seller_and_fee_and_deliver_the_nft() {
+    let mut ctx = MarketplaceTestContext::new();
+    let (buyer, seller) = (ctx.buyer.pubkey(), ctx.seller.pubkey());
+    ctx.list(PRICE).unwrap();
+
+    let rent = ctx.lamports(&ctx.listing) + ctx.lamports(&ctx.vault);
+    let seller_before = ctx.lamports(&seller);
+    let treasury_before = ctx.lamports(&ctx.treasury);
+    let buyer_before = ctx.lamports(&buyer);
+
+    ctx.purchase(&[]).unwrap();
+
+    let buyer_ata = get_associated_token_address(&buyer, &ctx.nft);
+    assert_eq!(ctx.token_amount(&buyer_ata), 1);
+    assert_eq!(ctx.lamports(&ctx.treasury), treasury_before + FEE);
+    assert_eq!(ctx.lamports(&seller), seller_before + PRICE - FEE + rent);
+    assert_eq!(ctx.lamports(&buyer), buyer_before - PRICE - ctx.lamports(&buyer_ata) - SIGNATURE_FEE);
+    assert_eq!(ctx.lamports(&ctx.listing), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_pay_the_royalty_to_the_creator() {
+    let mut ctx = MarketplaceTestContext::new();
+    let seller = ctx.seller.pubkey();
+    ctx.write_metadata(500, vec![creator(ctx.creator, 100)]);
+    ctx.list(PRICE).unwrap();
+
+    let rent = ctx.lamports(&ctx.listing) + ctx.lamports(&ctx.vault);
+    let seller_before = ctx.lamports(&seller);
+    let creator_before = ctx.lamports(&ctx.creator);
+
+    ctx.purchase(&[ctx.creator]).unwrap();
+
+    let royalty = PRICE / 20;
+    assert_eq!(ctx.lamports(&ctx.creator), creator_before + royalty);
+    assert_eq!(ctx.lamports(&seller), seller_before + PRICE - FEE - royalty + rent);
+}
+
+#[test]
+fn should_split_the_royalty_by_the_creators_shares() {
+    let mut ctx = MarketplaceTestContext::new();
+    let cosigner = keypair("cosigner").pubkey();
+    ctx.program.airdrop(&cosigner, PRICE).unwrap();
+    ctx.write_metadata(500, vec![creator(ctx.creator, 70), creator(cosigner, 30)]);
+    ctx.list(PRICE).unwrap();
+
+    let creator_before = ctx.lamports(&ctx.creator);
+    let cosigner_before = ctx.lamports(&cosigner);
+
+    ctx.purchase(&[ctx.creator, cosigner]).unwrap();
+
+    let royalty = PRICE / 20;
+    assert_eq!(ctx.lamports(&ctx.creator), creator_before + royalty * 70 / 100);
+    assert_eq!(ctx.lamports(&cosigner), cosigner_before + royalty * 30 / 100);
+}
+
+#[test]
+fn should_keep_a_share_too_small_to_open_a_wallet_for_the_seller() {
+    let mut ctx = MarketplaceTestContext::new();
+    let seller = ctx.seller.pubkey();
+    let unopened = keypair("unopened").pubkey();
+    ctx.write_metadata(500, vec![creator(ctx.creator, 50), creator(unopened, 50)]);
+
+    // Each creator's half of the royalty is below the rent minimum
+    let price = ctx.program.minimum_balance_for_rent_exemption(0) * 20;
+    ctx.list(price).unwrap();
+
+    let rent = ctx.lamports(&ctx.listing) + ctx.lamports(&ctx.vault);
+    let seller_before = ctx.lamports(&seller);
+    let creator_before = ctx.lamports(&ctx.creator);
+
+    ctx.purchase(&[ctx.creator, unopened]).unwrap();
+
+    let (fee, share) = (price / 40, price / 40);
+    assert_eq!(ctx.lamports(&ctx.creator), creator_before + share, "A funded creator is paid however small the share");
+    assert_eq!(ctx.lamports(&unopened), 0);
+    assert_eq!(ctx.lamports(&seller), seller_before + price - fee - share + rent);
+}
+
+#[test]
+fn should_reject_a_purchase_without_the_nfts_creators() {
+    let mut ctx = MarketplaceTestContext::new();
+    ctx.write_metadata(500, vec![creator(ctx.creator, 100)]);
+    ctx.list(PRICE).unwrap();
+
+    assert_custom_error!(ctx.purchase(&[]), MarketplaceError::InvalidCreator);
+    assert_custom_error!(ctx.purchase(&[Pubkey::new_unique()]), MarketplaceError::InvalidCreator);
+
+    assert_eq!(ctx.token_amount(&ctx.vault), 1);
+}
+
+#[test]
+fn should_ignore_creators_of_a_royalty_free_nft() {
+    let mut ctx = MarketplaceTestContext::new();
+    ctx.list(PRICE).unwrap();
+    let creator_before = ctx.lamports(&ctx.creator);
+
+    ctx.purchase(&[ctx.creator]).unwrap();
+
+    assert_eq!(ctx.lamports(&ctx.creator), creator_before);
+}
+
+#[test]
+fn should_sell_for_a_seller_who_reassigned_their_wallet() {
+    let mut ctx = MarketplaceTestContext::new();
+    let seller = ctx.seller.insecure_clone();
+    ctx.list(PRICE).unwrap();
+    ctx.reassign(&seller);
+
+    ctx.purchase(&[]).unwrap();
+
+    assert_eq!(ctx.token_amount(&get_associated_token_address(&ctx.buyer.pubkey(), &ctx.nft)), 1);
+    assert_eq!(ctx.lamports(&ctx.listing), 0);
+}
+
+#[test]
+fn should_not_sell_a_delisted_nft() {
+    let mut ctx = MarketplaceTestContext::new();
+    ctx.list(PRICE).unwrap();
+    ctx.delist().unwrap();
+
+    assert!(ctx.purchase(&[]).is_err(), "The listing should be gone");
+    assert_eq!(ctx.token_amount(&ctx.seller_ata), 1);
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+#[constant]
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+#[constant]
+pub const SIGNER_SEED: &[u8] = b"signer";
+#[constant]
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+
+/// Approvals are a bitmap over the owners' indexes
+pub const MAX_OWNERS: usize = 16;
+pub const MAX_INSTRUCTION_ACCOUNTS: usize = 24;
+pub const MAX_INSTRUCTION_DATA: usize = 512;
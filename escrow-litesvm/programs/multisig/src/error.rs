@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MultisigError {
+    #[msg("Owners must be unique, and at most 16")]
+    InvalidOwners,
+    #[msg("Threshold must be between 1 and the number of owners")]
+    InvalidThreshold,
+    #[msg("Signer is not an owner of the multisig")]
+    NotAnOwner,
+    #[msg("Proposed instruction exceeds the account or data limit")]
+    InstructionTooLarge,
+    #[msg("Proposal was already executed")]
+    AlreadyExecuted,
+    #[msg("Proposal has not reached the approval threshold")]
+    ThresholdNotMet,
+    #[msg("Remaining accounts do not match the proposed instruction")]
+    AccountsMismatch,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::{MULTISIG_SEED, PROPOSAL_SEED}, error::MultisigError, state::{Multisig, Proposal}};
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [PROPOSAL_SEED, multisig.key().as_ref(), proposal.index.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl<'info> Approve<'info> {
+    // Approving twice is harmless, the bit is already set
+    pub fn approve(&mut self) -> Result<()> {
+        require!(!self.proposal.executed, MultisigError::AlreadyExecuted);
+        let owner_index = self.multisig
+            .owner_index(&self.owner.key())
+            .ok_or(MultisigError::NotAnOwner)?;
+
+        self.proposal.approvals |= 1 << owner_index;
+
+        Ok(())
+    }
+}
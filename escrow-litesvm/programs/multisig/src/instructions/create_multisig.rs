@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::{ANCHOR_DISCRIMINATOR, MAX_OWNERS, MULTISIG_SEED, SIGNER_SEED}, error::MultisigError, state::Multisig};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [MULTISIG_SEED, creator.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Multisig::INIT_SPACE,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        seeds = [SIGNER_SEED, multisig.key().as_ref()],
+        bump,
+    )]
+    pub signer: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateMultisig<'info> {
+    pub fn create_multisig(&mut self, seed: u64, owners: Vec<Pubkey>, threshold: u8, bumps: &CreateMultisigBumps) -> Result<()> {
+        require!(!owners.is_empty() && owners.len() <= MAX_OWNERS, MultisigError::InvalidOwners);
+        let unique = owners.iter().enumerate().all(|(i, owner)| !owners[..i].contains(owner));
+        require!(unique, MultisigError::InvalidOwners);
+        require!(threshold > 0 && threshold as usize <= owners.len(), MultisigError::InvalidThreshold);
+
+        self.multisig.set_inner(Multisig {
+            creator: self.creator.key(),
+            seed,
+            owners,
+            threshold,
+            proposal_count: 0,
+            bump: bumps.multisig,
+            signer_bump: bumps.signer,
+        });
+
+        Ok(())
+    }
+}
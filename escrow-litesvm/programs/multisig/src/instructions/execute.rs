@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::{AccountMeta, Instruction}, program::invoke_signed};
+
+use crate::{constants::{MULTISIG_SEED, PROPOSAL_SEED, SIGNER_SEED}, error::MultisigError, state::{Multisig, Proposal}};
+
+// Anyone can execute a proposal once enough owners approved it
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [PROPOSAL_SEED, multisig.key().as_ref(), proposal.index.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [SIGNER_SEED, multisig.key().as_ref()],
+        bump = multisig.signer_bump,
+    )]
+    pub signer: SystemAccount<'info>,
+}
+
+impl<'info> Execute<'info> {
+    pub fn execute(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(!self.proposal.executed, MultisigError::AlreadyExecuted);
+        require!(
+            self.proposal.approval_count() >= self.multisig.threshold as u32,
+            MultisigError::ThresholdNotMet
+        );
+
+        let proposed = &self.proposal.instruction;
+        let (program, accounts) = remaining_accounts
+            .split_last()
+            .ok_or(MultisigError::AccountsMismatch)?;
+        require!(
+            program.key() == proposed.program_id
+                && accounts.len() == proposed.accounts.len()
+                && accounts.iter().zip(&proposed.accounts).all(|(info, meta)| info.key() == meta.pubkey),
+            MultisigError::AccountsMismatch
+        );
+
+        let instruction = Instruction {
+            program_id: proposed.program_id,
+            accounts: proposed.accounts
+                .iter()
+                .map(|meta| AccountMeta { pubkey: meta.pubkey, is_signer: meta.is_signer, is_writable: meta.is_writable })
+                .collect(),
+            data: proposed.data.clone(),
+        };
+
+        // Marked first, so the instruction cannot re-enter and execute it again
+        self.proposal.executed = true;
+        self.proposal.exit(&crate::ID)?;
+
+        let multisig = self.multisig.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[SIGNER_SEED, multisig.as_ref(), &[self.multisig.signer_bump]]];
+
+        invoke_signed(&instruction, remaining_accounts, &signer_seeds)?;
+
+        Ok(())
+    }
+}
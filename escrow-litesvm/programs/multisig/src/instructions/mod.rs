@@ -0,0 +1,9 @@
+pub mod approve;
+pub mod create_multisig;
+pub mod execute;
+pub mod propose;
+
+pub use approve::*;
+pub use create_multisig::*;
+pub use execute::*;
+pub use propose::*;
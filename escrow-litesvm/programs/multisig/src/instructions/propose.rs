@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, MAX_INSTRUCTION_ACCOUNTS, MAX_INSTRUCTION_DATA, MULTISIG_SEED, PROPOSAL_SEED},
+    error::MultisigError,
+    state::{Multisig, Proposal, ProposedInstruction},
+};
+
+#[derive(Accounts)]
+#[instruction(instruction: ProposedInstruction)]
+pub struct Propose<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [PROPOSAL_SEED, multisig.key().as_ref(), multisig.proposal_count.to_le_bytes().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Proposal::space(&instruction),
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Propose<'info> {
+    pub fn propose(&mut self, instruction: ProposedInstruction, bumps: &ProposeBumps) -> Result<()> {
+        let owner_index = self.multisig
+            .owner_index(&self.proposer.key())
+            .ok_or(MultisigError::NotAnOwner)?;
+        require!(
+            instruction.accounts.len() <= MAX_INSTRUCTION_ACCOUNTS && instruction.data.len() <= MAX_INSTRUCTION_DATA,
+            MultisigError::InstructionTooLarge
+        );
+
+        let index = self.multisig.proposal_count;
+        self.multisig.proposal_count = index
+            .checked_add(1)
+            .ok_or(MultisigError::MathOverflow)?;
+
+        self.proposal.set_inner(Proposal {
+            multisig: self.multisig.key(),
+            proposer: self.proposer.key(),
+            index,
+            instruction,
+            approvals: 1 << owner_index,
+            executed: false,
+            bump: bumps.proposal,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod constants;
+mod error;
+mod instructions;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+use instructions::*;
+use state::ProposedInstruction;
+
+declare_id!("99uDnMUHeXMFY7SVthEWk2HzS7VFx4XRgSAQUUjABJxM");
+
+#[program]
+pub mod multisig {
+    use super::*;
+
+    pub fn create_multisig(ctx: Context<CreateMultisig>, seed: u64, owners: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        ctx.accounts.create_multisig(seed, owners, threshold, &ctx.bumps)
+    }
+
+    /// Proposes one instruction for the multisig's signer to execute. The proposer's
+    /// approval is counted.
+    pub fn propose(ctx: Context<Propose>, instruction: ProposedInstruction) -> Result<()> {
+        ctx.accounts.propose(instruction, &ctx.bumps)
+    }
+
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        ctx.accounts.approve()
+    }
+
+    /// Takes the proposed instruction's accounts, in order, followed by its program
+    /// as remaining accounts
+    pub fn execute<'info>(ctx: Context<'_, '_, 'info, 'info, Execute<'info>>) -> Result<()> {
+        ctx.accounts.execute(ctx.remaining_accounts)
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_OWNERS;
+
+// Instructions are executed by a separate system-owned signer PDA rather than this
+// account, so the signer can hold SOL, own token accounts and pay for accounts it creates
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Multisig {
+    pub creator: Pubkey,
+    pub seed: u64,
+    #[max_len(MAX_OWNERS)]
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    /// Index of the next proposal
+    pub proposal_count: u64,
+    pub bump: u8,
+    pub signer_bump: u8,
+}
+
+impl Multisig {
+    pub fn owner_index(&self, key: &Pubkey) -> Option<usize> {
+        self.owners.iter().position(|owner| owner == key)
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProposedAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProposedInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposedAccount>,
+    pub data: Vec<u8>,
+}
+
+impl ProposedInstruction {
+    pub fn space(&self) -> usize {
+        32 + 4 + self.accounts.len() * (32 + 1 + 1) + 4 + self.data.len()
+    }
+}
+
+// Sized for its instruction when proposed, so it has no InitSpace
+#[account]
+#[derive(Debug)]
+pub struct Proposal {
+    pub multisig: Pubkey,
+    pub proposer: Pubkey,
+    pub index: u64,
+    pub instruction: ProposedInstruction,
+    /// Bit `i` is set once owner `i` approved
+    pub approvals: u16,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub fn space(instruction: &ProposedInstruction) -> usize {
+        32 + 32 + 8 + instruction.space() + 2 + 1 + 1
+    }
+
+    pub fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+}
@@ -0,0 +1,153 @@
+use {
+    crate::{
+        constants::{MULTISIG_SEED, PROPOSAL_SEED, SIGNER_SEED},
+        state::{Proposal, ProposedAccount, ProposedInstruction},
+    },
+    anchor_lang::{InstructionData, ToAccountMetas},
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_instruction::{AccountMeta, Instruction},
+    solana_keypair::Keypair,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, keys, send, svm_with_program},
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+const SEED: u64 = 1;
+
+pub fn multisig_address(creator: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(&[MULTISIG_SEED, creator.as_ref(), &seed.to_le_bytes()], &PROGRAM_ID).0
+}
+
+pub fn signer_address(multisig: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SIGNER_SEED, multisig.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn proposal_address(multisig: &Pubkey, index: u64) -> Pubkey {
+    Pubkey::find_program_address(&[PROPOSAL_SEED, multisig.as_ref(), &index.to_le_bytes()], &PROGRAM_ID).0
+}
+
+/// What the multisig stores for `instruction`
+pub fn proposed(instruction: &Instruction) -> ProposedInstruction {
+    ProposedInstruction {
+        program_id: instruction.program_id,
+        accounts: instruction.accounts
+            .iter()
+            .map(|meta| ProposedAccount { pubkey: meta.pubkey, is_signer: meta.is_signer, is_writable: meta.is_writable })
+            .collect(),
+        data: instruction.data.clone(),
+    }
+}
+
+/// Three owners and an outsider. `new` creates the multisig with the given threshold
+/// and funds its signer, `uncreated` leaves both to the test.
+pub struct MultisigTestContext {
+    pub program: LiteSVM,
+    pub owners: [Keypair; 3],
+    pub outsider: Keypair,
+    pub multisig: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl MultisigTestContext {
+    pub fn new(threshold: u8) -> Self {
+        let mut ctx = Self::uncreated();
+        let owners = ctx.owners.each_ref().map(|owner| owner.pubkey()).to_vec();
+        ctx.create(owners, threshold).unwrap();
+        ctx.program.airdrop(&ctx.signer, 10 * LAMPORTS_PER_SOL).unwrap();
+        ctx
+    }
+
+    pub fn uncreated() -> Self {
+        let owners = ["alice", "bob", "carol"].map(keys::keypair);
+        let outsider = keys::keypair("outsider");
+        let program = svm_with_program(&PROGRAM_ID, "multisig", env!("CARGO_MANIFEST_DIR"), &[&owners[0], &owners[1], &owners[2], &outsider]);
+
+        let multisig = multisig_address(&owners[0].pubkey(), SEED);
+        let signer = signer_address(&multisig);
+
+        Self { program, owners, outsider, multisig, signer }
+    }
+
+    pub fn create(&mut self, owners: Vec<Pubkey>, threshold: u8) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::CreateMultisig {
+                creator: self.owners[0].pubkey(),
+                multisig: self.multisig,
+                signer: self.signer,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::CreateMultisig { seed: SEED, owners, threshold }.data(),
+        };
+        send(&mut self.program, &[ix], &self.owners[0])
+    }
+
+    pub fn next_proposal(&self) -> Pubkey {
+        let count = account::anchor_account::<crate::state::Multisig>(&self.program, &self.multisig).proposal_count;
+        proposal_address(&self.multisig, count)
+    }
+
+    pub fn propose(&mut self, proposer: &Keypair, instruction: &Instruction) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Propose {
+                proposer: proposer.pubkey(),
+                multisig: self.multisig,
+                proposal: self.next_proposal(),
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Propose { instruction: proposed(instruction) }.data(),
+        };
+        send(&mut self.program, &[ix], proposer)
+    }
+
+    pub fn approve(&mut self, owner: &Keypair, proposal: &Pubkey) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Approve {
+                owner: owner.pubkey(),
+                multisig: self.multisig,
+                proposal: *proposal,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Approve {}.data(),
+        };
+        send(&mut self.program, &[ix], owner)
+    }
+
+    /// The proposed instruction's accounts are passed as they were proposed, except
+    /// that the signer cannot sign the outer transaction
+    pub fn execute(&mut self, executor: &Keypair, proposal: &Pubkey) -> TransactionResult {
+        let instruction = self.proposal_data(proposal).instruction;
+
+        let mut accounts = crate::accounts::Execute {
+            executor: executor.pubkey(),
+            multisig: self.multisig,
+            proposal: *proposal,
+            signer: self.signer,
+        }
+        .to_account_metas(None);
+        accounts.extend(instruction.accounts.iter().map(|meta| AccountMeta {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer && meta.pubkey != self.signer,
+            is_writable: meta.is_writable,
+        }));
+        accounts.push(AccountMeta::new_readonly(instruction.program_id, false));
+
+        let ix = Instruction { program_id: PROGRAM_ID, accounts, data: crate::instruction::Execute {}.data() };
+        send(&mut self.program, &[ix], executor)
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.program, address)
+    }
+
+    pub fn proposal_data(&self, proposal: &Pubkey) -> Proposal {
+        account::anchor_account(&self.program, proposal)
+    }
+}
@@ -0,0 +1,124 @@
+use {
+    super::context::{proposal_address, MultisigTestContext},
+    crate::error::MultisigError,
+    anchor_escrow::{constants::{ESCROW_SEED, MAKER_COUNTER_SEED}, state::Escrow},
+    anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas},
+    anchor_spl::{
+        associated_token::{self, get_associated_token_address},
+        token::spl_token,
+    },
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{artifact, assert_custom_error, keys, send, token},
+};
+
+const SEED: u64 = 42;
+const DEPOSIT: u64 = 1_000;
+const RECEIVE: u64 = 2_500;
+
+/// The multisig's signer holds mint A, and anchor-escrow is loaded next to the multisig
+fn setup() -> (MultisigTestContext, Pubkey, Pubkey) {
+    let mut ctx = MultisigTestContext::new(2);
+    ctx.program.add_program(
+        anchor_escrow::ID,
+        &artifact::program_bytes("anchor_escrow", concat!(env!("CARGO_MANIFEST_DIR"), "/../anchor-escrow")),
+    );
+
+    let payer = ctx.owners[0].insecure_clone();
+    let mint_a = token::create_mint(&mut ctx.program, &payer, &keys::keypair("mint_a"), 6, None);
+    let mint_b = token::create_mint(&mut ctx.program, &payer, &keys::keypair("mint_b"), 6, None);
+
+    let signer_ata_a = token::create_ata(&mut ctx.program, &payer, &mint_a, &ctx.signer);
+    let mint_ix = spl_token::instruction::mint_to(&spl_token::ID, &mint_a, &signer_ata_a, &payer.pubkey(), &[], DEPOSIT).unwrap();
+    send(&mut ctx.program, &[mint_ix], &payer).unwrap();
+
+    (ctx, mint_a, mint_b)
+}
+
+fn escrow_address(maker: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[ESCROW_SEED, maker.as_ref(), &SEED.to_le_bytes()], &anchor_escrow::ID).0
+}
+
+/// anchor-escrow's make with the multisig's signer as the maker, who pays the escrow
+/// and vault rent out of its own lamports
+fn make_ix(ctx: &MultisigTestContext, mint_a: &Pubkey, mint_b: &Pubkey) -> Instruction {
+    let escrow = escrow_address(&ctx.signer);
+    Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Make {
+            maker: ctx.signer,
+            mint_a: *mint_a,
+            mint_b: *mint_b,
+            maker_ata_a: get_associated_token_address(&ctx.signer, mint_a),
+            escrow,
+            vault: get_associated_token_address(&escrow, mint_a),
+            associated_token_program: associated_token::ID,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            maker_counter: Pubkey::find_program_address(&[MAKER_COUNTER_SEED, ctx.signer.as_ref()], &anchor_escrow::ID).0,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Make { seed: SEED, deposit: DEPOSIT, receive: RECEIVE, expires_at: 0, starts_at: 0 }.data(),
+    }
+}
+
+#[test]
+fn should_make_and_refund_an_escrow_as_the_multisig() {
+    let (mut ctx, mint_a, mint_b) = setup();
+    let [alice, bob, carol] = ctx.owners.each_ref().map(|owner| owner.insecure_clone());
+    let signer_ata_a = get_associated_token_address(&ctx.signer, &mint_a);
+    let escrow = escrow_address(&ctx.signer);
+    let vault = get_associated_token_address(&escrow, &mint_a);
+
+    let make_ix = make_ix(&ctx, &mint_a, &mint_b);
+
+    let make_proposal = proposal_address(&ctx.multisig, 0);
+    ctx.propose(&alice, &make_ix).unwrap();
+    ctx.approve(&bob, &make_proposal).unwrap();
+    ctx.execute(&carol, &make_proposal).unwrap();
+
+    let account = ctx.program.get_account(&escrow).expect("Escrow should be created");
+    let data = Escrow::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!((data.maker, data.mint_a, data.mint_b), (ctx.signer, mint_a, mint_b));
+    assert_eq!((data.deposit_total, data.receive), (DEPOSIT, RECEIVE));
+    assert_eq!(token::token_amount(&ctx.program, &vault), DEPOSIT);
+    assert_eq!(token::token_amount(&ctx.program, &signer_ata_a), 0);
+
+    // Refunding needs the owners' approval too
+    let refund_ix = Instruction {
+        program_id: anchor_escrow::ID,
+        accounts: anchor_escrow::accounts::Refund {
+            maker: ctx.signer,
+            mint_a,
+            maker_ata_a: signer_ata_a,
+            escrow,
+            vault,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: anchor_escrow::instruction::Refund {}.data(),
+    };
+
+    let refund_proposal = proposal_address(&ctx.multisig, 1);
+    ctx.propose(&bob, &refund_ix).unwrap();
+    ctx.approve(&carol, &refund_proposal).unwrap();
+    ctx.execute(&alice, &refund_proposal).unwrap();
+
+    assert_eq!(token::token_amount(&ctx.program, &signer_ata_a), DEPOSIT);
+    assert_eq!(ctx.lamports(&escrow), 0);
+    assert_eq!(ctx.lamports(&vault), 0);
+}
+
+#[test]
+fn should_not_let_a_single_owner_make_an_escrow() {
+    let (mut ctx, mint_a, mint_b) = setup();
+    let alice = ctx.owners[0].insecure_clone();
+
+    let escrow = escrow_address(&ctx.signer);
+    let make_ix = make_ix(&ctx, &mint_a, &mint_b);
+
+    ctx.propose(&alice, &make_ix).unwrap();
+    assert_custom_error!(ctx.execute(&alice, &proposal_address(&ctx.multisig, 0)), MultisigError::ThresholdNotMet);
+    assert!(ctx.program.get_account(&escrow).is_none());
+}
@@ -0,0 +1,4 @@
+pub mod context;
+
+mod escrow_maker;
+mod proposals;
@@ -0,0 +1,122 @@
+use {
+    super::context::{proposal_address, MultisigTestContext},
+    crate::error::MultisigError,
+    anchor_lang::{solana_program::system_instruction, InstructionData, ToAccountMetas},
+    solana_instruction::{AccountMeta, Instruction},
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, send},
+};
+
+fn payment(ctx: &MultisigTestContext, to: &Pubkey) -> Instruction {
+    system_instruction::transfer(&ctx.signer, to, LAMPORTS_PER_SOL)
+}
+
+#[test]
+fn should_reject_invalid_owners_and_thresholds() {
+    let mut ctx = MultisigTestContext::uncreated();
+    let [alice, bob, _] = ctx.owners.each_ref().map(|owner| owner.pubkey());
+
+    assert_custom_error!(ctx.create(vec![], 1), MultisigError::InvalidOwners);
+    assert_custom_error!(ctx.create(vec![alice, bob, alice], 2), MultisigError::InvalidOwners);
+    assert_custom_error!(ctx.create(vec![alice, bob], 0), MultisigError::InvalidThreshold);
+    assert_custom_error!(ctx.create(vec![alice, bob], 3), MultisigError::InvalidThreshold);
+
+    ctx.create(vec![alice, bob], 2).unwrap();
+}
+
+#[test]
+fn should_execute_once_the_threshold_is_met() {
+    let mut ctx = MultisigTestContext::new(2);
+    let [alice, bob, _] = ctx.owners.each_ref().map(|owner| owner.insecure_clone());
+    let recipient = Pubkey::new_unique();
+    let proposal = proposal_address(&ctx.multisig, 0);
+
+    let ix = payment(&ctx, &recipient);
+    ctx.propose(&alice, &ix).unwrap();
+    assert_eq!(ctx.proposal_data(&proposal).approval_count(), 1);
+
+    // The proposer's approval alone is not enough
+    assert_custom_error!(ctx.execute(&alice, &proposal), MultisigError::ThresholdNotMet);
+
+    ctx.approve(&bob, &proposal).unwrap();
+    let signer_before = ctx.lamports(&ctx.signer);
+    ctx.execute(&ctx.outsider.insecure_clone(), &proposal).unwrap();
+
+    assert_eq!(ctx.lamports(&recipient), LAMPORTS_PER_SOL);
+    assert_eq!(ctx.lamports(&ctx.signer), signer_before - LAMPORTS_PER_SOL);
+    assert!(ctx.proposal_data(&proposal).executed);
+}
+
+#[test]
+fn should_execute_a_proposal_only_once() {
+    let mut ctx = MultisigTestContext::new(1);
+    let alice = ctx.owners[0].insecure_clone();
+    let recipient = Pubkey::new_unique();
+    let proposal = proposal_address(&ctx.multisig, 0);
+
+    let ix = payment(&ctx, &recipient);
+    ctx.propose(&alice, &ix).unwrap();
+    ctx.execute(&alice, &proposal).unwrap();
+
+    ctx.program.expire_blockhash();
+    assert_custom_error!(ctx.execute(&alice, &proposal), MultisigError::AlreadyExecuted);
+    assert_custom_error!(ctx.approve(&ctx.owners[1].insecure_clone(), &proposal), MultisigError::AlreadyExecuted);
+    assert_eq!(ctx.lamports(&recipient), LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn should_reject_outsiders() {
+    let mut ctx = MultisigTestContext::new(2);
+    let alice = ctx.owners[0].insecure_clone();
+    let outsider = ctx.outsider.insecure_clone();
+    let proposal = proposal_address(&ctx.multisig, 0);
+
+    let ix = payment(&ctx, &outsider.pubkey());
+    assert_custom_error!(ctx.propose(&outsider, &ix), MultisigError::NotAnOwner);
+
+    ctx.propose(&alice, &ix).unwrap();
+    assert_custom_error!(ctx.approve(&outsider, &proposal), MultisigError::NotAnOwner);
+    assert_eq!(ctx.proposal_data(&proposal).approval_count(), 1);
+}
+
+#[test]
+fn should_number_proposals_in_order() {
+    let mut ctx = MultisigTestContext::new(2);
+    let [alice, bob, _] = ctx.owners.each_ref().map(|owner| owner.insecure_clone());
+
+    let ix = payment(&ctx, &Pubkey::new_unique());
+    ctx.propose(&alice, &ix).unwrap();
+    ctx.propose(&bob, &ix).unwrap();
+
+    let second = ctx.proposal_data(&proposal_address(&ctx.multisig, 1));
+    assert_eq!((second.index, second.proposer), (1, bob.pubkey()));
+}
+
+#[test]
+fn should_reject_accounts_that_differ_from_the_proposal() {
+    let mut ctx = MultisigTestContext::new(1);
+    let alice = ctx.owners[0].insecure_clone();
+    let proposal = proposal_address(&ctx.multisig, 0);
+
+    let ix = payment(&ctx, &Pubkey::new_unique());
+    ctx.propose(&alice, &ix).unwrap();
+
+    // The executor swaps the recipient for their own wallet
+    let mut accounts = crate::accounts::Execute {
+        executor: alice.pubkey(),
+        multisig: ctx.multisig,
+        proposal,
+        signer: ctx.signer,
+    }
+    .to_account_metas(None);
+    accounts.extend([
+        AccountMeta::new(ctx.signer, false),
+        AccountMeta::new(alice.pubkey(), true),
+        AccountMeta::new_readonly(ix.program_id, false),
+    ]);
+    let execute_ix = Instruction { program_id: crate::ID, accounts, data: crate::instruction::Execute {}.data() };
+
+    assert_custom_error!(send(&mut ctx.program, &[execute_ix], &alice), MultisigError::AccountsMismatch);
+}
@@ -0,0 +1,16 @@
+use pinocchio::program_error::ProgramError;
+
+/// Codes match `anchor_escrow::error::EscrowError`, so clients decode both programs alike
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscrowError {
+    InvalidExpiry = 6002,
+    EscrowExpired = 6003,
+    MathOverflow = 6004,
+    InvalidStatusTransition = 6005,
+}
+
+impl From<EscrowError> for ProgramError {
+    fn from(err: EscrowError) -> Self {
+        ProgramError::Custom(err as u32)
+    }
+}
@@ -0,0 +1,100 @@
+use {
+    super::{escrow_seeds, require, ASSOCIATED_TOKEN_PROGRAM_ID},
+    crate::{
+        error::EscrowError,
+        state::{NewEscrow, ESCROW_LEN, ESCROW_SEED},
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        cpi::invoke,
+        instruction::{AccountMeta, Instruction, Signer},
+        program_error::ProgramError,
+        pubkey::find_program_address,
+        sysvars::{clock::Clock, rent::Rent, Sysvar},
+        ProgramResult,
+    },
+    pinocchio_system::instructions::CreateAccount,
+    pinocchio_token::{instructions::TransferChecked, state::Mint},
+};
+
+// Accounts in the order of `anchor_escrow::accounts::Make`
+pub fn process(accounts: &[AccountInfo], args: &[u8]) -> ProgramResult {
+    let [maker, mint_a, mint_b, maker_ata_a, escrow, vault, associated_token_program, token_program, system_program, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let [seed, deposit, receive, expires_at] = parse(args)?;
+
+    require(maker.is_signer(), ProgramError::MissingRequiredSignature)?;
+    require(token_program.key() == &pinocchio_token::ID, ProgramError::IncorrectProgramId)?;
+    require(associated_token_program.key() == &ASSOCIATED_TOKEN_PROGRAM_ID, ProgramError::IncorrectProgramId)?;
+    require(mint_b.is_owned_by(&pinocchio_token::ID), ProgramError::InvalidAccountOwner)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let expires_at = expires_at as i64;
+    require(expires_at == 0 || expires_at > now, EscrowError::InvalidExpiry)?;
+
+    // The canonical bump, so the address is the one Anchor's `seeds` constraint expects
+    let seed_bytes = seed.to_le_bytes();
+    let (address, bump) = find_program_address(&[ESCROW_SEED, maker.key().as_ref(), seed_bytes.as_ref()], &crate::ID);
+    require(escrow.key() == &address, ProgramError::InvalidSeeds)?;
+
+    let bump_bytes = [bump];
+    let seeds = escrow_seeds(maker.key(), &seed_bytes, &bump_bytes);
+    CreateAccount {
+        from: maker,
+        to: escrow,
+        lamports: Rent::get()?.minimum_balance(ESCROW_LEN),
+        space: ESCROW_LEN as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Signer::from(&seeds)])?;
+
+    NewEscrow {
+        seed,
+        maker: *maker.key(),
+        mint_a: *mint_a.key(),
+        mint_b: *mint_b.key(),
+        receive,
+        deposit,
+        created_at: now,
+        expires_at,
+        bump,
+    }
+    .write(&mut escrow.try_borrow_mut_data()?);
+
+    // The associated token program checks the vault address and the mint
+    invoke(
+        &Instruction {
+            program_id: &ASSOCIATED_TOKEN_PROGRAM_ID,
+            accounts: &[
+                AccountMeta::writable_signer(maker.key()),
+                AccountMeta::writable(vault.key()),
+                AccountMeta::readonly(escrow.key()),
+                AccountMeta::readonly(mint_a.key()),
+                AccountMeta::readonly(system_program.key()),
+                AccountMeta::readonly(token_program.key()),
+            ],
+            data: &[0],
+        },
+        &[maker, vault, escrow, mint_a, system_program, token_program],
+    )?;
+
+    let decimals = Mint::from_account_info(mint_a)?.decimals();
+    TransferChecked {
+        from: maker_ata_a,
+        mint: mint_a,
+        to: vault,
+        authority: maker,
+        amount: deposit,
+        decimals,
+    }
+    .invoke()
+}
+
+fn parse(args: &[u8]) -> Result<[u64; 4], ProgramError> {
+    if args.len() != 32 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let word = |index: usize| u64::from_le_bytes(args[index * 8..index * 8 + 8].try_into().unwrap());
+    Ok([word(0), word(1), word(2), word(3)])
+}
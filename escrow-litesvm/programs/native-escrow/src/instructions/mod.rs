@@ -0,0 +1,39 @@
+pub mod make;
+pub mod refund;
+pub mod take;
+
+pub const MAKE: u8 = 0;
+pub const TAKE: u8 = 1;
+pub const REFUND: u8 = 2;
+
+use pinocchio::{
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pinocchio_pubkey::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// The escrow's associated token account for mint A. Any token account the escrow owns
+/// would pass the token program's checks, so take and refund pin the real vault.
+pub(crate) fn vault_address(escrow: &Pubkey, mint_a: &Pubkey) -> Pubkey {
+    find_program_address(&[escrow.as_ref(), pinocchio_token::ID.as_ref(), mint_a.as_ref()], &ASSOCIATED_TOKEN_PROGRAM_ID).0
+}
+
+/// The escrow PDA's signer seeds, `bump` and `seed` borrowed from the caller's locals
+pub(crate) fn escrow_seeds<'a>(maker: &'a [u8; 32], seed: &'a [u8; 8], bump: &'a [u8; 1]) -> [Seed<'a>; 4] {
+    [
+        Seed::from(crate::state::ESCROW_SEED),
+        Seed::from(maker.as_ref()),
+        Seed::from(seed.as_ref()),
+        Seed::from(bump.as_ref()),
+    ]
+}
+
+pub(crate) fn require(condition: bool, err: impl Into<ProgramError>) -> Result<(), ProgramError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(err.into())
+    }
+}
@@ -0,0 +1,52 @@
+use {
+    super::{escrow_seeds, require, vault_address},
+    crate::{
+        error::EscrowError,
+        state::{self, Escrow},
+    },
+    pinocchio::{account_info::AccountInfo, instruction::Signer, program_error::ProgramError, ProgramResult},
+    pinocchio_token::{
+        instructions::{CloseAccount, TransferChecked},
+        state::{Mint, TokenAccount},
+    },
+};
+
+// Accounts in the order of `anchor_escrow::accounts::Refund`
+pub fn process(accounts: &[AccountInfo]) -> ProgramResult {
+    let [maker, mint_a, maker_ata_a, escrow, vault, token_program, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require(maker.is_signer(), ProgramError::MissingRequiredSignature)?;
+    require(token_program.key() == &pinocchio_token::ID, ProgramError::IncorrectProgramId)?;
+
+    let state = Escrow::load(escrow)?;
+    require(maker.key() == &state.maker && mint_a.key() == &state.mint_a, ProgramError::InvalidAccountData)?;
+    require(state.is_live(), EscrowError::InvalidStatusTransition)?;
+
+    require(vault.key() == &vault_address(escrow.key(), mint_a.key()), ProgramError::InvalidSeeds)?;
+    let amount = TokenAccount::from_account_info(vault)?.amount();
+
+    let seed_bytes = state.seed.to_le_bytes();
+    let bump_bytes = [state.bump];
+    let seeds = escrow_seeds(&state.maker, &seed_bytes, &bump_bytes);
+    let signer = [Signer::from(&seeds)];
+
+    // Nothing to return from an empty vault, as in the Anchor program
+    if amount > 0 {
+        let decimals = Mint::from_account_info(mint_a)?.decimals();
+        TransferChecked {
+            from: vault,
+            mint: mint_a,
+            to: maker_ata_a,
+            authority: escrow,
+            amount,
+            decimals,
+        }
+        .invoke_signed(&signer)?;
+    }
+
+    CloseAccount { account: vault, destination: maker, authority: escrow }.invoke_signed(&signer)?;
+
+    state::close(escrow, maker)
+}
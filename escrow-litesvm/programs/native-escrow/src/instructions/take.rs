@@ -0,0 +1,74 @@
+use {
+    super::{escrow_seeds, require, vault_address},
+    crate::{
+        error::EscrowError,
+        state::{self, Escrow},
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::Signer,
+        program_error::ProgramError,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+    pinocchio_token::{
+        instructions::{CloseAccount, TransferChecked},
+        state::{Mint, TokenAccount},
+    },
+};
+
+// Accounts in the order of `anchor_escrow::accounts::Take` without the optional wSOL
+// account, the receipt and the programs only needed to create accounts. The escrow's
+// owner and discriminator are checked, so its address needs no re-derivation: only
+// this program creates accounts it owns, and only at that address.
+pub fn process(accounts: &[AccountInfo]) -> ProgramResult {
+    let [taker, maker, mint_a, mint_b, taker_ata_a, taker_ata_b, maker_ata_b, escrow, vault, token_program, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    require(taker.is_signer(), ProgramError::MissingRequiredSignature)?;
+    require(token_program.key() == &pinocchio_token::ID, ProgramError::IncorrectProgramId)?;
+
+    let state = Escrow::load(escrow)?;
+    require(maker.key() == &state.maker, ProgramError::InvalidAccountData)?;
+    require(mint_a.key() == &state.mint_a && mint_b.key() == &state.mint_b, ProgramError::InvalidAccountData)?;
+    require(state.is_live(), EscrowError::InvalidStatusTransition)?;
+    require(!state.is_expired(Clock::get()?.unix_timestamp), EscrowError::EscrowExpired)?;
+
+    // Proceeds go to the maker's own account, whatever the taker passed
+    require(TokenAccount::from_account_info(maker_ata_b)?.owner() == maker.key(), ProgramError::InvalidAccountData)?;
+    require(vault.key() == &vault_address(escrow.key(), mint_a.key()), ProgramError::InvalidSeeds)?;
+    let withdrawal = TokenAccount::from_account_info(vault)?.amount();
+
+    let decimals_b = Mint::from_account_info(mint_b)?.decimals();
+    TransferChecked {
+        from: taker_ata_b,
+        mint: mint_b,
+        to: maker_ata_b,
+        authority: taker,
+        amount: state.price_of_remaining()?,
+        decimals: decimals_b,
+    }
+    .invoke()?;
+
+    let seed_bytes = state.seed.to_le_bytes();
+    let bump_bytes = [state.bump];
+    let seeds = escrow_seeds(&state.maker, &seed_bytes, &bump_bytes);
+    let signer = [Signer::from(&seeds)];
+
+    // The whole vault, tokens sent straight to it included, as Anchor's last fill does
+    let decimals_a = Mint::from_account_info(mint_a)?.decimals();
+    TransferChecked {
+        from: vault,
+        mint: mint_a,
+        to: taker_ata_a,
+        authority: escrow,
+        amount: withdrawal,
+        decimals: decimals_a,
+    }
+    .invoke_signed(&signer)?;
+
+    CloseAccount { account: vault, destination: maker, authority: escrow }.invoke_signed(&signer)?;
+
+    state::close(escrow, maker)
+}
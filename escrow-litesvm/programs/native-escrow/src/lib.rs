@@ -0,0 +1,45 @@
+//! The fungible escrow's make, take and refund written against pinocchio instead of
+//! Anchor, to measure what the framework costs per instruction.
+//!
+//! Escrow accounts share `anchor_escrow`'s layout byte for byte, discriminator
+//! included, so the same decoders and memcmp filters read both programs' accounts,
+//! and every instruction takes its accounts in the order the Anchor one does. What
+//! is left out: partial fills, receipts, wSOL unwrapping, Token-2022, events, taker
+//! gates, operators, start times, and creating the taker's and maker's destination
+//! ATAs, which must already exist.
+//!
+//! Instruction data is a one-byte tag followed by the arguments, little-endian:
+//!
+//! ```text
+//! 0 make    seed: u64, deposit: u64, receive: u64, expires_at: i64
+//! 1 take
+//! 2 refund
+//! ```
+
+#[cfg(not(feature = "no-entrypoint"))]
+use pinocchio::entrypoint;
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult};
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+pinocchio_pubkey::declare_id!("4dovA6jEo1bSUciFmrSx6Vw6NE7bPAuCN36Q4q3yJ3fQ");
+
+#[cfg(not(feature = "no-entrypoint"))]
+entrypoint!(process_instruction);
+
+pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if program_id != &ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    match data.split_first() {
+        Some((&instructions::MAKE, args)) => instructions::make::process(accounts, args),
+        Some((&instructions::TAKE, [])) => instructions::take::process(accounts),
+        Some((&instructions::REFUND, [])) => instructions::refund::process(accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
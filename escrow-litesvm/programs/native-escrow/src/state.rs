@@ -0,0 +1,165 @@
+//! `anchor_escrow::state::Escrow` read and written in place, without deserializing it.
+//! Offsets include Anchor's 8-byte discriminator and must match its Borsh layout.
+//! Those past the gate assume `TakerGate::None`, the only gate this program writes.
+
+use {
+    crate::error::EscrowError,
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+};
+
+/// sha256("account:Escrow")[..8], as Anchor derives it
+pub const ESCROW_DISCRIMINATOR: [u8; 8] = [31, 213, 123, 187, 186, 22, 218, 155];
+
+pub const ESCROW_SEED: &[u8] = b"escrow";
+
+const SEED: usize = 8;
+const MAKER: usize = 16;
+const MINT_A: usize = 48;
+const MINT_B: usize = 80;
+const STATUS: usize = 112;
+const RECEIVE: usize = 113;
+const DEPOSIT_TOTAL: usize = 121;
+const FILLED: usize = 129;
+const REMAINING: usize = 137;
+const CREATED_AT: usize = 145;
+const EXPIRES_AT: usize = 153;
+const STARTS_AT: usize = 161;
+pub(crate) const BUMP: usize = 169;
+const GATE: usize = 170;
+const OPERATOR: usize = 171;
+const FILL_OR_KILL: usize = 203;
+const RESERVED_FOR: usize = 204;
+const METADATA_LEN: usize = 236;
+
+// Anchor allocates room for the largest `TakerGate` variant, `Attestation`, but
+// `TakerGate::None` serializes to its tag alone and leaves the rest zeroed at the end
+const GATE_PADDING: usize = 64;
+
+/// Size of an escrow with no metadata, what make allocates
+pub const ESCROW_LEN: usize = METADATA_LEN + 4 + GATE_PADDING;
+
+// `TakerGate::None` by Borsh index
+const GATE_NONE: u8 = 0;
+
+// `EscrowStatus` variants by Borsh index
+pub const STATUS_OPEN: u8 = 0;
+
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn pubkey_at(data: &[u8], offset: usize) -> Pubkey {
+    data[offset..offset + 32].try_into().unwrap()
+}
+
+/// The fields make writes, everything else starts zeroed
+pub struct NewEscrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    pub deposit: u64,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl NewEscrow {
+    pub fn write(&self, data: &mut [u8]) {
+        data[..8].copy_from_slice(&ESCROW_DISCRIMINATOR);
+        data[SEED..SEED + 8].copy_from_slice(&self.seed.to_le_bytes());
+        data[MAKER..MAKER + 32].copy_from_slice(&self.maker);
+        data[MINT_A..MINT_A + 32].copy_from_slice(&self.mint_a);
+        data[MINT_B..MINT_B + 32].copy_from_slice(&self.mint_b);
+        data[STATUS] = STATUS_OPEN;
+        data[RECEIVE..RECEIVE + 8].copy_from_slice(&self.receive.to_le_bytes());
+        data[DEPOSIT_TOTAL..DEPOSIT_TOTAL + 8].copy_from_slice(&self.deposit.to_le_bytes());
+        data[FILLED..FILLED + 8].copy_from_slice(&0_u64.to_le_bytes());
+        data[REMAINING..REMAINING + 8].copy_from_slice(&self.deposit.to_le_bytes());
+        data[CREATED_AT..CREATED_AT + 8].copy_from_slice(&self.created_at.to_le_bytes());
+        data[EXPIRES_AT..EXPIRES_AT + 8].copy_from_slice(&self.expires_at.to_le_bytes());
+        data[STARTS_AT..STARTS_AT + 8].copy_from_slice(&0_i64.to_le_bytes());
+        data[BUMP] = self.bump;
+        data[GATE] = GATE_NONE;
+        data[OPERATOR..OPERATOR + 32].copy_from_slice(&[0; 32]);
+        data[FILL_OR_KILL] = 0;
+        data[RESERVED_FOR..RESERVED_FOR + 32].copy_from_slice(&[0; 32]);
+        data[METADATA_LEN..METADATA_LEN + 4].copy_from_slice(&0_u32.to_le_bytes());
+    }
+}
+
+/// The fields take and refund read, copied out so the account is not kept borrowed
+pub struct Escrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub status: u8,
+    pub receive: u64,
+    pub deposit_total: u64,
+    pub filled: u64,
+    pub remaining: u64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl Escrow {
+    /// Reads an escrow this program owns, checking owner and discriminator
+    pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
+        if !account.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let data = account.try_borrow_data()?;
+        if data.len() < ESCROW_LEN || data[..8] != ESCROW_DISCRIMINATOR {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            seed: u64_at(&data, SEED),
+            maker: pubkey_at(&data, MAKER),
+            mint_a: pubkey_at(&data, MINT_A),
+            mint_b: pubkey_at(&data, MINT_B),
+            status: data[STATUS],
+            receive: u64_at(&data, RECEIVE),
+            deposit_total: u64_at(&data, DEPOSIT_TOTAL),
+            filled: u64_at(&data, FILLED),
+            remaining: u64_at(&data, REMAINING),
+            expires_at: u64_at(&data, EXPIRES_AT) as i64,
+            bump: data[BUMP],
+        })
+    }
+
+    /// Only open escrows can still be filled or cancelled
+    pub fn is_live(&self) -> bool {
+        self.status == STATUS_OPEN
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// Same rounding as `anchor_escrow::state::Escrow::price_of`, up in the maker's favour
+    pub fn price_of(&self, filled: u64) -> Result<u64, ProgramError> {
+        let total = (filled as u128) * (self.receive as u128);
+        let deposit_total = self.deposit_total as u128;
+
+        let price = total
+            .checked_add(deposit_total.saturating_sub(1))
+            .and_then(|total| total.checked_div(deposit_total))
+            .ok_or(EscrowError::MathOverflow)?;
+
+        u64::try_from(price).map_err(|_| EscrowError::MathOverflow.into())
+    }
+
+    /// What the rest of the deposit costs, given what earlier fills already paid
+    pub fn price_of_remaining(&self) -> Result<u64, ProgramError> {
+        Ok(self.price_of(self.deposit_total)? - self.price_of(self.filled)?)
+    }
+}
+
+/// Hands an account's lamports to `destination` and empties it
+pub fn close(account: &AccountInfo, destination: &AccountInfo) -> Result<(), ProgramError> {
+    *destination.try_borrow_mut_lamports()? += account.lamports();
+    account.close()
+}
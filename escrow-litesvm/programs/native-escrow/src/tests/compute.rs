@@ -0,0 +1,32 @@
+use {
+    super::context::Context,
+    test_utils::compute::ComputeReport,
+};
+
+// The same offer through both programs, each step side by side. Run with `--nocapture`
+// for the table. The Anchor take also creates a receipt, which the native one has no
+// equivalent of, so that row overstates the framework's share.
+#[test]
+fn should_cost_less_than_anchor_for_every_instruction() {
+    let mut report = ComputeReport::new("Native vs Anchor escrow");
+    let mut ctx = Context::new();
+
+    for (label, ix) in [("anchor make", ctx.anchor_make_ix()), ("native make", ctx.native_make_ix())] {
+        report.send(label, &mut ctx.svm, &[ix], &ctx.maker).unwrap();
+    }
+    for (label, ix) in [("anchor take", ctx.anchor_take_ix()), ("native take", ctx.native_take_ix())] {
+        report.send(label, &mut ctx.svm, &[ix], &ctx.taker).unwrap();
+    }
+    for (label, ix) in [("anchor make", ctx.anchor_make_ix()), ("native make", ctx.native_make_ix())] {
+        report.send(label, &mut ctx.svm, &[ix], &ctx.maker).unwrap();
+    }
+    for (label, ix) in [("anchor refund", ctx.anchor_refund_ix()), ("native refund", ctx.native_refund_ix())] {
+        report.send(label, &mut ctx.svm, &[ix], &ctx.maker).unwrap();
+    }
+
+    for instruction in ["make", "take", "refund"] {
+        let anchor = report.compute_units(&format!("anchor {}", instruction));
+        let native = report.compute_units(&format!("native {}", instruction));
+        assert!(native < anchor, "Native {} used {} CU, Anchor {}", instruction, native, anchor);
+    }
+}
@@ -0,0 +1,171 @@
+use {
+    anchor_escrow::constants::{ESCROW_SEED, MAKER_COUNTER_SEED, RECEIPT_SEED},
+    anchor_lang::{solana_program::program_pack::Pack, InstructionData, ToAccountMetas},
+    anchor_spl::{associated_token, token::spl_token},
+    litesvm::{types::TransactionResult, LiteSVM},
+    litesvm_token::{CreateAssociatedTokenAccount, MintTo},
+    solana_instruction::{AccountMeta, Instruction},
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, artifact, keys, send, svm_with_program, token::create_mint},
+};
+
+pub const NATIVE_ID: Pubkey = Pubkey::new_from_array(crate::ID);
+pub const ANCHOR_ID: Pubkey = anchor_escrow::ID;
+
+pub const DEPOSIT: u64 = 1_000;
+pub const RECEIVE: u64 = 2_500;
+const SEED: u64 = 7;
+
+/// One maker, one taker and a funded pair of mints, with both escrow programs loaded
+/// so the same offer can be run through either
+pub struct Context {
+    pub svm: LiteSVM,
+    pub maker: Keypair,
+    pub taker: Keypair,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub maker_ata_a: Pubkey,
+    pub maker_ata_b: Pubkey,
+    pub taker_ata_a: Pubkey,
+    pub taker_ata_b: Pubkey,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        let maker = keys::keypair("maker");
+        let taker = keys::keypair("taker");
+        let mut svm = svm_with_program(&NATIVE_ID, "native_escrow", env!("CARGO_MANIFEST_DIR"), &[&maker, &taker]);
+        svm.add_program(ANCHOR_ID, &artifact::program_bytes("anchor_escrow", concat!(env!("CARGO_MANIFEST_DIR"), "/../anchor-escrow")));
+
+        let mint_a = create_mint(&mut svm, &maker, &keys::keypair("mint_a"), 6, None);
+        let mint_b = create_mint(&mut svm, &maker, &keys::keypair("mint_b"), 9, None);
+
+        // The native take does not create destination ATAs, so every one exists up front
+        let mut ata = |owner: &Keypair, mint: &Pubkey| {
+            CreateAssociatedTokenAccount::new(&mut svm, owner, mint).owner(&owner.pubkey()).send().unwrap()
+        };
+        let (maker_ata_a, maker_ata_b) = (ata(&maker, &mint_a), ata(&maker, &mint_b));
+        let (taker_ata_a, taker_ata_b) = (ata(&taker, &mint_a), ata(&taker, &mint_b));
+
+        MintTo::new(&mut svm, &maker, &mint_a, &maker_ata_a, 10 * DEPOSIT).send().unwrap();
+        MintTo::new(&mut svm, &maker, &mint_b, &taker_ata_b, 10 * RECEIVE).send().unwrap();
+
+        Self { svm, maker, taker, mint_a, mint_b, maker_ata_a, maker_ata_b, taker_ata_a, taker_ata_b }
+    }
+
+    pub fn escrow(&self, program_id: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[ESCROW_SEED, self.maker.pubkey().as_ref(), &SEED.to_le_bytes()], program_id).0
+    }
+
+    pub fn vault(&self, program_id: &Pubkey) -> Pubkey {
+        associated_token::get_associated_token_address(&self.escrow(program_id), &self.mint_a)
+    }
+
+    fn make_accounts(&self, program_id: &Pubkey) -> Vec<AccountMeta> {
+        anchor_escrow::accounts::Make {
+            maker: self.maker.pubkey(),
+            mint_a: self.mint_a,
+            mint_b: self.mint_b,
+            maker_ata_a: self.maker_ata_a,
+            escrow: self.escrow(program_id),
+            vault: self.vault(program_id),
+            associated_token_program: associated_token::ID,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            // Past the accounts the native program reads, which ignores it
+            maker_counter: Pubkey::find_program_address(&[MAKER_COUNTER_SEED, self.maker.pubkey().as_ref()], program_id).0,
+        }
+        .to_account_metas(None)
+    }
+
+    fn refund_accounts(&self, program_id: &Pubkey) -> Vec<AccountMeta> {
+        anchor_escrow::accounts::Refund {
+            maker: self.maker.pubkey(),
+            mint_a: self.mint_a,
+            maker_ata_a: self.maker_ata_a,
+            escrow: self.escrow(program_id),
+            vault: self.vault(program_id),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None)
+    }
+
+    pub fn native_make_ix(&self) -> Instruction {
+        let mut data = vec![crate::instructions::MAKE];
+        for word in [SEED, DEPOSIT, RECEIVE, 0] {
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+        Instruction { program_id: NATIVE_ID, accounts: self.make_accounts(&NATIVE_ID), data }
+    }
+
+    pub fn native_take_ix(&self) -> Instruction {
+        let escrow = self.escrow(&NATIVE_ID);
+        let accounts = vec![
+            AccountMeta::new(self.taker.pubkey(), true),
+            AccountMeta::new(self.maker.pubkey(), false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(self.mint_b, false),
+            AccountMeta::new(self.taker_ata_a, false),
+            AccountMeta::new(self.taker_ata_b, false),
+            AccountMeta::new(self.maker_ata_b, false),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(self.vault(&NATIVE_ID), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+        Instruction { program_id: NATIVE_ID, accounts, data: vec![crate::instructions::TAKE] }
+    }
+
+    pub fn native_refund_ix(&self) -> Instruction {
+        Instruction { program_id: NATIVE_ID, accounts: self.refund_accounts(&NATIVE_ID), data: vec![crate::instructions::REFUND] }
+    }
+
+    pub fn anchor_make_ix(&self) -> Instruction {
+        let data = anchor_escrow::instruction::Make { seed: SEED, deposit: DEPOSIT, receive: RECEIVE, expires_at: 0, starts_at: 0 }.data();
+        Instruction { program_id: ANCHOR_ID, accounts: self.make_accounts(&ANCHOR_ID), data }
+    }
+
+    pub fn anchor_take_ix(&self) -> Instruction {
+        let escrow = self.escrow(&ANCHOR_ID);
+        let accounts = anchor_escrow::accounts::Take {
+            taker: self.taker.pubkey(),
+            maker: self.maker.pubkey(),
+            mint_a: self.mint_a,
+            mint_b: self.mint_b,
+            taker_ata_a: self.taker_ata_a,
+            taker_ata_b: self.taker_ata_b,
+            maker_ata_b: Some(self.maker_ata_b),
+            maker_wsol: None,
+            escrow,
+            vault: self.vault(&ANCHOR_ID),
+            receipt: Pubkey::find_program_address(&[RECEIPT_SEED, escrow.as_ref(), self.taker.pubkey().as_ref()], &ANCHOR_ID).0,
+            associated_token_program: associated_token::ID,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        };
+        Instruction { program_id: ANCHOR_ID, accounts: accounts.to_account_metas(None), data: anchor_escrow::instruction::Take {}.data() }
+    }
+
+    pub fn anchor_refund_ix(&self) -> Instruction {
+        Instruction { program_id: ANCHOR_ID, accounts: self.refund_accounts(&ANCHOR_ID), data: anchor_escrow::instruction::Refund {}.data() }
+    }
+
+    pub fn send_as_maker(&mut self, ix: Instruction) -> TransactionResult {
+        send(&mut self.svm, &[ix], &self.maker)
+    }
+
+    pub fn send_as_taker(&mut self, ix: Instruction) -> TransactionResult {
+        send(&mut self.svm, &[ix], &self.taker)
+    }
+
+    pub fn token_amount(&self, address: &Pubkey) -> u64 {
+        let account = self.svm.get_account(address).expect("Token account not found");
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    /// Lamports held by an account, 0 if it does not exist
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.svm, address)
+    }
+}
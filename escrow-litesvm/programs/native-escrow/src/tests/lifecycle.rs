@@ -0,0 +1,100 @@
+use {
+    super::context::{Context, ANCHOR_ID, DEPOSIT, NATIVE_ID, RECEIVE},
+    crate::state::BUMP,
+    anchor_escrow::state::{Escrow, EscrowStatus},
+    anchor_lang::AccountDeserialize,
+    solana_instruction::AccountMeta,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, send},
+};
+
+#[test]
+fn should_write_escrows_anchor_can_decode() {
+    let mut ctx = Context::new();
+
+    ctx.send_as_maker(ctx.native_make_ix()).unwrap();
+    ctx.send_as_maker(ctx.anchor_make_ix()).unwrap();
+
+    let native = ctx.svm.get_account(&ctx.escrow(&NATIVE_ID)).unwrap();
+    let anchor = ctx.svm.get_account(&ctx.escrow(&ANCHOR_ID)).unwrap();
+    assert_eq!(native.owner, NATIVE_ID);
+    assert_eq!(native.data.len(), anchor.data.len(), "Both programs should allocate the same layout");
+
+    let escrow = Escrow::try_deserialize(&mut native.data.as_slice()).unwrap();
+    assert_eq!(escrow.maker, ctx.maker.pubkey());
+    assert_eq!(escrow.status, EscrowStatus::Open);
+    assert_eq!((escrow.deposit_total, escrow.remaining, escrow.receive), (DEPOSIT, DEPOSIT, RECEIVE));
+
+    // Byte for byte identical but for the bump, which belongs to a different
+    // program's address
+    let mut reference = anchor.data.clone();
+    reference[BUMP] = escrow.bump;
+    assert_eq!(native.data, reference);
+    assert_eq!(ctx.token_amount(&ctx.vault(&NATIVE_ID)), DEPOSIT);
+}
+
+#[test]
+fn should_swap_and_close_on_take() {
+    let mut ctx = Context::new();
+    ctx.send_as_maker(ctx.native_make_ix()).unwrap();
+
+    let maker = ctx.maker.pubkey();
+    let rent = ctx.lamports(&ctx.escrow(&NATIVE_ID)) + ctx.lamports(&ctx.vault(&NATIVE_ID));
+    let maker_lamports = ctx.lamports(&maker);
+
+    ctx.send_as_taker(ctx.native_take_ix()).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.taker_ata_a), DEPOSIT);
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_b), RECEIVE);
+    assert_eq!(ctx.lamports(&ctx.escrow(&NATIVE_ID)), 0);
+    assert_eq!(ctx.lamports(&ctx.vault(&NATIVE_ID)), 0);
+    assert_eq!(ctx.lamports(&maker), maker_lamports + rent);
+}
+
+#[test]
+fn should_return_deposit_on_refund() {
+    let mut ctx = Context::new();
+    let maker_a = ctx.token_amount(&ctx.maker_ata_a);
+    ctx.send_as_maker(ctx.native_make_ix()).unwrap();
+
+    ctx.send_as_maker(ctx.native_refund_ix()).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.maker_ata_a), maker_a);
+    assert_eq!(ctx.lamports(&ctx.escrow(&NATIVE_ID)), 0);
+    assert_eq!(ctx.lamports(&ctx.vault(&NATIVE_ID)), 0);
+}
+
+#[test]
+fn should_reject_refund_by_anyone_but_the_maker() {
+    let mut ctx = Context::new();
+    ctx.send_as_maker(ctx.native_make_ix()).unwrap();
+
+    let mut refund_ix = ctx.native_refund_ix();
+    refund_ix.accounts[0] = AccountMeta::new(ctx.taker.pubkey(), true);
+
+    assert!(send(&mut ctx.svm, &[refund_ix], &ctx.taker).is_err());
+    assert_eq!(ctx.token_amount(&ctx.vault(&NATIVE_ID)), DEPOSIT);
+}
+
+#[test]
+fn should_reject_take_from_a_token_account_other_than_the_vault() {
+    let mut ctx = Context::new();
+    ctx.send_as_maker(ctx.native_make_ix()).unwrap();
+
+    // Owned by the taker rather than the escrow, and not its associated token account
+    let mut take_ix = ctx.native_take_ix();
+    take_ix.accounts[8] = AccountMeta::new(ctx.taker_ata_a, false);
+
+    assert!(ctx.send_as_taker(take_ix).is_err());
+    assert_eq!(ctx.token_amount(&ctx.vault(&NATIVE_ID)), DEPOSIT);
+}
+
+#[test]
+fn should_reject_make_expiring_in_the_past() {
+    let mut ctx = Context::new();
+
+    let mut make_ix = ctx.native_make_ix();
+    make_ix.data[25..33].copy_from_slice(&1_i64.to_le_bytes());
+
+    assert_custom_error!(ctx.send_as_maker(make_ix), crate::error::EscrowError::InvalidExpiry as u32);
+}
@@ -0,0 +1,3 @@
+mod compute;
+mod context;
+mod lifecycle;
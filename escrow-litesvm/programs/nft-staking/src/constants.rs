@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+#[constant]
+pub const CONFIG_SEED: &[u8] = b"config";
+#[constant]
+pub const REWARDS_SEED: &[u8] = b"rewards";
+#[constant]
+pub const STAKE_SEED: &[u8] = b"stake";
+
+pub const REWARD_DECIMALS: u8 = 6;
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum StakingError {
+    #[msg("Only mints with no decimals and a supply of one can be staked")]
+    NotAnNft,
+    #[msg("No full epoch has passed since the last claim")]
+    NothingToClaim,
+    #[msg("The NFT has not been staked for the minimum number of epochs")]
+    StakeLocked,
+    #[msg("The NFT is not a verified member of the config's collection")]
+    NotInCollection,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{constants::{CONFIG_SEED, REWARDS_SEED, STAKE_SEED}, error::StakingError, instructions::pay_rewards, state::{StakeAccount, StakeConfig}};
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.admin.as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Box<Account<'info, StakeConfig>>,
+    #[account(
+        mut,
+        seeds = [REWARDS_SEED, config.key().as_ref()],
+        bump = config.rewards_bump,
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [STAKE_SEED, stake_account.mint.as_ref(), config.key().as_ref()],
+        bump = stake_account.bump,
+    )]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_rewards: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Claim<'info> {
+    pub fn claim(&mut self) -> Result<()> {
+        let epoch = Clock::get()?.epoch;
+
+        let paid = pay_rewards(
+            &mut self.stake_account,
+            epoch,
+            &self.config,
+            &self.reward_mint,
+            &self.owner_rewards,
+            self.token_program.to_account_info(),
+        )?;
+        require!(paid > 0, StakingError::NothingToClaim);
+
+        Ok(())
+    }
+}
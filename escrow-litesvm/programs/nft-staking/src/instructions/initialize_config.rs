@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::{constants::{ANCHOR_DISCRIMINATOR, CONFIG_SEED, REWARDS_SEED, REWARD_DECIMALS}, state::StakeConfig};
+
+// Seeded by the admin, so nobody can take a config over by creating it first. Each
+// config mints its own rewards, which only NFTs of its collection earn.
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [CONFIG_SEED, admin.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + StakeConfig::INIT_SPACE,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [REWARDS_SEED, config.key().as_ref()],
+        bump,
+        mint::decimals = REWARD_DECIMALS,
+        mint::authority = config,
+        mint::token_program = token_program,
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeConfig<'info> {
+    pub fn initialize_config(
+        &mut self,
+        collection: Pubkey,
+        reward_per_epoch: u64,
+        min_stake_epochs: u64,
+        bumps: &InitializeConfigBumps,
+    ) -> Result<()> {
+        self.config.set_inner(StakeConfig {
+            admin: self.admin.key(),
+            collection,
+            reward_per_epoch,
+            min_stake_epochs,
+            config_bump: bumps.config,
+            rewards_bump: bumps.reward_mint,
+        });
+
+        Ok(())
+    }
+}
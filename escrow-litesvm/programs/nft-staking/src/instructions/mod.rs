@@ -0,0 +1,42 @@
+pub mod claim;
+pub mod initialize_config;
+pub mod stake;
+pub mod unstake;
+
+pub use claim::*;
+pub use initialize_config::*;
+pub use stake::*;
+pub use unstake::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount};
+
+use crate::{constants::CONFIG_SEED, state::{StakeAccount, StakeConfig}};
+
+/// Mints the rewards owed up to `epoch` and moves the stake's claimed epoch there
+pub(crate) fn pay_rewards<'info>(
+    stake_account: &mut StakeAccount,
+    epoch: u64,
+    config: &Account<'info, StakeConfig>,
+    reward_mint: &InterfaceAccount<'info, Mint>,
+    destination: &InterfaceAccount<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+) -> Result<u64> {
+    let (epochs, reward) = stake_account.claimable(epoch, config)?;
+    if epochs == 0 {
+        return Ok(0);
+    }
+    stake_account.claimed_epoch = epoch;
+
+    let signer_seeds: [&[&[u8]]; 1] = [&[CONFIG_SEED, config.admin.as_ref(), &[config.config_bump]]];
+
+    let cpi_accounts = MintTo {
+        mint: reward_mint.to_account_info(),
+        to: destination.to_account_info(),
+        authority: config.to_account_info(),
+    };
+
+    mint_to(CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds), reward)?;
+
+    Ok(reward)
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, CONFIG_SEED, STAKE_SEED},
+    error::StakingError,
+    metadata,
+    state::{StakeAccount, StakeConfig},
+};
+
+// The NFT is taken into custody rather than frozen in place, so staking works for
+// any mint regardless of who holds its freeze authority
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.decimals == 0 && mint.supply == 1 @ StakingError::NotAnNft,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Must be owned by Token Metadata and name the mint as part of the
+    /// config's collection, checked in `stake`
+    pub metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [CONFIG_SEED, config.admin.as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Box<Account<'info, StakeConfig>>,
+    #[account(
+        init,
+        payer = owner,
+        seeds = [STAKE_SEED, mint.key().as_ref(), config.key().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + StakeAccount::INIT_SPACE,
+    )]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = stake_account,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Stake<'info> {
+    pub fn stake(&mut self, bumps: &StakeBumps) -> Result<()> {
+        metadata::check_collection(&self.metadata, &self.mint.key(), &self.config.collection)?;
+
+        let epoch = Clock::get()?.epoch;
+
+        self.stake_account.set_inner(StakeAccount {
+            owner: self.owner.key(),
+            mint: self.mint.key(),
+            staked_epoch: epoch,
+            claimed_epoch: epoch,
+            bump: bumps.stake_account,
+        });
+
+        let cpi_accounts = TransferChecked {
+            from: self.owner_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.owner.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        transfer_checked(CpiContext::new(self.token_program.to_account_info(), cpi_accounts), 1, 0)
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{constants::{CONFIG_SEED, REWARDS_SEED, STAKE_SEED}, error::StakingError, instructions::pay_rewards, state::{StakeAccount, StakeConfig}};
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [CONFIG_SEED, config.admin.as_ref()],
+        bump = config.config_bump,
+    )]
+    pub config: Box<Account<'info, StakeConfig>>,
+    #[account(
+        mut,
+        seeds = [REWARDS_SEED, config.key().as_ref()],
+        bump = config.rewards_bump,
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        has_one = mint,
+        seeds = [STAKE_SEED, mint.key().as_ref(), config.key().as_ref()],
+        bump = stake_account.bump,
+    )]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = stake_account,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_rewards: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Unstake<'info> {
+    // Unclaimed rewards are paid out first, so unstaking never forfeits them
+    pub fn unstake(&mut self) -> Result<()> {
+        let epoch = Clock::get()?.epoch;
+        let staked_for = epoch.saturating_sub(self.stake_account.staked_epoch);
+        require!(staked_for >= self.config.min_stake_epochs, StakingError::StakeLocked);
+
+        pay_rewards(
+            &mut self.stake_account,
+            epoch,
+            &self.config,
+            &self.reward_mint,
+            &self.owner_rewards,
+            self.token_program.to_account_info(),
+        )?;
+
+        let mint = self.mint.key();
+        let config = self.config.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[STAKE_SEED, mint.as_ref(), config.as_ref(), &[self.stake_account.bump]]];
+        let token_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            to: self.owner_ata.to_account_info(),
+            authority: self.stake_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        transfer_checked(CpiContext::new_with_signer(token_program.clone(), cpi_accounts, &signer_seeds), 1, 0)?;
+
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.owner.to_account_info(),
+            authority: self.stake_account.to_account_info(),
+        };
+
+        close_account(CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds))
+    }
+}
@@ -0,0 +1,42 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod constants;
+mod error;
+mod instructions;
+pub mod metadata;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+use instructions::*;
+
+declare_id!("Do84seYgr1EjkifhWQr1UM59JxnNt3PFrNaabPEtHoQM");
+
+#[program]
+pub mod nft_staking {
+    use super::*;
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        collection: Pubkey,
+        reward_per_epoch: u64,
+        min_stake_epochs: u64,
+    ) -> Result<()> {
+        ctx.accounts.initialize_config(collection, reward_per_epoch, min_stake_epochs, &ctx.bumps)
+    }
+
+    pub fn stake(ctx: Context<Stake>) -> Result<()> {
+        ctx.accounts.stake(&ctx.bumps)
+    }
+
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        ctx.accounts.claim()
+    }
+
+    pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
+        ctx.accounts.unstake()
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::error::StakingError;
+
+// Metaplex Token Metadata, which records the collection an NFT belongs to
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// `Key::MetadataV1`, the first byte of every metadata account
+pub const METADATA_V1_KEY: u8 = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+/// The leading fields of a Token Metadata `Metadata` account, up to its collection.
+/// Everything after it is left unread.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Metadata {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<Collection>,
+}
+
+// Only Token Metadata writes accounts it owns, and only the collection's update
+// authority can verify an NFT into it, so an owned MetadataV1 account naming `mint`
+// with the collection verified is proof of membership.
+pub(crate) fn check_collection(metadata: &AccountInfo, mint: &Pubkey, collection: &Pubkey) -> Result<()> {
+    require_keys_eq!(*metadata.owner, TOKEN_METADATA_PROGRAM_ID, StakingError::NotInCollection);
+    let metadata = Metadata::deserialize(&mut &metadata.try_borrow_data()?[..])
+        .map_err(|_| error!(StakingError::NotInCollection))?;
+    require!(metadata.key == METADATA_V1_KEY && metadata.mint == *mint, StakingError::NotInCollection);
+
+    match metadata.collection {
+        Some(Collection { verified: true, key }) if key == *collection => Ok(()),
+        _ => err!(StakingError::NotInCollection),
+    }
+}
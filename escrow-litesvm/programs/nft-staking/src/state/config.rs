@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+// One per admin. The reward mint is a PDA of the config, which is its mint
+// authority, so rewards can only be minted by claims.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct StakeConfig {
+    pub admin: Pubkey,
+    /// Only NFTs verified as part of the collection with this mint can be staked
+    pub collection: Pubkey,
+    pub reward_per_epoch: u64,
+    pub min_stake_epochs: u64,
+    pub config_bump: u8,
+    pub rewards_bump: u8,
+}
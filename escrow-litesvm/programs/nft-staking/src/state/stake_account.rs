@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::StakingError, state::StakeConfig};
+
+// One per staked NFT, which sits in this account's associated token account while staked
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct StakeAccount {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub staked_epoch: u64,
+    /// Rewards are paid for every epoch from here up to the current one
+    pub claimed_epoch: u64,
+    pub bump: u8,
+}
+
+impl StakeAccount {
+    /// Whole epochs since the last claim and the rewards owed for them
+    pub fn claimable(&self, epoch: u64, config: &StakeConfig) -> Result<(u64, u64)> {
+        let epochs = epoch.saturating_sub(self.claimed_epoch);
+        let reward = epochs
+            .checked_mul(config.reward_per_epoch)
+            .ok_or(StakingError::MathOverflow)?;
+        Ok((epochs, reward))
+    }
+}
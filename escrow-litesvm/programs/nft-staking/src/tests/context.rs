@@ -0,0 +1,224 @@
+use {
+    crate::{
+        constants::{CONFIG_SEED, REWARDS_SEED, STAKE_SEED},
+        metadata::{Collection, Metadata, METADATA_V1_KEY, TOKEN_METADATA_PROGRAM_ID},
+        state::StakeAccount,
+    },
+    anchor_lang::{AnchorSerialize, InstructionData, ToAccountMetas},
+    anchor_spl::{associated_token::{self, get_associated_token_address}, token::spl_token},
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_account::Account,
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, keys, send, svm_with_program, token},
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+pub const REWARD_PER_EPOCH: u64 = 100;
+pub const MIN_STAKE_EPOCHS: u64 = 2;
+
+pub fn config_address(admin: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_SEED, admin.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn reward_mint_address(config: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[REWARDS_SEED, config.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn stake_address(mint: &Pubkey, config: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[STAKE_SEED, mint.as_ref(), config.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()], &TOKEN_METADATA_PROGRAM_ID).0
+}
+
+pub fn verified(key: Pubkey) -> Option<Collection> {
+    Some(Collection { verified: true, key })
+}
+
+/// An initialized config and a staker holding one NFT, not yet staked
+pub struct StakingTestContext {
+    pub program: LiteSVM,
+    pub admin: Keypair,
+    pub staker: Keypair,
+    pub config: Pubkey,
+    pub collection: Pubkey,
+    pub reward_mint: Pubkey,
+    pub nft: Pubkey,
+    pub stake_account: Pubkey,
+    pub vault: Pubkey,
+}
+
+impl StakingTestContext {
+    pub fn new() -> Self {
+        let admin = keys::keypair("admin");
+        let staker = keys::keypair("staker");
+        let program = svm_with_program(&PROGRAM_ID, "nft_staking", env!("CARGO_MANIFEST_DIR"), &[&admin, &staker]);
+
+        let config = config_address(&admin.pubkey());
+        let reward_mint = reward_mint_address(&config);
+        let collection = keys::keypair("collection").pubkey();
+
+        let mut ctx = Self {
+            program,
+            admin,
+            staker,
+            config,
+            collection,
+            reward_mint,
+            nft: Pubkey::default(),
+            stake_account: Pubkey::default(),
+            vault: Pubkey::default(),
+        };
+
+        let ix = ctx.initialize_config_ix(&ctx.admin.pubkey(), REWARD_PER_EPOCH, MIN_STAKE_EPOCHS);
+        send(&mut ctx.program, &[ix], &ctx.admin).unwrap();
+
+        ctx.nft = ctx.mint_nft("nft", &ctx.staker.insecure_clone());
+        ctx.stake_account = stake_address(&ctx.nft, &config);
+        ctx.vault = get_associated_token_address(&ctx.stake_account, &ctx.nft);
+        ctx
+    }
+
+    /// An NFT verified as part of the config's collection
+    pub fn mint_nft(&mut self, name: &str, owner: &Keypair) -> Pubkey {
+        let mint = token::mint_nft(&mut self.program, owner, &keys::keypair(name)).0;
+        self.write_metadata(&mint, verified(self.collection));
+        mint
+    }
+
+    /// Token Metadata's account for `mint`, naming `collection`
+    pub fn write_metadata(&mut self, mint: &Pubkey, collection: Option<Collection>) {
+        let metadata = Metadata {
+            key: METADATA_V1_KEY,
+            update_authority: self.admin.pubkey(),
+            mint: *mint,
+            name: "Staker #1".to_string(),
+            symbol: "STK".to_string(),
+            uri: "https://example.com/1.json".to_string(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: Some(0),
+            collection,
+        };
+        let data = metadata.try_to_vec().unwrap();
+        let lamports = self.program.minimum_balance_for_rent_exemption(data.len());
+        let account = Account { lamports, data, owner: TOKEN_METADATA_PROGRAM_ID, executable: false, rent_epoch: 0 };
+
+        self.program.set_account(metadata_address(mint), account).unwrap();
+    }
+
+    pub fn initialize_config_ix(&self, admin: &Pubkey, reward_per_epoch: u64, min_stake_epochs: u64) -> Instruction {
+        let config = config_address(admin);
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::InitializeConfig {
+                admin: *admin,
+                config,
+                reward_mint: reward_mint_address(&config),
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::InitializeConfig { collection: self.collection, reward_per_epoch, min_stake_epochs }.data(),
+        }
+    }
+
+    pub fn stake_ix(&self, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+        let stake_account = stake_address(mint, &self.config);
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Stake {
+                owner: *owner,
+                mint: *mint,
+                metadata: metadata_address(mint),
+                owner_ata: get_associated_token_address(owner, mint),
+                config: self.config,
+                stake_account,
+                vault: get_associated_token_address(&stake_account, mint),
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Stake {}.data(),
+        }
+    }
+
+    pub fn claim_ix(&self, owner: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Claim {
+                owner: *owner,
+                config: self.config,
+                reward_mint: self.reward_mint,
+                stake_account: self.stake_account,
+                owner_rewards: get_associated_token_address(owner, &self.reward_mint),
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Claim {}.data(),
+        }
+    }
+
+    pub fn unstake_ix(&self, owner: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Unstake {
+                owner: *owner,
+                mint: self.nft,
+                owner_ata: get_associated_token_address(owner, &self.nft),
+                config: self.config,
+                reward_mint: self.reward_mint,
+                stake_account: self.stake_account,
+                vault: self.vault,
+                owner_rewards: get_associated_token_address(owner, &self.reward_mint),
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Unstake {}.data(),
+        }
+    }
+
+    pub fn stake(&mut self) -> TransactionResult {
+        let ix = self.stake_ix(&self.staker.pubkey(), &self.nft);
+        send(&mut self.program, &[ix], &self.staker)
+    }
+
+    pub fn claim(&mut self) -> TransactionResult {
+        let ix = self.claim_ix(&self.staker.pubkey());
+        send(&mut self.program, &[ix], &self.staker)
+    }
+
+    pub fn unstake(&mut self) -> TransactionResult {
+        let ix = self.unstake_ix(&self.staker.pubkey());
+        send(&mut self.program, &[ix], &self.staker)
+    }
+
+    pub fn token_amount(&self, address: &Pubkey) -> u64 {
+        token::token_amount(&self.program, address)
+    }
+
+    pub fn rewards(&self, owner: &Pubkey) -> u64 {
+        self.token_amount(&get_associated_token_address(owner, &self.reward_mint))
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.program, address)
+    }
+
+    pub fn stake_data(&self) -> StakeAccount {
+        account::anchor_account(&self.program, &self.stake_account)
+    }
+}
@@ -0,0 +1,70 @@
+use {
+    super::context::{StakingTestContext, REWARD_PER_EPOCH},
+    crate::error::StakingError,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::{warp_epochs, warp_seconds}},
+};
+
+#[test]
+fn should_pay_nothing_within_the_staking_epoch() {
+    let mut ctx = StakingTestContext::new();
+    ctx.stake().unwrap();
+
+    // Time alone does not count, only epoch boundaries do
+    warp_seconds(&mut ctx.program, 60 * 60);
+    assert_custom_error!(ctx.claim(), StakingError::NothingToClaim);
+}
+
+#[test]
+fn should_pay_per_elapsed_epoch() {
+    let mut ctx = StakingTestContext::new();
+    let staker = ctx.staker.pubkey();
+    ctx.stake().unwrap();
+
+    warp_epochs(&mut ctx.program, 3);
+    ctx.claim().unwrap();
+    assert_eq!(ctx.rewards(&staker), 3 * REWARD_PER_EPOCH);
+
+    // Claiming again in the same epoch pays nothing more
+    ctx.program.expire_blockhash();
+    assert_custom_error!(ctx.claim(), StakingError::NothingToClaim);
+
+    warp_epochs(&mut ctx.program, 1);
+    ctx.program.expire_blockhash();
+    ctx.claim().unwrap();
+    assert_eq!(ctx.rewards(&staker), 4 * REWARD_PER_EPOCH);
+    assert_eq!(ctx.stake_data().claimed_epoch, ctx.stake_data().staked_epoch + 4);
+}
+
+#[test]
+fn should_pay_unclaimed_rewards_on_unstake() {
+    let mut ctx = StakingTestContext::new();
+    let staker = ctx.staker.pubkey();
+    ctx.stake().unwrap();
+
+    warp_epochs(&mut ctx.program, 2);
+    ctx.claim().unwrap();
+
+    warp_epochs(&mut ctx.program, 3);
+    ctx.unstake().unwrap();
+
+    assert_eq!(ctx.rewards(&staker), 5 * REWARD_PER_EPOCH);
+}
+
+#[test]
+fn should_restart_rewards_when_staked_again() {
+    let mut ctx = StakingTestContext::new();
+    let staker = ctx.staker.pubkey();
+    ctx.stake().unwrap();
+
+    warp_epochs(&mut ctx.program, 2);
+    ctx.unstake().unwrap();
+    ctx.program.expire_blockhash();
+    ctx.stake().unwrap();
+
+    // Epochs spent unstaked are not paid for
+    warp_epochs(&mut ctx.program, 1);
+    ctx.program.expire_blockhash();
+    ctx.claim().unwrap();
+    assert_eq!(ctx.rewards(&staker), 3 * REWARD_PER_EPOCH);
+}
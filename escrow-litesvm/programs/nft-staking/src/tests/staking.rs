@@ -0,0 +1,134 @@
+use {
+    super::context::{config_address, verified, StakingTestContext, MIN_STAKE_EPOCHS, REWARD_PER_EPOCH},
+    crate::{error::StakingError, metadata::Collection},
+    anchor_lang::error::ErrorCode,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::warp_epochs, keys::keypair, send},
+};
+
+#[test]
+fn should_take_the_nft_into_custody() {
+    let mut ctx = StakingTestContext::new();
+    let staker = ctx.staker.pubkey();
+    let staker_ata = anchor_spl::associated_token::get_associated_token_address(&staker, &ctx.nft);
+
+    ctx.stake().unwrap();
+
+    assert_eq!(ctx.token_amount(&staker_ata), 0);
+    assert_eq!(ctx.token_amount(&ctx.vault), 1);
+
+    let stake = ctx.stake_data();
+    assert_eq!((stake.owner, stake.mint), (staker, ctx.nft));
+    assert_eq!(stake.staked_epoch, stake.claimed_epoch);
+}
+
+#[test]
+fn should_reject_fungible_mints() {
+    let mut ctx = StakingTestContext::new();
+    let staker = ctx.staker.insecure_clone();
+
+    // Two tokens of the mint exist, so it is not an NFT
+    let mint = ctx.mint_nft("fungible", &staker);
+    let staker_ata = anchor_spl::associated_token::get_associated_token_address(&staker.pubkey(), &mint);
+    litesvm_token::MintTo::new(&mut ctx.program, &staker, &mint, &staker_ata, 1).send().unwrap();
+
+    let stake_ix = ctx.stake_ix(&staker.pubkey(), &mint);
+    assert_custom_error!(send(&mut ctx.program, &[stake_ix], &staker), StakingError::NotAnNft);
+}
+
+#[test]
+fn should_reject_nfts_outside_the_collection() {
+    let mut ctx = StakingTestContext::new();
+    let staker = ctx.staker.insecure_clone();
+    let mint = ctx.mint_nft("outsider", &staker);
+    let stake_ix = ctx.stake_ix(&staker.pubkey(), &mint);
+
+    // Anyone can mint an NFT naming the collection, only its authority can verify it
+    ctx.write_metadata(&mint, Some(Collection { verified: false, key: ctx.collection }));
+    assert_custom_error!(send(&mut ctx.program, &[stake_ix.clone()], &staker), StakingError::NotInCollection);
+
+    ctx.write_metadata(&mint, verified(keypair("other_collection").pubkey()));
+    ctx.program.expire_blockhash();
+    assert_custom_error!(send(&mut ctx.program, &[stake_ix.clone()], &staker), StakingError::NotInCollection);
+
+    ctx.write_metadata(&mint, None);
+    ctx.program.expire_blockhash();
+    assert_custom_error!(send(&mut ctx.program, &[stake_ix], &staker), StakingError::NotInCollection);
+}
+
+#[test]
+fn should_keep_each_admins_config_apart() {
+    let mut ctx = StakingTestContext::new();
+    let other = keypair("other_admin");
+    ctx.program.airdrop(&other.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    // Another admin gets a config of their own rather than the existing one
+    let ix = ctx.initialize_config_ix(&other.pubkey(), 10 * REWARD_PER_EPOCH, MIN_STAKE_EPOCHS);
+    send(&mut ctx.program, &[ix], &other).unwrap();
+    assert_ne!(config_address(&other.pubkey()), ctx.config);
+
+    ctx.stake().unwrap();
+    warp_epochs(&mut ctx.program, 1);
+    ctx.claim().unwrap();
+    assert_eq!(ctx.rewards(&ctx.staker.pubkey()), REWARD_PER_EPOCH);
+}
+
+#[test]
+fn should_return_the_nft_and_rent_on_unstake() {
+    let mut ctx = StakingTestContext::new();
+    let staker = ctx.staker.pubkey();
+    let staker_ata = anchor_spl::associated_token::get_associated_token_address(&staker, &ctx.nft);
+
+    ctx.stake().unwrap();
+    let held = ctx.lamports(&ctx.stake_account) + ctx.lamports(&ctx.vault);
+    let before = ctx.lamports(&staker);
+
+    warp_epochs(&mut ctx.program, 2);
+    ctx.unstake().unwrap();
+
+    assert_eq!(ctx.token_amount(&staker_ata), 1);
+    assert_eq!(ctx.lamports(&ctx.stake_account), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+    // The rewards ATA is created out of the returned rent, and the fee paid on top
+    assert!(ctx.lamports(&staker) > before + held - LAMPORTS_PER_SOL / 100);
+
+    // The NFT can be staked again
+    ctx.program.expire_blockhash();
+    ctx.stake().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.vault), 1);
+}
+
+#[test]
+fn should_lock_the_nft_for_the_minimum_epochs() {
+    let mut ctx = StakingTestContext::new();
+    ctx.stake().unwrap();
+
+    assert_custom_error!(ctx.unstake(), StakingError::StakeLocked);
+
+    warp_epochs(&mut ctx.program, 1);
+    ctx.program.expire_blockhash();
+    assert_custom_error!(ctx.unstake(), StakingError::StakeLocked);
+
+    warp_epochs(&mut ctx.program, 1);
+    ctx.program.expire_blockhash();
+    ctx.unstake().unwrap();
+}
+
+#[test]
+fn should_reject_unstaking_someone_elses_nft() {
+    let mut ctx = StakingTestContext::new();
+    ctx.stake().unwrap();
+    warp_epochs(&mut ctx.program, 2);
+
+    let thief = keypair("thief");
+    ctx.program.airdrop(&thief.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    let unstake_ix = ctx.unstake_ix(&thief.pubkey());
+    assert_custom_error!(send(&mut ctx.program, &[unstake_ix], &thief), ErrorCode::ConstraintHasOne);
+
+    let claim_ix = ctx.claim_ix(&thief.pubkey());
+    assert_custom_error!(send(&mut ctx.program, &[claim_ix], &thief), ErrorCode::ConstraintHasOne);
+
+    assert_eq!(ctx.token_amount(&ctx.vault), 1);
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+#[constant]
+pub const RAFFLE_SEED: &[u8] = b"raffle";
+#[constant]
+pub const POT_SEED: &[u8] = b"pot";
+#[constant]
+pub const TICKET_SEED: &[u8] = b"ticket";
+
+/// How far ahead of the commit the draw slot is, so its hash is unknown when committing
+pub const DRAW_DELAY_SLOTS: u64 = 10;
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum RaffleError {
+    #[msg("Ticket price must cover the pot's rent")]
+    InvalidPrice,
+    #[msg("End time must be in the future")]
+    InvalidEndTime,
+    #[msg("Ticket sales have closed")]
+    SalesClosed,
+    #[msg("Ticket sales are still open")]
+    SalesOpen,
+    #[msg("No tickets were sold")]
+    NoTickets,
+    #[msg("A draw slot is already committed")]
+    AlreadyCommitted,
+    #[msg("No draw slot is committed")]
+    NotCommitted,
+    #[msg("The draw slot has not passed yet")]
+    DrawSlotNotReached,
+    #[msg("The draw slot's hash is not in the slot hashes sysvar")]
+    SlotHashUnavailable,
+    #[msg("The winner has already been drawn")]
+    AlreadyDrawn,
+    #[msg("The winner has not been drawn yet")]
+    NotDrawn,
+    #[msg("This ticket did not win")]
+    NotWinningTicket,
+    #[msg("The winning ticket is closed by claiming the pot")]
+    WinningTicket,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
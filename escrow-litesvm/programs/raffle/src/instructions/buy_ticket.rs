@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, POT_SEED, RAFFLE_SEED, TICKET_SEED},
+    error::RaffleError,
+    state::{Raffle, Ticket},
+};
+
+#[derive(Accounts)]
+pub struct BuyTicket<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [RAFFLE_SEED, raffle.authority.as_ref(), raffle.seed.to_le_bytes().as_ref()],
+        bump = raffle.bump,
+    )]
+    pub raffle: Account<'info, Raffle>,
+    #[account(
+        mut,
+        seeds = [POT_SEED, raffle.key().as_ref()],
+        bump = raffle.pot_bump,
+    )]
+    pub pot: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [TICKET_SEED, raffle.key().as_ref(), raffle.tickets_sold.to_le_bytes().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Ticket::INIT_SPACE,
+    )]
+    pub ticket: Account<'info, Ticket>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuyTicket<'info> {
+    pub fn buy_ticket(&mut self, bumps: &BuyTicketBumps) -> Result<()> {
+        require!(!self.raffle.has_ended(Clock::get()?.unix_timestamp), RaffleError::SalesClosed);
+
+        self.ticket.set_inner(Ticket {
+            raffle: self.raffle.key(),
+            owner: self.buyer.key(),
+            index: self.raffle.tickets_sold,
+            bump: bumps.ticket,
+        });
+        self.raffle.tickets_sold = self.raffle.tickets_sold
+            .checked_add(1)
+            .ok_or(RaffleError::MathOverflow)?;
+        self.raffle.tickets_open = self.raffle.tickets_open
+            .checked_add(1)
+            .ok_or(RaffleError::MathOverflow)?;
+
+        let cpi_accounts = Transfer {
+            from: self.buyer.to_account_info(),
+            to: self.pot.to_account_info(),
+        };
+
+        transfer(CpiContext::new(self.system_program.to_account_info(), cpi_accounts), self.raffle.ticket_price)
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    constants::{POT_SEED, RAFFLE_SEED, TICKET_SEED},
+    error::RaffleError,
+    state::{Raffle, Ticket},
+};
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: Checked against the raffle. Receives its rent if this is the last ticket.
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [RAFFLE_SEED, authority.key().as_ref(), raffle.seed.to_le_bytes().as_ref()],
+        bump = raffle.bump,
+    )]
+    pub raffle: Account<'info, Raffle>,
+    #[account(
+        mut,
+        seeds = [POT_SEED, raffle.key().as_ref()],
+        bump = raffle.pot_bump,
+    )]
+    pub pot: SystemAccount<'info>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = raffle,
+        has_one = owner,
+        seeds = [TICKET_SEED, raffle.key().as_ref(), ticket.index.to_le_bytes().as_ref()],
+        bump = ticket.bump,
+    )]
+    pub ticket: Account<'info, Ticket>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Claim<'info> {
+    // Losing tickets stay open as a record of who entered, until `close_ticket`
+    pub fn claim(&mut self) -> Result<()> {
+        let winning_ticket = self.raffle.winning_ticket.ok_or(RaffleError::NotDrawn)?;
+        require!(self.ticket.index == winning_ticket, RaffleError::NotWinningTicket);
+
+        let raffle = self.raffle.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[POT_SEED, raffle.as_ref(), &[self.raffle.pot_bump]]];
+
+        let cpi_accounts = Transfer {
+            from: self.pot.to_account_info(),
+            to: self.owner.to_account_info(),
+        };
+
+        transfer(
+            CpiContext::new_with_signer(self.system_program.to_account_info(), cpi_accounts, &signer_seeds),
+            self.pot.lamports(),
+        )?;
+
+        if !self.raffle.close_ticket()? {
+            return Ok(());
+        }
+
+        // The raffle's rent goes back to the authority, who paid it
+        self.raffle.close(self.authority.to_account_info())
+    }
+}
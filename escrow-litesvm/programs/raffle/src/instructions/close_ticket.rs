@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{RAFFLE_SEED, TICKET_SEED},
+    error::RaffleError,
+    state::{Raffle, Ticket},
+};
+
+#[derive(Accounts)]
+pub struct CloseTicket<'info> {
+    pub caller: Signer<'info>,
+    /// CHECK: Checked against the ticket. Receives its rent.
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: Checked against the raffle. Receives its rent if this is the last ticket.
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [RAFFLE_SEED, authority.key().as_ref(), raffle.seed.to_le_bytes().as_ref()],
+        bump = raffle.bump,
+    )]
+    pub raffle: Account<'info, Raffle>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = raffle,
+        has_one = owner,
+        seeds = [TICKET_SEED, raffle.key().as_ref(), ticket.index.to_le_bytes().as_ref()],
+        bump = ticket.bump,
+    )]
+    pub ticket: Account<'info, Ticket>,
+}
+
+impl<'info> CloseTicket<'info> {
+    pub fn close_ticket(&mut self) -> Result<()> {
+        let winning_ticket = self.raffle.winning_ticket.ok_or(RaffleError::NotDrawn)?;
+        require!(self.ticket.index != winning_ticket, RaffleError::WinningTicket);
+
+        if !self.raffle.close_ticket()? {
+            return Ok(());
+        }
+
+        self.raffle.close(self.authority.to_account_info())
+    }
+}
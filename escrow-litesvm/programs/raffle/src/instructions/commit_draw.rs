@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DRAW_DELAY_SLOTS, RAFFLE_SEED},
+    error::RaffleError,
+    randomness::MAX_SLOT_HASHES,
+    state::Raffle,
+};
+
+#[derive(Accounts)]
+pub struct CommitDraw<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [RAFFLE_SEED, raffle.authority.as_ref(), raffle.seed.to_le_bytes().as_ref()],
+        bump = raffle.bump,
+    )]
+    pub raffle: Account<'info, Raffle>,
+}
+
+impl<'info> CommitDraw<'info> {
+    pub fn commit_draw(&mut self) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(self.raffle.has_ended(clock.unix_timestamp), RaffleError::SalesOpen);
+        require!(self.raffle.tickets_sold > 0, RaffleError::NoTickets);
+        require!(self.raffle.winning_ticket.is_none(), RaffleError::AlreadyDrawn);
+
+        // A commit can only be replaced once its slot hash has aged out of the sysvar
+        require!(
+            self.raffle.draw_slot.map_or(true, |slot| clock.slot > slot + MAX_SLOT_HASHES),
+            RaffleError::AlreadyCommitted
+        );
+
+        self.raffle.draw_slot = Some(clock.slot + DRAW_DELAY_SLOTS);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, POT_SEED, RAFFLE_SEED},
+    error::RaffleError,
+    state::Raffle,
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct Create<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [RAFFLE_SEED, authority.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Raffle::INIT_SPACE,
+    )]
+    pub raffle: Account<'info, Raffle>,
+    #[account(
+        seeds = [POT_SEED, raffle.key().as_ref()],
+        bump,
+    )]
+    pub pot: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Create<'info> {
+    pub fn create(&mut self, seed: u64, ticket_price: u64, end_ts: i64, bumps: &CreateBumps) -> Result<()> {
+        // The first ticket opens the pot, so it must cover the pot's rent on its own
+        require!(ticket_price >= Rent::get()?.minimum_balance(0), RaffleError::InvalidPrice);
+        require!(end_ts > Clock::get()?.unix_timestamp, RaffleError::InvalidEndTime);
+
+        self.raffle.set_inner(Raffle {
+            seed,
+            authority: self.authority.key(),
+            ticket_price,
+            end_ts,
+            tickets_sold: 0,
+            tickets_open: 0,
+            draw_slot: None,
+            winning_ticket: None,
+            bump: bumps.raffle,
+            pot_bump: bumps.pot,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+
+use crate::{
+    constants::RAFFLE_SEED,
+    error::RaffleError,
+    randomness::{find_slot_hash, pick_ticket},
+    state::Raffle,
+};
+
+#[derive(Accounts)]
+pub struct Draw<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [RAFFLE_SEED, raffle.authority.as_ref(), raffle.seed.to_le_bytes().as_ref()],
+        bump = raffle.bump,
+    )]
+    pub raffle: Account<'info, Raffle>,
+    /// CHECK: the address is the SlotHashes sysvar, whose data is read by hand
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+impl<'info> Draw<'info> {
+    pub fn draw(&mut self) -> Result<()> {
+        require!(self.raffle.winning_ticket.is_none(), RaffleError::AlreadyDrawn);
+        let draw_slot = self.raffle.draw_slot.ok_or(RaffleError::NotCommitted)?;
+        require!(Clock::get()?.slot > draw_slot, RaffleError::DrawSlotNotReached);
+
+        let slot_hash = find_slot_hash(&self.slot_hashes.try_borrow_data()?, draw_slot)
+            .ok_or(RaffleError::SlotHashUnavailable)?;
+
+        self.raffle.winning_ticket = Some(pick_ticket(&slot_hash, &self.raffle.key(), self.raffle.tickets_sold));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+pub mod buy_ticket;
+pub mod claim;
+pub mod close_ticket;
+pub mod commit_draw;
+pub mod create;
+pub mod draw;
+
+pub use buy_ticket::*;
+pub use claim::*;
+pub use close_ticket::*;
+pub use commit_draw::*;
+pub use create::*;
+pub use draw::*;
@@ -0,0 +1,51 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod constants;
+mod error;
+mod instructions;
+pub mod randomness;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+use instructions::*;
+
+declare_id!("9EWMwicUVTwQLm1GYjkeP9svcyp7HM1xorwZXtgs4qHW");
+
+#[program]
+pub mod raffle {
+    use super::*;
+
+    pub fn create(ctx: Context<Create>, seed: u64, ticket_price: u64, end_ts: i64) -> Result<()> {
+        ctx.accounts.create(seed, ticket_price, end_ts, &ctx.bumps)
+    }
+
+    pub fn buy_ticket(ctx: Context<BuyTicket>) -> Result<()> {
+        ctx.accounts.buy_ticket(&ctx.bumps)
+    }
+
+    /// Picks the future slot whose hash will draw the winner. Anyone can commit once
+    /// sales have closed, and again if that hash aged out before anyone drew.
+    pub fn commit_draw(ctx: Context<CommitDraw>) -> Result<()> {
+        ctx.accounts.commit_draw()
+    }
+
+    /// Draws the winning ticket from the committed slot's hash
+    pub fn draw(ctx: Context<Draw>) -> Result<()> {
+        ctx.accounts.draw()
+    }
+
+    /// Pays the pot to the winning ticket's owner
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        ctx.accounts.claim()
+    }
+
+    /// Returns a losing ticket's rent to its owner. Anyone can close one once the
+    /// winner is drawn, and the last ticket closed also closes the raffle.
+    pub fn close_ticket(ctx: Context<CloseTicket>) -> Result<()> {
+        ctx.accounts.close_ticket()
+    }
+}
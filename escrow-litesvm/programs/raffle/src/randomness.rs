@@ -0,0 +1,33 @@
+//! Drawing a ticket from a slot hash, free of accounts so it can be checked on its own.
+//!
+//! The draw commits to a slot that has not happened yet, then reads that slot's hash
+//! from the `SlotHashes` sysvar once it has. Nobody knows the hash at commit time, but
+//! the leader producing that slot could withhold it, so this suits low-stakes raffles.
+
+use anchor_lang::solana_program::{hash::hashv, pubkey::Pubkey};
+
+/// The `SlotHashes` sysvar only remembers this many recent slots
+pub const MAX_SLOT_HASHES: u64 = 512;
+
+const LEN_SIZE: usize = 8;
+const ENTRY_SIZE: usize = 8 + 32;
+
+/// Finds `slot`'s hash in the raw `SlotHashes` sysvar data: a little-endian u64 length
+/// followed by (slot, hash) entries. Reading the bytes directly avoids deserializing
+/// the whole sysvar, which would exhaust the compute budget.
+pub fn find_slot_hash(data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    let len = u64::from_le_bytes(data.get(..LEN_SIZE)?.try_into().ok()?) as usize;
+
+    data.get(LEN_SIZE..)?
+        .chunks_exact(ENTRY_SIZE)
+        .take(len)
+        .find(|entry| entry[..8] == slot.to_le_bytes())
+        .map(|entry| entry[8..].try_into().unwrap())
+}
+
+/// The winning ticket index out of `tickets`. The raffle's address is mixed in so raffles
+/// drawn from the same slot do not share winners.
+pub fn pick_ticket(slot_hash: &[u8; 32], raffle: &Pubkey, tickets: u32) -> u32 {
+    let seed = hashv(&[slot_hash, raffle.as_ref()]).to_bytes();
+    (u64::from_le_bytes(seed[..8].try_into().unwrap()) % tickets as u64) as u32
+}
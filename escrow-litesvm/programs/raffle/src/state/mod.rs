@@ -0,0 +1,5 @@
+pub mod raffle;
+pub mod ticket;
+
+pub use raffle::*;
+pub use ticket::*;
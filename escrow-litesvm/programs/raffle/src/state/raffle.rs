@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::error::RaffleError;
+
+// Ticket sales are held in the system-owned pot PDA until the winner claims them
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Raffle {
+    pub seed: u64,
+    pub authority: Pubkey,
+    pub ticket_price: u64,
+    pub end_ts: i64,
+    pub tickets_sold: u32,
+    /// Tickets not closed yet. The raffle outlives them all, so none is left under
+    /// its address when it is created again with the same seed.
+    pub tickets_open: u32,
+    /// The slot whose hash draws the winner, once committed
+    pub draw_slot: Option<u64>,
+    pub winning_ticket: Option<u32>,
+    pub bump: u8,
+    pub pot_bump: u8,
+}
+
+impl Raffle {
+    /// Counts a ticket as closed, returning whether it was the last one
+    pub fn close_ticket(&mut self) -> Result<bool> {
+        self.tickets_open = self.tickets_open
+            .checked_sub(1)
+            .ok_or(RaffleError::MathOverflow)?;
+        Ok(self.tickets_open == 0)
+    }
+
+    pub fn has_ended(&self, now: i64) -> bool {
+        now >= self.end_ts
+    }
+}
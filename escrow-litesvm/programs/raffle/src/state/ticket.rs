@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+// One per ticket sold, numbered in order of sale from 0
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Ticket {
+    pub raffle: Pubkey,
+    pub owner: Pubkey,
+    pub index: u32,
+    pub bump: u8,
+}
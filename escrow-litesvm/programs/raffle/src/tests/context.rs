@@ -0,0 +1,196 @@
+use {
+    crate::{
+        constants::{POT_SEED, RAFFLE_SEED, TICKET_SEED},
+        randomness::pick_ticket,
+        state::{Raffle, Ticket},
+    },
+    anchor_lang::{
+        solana_program::{clock::Clock, hash::Hash, slot_hashes::SlotHashes, sysvar::slot_hashes},
+        InstructionData, ToAccountMetas,
+    },
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, keys, send, svm_with_program},
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+pub const SEED: u64 = 1;
+pub const TICKET_PRICE: u64 = LAMPORTS_PER_SOL / 10;
+/// How long after creation tickets are sold
+pub const DURATION: i64 = 24 * 60 * 60;
+
+pub fn raffle_address(authority: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(&[RAFFLE_SEED, authority.as_ref(), &seed.to_le_bytes()], &PROGRAM_ID).0
+}
+
+pub fn pot_address(raffle: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POT_SEED, raffle.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn ticket_address(raffle: &Pubkey, index: u32) -> Pubkey {
+    Pubkey::find_program_address(&[TICKET_SEED, raffle.as_ref(), &index.to_le_bytes()], &PROGRAM_ID).0
+}
+
+/// An authority and two players. `new` creates the raffle, `uncreated` leaves it to the test.
+pub struct RaffleTestContext {
+    pub program: LiteSVM,
+    pub authority: Keypair,
+    pub alice: Keypair,
+    pub bob: Keypair,
+    pub raffle: Pubkey,
+    pub pot: Pubkey,
+    pub end_ts: i64,
+}
+
+impl RaffleTestContext {
+    pub fn new() -> Self {
+        let mut ctx = Self::uncreated();
+        ctx.create(TICKET_PRICE, ctx.end_ts).unwrap();
+        ctx
+    }
+
+    pub fn uncreated() -> Self {
+        let authority = keys::keypair("authority");
+        let alice = keys::keypair("alice");
+        let bob = keys::keypair("bob");
+        let mut program = svm_with_program(&PROGRAM_ID, "raffle", env!("CARGO_MANIFEST_DIR"), &[&authority, &alice, &bob]);
+
+        let raffle = raffle_address(&authority.pubkey(), SEED);
+        let end_ts = program.get_sysvar::<Clock>().unix_timestamp + DURATION;
+
+        Self {
+            pot: pot_address(&raffle),
+            program,
+            authority,
+            alice,
+            bob,
+            raffle,
+            end_ts,
+        }
+    }
+
+    pub fn create(&mut self, ticket_price: u64, end_ts: i64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Create {
+                authority: self.authority.pubkey(),
+                raffle: self.raffle,
+                pot: self.pot,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Create { seed: SEED, ticket_price, end_ts }.data(),
+        };
+        send(&mut self.program, &[ix], &self.authority)
+    }
+
+    /// Buys the next ticket in line
+    pub fn buy_ticket(&mut self, buyer: &Keypair) -> TransactionResult {
+        let index = self.raffle_data().tickets_sold;
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::BuyTicket {
+                buyer: buyer.pubkey(),
+                raffle: self.raffle,
+                pot: self.pot,
+                ticket: ticket_address(&self.raffle, index),
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::BuyTicket {}.data(),
+        };
+        send(&mut self.program, &[ix], buyer)
+    }
+
+    /// Each commit gets a fresh blockhash, since repeated ones are identical transactions
+    pub fn commit_draw(&mut self, caller: &Keypair) -> TransactionResult {
+        self.program.expire_blockhash();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::CommitDraw { caller: caller.pubkey(), raffle: self.raffle }.to_account_metas(None),
+            data: crate::instruction::CommitDraw {}.data(),
+        };
+        send(&mut self.program, &[ix], caller)
+    }
+
+    pub fn draw(&mut self, caller: &Keypair) -> TransactionResult {
+        self.program.expire_blockhash();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Draw {
+                caller: caller.pubkey(),
+                raffle: self.raffle,
+                slot_hashes: slot_hashes::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Draw {}.data(),
+        };
+        send(&mut self.program, &[ix], caller)
+    }
+
+    pub fn claim(&mut self, owner: &Keypair, index: u32) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Claim {
+                owner: owner.pubkey(),
+                authority: self.authority.pubkey(),
+                raffle: self.raffle,
+                pot: self.pot,
+                ticket: ticket_address(&self.raffle, index),
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Claim {}.data(),
+        };
+        send(&mut self.program, &[ix], owner)
+    }
+
+    pub fn close_ticket(&mut self, caller: &Keypair, index: u32) -> TransactionResult {
+        self.program.expire_blockhash();
+        let ticket = ticket_address(&self.raffle, index);
+        let owner = self.ticket_data(index).owner;
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::CloseTicket {
+                caller: caller.pubkey(),
+                owner,
+                authority: self.authority.pubkey(),
+                raffle: self.raffle,
+                ticket,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::CloseTicket {}.data(),
+        };
+        send(&mut self.program, &[ix], caller)
+    }
+
+    /// Stands in for the cluster, which LiteSVM does not run, by recording `hash` as `slot`'s
+    pub fn mock_slot_hash(&mut self, slot: u64, hash: [u8; 32]) {
+        self.program.set_sysvar::<SlotHashes>(&SlotHashes::new(&[(slot, Hash::new_from_array(hash))]));
+    }
+
+    /// A slot hash that draws ticket `index` out of `tickets`
+    pub fn hash_drawing(&self, index: u32, tickets: u32) -> [u8; 32] {
+        (0..=u8::MAX)
+            .map(|byte| [byte; 32])
+            .find(|hash| pick_ticket(hash, &self.raffle, tickets) == index)
+            .expect("No hash draws this ticket")
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.program, address)
+    }
+
+    pub fn raffle_data(&self) -> Raffle {
+        account::anchor_account(&self.program, &self.raffle)
+    }
+
+    pub fn ticket_data(&self, index: u32) -> Ticket {
+        account::anchor_account(&self.program, &ticket_address(&self.raffle, index))
+    }
+}
@@ -0,0 +1,211 @@
+use {
+    super::context::{ticket_address, RaffleTestContext, DURATION, TICKET_PRICE},
+    crate::{constants::DRAW_DELAY_SLOTS, error::RaffleError},
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::{warp_slots, warp_to_timestamp}, SIGNATURE_FEE},
+};
+
+/// Alice holds tickets 0 and 2, Bob ticket 1, and sales have closed
+fn sold_out() -> RaffleTestContext {
+    let mut ctx = RaffleTestContext::new();
+    let (alice, bob) = (ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.buy_ticket(&alice).unwrap();
+    ctx.buy_ticket(&bob).unwrap();
+    ctx.buy_ticket(&alice).unwrap();
+    warp_to_timestamp(&mut ctx.program, ctx.end_ts);
+
+    ctx
+}
+
+/// `sold_out`, with ticket `winner` drawn
+fn drawn(winner: u32) -> RaffleTestContext {
+    let mut ctx = sold_out();
+    let authority = ctx.authority.insecure_clone();
+
+    ctx.commit_draw(&authority).unwrap();
+    let draw_slot = ctx.raffle_data().draw_slot.unwrap();
+    warp_slots(&mut ctx.program, DRAW_DELAY_SLOTS + 1);
+    let hash = ctx.hash_drawing(winner, 3);
+    ctx.mock_slot_hash(draw_slot, hash);
+    ctx.draw(&authority).unwrap();
+
+    ctx
+}
+
+#[test]
+fn should_draw_the_ticket_picked_by_the_slot_hash() {
+    for winner in 0..3 {
+        let mut ctx = sold_out();
+        let caller = ctx.authority.insecure_clone();
+
+        ctx.commit_draw(&caller).unwrap();
+        let draw_slot = ctx.raffle_data().draw_slot.unwrap();
+
+        warp_slots(&mut ctx.program, DRAW_DELAY_SLOTS + 1);
+        let hash = ctx.hash_drawing(winner, 3);
+        ctx.mock_slot_hash(draw_slot, hash);
+        ctx.draw(&caller).unwrap();
+
+        assert_eq!(ctx.raffle_data().winning_ticket, Some(winner));
+    }
+}
+
+#[test]
+fn should_pay_the_pot_to_the_winner() {
+    let mut ctx = drawn(1);
+    let bob = ctx.bob.insecure_clone();
+
+    let ticket_rent = ctx.lamports(&ticket_address(&ctx.raffle, 1));
+    let bob_before = ctx.lamports(&bob.pubkey());
+
+    ctx.claim(&bob, 1).unwrap();
+
+    assert_eq!(ctx.lamports(&bob.pubkey()), bob_before + 3 * TICKET_PRICE + ticket_rent - SIGNATURE_FEE);
+    assert_eq!(ctx.lamports(&ctx.pot), 0);
+
+    // The losing tickets are still open, and with them the raffle
+    assert_eq!(ctx.raffle_data().tickets_open, 2);
+}
+
+#[test]
+fn should_close_losing_tickets_and_the_raffle_after_the_last() {
+    let mut ctx = drawn(1);
+    let (authority, alice, bob) = (ctx.authority.insecure_clone(), ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.claim(&bob, 1).unwrap();
+
+    // Anyone can close a losing ticket, its rent goes to its owner
+    let ticket_rent = ctx.lamports(&ticket_address(&ctx.raffle, 0));
+    let alice_before = ctx.lamports(&alice.pubkey());
+    ctx.close_ticket(&bob, 0).unwrap();
+
+    assert_eq!(ctx.lamports(&alice.pubkey()), alice_before + ticket_rent);
+    assert_eq!(ctx.lamports(&ticket_address(&ctx.raffle, 0)), 0);
+    assert_eq!(ctx.raffle_data().tickets_open, 1);
+
+    let raffle_rent = ctx.lamports(&ctx.raffle);
+    let authority_before = ctx.lamports(&authority.pubkey());
+    ctx.close_ticket(&bob, 2).unwrap();
+
+    assert_eq!(ctx.lamports(&ctx.raffle), 0);
+    assert_eq!(ctx.lamports(&authority.pubkey()), authority_before + raffle_rent);
+
+    // No ticket is left under the raffle's address, so the same seed sells from 0 again
+    let end_ts = ctx.end_ts + DURATION;
+    ctx.create(TICKET_PRICE, end_ts).unwrap();
+    ctx.buy_ticket(&alice).unwrap();
+    assert_eq!(ctx.ticket_data(0).owner, alice.pubkey());
+}
+
+#[test]
+fn should_close_the_raffle_on_a_claim_after_the_losing_tickets() {
+    let mut ctx = drawn(1);
+    let (authority, bob) = (ctx.authority.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.close_ticket(&authority, 0).unwrap();
+    ctx.close_ticket(&authority, 2).unwrap();
+
+    let raffle_rent = ctx.lamports(&ctx.raffle);
+    let authority_before = ctx.lamports(&authority.pubkey());
+    ctx.claim(&bob, 1).unwrap();
+
+    assert_eq!(ctx.lamports(&ctx.raffle), 0);
+    assert_eq!(ctx.lamports(&authority.pubkey()), authority_before + raffle_rent);
+}
+
+#[test]
+fn should_reject_closing_the_winning_ticket() {
+    let mut ctx = drawn(1);
+    let authority = ctx.authority.insecure_clone();
+
+    assert_custom_error!(ctx.close_ticket(&authority, 1), RaffleError::WinningTicket);
+    assert_eq!(ctx.lamports(&ctx.pot), 3 * TICKET_PRICE);
+}
+
+#[test]
+fn should_reject_closing_tickets_before_the_draw() {
+    let mut ctx = sold_out();
+    let authority = ctx.authority.insecure_clone();
+
+    assert_custom_error!(ctx.close_ticket(&authority, 0), RaffleError::NotDrawn);
+}
+
+#[test]
+fn should_reject_claims_on_losing_tickets() {
+    let mut ctx = drawn(1);
+    let alice = ctx.alice.insecure_clone();
+
+    assert_custom_error!(ctx.claim(&alice, 0), RaffleError::NotWinningTicket);
+    assert_eq!(ctx.lamports(&ctx.pot), 3 * TICKET_PRICE);
+}
+
+#[test]
+fn should_reject_claims_before_the_draw() {
+    let mut ctx = sold_out();
+    let alice = ctx.alice.insecure_clone();
+
+    assert_custom_error!(ctx.claim(&alice, 0), RaffleError::NotDrawn);
+}
+
+#[test]
+fn should_reject_commits_while_sales_are_open() {
+    let mut ctx = RaffleTestContext::new();
+    let (authority, alice) = (ctx.authority.insecure_clone(), ctx.alice.insecure_clone());
+
+    ctx.buy_ticket(&alice).unwrap();
+
+    assert_custom_error!(ctx.commit_draw(&authority), RaffleError::SalesOpen);
+}
+
+#[test]
+fn should_reject_commits_without_tickets() {
+    let mut ctx = RaffleTestContext::new();
+    let authority = ctx.authority.insecure_clone();
+
+    warp_to_timestamp(&mut ctx.program, ctx.end_ts);
+
+    assert_custom_error!(ctx.commit_draw(&authority), RaffleError::NoTickets);
+}
+
+#[test]
+fn should_keep_the_commit_until_its_hash_ages_out() {
+    let mut ctx = sold_out();
+    let authority = ctx.authority.insecure_clone();
+
+    ctx.commit_draw(&authority).unwrap();
+    let draw_slot = ctx.raffle_data().draw_slot.unwrap();
+
+    assert_custom_error!(ctx.commit_draw(&authority), RaffleError::AlreadyCommitted);
+
+    // Nobody drew in time, so the hash is gone and a new slot can be committed to
+    warp_slots(&mut ctx.program, DRAW_DELAY_SLOTS + 513);
+    ctx.commit_draw(&authority).unwrap();
+    assert!(ctx.raffle_data().draw_slot.unwrap() > draw_slot);
+}
+
+#[test]
+fn should_reject_draws_before_the_draw_slot() {
+    let mut ctx = sold_out();
+    let authority = ctx.authority.insecure_clone();
+
+    ctx.commit_draw(&authority).unwrap();
+    let draw_slot = ctx.raffle_data().draw_slot.unwrap();
+    ctx.mock_slot_hash(draw_slot, [7; 32]);
+
+    assert_custom_error!(ctx.draw(&authority), RaffleError::DrawSlotNotReached);
+}
+
+#[test]
+fn should_reject_draws_without_the_slot_hash() {
+    let mut ctx = sold_out();
+    let authority = ctx.authority.insecure_clone();
+
+    ctx.commit_draw(&authority).unwrap();
+    let draw_slot = ctx.raffle_data().draw_slot.unwrap();
+    warp_slots(&mut ctx.program, DRAW_DELAY_SLOTS + 1);
+    ctx.mock_slot_hash(draw_slot - 1, [7; 32]);
+
+    assert_custom_error!(ctx.draw(&authority), RaffleError::SlotHashUnavailable);
+    assert_eq!(ctx.raffle_data().winning_ticket, None);
+}
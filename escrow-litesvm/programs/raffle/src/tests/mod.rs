@@ -0,0 +1,5 @@
+pub mod context;
+
+mod draw;
+mod randomness;
+mod tickets;
@@ -0,0 +1,50 @@
+use {
+    crate::randomness::{find_slot_hash, pick_ticket},
+    anchor_lang::solana_program::{hash::Hash, slot_hashes::SlotHashes, sysvar::slot_hashes},
+    litesvm::LiteSVM,
+    solana_pubkey::Pubkey,
+};
+
+#[test]
+fn should_find_slot_hashes_in_the_sysvar_data() {
+    let mut program = LiteSVM::new();
+    program.set_sysvar::<SlotHashes>(&SlotHashes::new(&[
+        (12, Hash::new_from_array([12; 32])),
+        (11, Hash::new_from_array([11; 32])),
+        (10, Hash::new_from_array([10; 32])),
+    ]));
+
+    let data = program.get_account(&slot_hashes::ID).unwrap().data;
+    assert_eq!(find_slot_hash(&data, 11), Some([11; 32]));
+    assert_eq!(find_slot_hash(&data, 10), Some([10; 32]));
+    assert_eq!(find_slot_hash(&data, 9), None);
+}
+
+#[test]
+fn should_ignore_truncated_sysvar_data() {
+    assert_eq!(find_slot_hash(&[], 0), None);
+    // Claims one entry but holds none
+    assert_eq!(find_slot_hash(&1u64.to_le_bytes(), 0), None);
+}
+
+#[test]
+fn should_pick_every_ticket_for_some_hash() {
+    let raffle = Pubkey::new_unique();
+    let mut picked = [false; 5];
+
+    for byte in 0..=u8::MAX {
+        let ticket = pick_ticket(&[byte; 32], &raffle, 5);
+        assert!(ticket < 5);
+        picked[ticket as usize] = true;
+    }
+
+    assert!(picked.iter().all(|&picked| picked));
+}
+
+#[test]
+fn should_draw_differently_for_different_raffles() {
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let differs = (0..=u8::MAX).any(|byte| pick_ticket(&[byte; 32], &first, 1_000) != pick_ticket(&[byte; 32], &second, 1_000));
+    assert!(differs);
+}
@@ -0,0 +1,57 @@
+use {
+    super::context::{ticket_address, RaffleTestContext, TICKET_PRICE},
+    crate::error::RaffleError,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::warp_to_timestamp, SIGNATURE_FEE},
+};
+
+#[test]
+fn should_number_tickets_in_order_of_sale() {
+    let mut ctx = RaffleTestContext::new();
+    let (alice, bob) = (ctx.alice.insecure_clone(), ctx.bob.insecure_clone());
+
+    ctx.buy_ticket(&alice).unwrap();
+    ctx.buy_ticket(&bob).unwrap();
+    ctx.buy_ticket(&alice).unwrap();
+
+    assert_eq!(ctx.raffle_data().tickets_sold, 3);
+    assert_eq!(ctx.raffle_data().tickets_open, 3);
+    assert_eq!(ctx.lamports(&ctx.pot), 3 * TICKET_PRICE);
+    for (index, owner) in [(0, &alice), (1, &bob), (2, &alice)] {
+        let ticket = ctx.ticket_data(index);
+        assert_eq!(ticket.index, index);
+        assert_eq!(ticket.owner, owner.pubkey());
+        assert_eq!(ticket.raffle, ctx.raffle);
+    }
+}
+
+#[test]
+fn should_charge_the_ticket_price() {
+    let mut ctx = RaffleTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+    let before = ctx.lamports(&alice.pubkey());
+
+    ctx.buy_ticket(&alice).unwrap();
+
+    let ticket_rent = ctx.lamports(&ticket_address(&ctx.raffle, 0));
+    assert_eq!(ctx.lamports(&alice.pubkey()), before - TICKET_PRICE - ticket_rent - SIGNATURE_FEE);
+}
+
+#[test]
+fn should_reject_a_price_below_the_pot_rent() {
+    let mut ctx = RaffleTestContext::uncreated();
+    let end_ts = ctx.end_ts;
+
+    assert_custom_error!(ctx.create(1, end_ts), RaffleError::InvalidPrice);
+}
+
+#[test]
+fn should_reject_sales_after_the_end() {
+    let mut ctx = RaffleTestContext::new();
+    let alice = ctx.alice.insecure_clone();
+
+    warp_to_timestamp(&mut ctx.program, ctx.end_ts);
+
+    assert_custom_error!(ctx.buy_ticket(&alice), RaffleError::SalesClosed);
+    assert_eq!(ctx.raffle_data().tickets_sold, 0);
+}
@@ -0,0 +1,6 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+#[constant]
+pub const STREAM_SEED: &[u8] = b"stream";
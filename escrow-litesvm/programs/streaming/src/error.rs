@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum StreamError {
+    #[msg("Amount must be positive")]
+    InvalidAmount,
+    #[msg("Rate must be positive and no more than the deposit")]
+    InvalidRate,
+    #[msg("Nothing has accrued since the last withdrawal")]
+    NothingToWithdraw,
+    #[msg("The stream has run dry and can no longer be topped up")]
+    StreamDepleted,
+    #[msg("Only the sender or the recipient can cancel the stream")]
+    Unauthorized,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{constants::STREAM_SEED, error::StreamError, instructions::{close_vault, pay_out}, state::Stream};
+
+#[derive(Accounts)]
+pub struct Cancel<'info> {
+    /// The sender or the recipient
+    #[account(
+        mut,
+        constraint = canceller.key() == stream.sender || canceller.key() == stream.recipient @ StreamError::Unauthorized,
+    )]
+    pub canceller: Signer<'info>,
+    /// Receives the unstreamed remainder and both rents
+    #[account(mut)]
+    pub sender: SystemAccount<'info>,
+    pub recipient: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = canceller,
+        associated_token::mint = mint,
+        associated_token::authority = sender,
+        associated_token::token_program = token_program,
+    )]
+    pub sender_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = canceller,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = sender,
+        has_one = sender,
+        has_one = recipient,
+        has_one = mint,
+        seeds = [STREAM_SEED, sender.key().as_ref(), recipient.key().as_ref(), stream.seed.to_le_bytes().as_ref()],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, Stream>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = stream,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Cancel<'info> {
+    pub fn cancel(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let owed = self.stream.withdrawable_at(now)?;
+        let remainder = self.vault.amount
+            .checked_sub(owed)
+            .ok_or(StreamError::MathOverflow)?;
+
+        let token_program = self.token_program.to_account_info();
+        if owed > 0 {
+            pay_out(&self.stream, &self.mint, &self.vault, &self.recipient_ata, token_program.clone(), owed)?;
+        }
+        if remainder > 0 {
+            pay_out(&self.stream, &self.mint, &self.vault, &self.sender_ata, token_program.clone(), remainder)?;
+        }
+
+        close_vault(&self.stream, &self.vault, &self.sender, token_program)
+    }
+}
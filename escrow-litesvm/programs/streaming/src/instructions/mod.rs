@@ -0,0 +1,55 @@
+pub mod cancel;
+pub mod open;
+pub mod top_up;
+pub mod withdraw;
+
+pub use cancel::*;
+pub use open::*;
+pub use top_up::*;
+pub use withdraw::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TransferChecked};
+
+use crate::{constants::STREAM_SEED, state::Stream};
+
+/// Sends `amount` out of the vault, signed by the stream
+pub(crate) fn pay_out<'info>(
+    stream: &Account<'info, Stream>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    destination: &InterfaceAccount<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: vault.to_account_info(),
+        to: destination.to_account_info(),
+        authority: stream.to_account_info(),
+        mint: mint.to_account_info(),
+    };
+
+    let seed = stream.seed.to_le_bytes();
+    let signer_seeds: [&[&[u8]]; 1] = [&[STREAM_SEED, stream.sender.as_ref(), stream.recipient.as_ref(), &seed, &[stream.bump]]];
+
+    transfer_checked(CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds), amount, mint.decimals)
+}
+
+/// Closes the emptied vault, returning its rent to the sender who paid it
+pub(crate) fn close_vault<'info>(
+    stream: &Account<'info, Stream>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    sender: &AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: sender.clone(),
+        authority: stream.to_account_info(),
+    };
+
+    let seed = stream.seed.to_le_bytes();
+    let signer_seeds: [&[&[u8]]; 1] = [&[STREAM_SEED, stream.sender.as_ref(), stream.recipient.as_ref(), &seed, &[stream.bump]]];
+
+    close_account(CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds))
+}
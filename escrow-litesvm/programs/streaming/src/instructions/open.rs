@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked}};
+
+use crate::{constants::{ANCHOR_DISCRIMINATOR, STREAM_SEED}, error::StreamError, state::Stream};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct Open<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    pub recipient: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = sender,
+        associated_token::token_program = token_program,
+    )]
+    pub sender_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = sender,
+        seeds = [STREAM_SEED, sender.key().as_ref(), recipient.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Stream::INIT_SPACE,
+    )]
+    pub stream: Account<'info, Stream>,
+    #[account(
+        init,
+        payer = sender,
+        associated_token::mint = mint,
+        associated_token::authority = stream,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Open<'info> {
+    pub fn open(&mut self, seed: u64, rate: u64, deposit: u64, bumps: &OpenBumps) -> Result<()> {
+        require!(deposit > 0, StreamError::InvalidAmount);
+        require!(rate > 0 && rate <= deposit, StreamError::InvalidRate);
+
+        self.stream.set_inner(Stream {
+            seed,
+            sender: self.sender.key(),
+            recipient: self.recipient.key(),
+            mint: self.mint.key(),
+            rate,
+            start_ts: Clock::get()?.unix_timestamp,
+            deposited: deposit,
+            withdrawn: 0,
+            bump: bumps.stream,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, deposit: u64) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.sender_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.sender.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, deposit, self.mint.decimals)
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{constants::STREAM_SEED, error::StreamError, state::Stream};
+
+#[derive(Accounts)]
+pub struct TopUp<'info> {
+    pub sender: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = sender,
+        associated_token::token_program = token_program,
+    )]
+    pub sender_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = sender,
+        has_one = mint,
+        seeds = [STREAM_SEED, sender.key().as_ref(), stream.recipient.as_ref(), stream.seed.to_le_bytes().as_ref()],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, Stream>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = stream,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> TopUp<'info> {
+    // Accrual is computed from the start, so topping up a dry stream would pay the
+    // recipient for the time it was dry. Such a stream has to be reopened instead.
+    pub fn top_up(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, StreamError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        require!(!self.stream.is_depleted_at(now)?, StreamError::StreamDepleted);
+
+        self.stream.deposited = self.stream.deposited
+            .checked_add(amount)
+            .ok_or(StreamError::MathOverflow)?;
+
+        let cpi_accounts = TransferChecked {
+            from: self.sender_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.sender.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{constants::STREAM_SEED, error::StreamError, instructions::pay_out, state::Stream};
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = recipient,
+        has_one = mint,
+        seeds = [STREAM_SEED, stream.sender.as_ref(), recipient.key().as_ref(), stream.seed.to_le_bytes().as_ref()],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, Stream>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = stream,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Withdraw<'info> {
+    // A drained stream stays open so the sender can still cancel it and reclaim the rent
+    pub fn withdraw(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let amount = self.stream.withdrawable_at(now)?;
+        require!(amount > 0, StreamError::NothingToWithdraw);
+
+        self.stream.withdrawn = self.stream.withdrawn
+            .checked_add(amount)
+            .ok_or(StreamError::MathOverflow)?;
+
+        pay_out(&self.stream, &self.mint, &self.vault, &self.recipient_ata, self.token_program.to_account_info(), amount)
+    }
+}
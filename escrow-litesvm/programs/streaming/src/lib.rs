@@ -0,0 +1,40 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod constants;
+mod error;
+mod instructions;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+use instructions::*;
+
+declare_id!("D3p7icYbwuxcZuHK4LJieohnareHNdcpiH991BPLBHSZ");
+
+#[program]
+pub mod streaming {
+    use super::*;
+
+    /// Streams `rate` per second to the recipient from now until `deposit` runs out
+    pub fn open(ctx: Context<Open>, seed: u64, rate: u64, deposit: u64) -> Result<()> {
+        ctx.accounts.open(seed, rate, deposit, &ctx.bumps)?;
+        ctx.accounts.deposit(deposit)
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+        ctx.accounts.withdraw()
+    }
+
+    /// Extends a stream that has not run dry yet
+    pub fn top_up(ctx: Context<TopUp>, amount: u64) -> Result<()> {
+        ctx.accounts.top_up(amount)
+    }
+
+    /// Either side can cancel. The recipient is paid what accrued, the sender gets the rest.
+    pub fn cancel(ctx: Context<Cancel>) -> Result<()> {
+        ctx.accounts.cancel()
+    }
+}
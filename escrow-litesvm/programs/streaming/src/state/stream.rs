@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::error::StreamError;
+
+// The deposit sits in this account's associated token account and accrues to the
+// recipient at `rate` per second from `start_ts` until all of it has
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Stream {
+    pub seed: u64,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub rate: u64,
+    pub start_ts: i64,
+    /// Everything the sender put in, top-ups included
+    pub deposited: u64,
+    pub withdrawn: u64,
+    pub bump: u8,
+}
+
+impl Stream {
+    /// Everything streamed by `now`, withdrawn or not
+    pub fn accrued_at(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.start_ts).max(0) as u128;
+        let accrued = (self.rate as u128)
+            .checked_mul(elapsed)
+            .ok_or(StreamError::MathOverflow)?;
+        Ok(accrued.min(self.deposited as u128) as u64)
+    }
+
+    pub fn withdrawable_at(&self, now: i64) -> Result<u64> {
+        self.accrued_at(now)?
+            .checked_sub(self.withdrawn)
+            .ok_or(StreamError::MathOverflow.into())
+    }
+
+    pub fn is_depleted_at(&self, now: i64) -> Result<bool> {
+        Ok(self.accrued_at(now)? == self.deposited)
+    }
+}
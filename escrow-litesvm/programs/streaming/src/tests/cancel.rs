@@ -0,0 +1,66 @@
+use {
+    super::context::{StreamTestContext, DEPOSIT, RATE, SPARE},
+    crate::error::StreamError,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::warp_seconds, keys::keypair, SIGNATURE_FEE},
+};
+
+#[test]
+fn should_split_pro_rata_when_the_sender_cancels() {
+    let mut ctx = StreamTestContext::new();
+    let sender = ctx.sender.insecure_clone();
+
+    warp_seconds(&mut ctx.program, 100);
+    ctx.withdraw().unwrap();
+    warp_seconds(&mut ctx.program, 150);
+
+    let rent = ctx.lamports(&ctx.stream) + ctx.lamports(&ctx.vault);
+    let sender_before = ctx.lamports(&sender.pubkey());
+
+    ctx.cancel(&sender).unwrap();
+
+    // 250 seconds streamed in total, 100 of them withdrawn already
+    assert_eq!(ctx.token_amount(&ctx.recipient_ata), 250 * RATE);
+    assert_eq!(ctx.token_amount(&ctx.sender_ata), SPARE + DEPOSIT - 250 * RATE);
+    assert_eq!(ctx.lamports(&ctx.stream), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+    assert_eq!(ctx.lamports(&sender.pubkey()), sender_before + rent - SIGNATURE_FEE);
+}
+
+#[test]
+fn should_let_the_recipient_cancel() {
+    let mut ctx = StreamTestContext::new();
+    let recipient = ctx.recipient.insecure_clone();
+
+    // The recipient never withdrew, so their token account is created by the cancel
+    warp_seconds(&mut ctx.program, 200);
+    ctx.cancel(&recipient).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.recipient_ata), 200 * RATE);
+    assert_eq!(ctx.token_amount(&ctx.sender_ata), SPARE + DEPOSIT - 200 * RATE);
+    assert_eq!(ctx.lamports(&ctx.stream), 0);
+}
+
+#[test]
+fn should_pay_the_recipient_everything_once_streamed() {
+    let mut ctx = StreamTestContext::new();
+    let sender = ctx.sender.insecure_clone();
+
+    warp_seconds(&mut ctx.program, 60 * 60);
+    ctx.cancel(&sender).unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.recipient_ata), DEPOSIT);
+    assert_eq!(ctx.token_amount(&ctx.sender_ata), SPARE);
+}
+
+#[test]
+fn should_reject_cancels_from_anyone_else() {
+    let mut ctx = StreamTestContext::new();
+
+    let stranger = keypair("stranger");
+    ctx.program.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    assert_custom_error!(ctx.cancel(&stranger), StreamError::Unauthorized);
+    assert_eq!(ctx.token_amount(&ctx.vault), DEPOSIT);
+}
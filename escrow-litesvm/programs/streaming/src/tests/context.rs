@@ -0,0 +1,164 @@
+use {
+    crate::{constants::STREAM_SEED, state::Stream},
+    anchor_lang::{InstructionData, ToAccountMetas},
+    anchor_spl::{associated_token::{self, get_associated_token_address}, token::spl_token},
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, keys, send, svm_with_program, token},
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+pub const SEED: u64 = 1;
+pub const RATE: u64 = 100;
+/// Enough for ten minutes at `RATE`
+pub const DEPOSIT: u64 = 60_000;
+/// What the sender holds beyond the deposit, for top-ups
+pub const SPARE: u64 = 1_000_000;
+
+pub fn stream_address(sender: &Pubkey, recipient: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(&[STREAM_SEED, sender.as_ref(), recipient.as_ref(), &seed.to_le_bytes()], &PROGRAM_ID).0
+}
+
+/// A sender and a recipient. `new` opens the stream, `unopened` leaves it to the test.
+pub struct StreamTestContext {
+    pub program: LiteSVM,
+    pub sender: Keypair,
+    pub recipient: Keypair,
+    pub mint: Pubkey,
+    pub sender_ata: Pubkey,
+    pub recipient_ata: Pubkey,
+    pub stream: Pubkey,
+    pub vault: Pubkey,
+}
+
+impl StreamTestContext {
+    pub fn new() -> Self {
+        let mut ctx = Self::unopened();
+        ctx.open(RATE, DEPOSIT).unwrap();
+        ctx
+    }
+
+    pub fn unopened() -> Self {
+        let sender = keys::keypair("sender");
+        let recipient = keys::keypair("recipient");
+        let mut program = svm_with_program(&PROGRAM_ID, "streaming", env!("CARGO_MANIFEST_DIR"), &[&sender, &recipient]);
+
+        let mint = token::create_mint(&mut program, &sender, &keys::keypair("mint"), 6, None);
+        let sender_ata = token::create_ata(&mut program, &sender, &mint, &sender.pubkey());
+        let mint_ix = spl_token::instruction::mint_to(&spl_token::ID, &mint, &sender_ata, &sender.pubkey(), &[], DEPOSIT + SPARE).unwrap();
+        send(&mut program, &[mint_ix], &sender).unwrap();
+
+        let stream = stream_address(&sender.pubkey(), &recipient.pubkey(), SEED);
+
+        Self {
+            recipient_ata: get_associated_token_address(&recipient.pubkey(), &mint),
+            vault: get_associated_token_address(&stream, &mint),
+            program,
+            sender,
+            recipient,
+            mint,
+            sender_ata,
+            stream,
+        }
+    }
+
+    pub fn open(&mut self, rate: u64, deposit: u64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Open {
+                sender: self.sender.pubkey(),
+                recipient: self.recipient.pubkey(),
+                mint: self.mint,
+                sender_ata: self.sender_ata,
+                stream: self.stream,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Open { seed: SEED, rate, deposit }.data(),
+        };
+        send(&mut self.program, &[ix], &self.sender)
+    }
+
+    /// Each withdrawal gets a fresh blockhash, since repeated ones are identical transactions
+    pub fn withdraw(&mut self) -> TransactionResult {
+        self.program.expire_blockhash();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Withdraw {
+                recipient: self.recipient.pubkey(),
+                mint: self.mint,
+                recipient_ata: self.recipient_ata,
+                stream: self.stream,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Withdraw {}.data(),
+        };
+        send(&mut self.program, &[ix], &self.recipient)
+    }
+
+    pub fn top_up(&mut self, amount: u64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::TopUp {
+                sender: self.sender.pubkey(),
+                mint: self.mint,
+                sender_ata: self.sender_ata,
+                stream: self.stream,
+                vault: self.vault,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::TopUp { amount }.data(),
+        };
+        send(&mut self.program, &[ix], &self.sender)
+    }
+
+    pub fn cancel_ix(&self, canceller: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Cancel {
+                canceller: *canceller,
+                sender: self.sender.pubkey(),
+                recipient: self.recipient.pubkey(),
+                mint: self.mint,
+                sender_ata: self.sender_ata,
+                recipient_ata: self.recipient_ata,
+                stream: self.stream,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Cancel {}.data(),
+        }
+    }
+
+    pub fn cancel(&mut self, canceller: &Keypair) -> TransactionResult {
+        let ix = self.cancel_ix(&canceller.pubkey());
+        send(&mut self.program, &[ix], canceller)
+    }
+
+    pub fn token_amount(&self, address: &Pubkey) -> u64 {
+        token::token_amount(&self.program, address)
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.program, address)
+    }
+
+    pub fn stream_data(&self) -> Stream {
+        account::anchor_account(&self.program, &self.stream)
+    }
+}
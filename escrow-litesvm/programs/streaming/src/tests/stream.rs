@@ -0,0 +1,78 @@
+use {
+    super::context::{StreamTestContext, DEPOSIT, RATE, SPARE},
+    crate::error::StreamError,
+    test_utils::{assert_custom_error, clock::warp_seconds},
+};
+
+#[test]
+fn should_lock_the_deposit() {
+    let ctx = StreamTestContext::new();
+
+    assert_eq!(ctx.token_amount(&ctx.vault), DEPOSIT);
+    assert_eq!(ctx.token_amount(&ctx.sender_ata), SPARE);
+
+    let stream = ctx.stream_data();
+    assert_eq!((stream.rate, stream.deposited, stream.withdrawn), (RATE, DEPOSIT, 0));
+}
+
+#[test]
+fn should_reject_invalid_rates() {
+    let mut ctx = StreamTestContext::unopened();
+
+    assert_custom_error!(ctx.open(0, DEPOSIT), StreamError::InvalidRate);
+    assert_custom_error!(ctx.open(DEPOSIT + 1, DEPOSIT), StreamError::InvalidRate);
+    assert_custom_error!(ctx.open(RATE, 0), StreamError::InvalidAmount);
+}
+
+#[test]
+fn should_accrue_per_second() {
+    let mut ctx = StreamTestContext::new();
+
+    assert_custom_error!(ctx.withdraw(), StreamError::NothingToWithdraw);
+
+    warp_seconds(&mut ctx.program, 30);
+    ctx.withdraw().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.recipient_ata), 30 * RATE);
+
+    // Only what accrued since the last withdrawal
+    warp_seconds(&mut ctx.program, 45);
+    ctx.withdraw().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.recipient_ata), 75 * RATE);
+    assert_eq!(ctx.stream_data().withdrawn, 75 * RATE);
+    assert_eq!(ctx.token_amount(&ctx.vault), DEPOSIT - 75 * RATE);
+}
+
+#[test]
+fn should_stop_accruing_once_the_deposit_is_streamed() {
+    let mut ctx = StreamTestContext::new();
+
+    warp_seconds(&mut ctx.program, 60 * 60);
+    ctx.withdraw().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.recipient_ata), DEPOSIT);
+
+    warp_seconds(&mut ctx.program, 60);
+    assert_custom_error!(ctx.withdraw(), StreamError::NothingToWithdraw);
+}
+
+#[test]
+fn should_extend_the_stream_on_top_up() {
+    let mut ctx = StreamTestContext::new();
+
+    warp_seconds(&mut ctx.program, 300);
+    ctx.top_up(DEPOSIT).unwrap();
+    assert_eq!(ctx.stream_data().deposited, 2 * DEPOSIT);
+
+    // The original deposit alone would have run out at 600 seconds
+    warp_seconds(&mut ctx.program, 600);
+    ctx.withdraw().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.recipient_ata), 900 * RATE);
+}
+
+#[test]
+fn should_reject_topping_up_a_dry_stream() {
+    let mut ctx = StreamTestContext::new();
+
+    warp_seconds(&mut ctx.program, 600);
+    assert_custom_error!(ctx.top_up(DEPOSIT), StreamError::StreamDepleted);
+    assert_eq!(ctx.token_amount(&ctx.vault), DEPOSIT);
+}
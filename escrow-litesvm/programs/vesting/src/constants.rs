@@ -0,0 +1,6 @@
+use anchor_lang::prelude::*;
+
+pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+#[constant]
+pub const VESTING_SEED: &[u8] = b"vesting";
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VestingError {
+    #[msg("Amount must be positive")]
+    InvalidAmount,
+    #[msg("Schedule must satisfy start <= cliff <= end and start < end")]
+    InvalidSchedule,
+    #[msg("Nothing has vested since the last claim")]
+    NothingToClaim,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
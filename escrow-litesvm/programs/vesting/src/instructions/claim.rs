@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{constants::VESTING_SEED, error::VestingError, instructions::{close_vault, pay_out}, state::Vesting};
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    /// Receives the rent once everything is claimed
+    #[account(mut)]
+    pub authority: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
+    )]
+    pub beneficiary_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = authority,
+        has_one = beneficiary,
+        has_one = mint,
+        seeds = [VESTING_SEED, authority.key().as_ref(), beneficiary.key().as_ref(), vesting.seed.to_le_bytes().as_ref()],
+        bump = vesting.bump,
+    )]
+    pub vesting: Account<'info, Vesting>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Claim<'info> {
+    // The last claim closes the vault and the vesting account
+    pub fn claim(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let amount = self.vesting.claimable_at(now)?;
+        require!(amount > 0, VestingError::NothingToClaim);
+
+        self.vesting.claimed = self.vesting.claimed
+            .checked_add(amount)
+            .ok_or(VestingError::MathOverflow)?;
+
+        let token_program = self.token_program.to_account_info();
+
+        if self.vesting.claimed < self.vesting.total {
+            return pay_out(&self.vesting, &self.mint, &self.vault, &self.beneficiary_ata, token_program, amount);
+        }
+
+        // The last claim also takes anything sent straight to the vault, which would
+        // otherwise keep it from closing
+        pay_out(&self.vesting, &self.mint, &self.vault, &self.beneficiary_ata, token_program.clone(), self.vault.amount)?;
+
+        close_vault(&self.vesting, &self.vault, &self.authority, token_program)?;
+        self.vesting.close(self.authority.to_account_info())
+    }
+}
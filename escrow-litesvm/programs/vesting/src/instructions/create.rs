@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked}};
+
+use crate::{constants::{ANCHOR_DISCRIMINATOR, VESTING_SEED}, error::VestingError, state::Vesting};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct Create<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub beneficiary: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program,
+    )]
+    pub authority_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [VESTING_SEED, authority.key().as_ref(), beneficiary.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+        space = ANCHOR_DISCRIMINATOR + Vesting::INIT_SPACE,
+    )]
+    pub vesting: Account<'info, Vesting>,
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Create<'info> {
+    pub fn create(&mut self, seed: u64, total: u64, start_ts: i64, cliff_ts: i64, end_ts: i64, bumps: &CreateBumps) -> Result<()> {
+        require!(total > 0, VestingError::InvalidAmount);
+        require!(start_ts <= cliff_ts && cliff_ts <= end_ts && start_ts < end_ts, VestingError::InvalidSchedule);
+
+        self.vesting.set_inner(Vesting {
+            seed,
+            authority: self.authority.key(),
+            beneficiary: self.beneficiary.key(),
+            mint: self.mint.key(),
+            total,
+            claimed: 0,
+            start_ts,
+            cliff_ts,
+            end_ts,
+            bump: bumps.vesting,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, total: u64) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.authority_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.authority.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, total, self.mint.decimals)
+    }
+}
@@ -0,0 +1,53 @@
+pub mod claim;
+pub mod create;
+pub mod revoke;
+
+pub use claim::*;
+pub use create::*;
+pub use revoke::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TransferChecked};
+
+use crate::{constants::VESTING_SEED, state::Vesting};
+
+/// Sends `amount` out of the vault, signed by the vesting account
+pub(crate) fn pay_out<'info>(
+    vesting: &Account<'info, Vesting>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    destination: &InterfaceAccount<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: vault.to_account_info(),
+        to: destination.to_account_info(),
+        authority: vesting.to_account_info(),
+        mint: mint.to_account_info(),
+    };
+
+    let seed = vesting.seed.to_le_bytes();
+    let signer_seeds: [&[&[u8]]; 1] = [&[VESTING_SEED, vesting.authority.as_ref(), vesting.beneficiary.as_ref(), &seed, &[vesting.bump]]];
+
+    transfer_checked(CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds), amount, mint.decimals)
+}
+
+/// Closes the emptied vault, returning its rent to the authority who paid it
+pub(crate) fn close_vault<'info>(
+    vesting: &Account<'info, Vesting>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: authority.clone(),
+        authority: vesting.to_account_info(),
+    };
+
+    let seed = vesting.seed.to_le_bytes();
+    let signer_seeds: [&[&[u8]]; 1] = [&[VESTING_SEED, vesting.authority.as_ref(), vesting.beneficiary.as_ref(), &seed, &[vesting.bump]]];
+
+    close_account(CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds))
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{constants::VESTING_SEED, error::VestingError, instructions::{close_vault, pay_out}, state::Vesting};
+
+#[derive(Accounts)]
+pub struct Revoke<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub beneficiary: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program,
+    )]
+    pub authority_ata: InterfaceAccount<'info, TokenAccount>,
+    // The authority pays for it if the beneficiary never claimed
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
+    )]
+    pub beneficiary_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority,
+        has_one = beneficiary,
+        has_one = mint,
+        seeds = [VESTING_SEED, authority.key().as_ref(), beneficiary.key().as_ref(), vesting.seed.to_le_bytes().as_ref()],
+        bump = vesting.bump,
+    )]
+    pub vesting: Account<'info, Vesting>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Revoke<'info> {
+    pub fn revoke(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let vested = self.vesting.claimable_at(now)?;
+        let unvested = self.vault.amount
+            .checked_sub(vested)
+            .ok_or(VestingError::MathOverflow)?;
+
+        let token_program = self.token_program.to_account_info();
+        if vested > 0 {
+            pay_out(&self.vesting, &self.mint, &self.vault, &self.beneficiary_ata, token_program.clone(), vested)?;
+        }
+        if unvested > 0 {
+            pay_out(&self.vesting, &self.mint, &self.vault, &self.authority_ata, token_program.clone(), unvested)?;
+        }
+
+        close_vault(&self.vesting, &self.vault, &self.authority, token_program)
+    }
+}
@@ -0,0 +1,36 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod constants;
+mod error;
+mod instructions;
+pub mod state;
+#[cfg(test)]
+mod tests;
+
+use instructions::*;
+
+declare_id!("BJu6QxWhb7Hp9DWcLE3CukLcUz8XgAeUZEgAKaBpNXwD");
+
+#[program]
+pub mod vesting {
+    use super::*;
+
+    /// Locks `total` of the mint for the beneficiary. Nothing vests before `cliff_ts`,
+    /// then it vests linearly from `start_ts` until everything has by `end_ts`.
+    pub fn create(ctx: Context<Create>, seed: u64, total: u64, start_ts: i64, cliff_ts: i64, end_ts: i64) -> Result<()> {
+        ctx.accounts.create(seed, total, start_ts, cliff_ts, end_ts, &ctx.bumps)?;
+        ctx.accounts.deposit(total)
+    }
+
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        ctx.accounts.claim()
+    }
+
+    /// Pays the beneficiary what has vested and returns the rest to the authority
+    pub fn revoke(ctx: Context<Revoke>) -> Result<()> {
+        ctx.accounts.revoke()
+    }
+}
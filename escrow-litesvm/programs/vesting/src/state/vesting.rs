@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::error::VestingError;
+
+// The locked tokens sit in this account's associated token account, like an escrow's vault
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Vesting {
+    pub seed: u64,
+    pub authority: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub total: u64,
+    pub claimed: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+    pub bump: u8,
+}
+
+impl Vesting {
+    /// Everything vested by `now`, claimed or not. Rounds down, so the last unit
+    /// only vests at `end_ts`.
+    pub fn vested_at(&self, now: i64) -> Result<u64> {
+        if now < self.cliff_ts {
+            return Ok(0);
+        }
+        if now >= self.end_ts {
+            return Ok(self.total);
+        }
+
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        let vested = self.total as u128 * elapsed / duration;
+        u64::try_from(vested).map_err(|_| VestingError::MathOverflow.into())
+    }
+
+    pub fn claimable_at(&self, now: i64) -> Result<u64> {
+        self.vested_at(now)?
+            .checked_sub(self.claimed)
+            .ok_or(VestingError::MathOverflow.into())
+    }
+}
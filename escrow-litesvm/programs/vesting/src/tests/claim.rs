@@ -0,0 +1,95 @@
+use {
+    super::context::{VestingTestContext, CLIFF, DURATION, TOTAL},
+    crate::error::VestingError,
+    anchor_lang::error::ErrorCode,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::warp_to_timestamp, keys::keypair, send},
+};
+
+#[test]
+fn should_lock_the_total_in_the_vault() {
+    let ctx = VestingTestContext::new();
+
+    assert_eq!(ctx.token_amount(&ctx.vault), TOTAL);
+    assert_eq!(ctx.token_amount(&ctx.authority_ata), 0);
+
+    let vesting = ctx.vesting_data();
+    assert_eq!((vesting.beneficiary, vesting.total, vesting.claimed), (ctx.beneficiary.pubkey(), TOTAL, 0));
+}
+
+#[test]
+fn should_not_pay_before_the_cliff() {
+    let mut ctx = VestingTestContext::new();
+
+    assert_custom_error!(ctx.claim(), VestingError::NothingToClaim);
+
+    warp_to_timestamp(&mut ctx.program, ctx.start_ts + CLIFF - 1);
+    assert_custom_error!(ctx.claim(), VestingError::NothingToClaim);
+}
+
+#[test]
+fn should_pay_what_vested_since_the_last_claim() {
+    let mut ctx = VestingTestContext::new();
+
+    warp_to_timestamp(&mut ctx.program, ctx.start_ts + CLIFF);
+    ctx.claim().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.beneficiary_ata), 300_000);
+
+    // Nothing more until time moves on
+    assert_custom_error!(ctx.claim(), VestingError::NothingToClaim);
+
+    warp_to_timestamp(&mut ctx.program, ctx.start_ts + DURATION / 2);
+    ctx.claim().unwrap();
+    assert_eq!(ctx.token_amount(&ctx.beneficiary_ata), TOTAL / 2);
+    assert_eq!(ctx.vesting_data().claimed, TOTAL / 2);
+    assert_eq!(ctx.token_amount(&ctx.vault), TOTAL / 2);
+}
+
+#[test]
+fn should_close_the_schedule_on_the_last_claim() {
+    let mut ctx = VestingTestContext::new();
+    let authority = ctx.authority.pubkey();
+
+    warp_to_timestamp(&mut ctx.program, ctx.start_ts + DURATION / 2);
+    ctx.claim().unwrap();
+
+    let rent = ctx.lamports(&ctx.vesting) + ctx.lamports(&ctx.vault);
+    let authority_before = ctx.lamports(&authority);
+
+    warp_to_timestamp(&mut ctx.program, ctx.start_ts + DURATION + 60);
+    ctx.claim().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.beneficiary_ata), TOTAL);
+    assert_eq!(ctx.lamports(&ctx.vesting), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+    assert_eq!(ctx.lamports(&authority), authority_before + rent, "The authority paid the rent");
+}
+
+#[test]
+fn should_close_a_vault_holding_donated_tokens_on_the_last_claim() {
+    let mut ctx = VestingTestContext::new();
+    ctx.donate_to_vault(5);
+
+    // The donated tokens go out with the last claim instead of keeping the vault open
+    warp_to_timestamp(&mut ctx.program, ctx.start_ts + DURATION);
+    ctx.claim().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.beneficiary_ata), TOTAL + 5);
+    assert_eq!(ctx.lamports(&ctx.vesting), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_only_pay_the_beneficiary() {
+    let mut ctx = VestingTestContext::new();
+    warp_to_timestamp(&mut ctx.program, ctx.start_ts + DURATION);
+
+    let thief = keypair("thief");
+    ctx.program.airdrop(&thief.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+    // The vesting is derived from its beneficiary, so a thief signing in their place fails its seeds
+    let claim_ix = ctx.claim_ix(&thief.pubkey());
+    assert_custom_error!(send(&mut ctx.program, &[claim_ix], &thief), ErrorCode::ConstraintSeeds);
+    assert_eq!(ctx.token_amount(&ctx.vault), TOTAL);
+}
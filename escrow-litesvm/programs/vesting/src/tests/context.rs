@@ -0,0 +1,161 @@
+use {
+    crate::{constants::VESTING_SEED, state::Vesting},
+    anchor_lang::{solana_program::clock::Clock, InstructionData, ToAccountMetas},
+    anchor_spl::{associated_token::{self, get_associated_token_address}, token::spl_token},
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    test_utils::{account, keys, send, svm_with_program, token},
+};
+
+pub static PROGRAM_ID: Pubkey = crate::ID;
+
+pub const SEED: u64 = 1;
+/// 1_000 units vest per second over the 1_200 second schedule
+pub const TOTAL: u64 = 1_200_000;
+pub const CLIFF: i64 = 300;
+pub const DURATION: i64 = 1_200;
+
+pub fn vesting_address(authority: &Pubkey, beneficiary: &Pubkey, seed: u64) -> Pubkey {
+    Pubkey::find_program_address(&[VESTING_SEED, authority.as_ref(), beneficiary.as_ref(), &seed.to_le_bytes()], &PROGRAM_ID).0
+}
+
+/// An authority holding the tokens to vest and a beneficiary. `new` creates the
+/// schedule starting now, `uncreated` leaves it to the test.
+pub struct VestingTestContext {
+    pub program: LiteSVM,
+    pub authority: Keypair,
+    pub beneficiary: Keypair,
+    pub mint: Pubkey,
+    pub authority_ata: Pubkey,
+    pub beneficiary_ata: Pubkey,
+    pub vesting: Pubkey,
+    pub vault: Pubkey,
+    pub start_ts: i64,
+}
+
+impl VestingTestContext {
+    pub fn new() -> Self {
+        let mut ctx = Self::uncreated();
+        let start_ts = ctx.start_ts;
+        ctx.create(TOTAL, start_ts, start_ts + CLIFF, start_ts + DURATION).unwrap();
+        ctx
+    }
+
+    pub fn uncreated() -> Self {
+        let authority = keys::keypair("authority");
+        let beneficiary = keys::keypair("beneficiary");
+        let mut program = svm_with_program(&PROGRAM_ID, "vesting", env!("CARGO_MANIFEST_DIR"), &[&authority, &beneficiary]);
+
+        let mint = token::create_mint(&mut program, &authority, &keys::keypair("mint"), 6, None);
+        let authority_ata = token::create_ata(&mut program, &authority, &mint, &authority.pubkey());
+        let mint_ix = spl_token::instruction::mint_to(&spl_token::ID, &mint, &authority_ata, &authority.pubkey(), &[], TOTAL).unwrap();
+        send(&mut program, &[mint_ix], &authority).unwrap();
+
+        let vesting = vesting_address(&authority.pubkey(), &beneficiary.pubkey(), SEED);
+        let start_ts = program.get_sysvar::<Clock>().unix_timestamp;
+
+        Self {
+            beneficiary_ata: get_associated_token_address(&beneficiary.pubkey(), &mint),
+            vault: get_associated_token_address(&vesting, &mint),
+            program,
+            authority,
+            beneficiary,
+            mint,
+            authority_ata,
+            vesting,
+            start_ts,
+        }
+    }
+
+    pub fn create(&mut self, total: u64, start_ts: i64, cliff_ts: i64, end_ts: i64) -> TransactionResult {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Create {
+                authority: self.authority.pubkey(),
+                beneficiary: self.beneficiary.pubkey(),
+                mint: self.mint,
+                authority_ata: self.authority_ata,
+                vesting: self.vesting,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Create { seed: SEED, total, start_ts, cliff_ts, end_ts }.data(),
+        };
+        send(&mut self.program, &[ix], &self.authority)
+    }
+
+    pub fn claim_ix(&self, beneficiary: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Claim {
+                beneficiary: *beneficiary,
+                authority: self.authority.pubkey(),
+                mint: self.mint,
+                beneficiary_ata: get_associated_token_address(beneficiary, &self.mint),
+                vesting: self.vesting,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Claim {}.data(),
+        }
+    }
+
+    pub fn revoke_ix(&self, authority: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: crate::accounts::Revoke {
+                authority: *authority,
+                beneficiary: self.beneficiary.pubkey(),
+                mint: self.mint,
+                authority_ata: get_associated_token_address(authority, &self.mint),
+                beneficiary_ata: self.beneficiary_ata,
+                vesting: self.vesting,
+                vault: self.vault,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::Revoke {}.data(),
+        }
+    }
+
+    /// Each claim gets a fresh blockhash, since repeated claims are identical transactions
+    pub fn claim(&mut self) -> TransactionResult {
+        self.program.expire_blockhash();
+        let ix = self.claim_ix(&self.beneficiary.pubkey());
+        send(&mut self.program, &[ix], &self.beneficiary)
+    }
+
+    pub fn revoke(&mut self) -> TransactionResult {
+        let ix = self.revoke_ix(&self.authority.pubkey());
+        send(&mut self.program, &[ix], &self.authority)
+    }
+
+    /// Mints `amount` straight into the vault, around the program
+    pub fn donate_to_vault(&mut self, amount: u64) {
+        let ix = spl_token::instruction::mint_to(&spl_token::ID, &self.mint, &self.vault, &self.authority.pubkey(), &[], amount).unwrap();
+        send(&mut self.program, &[ix], &self.authority).unwrap();
+    }
+
+    pub fn token_amount(&self, address: &Pubkey) -> u64 {
+        token::token_amount(&self.program, address)
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        account::lamports(&self.program, address)
+    }
+
+    pub fn vesting_data(&self) -> Vesting {
+        account::anchor_account(&self.program, &self.vesting)
+    }
+}
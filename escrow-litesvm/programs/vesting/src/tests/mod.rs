@@ -0,0 +1,5 @@
+pub mod context;
+
+mod claim;
+mod revoke;
+mod schedule;
@@ -0,0 +1,52 @@
+use {
+    super::context::{VestingTestContext, CLIFF, DURATION, TOTAL},
+    anchor_lang::error::ErrorCode,
+    solana_signer::Signer,
+    test_utils::{assert_custom_error, clock::warp_to_timestamp, send, token, SIGNATURE_FEE},
+};
+
+#[test]
+fn should_split_the_vault_between_beneficiary_and_authority() {
+    let mut ctx = VestingTestContext::new();
+
+    warp_to_timestamp(&mut ctx.program, ctx.start_ts + CLIFF);
+    ctx.claim().unwrap();
+
+    // A quarter more vests before the authority revokes
+    warp_to_timestamp(&mut ctx.program, ctx.start_ts + DURATION / 2);
+    ctx.revoke().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.beneficiary_ata), TOTAL / 2);
+    assert_eq!(ctx.token_amount(&ctx.authority_ata), TOTAL / 2);
+    assert_eq!(ctx.lamports(&ctx.vesting), 0);
+    assert_eq!(ctx.lamports(&ctx.vault), 0);
+}
+
+#[test]
+fn should_return_everything_when_revoked_before_the_cliff() {
+    let mut ctx = VestingTestContext::new();
+    let authority = ctx.authority.pubkey();
+
+    let rent = ctx.lamports(&ctx.vesting) + ctx.lamports(&ctx.vault);
+    let authority_before = ctx.lamports(&authority);
+
+    ctx.revoke().unwrap();
+
+    assert_eq!(ctx.token_amount(&ctx.authority_ata), TOTAL);
+    assert_eq!(ctx.token_amount(&ctx.beneficiary_ata), 0);
+    // The beneficiary's token account is created out of the returned rent
+    let beneficiary_ata_rent = ctx.lamports(&ctx.beneficiary_ata);
+    assert_eq!(ctx.lamports(&authority), authority_before + rent - beneficiary_ata_rent - SIGNATURE_FEE);
+}
+
+#[test]
+fn should_only_be_revoked_by_the_authority() {
+    let mut ctx = VestingTestContext::new();
+    let beneficiary = ctx.beneficiary.insecure_clone();
+    token::create_ata(&mut ctx.program, &beneficiary, &ctx.mint, &beneficiary.pubkey());
+
+    // The vesting is derived from its authority, so another signer fails its seeds
+    let revoke_ix = ctx.revoke_ix(&beneficiary.pubkey());
+    assert_custom_error!(send(&mut ctx.program, &[revoke_ix], &beneficiary), ErrorCode::ConstraintSeeds);
+    assert_eq!(ctx.token_amount(&ctx.vault), TOTAL);
+}
@@ -0,0 +1,59 @@
+use {
+    super::context::{VestingTestContext, CLIFF, DURATION, TOTAL},
+    crate::{error::VestingError, state::Vesting},
+    anchor_lang::prelude::Pubkey,
+    test_utils::assert_custom_error,
+};
+
+fn schedule(start_ts: i64, cliff_ts: i64, end_ts: i64) -> Vesting {
+    Vesting {
+        seed: 0,
+        authority: Pubkey::default(),
+        beneficiary: Pubkey::default(),
+        mint: Pubkey::default(),
+        total: TOTAL,
+        claimed: 0,
+        start_ts,
+        cliff_ts,
+        end_ts,
+        bump: 0,
+    }
+}
+
+#[test]
+fn should_vest_nothing_before_the_cliff_then_linearly() {
+    let vesting = schedule(0, CLIFF, DURATION);
+
+    assert_eq!(vesting.vested_at(-1).unwrap(), 0);
+    assert_eq!(vesting.vested_at(CLIFF - 1).unwrap(), 0);
+    // At the cliff, everything since the start vests at once
+    assert_eq!(vesting.vested_at(CLIFF).unwrap(), 300_000);
+    assert_eq!(vesting.vested_at(DURATION / 2).unwrap(), TOTAL / 2);
+    assert_eq!(vesting.vested_at(DURATION - 1).unwrap(), TOTAL - 1_000);
+    assert_eq!(vesting.vested_at(DURATION).unwrap(), TOTAL);
+    assert_eq!(vesting.vested_at(i64::MAX).unwrap(), TOTAL);
+}
+
+#[test]
+fn should_round_vesting_down() {
+    let mut vesting = schedule(0, 0, 7);
+    vesting.total = 10;
+
+    // 10 * 3 / 7 = 4.28...
+    assert_eq!(vesting.vested_at(3).unwrap(), 4);
+    assert_eq!(vesting.vested_at(6).unwrap(), 8);
+}
+
+#[test]
+fn should_reject_invalid_schedules() {
+    let mut ctx = VestingTestContext::uncreated();
+    let now = ctx.start_ts;
+
+    assert_custom_error!(ctx.create(0, now, now, now + 10), VestingError::InvalidAmount);
+    assert_custom_error!(ctx.create(TOTAL, now, now - 1, now + 10), VestingError::InvalidSchedule);
+    assert_custom_error!(ctx.create(TOTAL, now, now + 11, now + 10), VestingError::InvalidSchedule);
+    assert_custom_error!(ctx.create(TOTAL, now, now, now), VestingError::InvalidSchedule);
+
+    // A schedule with no cliff is fine
+    ctx.create(TOTAL, now, now, now + 10).unwrap();
+}
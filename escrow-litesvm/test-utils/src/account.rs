@@ -0,0 +1,33 @@
+//! Reading accounts back out of the SVM.
+
+use {
+    anchor_lang::AccountDeserialize,
+    litesvm::LiteSVM,
+    solana_pubkey::Pubkey,
+};
+
+/// Lamports held by an account, 0 if it does not exist
+pub fn lamports(program: &LiteSVM, address: &Pubkey) -> u64 {
+    program.get_account(address).map_or(0, |account| account.lamports)
+}
+
+/// Deserializes the Anchor account at `address`, checking its discriminator. Panics
+/// naming the expected type if the account does not exist or holds another one.
+pub fn anchor_account<T: AccountDeserialize>(program: &LiteSVM, address: &Pubkey) -> T {
+    let name = std::any::type_name::<T>().rsplit("::").next().unwrap();
+    let account = program
+        .get_account(address)
+        .unwrap_or_else(|| panic!("{} not found at {}", name, address));
+    T::try_deserialize(&mut account.data.as_slice())
+        .unwrap_or_else(|err| panic!("{} at {} does not deserialize: {}", name, address, err))
+}
+
+#[test]
+fn should_read_zero_lamports_for_a_missing_account() {
+    let mut program = LiteSVM::new();
+    let address = Pubkey::new_unique();
+    assert_eq!(lamports(&program, &address), 0);
+
+    program.airdrop(&address, 1_000_000_000).unwrap();
+    assert_eq!(lamports(&program, &address), 1_000_000_000);
+}
@@ -0,0 +1,79 @@
+//! Locates a compiled program for the tests, wherever the workspace puts it.
+//!
+//! `SBF_OUT_DIR`, `BPF_OUT_DIR` and `CARGO_TARGET_DIR` are honoured before falling
+//! back to the nearest `target/deploy` above the program crate. With the
+//! `build-program` feature a missing artifact is built with `cargo build-sbf` on first use.
+
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+fn candidates(file: &str, manifest_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = ["SBF_OUT_DIR", "BPF_OUT_DIR"]
+        .into_iter()
+        .filter_map(env::var_os)
+        .map(PathBuf::from)
+        .collect();
+
+    if let Some(target_dir) = env::var_os("CARGO_TARGET_DIR") {
+        dirs.push(PathBuf::from(target_dir).join("deploy"));
+    }
+
+    // The workspace root is some ancestor of the program crate, however deeply it is nested
+    dirs.extend(manifest_dir.ancestors().map(|dir| dir.join("target/deploy")));
+
+    dirs.into_iter().map(|dir| dir.join(file)).collect()
+}
+
+fn find(file: &str, manifest_dir: &Path) -> Option<PathBuf> {
+    candidates(file, manifest_dir).into_iter().find(|path| path.is_file())
+}
+
+#[cfg(feature = "build-program")]
+fn build(file: &str, manifest_dir: &Path) -> Option<PathBuf> {
+    let status = std::process::Command::new("cargo")
+        .arg("build-sbf")
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("Cargo.toml"))
+        .status()
+        .expect("Failed to run cargo build-sbf, is the Solana toolchain installed?");
+    assert!(status.success(), "cargo build-sbf failed with {}", status);
+
+    find(file, manifest_dir)
+}
+
+#[cfg(not(feature = "build-program"))]
+fn build(_file: &str, _manifest_dir: &Path) -> Option<PathBuf> {
+    None
+}
+
+/// Path of `<name>.so`, building it first when allowed and missing. `manifest_dir` is
+/// the program crate's `CARGO_MANIFEST_DIR`. Resolved once per test binary, so
+/// concurrent tests never build the same program twice.
+pub fn program_path(name: &str, manifest_dir: &str) -> PathBuf {
+    static PATHS: OnceLock<Mutex<HashMap<String, PathBuf>>> = OnceLock::new();
+
+    let file = format!("{}.so", name);
+    let manifest_dir = Path::new(manifest_dir);
+
+    let mut paths = PATHS.get_or_init(Default::default).lock().unwrap();
+    paths
+        .entry(file.clone())
+        .or_insert_with(|| {
+            find(&file, manifest_dir).or_else(|| build(&file, manifest_dir)).unwrap_or_else(|| {
+                panic!(
+                    "{} not found, run `anchor build` or test with `--features build-program`. Searched: {:#?}",
+                    file,
+                    candidates(&file, manifest_dir)
+                )
+            })
+        })
+        .clone()
+}
+
+pub fn program_bytes(name: &str, manifest_dir: &str) -> Vec<u8> {
+    std::fs::read(program_path(name, manifest_dir)).expect("Failed to read program SO file")
+}
@@ -0,0 +1,35 @@
+use {
+    anchor_lang::solana_program::{clock::Clock, epoch_schedule::EpochSchedule},
+    litesvm::LiteSVM,
+};
+
+/// Moves the cluster time forward by `seconds`, leaving the slot untouched
+pub fn warp_seconds(program: &mut LiteSVM, seconds: i64) {
+    let mut clock = program.get_sysvar::<Clock>();
+    clock.unix_timestamp += seconds;
+    program.set_sysvar::<Clock>(&clock);
+}
+
+/// Sets the cluster time to an absolute unix timestamp, e.g. an escrow's expiry
+pub fn warp_to_timestamp(program: &mut LiteSVM, unix_timestamp: i64) {
+    let mut clock = program.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp;
+    program.set_sysvar::<Clock>(&clock);
+}
+
+/// Moves the slot forward by `slots`, leaving the cluster time untouched
+pub fn warp_slots(program: &mut LiteSVM, slots: u64) {
+    let slot = program.get_sysvar::<Clock>().slot;
+    program.warp_to_slot(slot + slots);
+}
+
+/// Moves to the same slot offset `epochs` epochs later, per the epoch schedule sysvar
+pub fn warp_epochs(program: &mut LiteSVM, epochs: u64) {
+    let schedule = program.get_sysvar::<EpochSchedule>();
+    let mut clock = program.get_sysvar::<Clock>();
+
+    clock.slot += epochs * schedule.slots_per_epoch;
+    clock.epoch += epochs;
+    clock.leader_schedule_epoch = schedule.get_leader_schedule_epoch(clock.slot);
+    program.set_sysvar::<Clock>(&clock);
+}
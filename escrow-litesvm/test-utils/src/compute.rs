@@ -0,0 +1,106 @@
+//! Compute unit and log size reporting while iterating on a program.
+//!
+//! Route transactions through a `ComputeReport` and it prints one table row per
+//! transaction when it goes out of scope. Run the tests with `--nocapture` to see it.
+
+use {
+    crate::send,
+    litesvm::{
+        types::{TransactionMetadata, TransactionResult},
+        LiteSVM,
+    },
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+};
+
+struct Row {
+    label: String,
+    succeeded: bool,
+    compute_units: u64,
+    log_lines: usize,
+    log_bytes: usize,
+}
+
+pub struct ComputeReport {
+    title: String,
+    rows: Vec<Row>,
+}
+
+impl ComputeReport {
+    pub fn new(title: &str) -> Self {
+        Self { title: title.to_string(), rows: Vec::new() }
+    }
+
+    /// Sends like `crate::send` and records the outcome under `label`
+    pub fn send(&mut self, label: &str, program: &mut LiteSVM, instructions: &[Instruction], payer: &Keypair) -> TransactionResult {
+        let result = send(program, instructions, payer);
+        self.record(label, &result);
+        result
+    }
+
+    /// Records a transaction that was sent some other way, failed ones included
+    pub fn record(&mut self, label: &str, result: &TransactionResult) {
+        let (succeeded, meta): (bool, &TransactionMetadata) = match result {
+            Ok(meta) => (true, meta),
+            Err(failed) => (false, &failed.meta),
+        };
+
+        self.rows.push(Row {
+            label: label.to_string(),
+            succeeded,
+            compute_units: meta.compute_units_consumed,
+            log_lines: meta.logs.len(),
+            log_bytes: meta.logs.iter().map(String::len).sum(),
+        });
+    }
+
+    /// Compute units of the last transaction recorded under `label`
+    pub fn compute_units(&self, label: &str) -> u64 {
+        self.rows
+            .iter()
+            .rev()
+            .find(|row| row.label == label)
+            .unwrap_or_else(|| panic!("Nothing recorded under {:?}", label))
+            .compute_units
+    }
+
+    pub fn render(&self) -> String {
+        let width = self.rows.iter().map(|row| row.label.len()).chain([11]).max().unwrap();
+
+        let mut out = format!("{}\n", self.title);
+        out += &format!("{:<width$}  {:>6}  {:>9}  {:>9}  {:>9}\n", "Transaction", "Status", "CU", "Log lines", "Log bytes");
+        for row in &self.rows {
+            let status = if row.succeeded { "ok" } else { "failed" };
+            out += &format!(
+                "{:<width$}  {:>6}  {:>9}  {:>9}  {:>9}\n",
+                row.label, status, row.compute_units, row.log_lines, row.log_bytes
+            );
+        }
+        out
+    }
+}
+
+impl Drop for ComputeReport {
+    fn drop(&mut self) {
+        if !self.rows.is_empty() {
+            println!("{}", self.render());
+        }
+    }
+}
+
+#[test]
+fn should_render_one_row_per_transaction() {
+    let mut report = ComputeReport::new("Sample");
+    report.rows.push(Row { label: "Make".to_string(), succeeded: true, compute_units: 12_345, log_lines: 10, log_bytes: 640 });
+    report.rows.push(Row { label: "Take partial".to_string(), succeeded: false, compute_units: 7, log_lines: 2, log_bytes: 80 });
+
+    assert_eq!(
+        report.render(),
+        "Sample\n\
+         Transaction   Status         CU  Log lines  Log bytes\n\
+         Make              ok      12345         10        640\n\
+         Take partial  failed          7          2         80\n"
+    );
+
+    report.rows.clear();
+}
@@ -0,0 +1,40 @@
+//! Decodes Anchor events out of a transaction's logs and inner instructions.
+
+use {
+    anchor_lang::{AnchorDeserialize, Discriminator},
+    base64::{engine::general_purpose::STANDARD, Engine},
+    litesvm::types::TransactionMetadata,
+};
+
+// `emit_cpi!` self-invokes the program with this tag ahead of the event bytes
+const EVENT_IX_TAG_LE: [u8; 8] = 0x1d9acb512ea545e4_u64.to_le_bytes();
+
+fn decode<E: Discriminator + AnchorDeserialize>(data: &[u8]) -> Option<E> {
+    let payload = data.strip_prefix(E::DISCRIMINATOR)?;
+    E::deserialize(&mut &payload[..]).ok()
+}
+
+/// Every `E` the transaction emitted, whether through `emit!` (a base64
+/// "Program data:" log line) or `emit_cpi!` (inner instruction data)
+pub fn events<E: Discriminator + AnchorDeserialize>(result: &TransactionMetadata) -> Vec<E> {
+    let logged = result.logs
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter_map(|data| decode::<E>(&data));
+
+    let self_invoked = result.inner_instructions
+        .iter()
+        .flatten()
+        .filter_map(|inner| inner.instruction.data.strip_prefix(&EVENT_IX_TAG_LE[..]))
+        .filter_map(decode::<E>);
+
+    logged.chain(self_invoked).collect()
+}
+
+/// The single `E` the transaction emitted
+pub fn event<E: Discriminator + AnchorDeserialize>(result: &TransactionMetadata) -> E {
+    let mut emitted = events::<E>(result);
+    assert_eq!(emitted.len(), 1, "Expected exactly one event, logs: {:#?}", result.logs);
+    emitted.remove(0)
+}
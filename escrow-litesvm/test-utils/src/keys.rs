@@ -0,0 +1,41 @@
+//! Deterministic keys, so a failing test reproduces with the same addresses and
+//! anything committed that mentions a pubkey stays stable across runs.
+//!
+//! Every keypair is derived from the run seed and a label. The seed defaults to
+//! `DEFAULT_SEED` and can be overridden with the `TEST_SEED` environment variable
+//! to run the suite against a different set of keys.
+
+use {
+    anchor_lang::solana_program::hash::hashv,
+    solana_keypair::Keypair,
+    std::{cell::Cell, sync::OnceLock},
+};
+
+const DEFAULT_SEED: u64 = 0x5eed_e5c2_0000_0001;
+
+thread_local! {
+    // Each test runs on its own thread, so the sequence restarts for every test
+    static NEXT_INDEX: Cell<u64> = const { Cell::new(0) };
+}
+
+/// The seed keys are derived from, `TEST_SEED` if set
+pub fn seed() -> u64 {
+    static SEED: OnceLock<u64> = OnceLock::new();
+
+    *SEED.get_or_init(|| match std::env::var("TEST_SEED") {
+        Ok(value) => value.parse().expect("TEST_SEED must be a u64"),
+        Err(_) => DEFAULT_SEED,
+    })
+}
+
+/// The keypair for `label`, the same label always yields the same key
+pub fn keypair(label: &str) -> Keypair {
+    let secret = hashv(&[&seed().to_le_bytes(), label.as_bytes()]);
+    Keypair::new_from_array(secret.to_bytes())
+}
+
+/// The next keypair in the current test's sequence, for keys nothing refers to by name
+pub fn next_keypair() -> Keypair {
+    let index = NEXT_INDEX.with(|next| next.replace(next.get() + 1));
+    keypair(&format!("#{}", index))
+}
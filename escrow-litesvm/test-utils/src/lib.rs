@@ -0,0 +1,82 @@
+//! LiteSVM helpers shared by the tests of every program in the workspace:
+//! artifact loading, deterministic keys, clock warping, mint and token account
+//! setup, reading accounts back, compute reporting, and assertions over errors,
+//! logs and events. The `replay` feature adds re-execution of cluster
+//! transactions.
+
+pub mod account;
+pub mod artifact;
+pub mod clock;
+pub mod compute;
+pub mod events;
+pub mod keys;
+pub mod logs;
+#[cfg(feature = "replay")]
+pub mod replay;
+pub mod token;
+pub mod token_2022;
+
+use {
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_instruction::{error::InstructionError, Instruction},
+    solana_keypair::Keypair,
+    solana_message::Message,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    solana_transaction_error::TransactionError,
+};
+
+// Fee per signature under LiteSVM's default fee structure
+pub const SIGNATURE_FEE: u64 = 5_000;
+
+// What `svm_with_program` airdrops to each wallet, 10 SOL
+pub const WALLET_LAMPORTS: u64 = 10_000_000_000;
+
+/// A fresh SVM with `<name>.so` deployed at `program_id` and `WALLET_LAMPORTS` airdropped
+/// to each of `wallets`. `manifest_dir` is the program crate's `CARGO_MANIFEST_DIR`, see
+/// `artifact::program_path`. Prints the key seed, which a failing test shows with its
+/// output, so it can be rerun with the same keys.
+pub fn svm_with_program(program_id: &Pubkey, name: &str, manifest_dir: &str, wallets: &[&Keypair]) -> LiteSVM {
+    let mut program = LiteSVM::new();
+    program.add_program(*program_id, &artifact::program_bytes(name, manifest_dir));
+
+    println!("TEST_SEED={}", keys::seed());
+
+    for wallet in wallets {
+        program.airdrop(&wallet.pubkey(), WALLET_LAMPORTS).expect("Failed to airdrop SOL");
+    }
+
+    program
+}
+
+// Signs with the payer only and sends against the latest blockhash
+pub fn send(program: &mut LiteSVM, instructions: &[Instruction], payer: &Keypair) -> TransactionResult {
+    let message = Message::new(instructions, Some(&payer.pubkey()));
+    let recent_blockhash = program.latest_blockhash();
+    let transaction = Transaction::new(&[payer], message, recent_blockhash);
+    program.send_transaction(transaction)
+}
+
+#[doc(hidden)]
+pub fn check_custom_error(result: TransactionResult, expected: u32, name: &str) {
+    match result {
+        Err(failed) => match failed.err {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                assert_eq!(code, expected, "Expected {}, logs: {:#?}", name, failed.meta.logs);
+            }
+            err => panic!("Expected {}, got {:?}", name, err),
+        },
+        Ok(_) => panic!("Expected {}, but the transaction succeeded", name),
+    }
+}
+
+/// Asserts a transaction result failed with the given custom error code, either a
+/// program error enum, an Anchor `ErrorCode` or a native program error cast to `u32`.
+/// Failures name the expected variant and dump the program logs.
+#[macro_export]
+macro_rules! assert_custom_error {
+    ($result:expr, $expected:expr $(,)?) => {
+        $crate::check_custom_error($result, u32::from($expected), stringify!($expected))
+    };
+}
@@ -0,0 +1,137 @@
+//! Assertions over program logs. Needles match anywhere within a line, and every
+//! failure prints the numbered logs it searched.
+
+/// Index of the first line at or after `from` containing `needle`
+pub fn find(logs: &[String], needle: &str, from: usize) -> Option<usize> {
+    logs.iter().skip(from).position(|log| log.contains(needle)).map(|index| from + index)
+}
+
+/// `Program log:` lines written by `program_id` itself, not by the programs it invokes
+pub fn own_logs<'a>(logs: &'a [String], program_id: &str) -> Vec<&'a String> {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut own = Vec::new();
+    for log in logs {
+        if let Some((invoked, _)) = log.strip_prefix("Program ").and_then(|rest| rest.split_once(" invoke [")) {
+            stack.push(invoked);
+        } else if log.starts_with("Program ") && (log.ends_with(" success") || log.contains(" failed: ")) {
+            stack.pop();
+        } else if log.starts_with("Program log: ") && stack.last() == Some(&program_id) {
+            own.push(log);
+        }
+    }
+    own
+}
+
+pub fn render(logs: &[String]) -> String {
+    logs.iter().enumerate().map(|(index, log)| format!("{:>3} {}\n", index, log)).collect()
+}
+
+/// Line by line comparison, `-` for expected lines that differ and `+` for actual ones
+pub fn diff(expected: &[&str], actual: &[String]) -> String {
+    let mut out = String::new();
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) if actual.contains(expected) => {
+                out += &format!("  {:>3} {}\n", index, actual);
+            }
+            (expected, actual) => {
+                if let Some(expected) = expected {
+                    out += &format!("- {:>3} {}\n", index, expected);
+                }
+                if let Some(actual) = actual {
+                    out += &format!("+ {:>3} {}\n", index, actual);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Asserts some log line contains the needle
+#[macro_export]
+macro_rules! assert_log_contains {
+    ($logs:expr, $needle:expr $(,)?) => {{
+        let logs: &[String] = &$logs;
+        assert!(
+            $crate::logs::find(logs, $needle, 0).is_some(),
+            "No log line contains {:?}:\n{}", $needle, $crate::logs::render(logs)
+        );
+    }};
+}
+
+/// Asserts no log line contains the needle
+#[macro_export]
+macro_rules! assert_log_absent {
+    ($logs:expr, $needle:expr $(,)?) => {{
+        let logs: &[String] = &$logs;
+        if let Some(index) = $crate::logs::find(logs, $needle, 0) {
+            panic!("Log line {} contains {:?}:\n{}", index, $needle, $crate::logs::render(logs));
+        }
+    }};
+}
+
+/// Asserts the needles appear in order, other lines may come in between
+#[macro_export]
+macro_rules! assert_log_sequence {
+    ($logs:expr, [$($needle:expr),+ $(,)?] $(,)?) => {{
+        let logs: &[String] = &$logs;
+        let mut from = 0;
+        for needle in [$($needle),+] {
+            match $crate::logs::find(logs, needle, from) {
+                Some(index) => from = index + 1,
+                None => panic!(
+                    "No log line from {} on contains {:?}:\n{}", from, needle, $crate::logs::render(logs)
+                ),
+            }
+        }
+    }};
+}
+
+/// Asserts each line contains its expected counterpart, printing a diff otherwise
+#[macro_export]
+macro_rules! assert_logs_match {
+    ($logs:expr, [$($expected:expr),* $(,)?] $(,)?) => {{
+        let logs: &[String] = &$logs;
+        let expected: &[&str] = &[$($expected),*];
+        let matches = logs.len() == expected.len()
+            && logs.iter().zip(expected).all(|(log, expected)| log.contains(expected));
+        assert!(matches, "Logs differ:\n{}", $crate::logs::diff(expected, logs));
+    }};
+}
+
+#[test]
+fn should_match_log_assertions_against_sample_logs() {
+    let logs: Vec<String> = ["first", "Program log: Instruction: Take", "middle", "last"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    assert_log_contains!(logs, "Instruction: Take");
+    assert_log_absent!(logs, "Instruction: Refund");
+    assert_log_sequence!(logs, ["first", "Take", "last"]);
+    assert_logs_match!(logs, ["first", "Take", "middle", "last"]);
+
+    let reversed = std::panic::catch_unwind(|| assert_log_sequence!(logs, ["last", "first"]));
+    assert!(reversed.is_err());
+
+    assert_eq!(diff(&["first", "second"], &logs[..1]), "    0 first\n-   1 second\n");
+}
+
+#[test]
+fn should_attribute_logs_to_the_innermost_program() {
+    let logs: Vec<String> = [
+        "Program Escrow invoke [1]",
+        "Program log: Instruction: Take",
+        "Program Token invoke [2]",
+        "Program log: Instruction: TransferChecked",
+        "Program Token success",
+        "Program data: ZXZlbnQ=",
+        "Program Escrow success",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+
+    assert_eq!(own_logs(&logs, "Escrow"), [&logs[1]]);
+    assert_eq!(own_logs(&logs, "Token"), [&logs[3]]);
+}
@@ -0,0 +1,191 @@
+//! Re-executes a cluster transaction in LiteSVM for regression debugging.
+//!
+//! The transaction is fetched by signature, every account it touches (lookup tables
+//! and the addresses they load included) is fetched and written with `set_account`,
+//! and deployed programs are loaded from their program data. Accounts hold their
+//! *current* cluster state, not the state at the transaction's slot, so a replay is
+//! only faithful while the accounts involved have not moved on since.
+//!
+//! Signature and blockhash checks are disabled, so the original signed bytes replay
+//! as they are. A program can be swapped for a local build with [`Replay::with_program`]
+//! to check a fix against the transaction that exposed the bug.
+
+use {
+    base64::{engine::general_purpose::STANDARD, Engine},
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_account::Account,
+    solana_address::Address,
+    solana_commitment_config::CommitmentConfig,
+    solana_pubkey::Pubkey,
+    solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client_api::config::RpcTransactionConfig,
+    solana_sdk_ids::{bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable, native_loader, sysvar},
+    solana_signature::Signature,
+    solana_transaction::versioned::VersionedTransaction,
+    solana_transaction_status_client_types::{EncodedTransaction, TransactionBinaryEncoding, UiLoadedAddresses, UiTransactionEncoding},
+    std::{collections::BTreeMap, str::FromStr},
+};
+
+// Size of the `UpgradeableLoaderState::ProgramData` header in front of the ELF
+const PROGRAM_DATA_METADATA_SIZE: usize = 45;
+
+// `getMultipleAccounts` limit per request
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// A fetched transaction and the accounts needed to execute it again
+pub struct Replay {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub transaction: VersionedTransaction,
+    /// Logs and error of the original execution, to compare the replay against
+    pub original_logs: Vec<String>,
+    pub original_err: Option<String>,
+    accounts: BTreeMap<Pubkey, Account>,
+    programs: BTreeMap<Pubkey, Vec<u8>>,
+}
+
+impl Replay {
+    /// Fetches the transaction `signature` and everything it touches from `rpc_url`
+    pub fn fetch(rpc_url: &str, signature: &str) -> Self {
+        let rpc_client = RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::confirmed());
+
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let fetched = rpc_client
+            .get_transaction_with_config(&Signature::from_str(signature).expect("Invalid signature"), config)
+            .unwrap_or_else(|err| panic!("Failed to fetch transaction {}: {}", signature, err));
+
+        // The raw wire bytes decode straight into LiteSVM's transaction type
+        let EncodedTransaction::Binary(encoded, TransactionBinaryEncoding::Base64) = fetched.transaction.transaction else {
+            panic!("Transaction {} was not returned as base64", signature);
+        };
+        let bytes = STANDARD.decode(encoded).expect("Invalid base64 transaction");
+        let transaction: VersionedTransaction = bincode::deserialize(&bytes).expect("Invalid transaction bytes");
+
+        let meta = fetched.transaction.meta.expect("Transaction has no status meta");
+        let original_logs = Option::<Vec<String>>::from(meta.log_messages).unwrap_or_default();
+        let original_err = meta.err.map(|err| format!("{:?}", err));
+
+        let mut addresses: Vec<Pubkey> = transaction.message.static_account_keys().to_vec();
+        if let Some(lookups) = transaction.message.address_table_lookups() {
+            addresses.extend(lookups.iter().map(|lookup| lookup.account_key));
+        }
+        if let Some(loaded) = Option::<UiLoadedAddresses>::from(meta.loaded_addresses) {
+            addresses.extend(loaded.writable.iter().chain(&loaded.readonly).map(|address| Pubkey::from_str(address).unwrap()));
+        }
+
+        let mut replay = Self {
+            signature: signature.to_string(),
+            slot: fetched.slot,
+            block_time: fetched.block_time,
+            transaction,
+            original_logs,
+            original_err,
+            accounts: BTreeMap::new(),
+            programs: BTreeMap::new(),
+        };
+
+        for (address, account) in fetch_accounts(&rpc_client, &addresses) {
+            replay.insert(&rpc_client, address, account);
+        }
+
+        replay
+    }
+
+    fn insert(&mut self, rpc_client: &RpcClient, address: Pubkey, account: Account) {
+        // LiteSVM provides its own sysvars and builtins
+        if account.owner == sysvar::ID || account.owner == native_loader::ID {
+            return;
+        }
+
+        if !account.executable {
+            self.accounts.insert(address, account);
+            return;
+        }
+
+        let elf = if account.owner == bpf_loader_upgradeable::ID {
+            // `UpgradeableLoaderState::Program` is a u32 tag followed by the program data address
+            let program_data = Pubkey::try_from(&account.data[4..36]).unwrap();
+            let (_, program_data_account) = fetch_accounts(rpc_client, &[program_data])
+                .pop()
+                .unwrap_or_else(|| panic!("Program data of {} not found", address));
+            program_data_account.data[PROGRAM_DATA_METADATA_SIZE..].to_vec()
+        } else if account.owner == bpf_loader::ID || account.owner == bpf_loader_deprecated::ID {
+            account.data
+        } else {
+            panic!("Program {} is owned by unsupported loader {}", address, account.owner);
+        };
+
+        self.programs.insert(address, elf);
+    }
+
+    /// Replaces a fetched program with a local build
+    pub fn with_program(mut self, program_id: Pubkey, elf: Vec<u8>) -> Self {
+        self.programs.insert(program_id, elf);
+        self
+    }
+
+    /// Overrides a fetched account, e.g. to restore the state it had at the transaction's slot
+    pub fn with_account(mut self, address: Pubkey, account: Account) -> Self {
+        self.accounts.insert(address, account);
+        self
+    }
+
+    /// A fresh SVM holding every fetched account and program, with the clock set to the
+    /// transaction's slot and block time
+    pub fn load(&self) -> LiteSVM {
+        let mut program = LiteSVM::new().with_sigverify(false).with_blockhash_check(false);
+
+        program.warp_to_slot(self.slot);
+        if let Some(block_time) = self.block_time {
+            crate::clock::warp_to_timestamp(&mut program, block_time);
+        }
+
+        for (address, account) in &self.accounts {
+            program.set_account(*address, account.clone()).expect("Failed to load account");
+        }
+        for (program_id, elf) in &self.programs {
+            program.add_program(*program_id, elf);
+        }
+
+        program
+    }
+
+    /// Executes the original transaction in a freshly loaded SVM
+    pub fn execute(&self) -> TransactionResult {
+        self.load().send_transaction(self.transaction.clone())
+    }
+}
+
+// Fetches in batches and converts to LiteSVM's account type, skipping accounts that do
+// not exist, which the transaction itself creates
+fn fetch_accounts(rpc_client: &RpcClient, addresses: &[Pubkey]) -> Vec<(Pubkey, Account)> {
+    let mut unique = addresses.to_vec();
+    unique.sort();
+    unique.dedup();
+
+    let mut accounts = Vec::with_capacity(unique.len());
+    for chunk in unique.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let chunk_addresses: Vec<Address> = chunk.iter().map(|address| Address::new_from_array(address.to_bytes())).collect();
+        let fetched = rpc_client
+            .get_multiple_accounts(&chunk_addresses)
+            .expect("Failed to fetch accounts");
+
+        for (address, account) in chunk.iter().zip(fetched) {
+            let Some(account) = account else { continue };
+            accounts.push((*address, Account {
+                lamports: account.lamports,
+                data: account.data,
+                owner: Pubkey::new_from_array(account.owner.to_bytes()),
+                executable: account.executable,
+                rent_epoch: account.rent_epoch,
+            }));
+        }
+    }
+
+    accounts
+}
@@ -0,0 +1,87 @@
+//! SPL Token setup that litesvm_token does not cover.
+
+use {
+    crate::send,
+    anchor_lang::solana_program::program_pack::Pack,
+    anchor_spl::{
+        associated_token::{get_associated_token_address, spl_associated_token_account},
+        token::spl_token,
+    },
+    litesvm::LiteSVM,
+    solana_keypair::Keypair,
+    solana_message::Message,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+};
+
+/// Creates an SPL Token mint at `mint`'s address, with the payer as mint authority.
+/// Unlike litesvm_token's `CreateMint` the address is chosen by the caller, see `keys`.
+pub fn create_mint(program: &mut LiteSVM, payer: &Keypair, mint: &Keypair, decimals: u8, freeze_authority: Option<&Pubkey>) -> Pubkey {
+    let space = spl_token::state::Mint::LEN;
+    let lamports = program.minimum_balance_for_rent_exemption(space);
+
+    let instructions = [
+        solana_system_interface::instruction::create_account(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            lamports,
+            space as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(
+            &spl_token::ID,
+            &mint.pubkey(),
+            &payer.pubkey(),
+            freeze_authority,
+            decimals,
+        ).unwrap(),
+    ];
+
+    let message = Message::new(&instructions, Some(&payer.pubkey()));
+    let transaction = Transaction::new(&[payer, mint], message, program.latest_blockhash());
+    program.send_transaction(transaction).expect("Failed to create mint");
+
+    mint.pubkey()
+}
+
+/// Creates `owner`'s associated token account for `mint`
+pub fn create_ata(program: &mut LiteSVM, payer: &Keypair, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let ix = spl_associated_token_account::instruction::create_associated_token_account(&payer.pubkey(), owner, mint, &spl_token::ID);
+    send(program, &[ix], payer).expect("Failed to create ATA");
+
+    get_associated_token_address(owner, mint)
+}
+
+/// Creates a mint with no decimals, `owner` as its authority, and mints its single
+/// token to `owner`'s associated token account. Returns the mint and that account.
+pub fn mint_nft(program: &mut LiteSVM, owner: &Keypair, mint: &Keypair) -> (Pubkey, Pubkey) {
+    let mint = create_mint(program, owner, mint, 0, None);
+    let ata = create_ata(program, owner, &mint, &owner.pubkey());
+
+    let ix = spl_token::instruction::mint_to(&spl_token::ID, &mint, &ata, &owner.pubkey(), &[], 1).unwrap();
+    send(program, &[ix], owner).expect("Failed to mint NFT");
+
+    (mint, ata)
+}
+
+/// Amount held by an SPL Token account, 0 if it does not exist
+pub fn token_amount(program: &LiteSVM, address: &Pubkey) -> u64 {
+    program
+        .get_account(address)
+        .map_or(0, |account| spl_token::state::Account::unpack(&account.data).unwrap().amount)
+}
+
+#[test]
+fn should_mint_a_single_token_nft() {
+    let mut program = LiteSVM::new();
+    let owner = crate::keys::keypair("owner");
+    program.airdrop(&owner.pubkey(), 1_000_000_000).unwrap();
+
+    let (mint, ata) = mint_nft(&mut program, &owner, &crate::keys::keypair("nft"));
+
+    let mint = spl_token::state::Mint::unpack(&program.get_account(&mint).unwrap().data).unwrap();
+    assert_eq!((mint.decimals, mint.supply), (0, 1));
+    assert_eq!(token_amount(&program, &ata), 1);
+    assert_eq!(token_amount(&program, &Pubkey::new_unique()), 0);
+}
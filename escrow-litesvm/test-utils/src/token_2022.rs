@@ -0,0 +1,218 @@
+//! Token-2022 counterparts of the litesvm_token helpers, with mint extensions.
+
+use {
+    crate::{
+        keys::{keypair, next_keypair},
+        send,
+    },
+    litesvm::{types::TransactionResult, LiteSVM},
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    spl_token_2022::{
+        extension::{transfer_fee, transfer_hook, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+        instruction as token_instruction,
+        state::Mint,
+        ID as TOKEN_2022_PROGRAM_ID,
+    },
+};
+
+/// Mint extensions the helpers know how to initialize
+#[derive(Clone, Debug)]
+pub enum MintExtension {
+    TransferFee { basis_points: u16, maximum_fee: u64 },
+    TransferHook { program_id: Pubkey },
+    NonTransferable,
+}
+
+impl MintExtension {
+    fn extension_type(&self) -> ExtensionType {
+        match self {
+            MintExtension::TransferFee { .. } => ExtensionType::TransferFeeConfig,
+            MintExtension::TransferHook { .. } => ExtensionType::TransferHook,
+            MintExtension::NonTransferable => ExtensionType::NonTransferable,
+        }
+    }
+}
+
+/// Token-2022 counterpart of litesvm_token's `CreateMint`, with extensions.
+/// The payer is also the mint and extension authority unless overridden.
+pub struct CreateMint2022<'a> {
+    program: &'a mut LiteSVM,
+    payer: &'a Keypair,
+    authority: Option<Pubkey>,
+    decimals: u8,
+    extensions: Vec<MintExtension>,
+}
+
+impl<'a> CreateMint2022<'a> {
+    pub fn new(program: &'a mut LiteSVM, payer: &'a Keypair) -> Self {
+        Self { program, payer, authority: None, decimals: 6, extensions: Vec::new() }
+    }
+
+    pub fn authority(mut self, authority: &Pubkey) -> Self {
+        self.authority = Some(*authority);
+        self
+    }
+
+    pub fn decimals(mut self, decimals: u8) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub fn extension(mut self, extension: MintExtension) -> Self {
+        self.extensions.push(extension);
+        self
+    }
+
+    pub fn send(self) -> Result<Pubkey, litesvm::types::FailedTransactionMetadata> {
+        let mint = next_keypair();
+        let payer = self.payer.pubkey();
+        let authority = self.authority.unwrap_or(payer);
+
+        let extension_types: Vec<ExtensionType> = self.extensions.iter().map(MintExtension::extension_type).collect();
+        let space = ExtensionType::try_calculate_account_len::<Mint>(&extension_types).unwrap();
+        let lamports = self.program.minimum_balance_for_rent_exemption(space);
+
+        let mut instructions = vec![solana_system_interface::instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            lamports,
+            space as u64,
+            &TOKEN_2022_PROGRAM_ID,
+        )];
+
+        // Extensions must be initialized before the mint itself
+        for extension in &self.extensions {
+            instructions.push(match extension {
+                MintExtension::TransferFee { basis_points, maximum_fee } => transfer_fee::instruction::initialize_transfer_fee_config(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &mint.pubkey(),
+                    Some(&authority),
+                    Some(&authority),
+                    *basis_points,
+                    *maximum_fee,
+                ),
+                MintExtension::TransferHook { program_id } => transfer_hook::instruction::initialize(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &mint.pubkey(),
+                    Some(authority),
+                    Some(*program_id),
+                ),
+                MintExtension::NonTransferable => token_instruction::initialize_non_transferable_mint(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &mint.pubkey(),
+                ),
+            }.unwrap());
+        }
+
+        instructions.push(token_instruction::initialize_mint2(
+            &TOKEN_2022_PROGRAM_ID,
+            &mint.pubkey(),
+            &authority,
+            None,
+            self.decimals,
+        ).unwrap());
+
+        let message = solana_message::Message::new(&instructions, Some(&payer));
+        let transaction = solana_transaction::Transaction::new(&[self.payer, &mint], message, self.program.latest_blockhash());
+        self.program.send_transaction(transaction)?;
+
+        Ok(mint.pubkey())
+    }
+}
+
+/// Creates `owner`'s Token-2022 associated token account for `mint`
+pub fn create_ata_2022(program: &mut LiteSVM, payer: &Keypair, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let ix = anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account(
+        &payer.pubkey(),
+        owner,
+        mint,
+        &TOKEN_2022_PROGRAM_ID,
+    );
+    send(program, &[ix], payer).expect("Failed to create Token-2022 ATA");
+
+    anchor_spl::associated_token::get_associated_token_address_with_program_id(owner, mint, &TOKEN_2022_PROGRAM_ID)
+}
+
+pub fn mint_to_2022(program: &mut LiteSVM, authority: &Keypair, mint: &Pubkey, destination: &Pubkey, amount: u64) -> TransactionResult {
+    let ix = token_instruction::mint_to(&TOKEN_2022_PROGRAM_ID, mint, destination, &authority.pubkey(), &[], amount).unwrap();
+    send(program, &[ix], authority)
+}
+
+pub fn transfer_checked_2022(program: &mut LiteSVM, owner: &Keypair, mint: &Pubkey, from: &Pubkey, to: &Pubkey, amount: u64, decimals: u8) -> TransactionResult {
+    let ix = token_instruction::transfer_checked(&TOKEN_2022_PROGRAM_ID, from, mint, to, &owner.pubkey(), &[], amount, decimals).unwrap();
+    send(program, &[ix], owner)
+}
+
+/// A fresh SVM with a payer and a receiver, both funded
+#[cfg(test)]
+fn setup() -> (LiteSVM, Keypair, Keypair) {
+    let mut program = LiteSVM::new();
+    let payer = keypair("payer");
+    let receiver = keypair("receiver");
+
+    for keypair in [&payer, &receiver] {
+        program.airdrop(&keypair.pubkey(), 10_000_000_000).unwrap();
+    }
+
+    (program, payer, receiver)
+}
+
+/// Amount held by a Token-2022 account, which may carry extensions
+pub fn token_2022_amount(program: &LiteSVM, address: &Pubkey) -> u64 {
+    let account = program.get_account(address).expect("Token account not found");
+    StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data).unwrap().base.amount
+}
+
+#[test]
+fn should_withhold_transfer_fee_on_transfer_fee_mint() {
+    let (mut program, payer, receiver) = setup();
+
+    let mint = CreateMint2022::new(&mut program, &payer)
+        .extension(MintExtension::TransferFee { basis_points: 100, maximum_fee: 1_000 })
+        .send()
+        .unwrap();
+
+    let from = create_ata_2022(&mut program, &payer, &mint, &payer.pubkey());
+    let to = create_ata_2022(&mut program, &payer, &mint, &receiver.pubkey());
+    mint_to_2022(&mut program, &payer, &mint, &from, 1_000_000).unwrap();
+
+    // 1% of 10_000 is withheld in the destination account
+    transfer_checked_2022(&mut program, &payer, &mint, &from, &to, 10_000, 6).unwrap();
+    assert_eq!(token_2022_amount(&program, &to), 9_900);
+}
+
+#[test]
+fn should_store_transfer_hook_program_on_mint() {
+    let (mut program, payer, _receiver) = setup();
+    let hook_program = keypair("hook_program").pubkey();
+
+    let mint = CreateMint2022::new(&mut program, &payer)
+        .extension(MintExtension::TransferHook { program_id: hook_program })
+        .send()
+        .unwrap();
+
+    let account = program.get_account(&mint).unwrap();
+    let state = StateWithExtensions::<Mint>::unpack(&account.data).unwrap();
+    let hook = state.get_extension::<transfer_hook::TransferHook>().unwrap();
+    assert_eq!(Option::<Pubkey>::from(hook.program_id), Some(hook_program));
+}
+
+#[test]
+fn should_reject_transfers_of_non_transferable_mint() {
+    let (mut program, payer, receiver) = setup();
+
+    let mint = CreateMint2022::new(&mut program, &payer)
+        .extension(MintExtension::NonTransferable)
+        .send()
+        .unwrap();
+
+    let from = create_ata_2022(&mut program, &payer, &mint, &payer.pubkey());
+    let to = create_ata_2022(&mut program, &payer, &mint, &receiver.pubkey());
+    mint_to_2022(&mut program, &payer, &mint, &from, 10).unwrap();
+
+    let result = transfer_checked_2022(&mut program, &payer, &mint, &from, &to, 10, 6);
+    crate::assert_custom_error!(result, spl_token_2022::error::TokenError::NonTransferable as u32);
+    assert_eq!(token_2022_amount(&program, &from), 10);
+}